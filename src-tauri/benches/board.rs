@@ -0,0 +1,144 @@
+//! Benchmarks of the hot `Board` operations
+//!
+//! Run with `cargo bench` for the dense board and `cargo bench --features sparse-board` for the sparse board
+
+use std::cmp::Reverse;
+use std::hint::black_box;
+use std::sync::{atomic::AtomicBool, Arc};
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use hashbrown::HashSet;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use bananagrams_solver::{convert_word_to_array, Letters, Word, BOARD_SIZE, REGULAR_TILES};
+use bananagrams_solver::board::{is_board_valid_horizontal, Board, Direction};
+use bananagrams_solver::solver::{solve_hand, solve_hand_multiseed, SolveConfig, Solver};
+use bananagrams_solver::trie::{DictionaryIndex, Trie};
+
+/// Builds a connected board of 60 tiles in the middle of the grid
+/// # Returns
+/// * `Board` - The board
+/// * `Letters` - The number of each letter on the board
+/// * `usize` - Minimum occupied column index
+/// * `usize` - Maximum occupied column index
+/// * `usize` - Minimum occupied row index
+/// * `usize` - Maximum occupied row index
+fn sixty_tile_board() -> (Board, Letters, usize, usize, usize, usize) {
+    let mut board = Board::new(BOARD_SIZE);
+    let mut letters_on_board = [0usize; 27];
+    let (min_col, min_row) = (BOARD_SIZE/2 - 4, BOARD_SIZE/2 - 5);
+    // Six rows of eight letters...
+    for (i, word) in ["ABSOLUTE", "CHAMPION", "DELIGHTS", "FRACTION", "GUMBALLS", "HOSPITAL"].iter().enumerate() {
+        for (j, letter) in convert_word_to_array(word).into_iter().enumerate() {
+            board.set_val(min_row + 2*i, min_col + j, letter);
+            letters_on_board[letter] += 1;
+        }
+    }
+    // ...joined by twelve letters in between them
+    for (i, word) in ["EAST", "NOSE", "TEAR", "LIME", "STAR"].iter().enumerate() {
+        let word = convert_word_to_array(word);
+        for (j, col) in [0, 7].into_iter().enumerate() {
+            board.set_val(min_row + 2*i + 1, min_col + col, word[j]);
+            letters_on_board[word[j]] += 1;
+        }
+        if i < 2 {
+            board.set_val(min_row + 2*i + 1, min_col + 4, word[2]);
+            letters_on_board[word[2]] += 1;
+        }
+    }
+    (board, letters_on_board, min_col, min_col + 7, min_row, min_row + 10)
+}
+
+fn board_benchmarks(c: &mut Criterion) {
+    let backend = if cfg!(feature = "sparse-board") {"sparse"} else {"dense"};
+    let dictionary: Vec<Word> = include_str!("../src/dictionary.txt").lines().map(convert_word_to_array).collect();
+    let trie = Trie::from_words(&dictionary);
+    let (board, letters_on_board, min_col, max_col, min_row, max_row) = sixty_tile_board();
+    let word = convert_word_to_array("BANANAS");
+    let letters: Letters = [10; 27];
+    // Play the word beneath the bottom row of the board
+    let (row, col) = (max_row + 1, min_col);
+
+    c.bench_function(&format!("play_word ({})", backend), |b| b.iter_batched_ref(
+        || (board.clone(), letters_on_board),
+        |(board, letters_on_board)| board.play_word(black_box(&word), row, col, Direction::Horizontal, &letters, letters_on_board),
+        BatchSize::SmallInput
+    ));
+
+    c.bench_function(&format!("undo_play ({})", backend), |b| b.iter_batched_ref(
+        || {
+            let mut board = board.clone();
+            let mut letters_on_board = letters_on_board;
+            let played = board.play_word(&word, row, col, Direction::Horizontal, &letters, &mut letters_on_board).1;
+            (board, letters_on_board, played)
+        },
+        |(board, letters_on_board, played)| board.undo_play(black_box(played), letters_on_board),
+        BatchSize::SmallInput
+    ));
+
+    let mut played_board = board.clone();
+    played_board.play_word(&word, row, col, Direction::Horizontal, &letters, &mut letters_on_board.clone());
+    c.bench_function(&format!("is_board_valid_horizontal ({})", backend), |b| b.iter(
+        || is_board_valid_horizontal(black_box(&played_board), min_col, max_col, min_row, row, row, col, col + word.len() - 1, &trie, 2)
+    ));
+
+    c.bench_function(&format!("clone ({})", backend), |b| b.iter(
+        || black_box(&board).clone()
+    ));
+
+    // Solving a fixed 21-letter hand from scratch, which exercises board clones and the validity checks together
+    let mut short_dictionary: Vec<Word> = include_str!("../src/updated_short_dictionary.txt").lines().map(convert_word_to_array).collect();
+    short_dictionary.sort_by_key(|word| Reverse(word.len()));
+    let short_trie = Trie::from_words(&short_dictionary);
+    let short_index = DictionaryIndex::from_words(&short_dictionary);
+    let mut hand: Letters = [0; 27];
+    for letter in convert_word_to_array("HELLOWORLDQUEENSTABXE") {
+        hand[letter] += 1;
+    }
+    c.bench_function(&format!("solve_hand 21 letters ({})", backend), |b| b.iter(
+        || solve_hand(black_box(hand), &None, &short_dictionary, &short_trie, &short_index, &HashSet::new(), &SolveConfig { max_words_to_check: 10_000_000, ..SolveConfig::default() }, None, &Arc::new(AtomicBool::new(false)), None)
+    ));
+
+    // Peeling two tiles onto the board solved for an 18-letter hand, which plays off of it with the threads of `play_existing`
+    let solver = Solver::new(include_str!("../src/updated_short_dictionary.txt").lines().map(String::from).collect());
+    let mut previous_hand: Letters = [0; 27];
+    for letter in convert_word_to_array("HELLOWORLDQUEENSTA") {
+        previous_hand[letter] += 1;
+    }
+    let previous = solver.solve(previous_hand, SolveConfig::default()).expect("The hand before the peel should be solvable");
+    let mut peeled_hand = previous_hand;
+    for letter in convert_word_to_array("BE") {
+        peeled_hand[letter] += 1;
+    }
+    c.bench_function(&format!("peel to 20 letters ({})", backend), |b| b.iter(
+        || solver.solve_incremental(&previous, black_box(peeled_hand), SolveConfig::default())
+    ));
+
+    // Solving 100 random 21-letter hands from scratch (with the default word limit), splitting the first words between threads versus racing one seeded solve per thread
+    let mut rng = StdRng::seed_from_u64(1525);
+    let bag: Vec<usize> = REGULAR_TILES.iter().enumerate().flat_map(|(letter, count)| std::iter::repeat_n(letter, *count as usize)).collect();
+    let hands: Vec<Letters> = (0..100).map(|_| {
+        let mut hand: Letters = [0; 27];
+        for letter in bag.choose_multiple(&mut rng, 21) {
+            hand[*letter] += 1;
+        }
+        hand
+    }).collect();
+    let seeds: Vec<u64> = (0..std::thread::available_parallelism().map_or(1, |n| n.get()) as u64).collect();
+    let mut group = c.benchmark_group(format!("100 random hands ({})", backend));
+    group.sample_size(10);
+    group.bench_function("chunked", |b| b.iter(
+        || for hand in hands.iter() {
+            let _ = solve_hand(black_box(*hand), &None, &short_dictionary, &short_trie, &short_index, &HashSet::new(), &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), None);
+        }
+    ));
+    group.bench_function("multiseed", |b| b.iter(
+        || for hand in hands.iter() {
+            let _ = solve_hand_multiseed(black_box(*hand), &short_dictionary, &short_trie, &short_index, &HashSet::new(), &SolveConfig::default(), &seeds, None, &Arc::new(AtomicBool::new(false)));
+        }
+    ));
+    group.finish();
+}
+
+criterion_group!(benches, board_benchmarks);
+criterion_main!(benches);
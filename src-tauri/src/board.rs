@@ -0,0 +1,2269 @@
+//! The board of tiles, along with converting, scoring and checking the validity of boards
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::{fmt, collections::VecDeque};
+use hashbrown::HashSet;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use crate::{convert_array_to_word, letters_count, BoardAndIdxs, Letters, Word, BLANK, BOARD_SIZE, LETTER_VALUES, MAX_BOARD_SIZE, MAX_WORD_LENGTH};
+use crate::trie::{IndexedWord, Trie};
+pub use cell::Cell;
+
+/// Represents a set of removable indices that will storm form a valid board, plus that new board's minimum and maximum played columns and rows
+pub type Removable = (Vec<(usize, usize)>, usize, usize, usize, usize);
+
+/// Default width and height of each cell when exporting a board as SVG
+pub const SVG_CELL_SIZE: usize = 40;
+/// Fill color of tiles in an exported SVG
+const SVG_TILE_COLOR: &str = "#f5deb3";
+/// Fill color of tiles added by the latest peel in an exported SVG
+const SVG_NEW_TILE_COLOR: &str = "#9be59b";
+/// Length of the shortest diagonal run of letters that is read as a word; shorter runs are just tiles touching at their corners, which neither spell a word nor join the tiles together
+#[cfg(feature = "diagonal")]
+pub const MIN_DIAGONAL_WORD_LENGTH: usize = 3;
+
+/// The contents of a single cell of the board
+mod cell {
+    /// A cell of the board, which is either empty or holds one of the 26 letters
+    /// 
+    /// The value is private so that a cell holding anything other than a letter can't be constructed
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    pub struct Cell(u8);
+    impl Cell {
+        /// A cell without a letter
+        pub const EMPTY: Cell = Cell(u8::MAX);
+
+        /// Creates a cell holding a letter
+        /// # Arguments
+        /// * `letter` - Index of the letter (`0` for A through `25` for Z)
+        /// # Returns
+        /// `Option<Cell>` - The cell, or `None` if `letter` isn't a letter index
+        pub fn new(letter: usize) -> Option<Cell> {
+            if letter < 26 {Some(Cell(letter as u8))} else {None}
+        }
+
+        /// Gets whether the cell is empty
+        /// # Returns
+        /// `bool` - Whether the cell holds no letter
+        pub fn is_empty(self) -> bool {
+            self == Cell::EMPTY
+        }
+
+        /// Gets the letter in the cell
+        /// # Returns
+        /// `Option<usize>` - Index of the letter in the cell, or `None` if it is empty
+        pub fn letter(self) -> Option<usize> {
+            if self.is_empty() {None} else {Some(usize::from(self.0))}
+        }
+    }
+}
+
+/// The current board
+/// 
+/// By default every cell is stored in a dense array; with the `sparse-board` feature only occupied cells are stored
+#[derive(Clone)]
+pub struct Board {
+    /// Number of rows/columns in the board
+    pub size: usize,
+    /// The cells of the board, row by row
+    #[cfg(not(feature = "sparse-board"))]
+    pub(crate) arr: Vec<Cell>,
+    /// Whether each cell of the board was played using a blank tile
+    #[cfg(not(feature = "sparse-board"))]
+    pub blanks: Vec<bool>,
+    /// Each occupied cell of the board, and whether it was played using a blank tile
+    #[cfg(feature = "sparse-board")]
+    pub cells: hashbrown::HashMap<(usize, usize), (Cell, bool)>,
+    /// Each empty cell that is directly adjacent to an occupied one (kept up to date as cells are set and cleared)
+    border: HashSet<(usize, usize)>,
+    /// The maximum width and height that the occupied part of the board can grow to while solving (see `Board::set_max_span`)
+    max_span: (usize, usize)
+}
+
+#[cfg(not(feature = "sparse-board"))]
+impl Board {
+    /// Creates a new board of dimensions `size`x`size` filled with empty cells
+    pub fn new(size: usize) -> Board {
+        Board { size, arr: vec![Cell::EMPTY; size*size], blanks: vec![false; size*size], border: HashSet::new(), max_span: (size, size) }
+    }
+
+    /// Gets a cell from the board at the given index
+    /// # Arguments
+    /// * `row` - Row index of the cell to get (must be less than the board size)
+    /// * `col` - Column index of the cell to get (must be less than the board size)
+    /// # Returns
+    /// `Cell` - The cell in the board at `(row, col)`
+    /// # Panics
+    /// If `row` or `col` are out-of-bounds
+    pub fn get_val(&self, row: usize, col: usize) -> Cell {
+        *self.arr.get(row*self.size + col).expect("Index not in range!")
+    }
+
+    /// Places a letter on the board at the given index (the cell is marked as not being a blank)
+    /// # Arguments
+    /// * `row` - Row index of the cell to set (must be less than the board size)
+    /// * `col` - Column index of the cell to set (must be less than the board size)
+    /// * `letter` - Letter to place at `(row, col)` in the board
+    /// # Panics
+    /// If `row` or `col` are out-of-bounds, or if `letter` isn't a letter index
+    pub fn set_val(&mut self, row: usize, col: usize, letter: usize) {
+        let v = self.arr.get_mut(row*self.size + col).expect("Index not in range!");
+        *v = Cell::new(letter).expect("Not a letter!");
+        self.blanks[row*self.size + col] = false;
+        self.update_border(row, col);
+    }
+
+    /// Places a letter on the board at the given index, marking it as having been played using a blank tile
+    /// # Arguments
+    /// * `row` - Row index of the cell to set (must be less than the board size)
+    /// * `col` - Column index of the cell to set (must be less than the board size)
+    /// * `letter` - Letter the blank represents at `(row, col)` in the board
+    /// # Panics
+    /// If `row` or `col` are out-of-bounds, or if `letter` isn't a letter index
+    pub fn set_blank(&mut self, row: usize, col: usize, letter: usize) {
+        self.set_val(row, col, letter);
+        self.blanks[row*self.size + col] = true;
+    }
+
+    /// Empties the cell of the board at the given index
+    /// # Arguments
+    /// * `row` - Row index of the cell to empty (must be less than the board size)
+    /// * `col` - Column index of the cell to empty (must be less than the board size)
+    /// # Panics
+    /// If `row` or `col` are out-of-bounds
+    pub fn clear(&mut self, row: usize, col: usize) {
+        *self.arr.get_mut(row*self.size + col).expect("Index not in range!") = Cell::EMPTY;
+        self.blanks[row*self.size + col] = false;
+        self.update_border(row, col);
+    }
+
+    /// Gets whether the value at the given index was played using a blank tile
+    /// # Arguments
+    /// * `row` - Row index of the value to check (must be less than the board size)
+    /// * `col` - Column index of the value to check (must be less than the board size)
+    /// # Returns
+    /// `bool` - Whether `(row, col)` holds a blank tile
+    /// # Panics
+    /// If `row` or `col` are out-of-bounds
+    pub fn is_blank(&self, row: usize, col: usize) -> bool {
+        *self.blanks.get(row*self.size + col).expect("Index not in range!")
+    }
+
+    /// Gets the indices of every occupied cell by checking each cell of the board
+    /// # Returns
+    /// `impl Iterator<Item=(usize, usize)>` - The `(row, col)` of each tile, row by row
+    pub fn occupied_cells(&self) -> impl Iterator<Item=(usize, usize)> + '_ {
+        self.arr.iter().enumerate().filter(|(_, cell)| !cell.is_empty()).map(|(idx, _)| (idx / self.size, idx % self.size))
+    }
+}
+
+#[cfg(feature = "sparse-board")]
+impl Board {
+    /// Creates a new board of dimensions `size`x`size` filled with empty cells
+    pub fn new(size: usize) -> Board {
+        Board::new_sparse(size)
+    }
+
+    /// Creates a new empty board of dimensions `size`x`size` that only stores its occupied cells
+    pub(crate) fn new_sparse(size: usize) -> Board {
+        Board { size, cells: hashbrown::HashMap::new(), border: HashSet::new(), max_span: (size, size) }
+    }
+
+    /// Gets a cell from the board at the given index
+    /// # Arguments
+    /// * `row` - Row index of the cell to get (must be less than the board size)
+    /// * `col` - Column index of the cell to get (must be less than the board size)
+    /// # Returns
+    /// `Cell` - The cell in the board at `(row, col)`
+    /// # Panics
+    /// If `row` or `col` are out-of-bounds
+    pub fn get_val(&self, row: usize, col: usize) -> Cell {
+        assert!(row < self.size && col < self.size, "Index not in range!");
+        self.cells.get(&(row, col)).map_or(Cell::EMPTY, |cell| cell.0)
+    }
+
+    /// Places a letter on the board at the given index (the cell is marked as not being a blank)
+    /// # Arguments
+    /// * `row` - Row index of the cell to set (must be less than the board size)
+    /// * `col` - Column index of the cell to set (must be less than the board size)
+    /// * `letter` - Letter to place at `(row, col)` in the board
+    /// # Panics
+    /// If `row` or `col` are out-of-bounds, or if `letter` isn't a letter index
+    pub fn set_val(&mut self, row: usize, col: usize, letter: usize) {
+        assert!(row < self.size && col < self.size, "Index not in range!");
+        self.cells.insert((row, col), (Cell::new(letter).expect("Not a letter!"), false));
+        self.update_border(row, col);
+    }
+
+    /// Places a letter on the board at the given index, marking it as having been played using a blank tile
+    /// # Arguments
+    /// * `row` - Row index of the cell to set (must be less than the board size)
+    /// * `col` - Column index of the cell to set (must be less than the board size)
+    /// * `letter` - Letter the blank represents at `(row, col)` in the board
+    /// # Panics
+    /// If `row` or `col` are out-of-bounds, or if `letter` isn't a letter index
+    pub fn set_blank(&mut self, row: usize, col: usize, letter: usize) {
+        assert!(row < self.size && col < self.size, "Index not in range!");
+        self.cells.insert((row, col), (Cell::new(letter).expect("Not a letter!"), true));
+        self.update_border(row, col);
+    }
+
+    /// Empties the cell of the board at the given index
+    /// # Arguments
+    /// * `row` - Row index of the cell to empty (must be less than the board size)
+    /// * `col` - Column index of the cell to empty (must be less than the board size)
+    /// # Panics
+    /// If `row` or `col` are out-of-bounds
+    pub fn clear(&mut self, row: usize, col: usize) {
+        assert!(row < self.size && col < self.size, "Index not in range!");
+        self.cells.remove(&(row, col));
+        self.update_border(row, col);
+    }
+
+    /// Gets whether the value at the given index was played using a blank tile
+    /// # Arguments
+    /// * `row` - Row index of the value to check (must be less than the board size)
+    /// * `col` - Column index of the value to check (must be less than the board size)
+    /// # Returns
+    /// `bool` - Whether `(row, col)` holds a blank tile
+    /// # Panics
+    /// If `row` or `col` are out-of-bounds
+    pub fn is_blank(&self, row: usize, col: usize) -> bool {
+        assert!(row < self.size && col < self.size, "Index not in range!");
+        self.cells.get(&(row, col)).is_some_and(|cell| cell.1)
+    }
+
+    /// Gets the indices of every occupied cell
+    /// # Returns
+    /// `impl Iterator<Item=(usize, usize)>` - The `(row, col)` of each tile, in no particular order
+    pub fn occupied_cells(&self) -> impl Iterator<Item=(usize, usize)> + '_ {
+        self.cells.keys().copied()
+    }
+}
+
+impl Board {
+    /// Gets the number of rows/columns in the board
+    /// # Returns
+    /// `usize` - The width and height of the board
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Finds the occupied part of the board from scratch, rather than relying on the bounds tracked while playing
+    /// # Returns
+    /// `Option<(usize, usize, usize, usize)>` - The minimum and maximum occupied column and row indices (as `(min_col, max_col, min_row, max_row)`), or `None` if the board is empty
+    pub(crate) fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        self.occupied_cells().fold(None, |bounds, (row, col)| match bounds {
+            Some((min_col, max_col, min_row, max_row)) => Some((min_col.min(col), max_col.max(col), min_row.min(row), max_row.max(row))),
+            None => Some((col, col, row, row))
+        })
+    }
+
+    /// Limits how wide and tall the occupied part of the board can grow while solving (neither can be more than the board size)
+    /// # Arguments
+    /// * `max_width` - Maximum number of columns (if `None`, then the board size)
+    /// * `max_height` - Maximum number of rows (if `None`, then the board size)
+    pub(crate) fn set_max_span(&mut self, max_width: Option<usize>, max_height: Option<usize>) {
+        self.max_span = (max_width.unwrap_or(self.size).min(self.size), max_height.unwrap_or(self.size).min(self.size));
+    }
+
+    /// Checks whether the occupied part of the board would fit within its maximum span (see `Board::set_max_span`)
+    /// # Arguments
+    /// * `min_col` - Minimum occupied column index
+    /// * `max_col` - Maximum occupied column index
+    /// * `min_row` - Minimum occupied row index
+    /// * `max_row` - Maximum occupied row index
+    /// # Returns
+    /// `bool` - Whether the columns and rows from the minimums to the maximums are no more than the maximum width and height
+    pub(crate) fn fits_span(&self, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> bool {
+        max_col - min_col < self.max_span.0 && max_row - min_row < self.max_span.1
+    }
+
+    /// Gets the cells directly above, below, left and right of a cell that are on the board
+    /// # Arguments
+    /// * `row` - Row index of the cell
+    /// * `col` - Column index of the cell
+    /// # Returns
+    /// `impl Iterator<Item=(usize, usize)>` - The `(row, col)` of each adjacent cell
+    fn adjacent(&self, row: usize, col: usize) -> impl Iterator<Item=(usize, usize)> {
+        let size = self.size;
+        // Subtracting from 0 wraps around to a huge index, which is then filtered out
+        [(row.wrapping_sub(1), col), (row+1, col), (row, col.wrapping_sub(1)), (row, col+1)].into_iter().filter(move |(r, c)| *r < size && *c < size)
+    }
+
+    /// Gets whether any cell directly adjacent to a cell is occupied
+    /// # Arguments
+    /// * `row` - Row index of the cell
+    /// * `col` - Column index of the cell
+    /// # Returns
+    /// `bool` - Whether a tile is directly above, below, left or right of `(row, col)`
+    fn has_occupied_neighbour(&self, row: usize, col: usize) -> bool {
+        self.adjacent(row, col).any(|(r, c)| !self.get_val(r, c).is_empty())
+    }
+
+    /// Updates the set of empty cells bordering the tiles after a cell is set or cleared
+    /// # Arguments
+    /// * `row` - Row index of the cell that changed
+    /// * `col` - Column index of the cell that changed
+    fn update_border(&mut self, row: usize, col: usize) {
+        if self.get_val(row, col).is_empty() {
+            if self.has_occupied_neighbour(row, col) {
+                self.border.insert((row, col));
+            }
+            // Neighbours that only bordered this tile no longer border any
+            for (r, c) in self.adjacent(row, col) {
+                if self.get_val(r, c).is_empty() && !self.has_occupied_neighbour(r, c) {
+                    self.border.remove(&(r, c));
+                }
+            }
+        }
+        else {
+            self.border.remove(&(row, col));
+            for (r, c) in self.adjacent(row, col) {
+                if self.get_val(r, c).is_empty() {
+                    self.border.insert((r, c));
+                }
+            }
+        }
+    }
+
+    /// Gets the empty cells that are directly adjacent to at least one tile, i.e. where a single letter could be played
+    /// # Arguments
+    /// * `min_col` - Minimum occupied column index
+    /// * `max_col` - Maximum occupied column index
+    /// * `min_row` - Minimum occupied row index
+    /// * `max_row` - Maximum occupied row index
+    /// # Returns
+    /// `impl Iterator<Item=(usize, usize)>` - The `(row, col)` of each such cell within one cell of the occupied area, row by row
+    pub(crate) fn iter_empty_adjacent(&self, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> impl Iterator<Item=(usize, usize)> {
+        let mut cells: Vec<(usize, usize)> = self.border.iter().copied().filter(|(row, col)| (min_row.saturating_sub(1)..=max_row+1).contains(row) && (min_col.saturating_sub(1)..=max_col+1).contains(col)).collect();
+        // The set's order isn't stable between runs, so sort to keep solving deterministic
+        cells.sort_unstable();
+        cells.into_iter()
+    }
+
+    /// Gets part of a row of the board
+    /// # Arguments
+    /// * `row` - Row index to read
+    /// * `min_col` - First column to read
+    /// * `max_col` - Last column to read
+    /// # Returns
+    /// `Vec<Cell>` - The cells of `row` from `min_col` to `max_col` (inclusive)
+    /// # Panics
+    /// If any of the cells are out-of-bounds
+    pub(crate) fn get_row(&self, row: usize, min_col: usize, max_col: usize) -> Vec<Cell> {
+        (min_col..=max_col).map(|col| self.get_val(row, col)).collect()
+    }
+
+    /// Gets part of a column of the board
+    /// # Arguments
+    /// * `col` - Column index to read
+    /// * `min_row` - First row to read
+    /// * `max_row` - Last row to read
+    /// # Returns
+    /// `Vec<Cell>` - The cells of `col` from `min_row` to `max_row` (inclusive)
+    /// # Panics
+    /// If any of the cells are out-of-bounds
+    pub(crate) fn get_col(&self, col: usize, min_row: usize, max_row: usize) -> Vec<Cell> {
+        (min_row..=max_row).map(|row| self.get_val(row, col)).collect()
+    }
+
+    /// Iterates over every word (i.e. maximal horizontal or vertical run of two or more letters, or diagonal run of at least `MIN_DIAGONAL_WORD_LENGTH` with the `diagonal` feature) within the given bounds
+    /// 
+    /// Words crossing at a cell are each yielded separately
+    /// # Arguments
+    /// * `min_col` - Minimum occupied column index
+    /// * `max_col` - Maximum occupied column index
+    /// * `min_row` - Minimum occupied row index
+    /// * `max_row` - Maximum occupied row index
+    /// # Returns
+    /// `Iterator` of `(word, direction, start_row, start_col)` tuples, horizontal words first (and diagonal words last)
+    pub fn iter_words(&self, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> impl Iterator<Item = (Word, Direction, usize, usize)> + '_ {
+        let rows = (min_row..=max_row).flat_map(move |row| self.words_in_line((min_col..=max_col).map(move |col| (row, col)), Direction::Horizontal));
+        let cols = (min_col..=max_col).flat_map(move |col| self.words_in_line((min_row..=max_row).map(move |row| (row, col)), Direction::Vertical));
+        let words = rows.chain(cols);
+        #[cfg(feature = "diagonal")]
+        let words = words.chain(self.iter_diagonal_words(min_col, max_col, min_row, max_row));
+        words
+    }
+
+    /// Iterates over every diagonal word (i.e. maximal diagonal run of at least `MIN_DIAGONAL_WORD_LENGTH` letters) within the given bounds, each read from left to right
+    /// # Arguments
+    /// * `min_col` - Minimum occupied column index
+    /// * `max_col` - Maximum occupied column index
+    /// * `min_row` - Minimum occupied row index
+    /// * `max_row` - Maximum occupied row index
+    /// # Returns
+    /// `Iterator` of `(word, direction, start_row, start_col)` tuples, words going down to the right first
+    #[cfg(feature = "diagonal")]
+    fn iter_diagonal_words(&self, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> impl Iterator<Item = (Word, Direction, usize, usize)> + '_ {
+        // Each diagonal starts from the top (or bottom) row or the left column
+        let down = (min_col..=max_col).map(move |col| (min_row, col)).chain((min_row+1..=max_row).map(move |row| (row, min_col)))
+            .flat_map(move |(row, col)| self.words_in_line((0..=(max_row-row).min(max_col-col)).map(move |i| (row+i, col+i)), Direction::Diagonal(1, 1)));
+        let up = (min_col..=max_col).map(move |col| (max_row, col)).chain((min_row..max_row).map(move |row| (row, min_col)))
+            .flat_map(move |(row, col)| self.words_in_line((0..=(row-min_row).min(max_col-col)).map(move |i| (row-i, col+i)), Direction::Diagonal(1, -1)));
+        down.chain(up).filter(|(word, ..)| word.len() >= MIN_DIAGONAL_WORD_LENGTH)
+    }
+
+    /// Gets the words along a single line of the board
+    /// # Arguments
+    /// * `cells` - The `(row, col)` indices of the line, in order
+    /// * `direction` - The `Direction` of the line
+    /// # Returns
+    /// `Vec` of `(word, direction, start_row, start_col)` tuples
+    fn words_in_line(&self, cells: impl Iterator<Item = (usize, usize)>, direction: Direction) -> Vec<(Word, Direction, usize, usize)> {
+        let mut words = Vec::new();
+        let mut current_letters: Word = Vec::with_capacity(MAX_WORD_LENGTH);
+        let mut start = (0, 0);
+        for (row, col) in cells {
+            if let Some(letter) = self.get_val(row, col).letter() {
+                if current_letters.is_empty() {
+                    start = (row, col);
+                }
+                current_letters.push(letter);
+            }
+            else {
+                if current_letters.len() > 1 {
+                    words.push((current_letters.clone(), direction, start.0, start.1));
+                }
+                current_letters.clear();
+            }
+        }
+        if current_letters.len() > 1 {
+            words.push((current_letters, direction, start.0, start.1));
+        }
+        words
+    }
+
+    /// Finds everywhere a word appears on the board within the given bounds when read in one direction
+    /// 
+    /// The word can be part of a longer run of letters (e.g. `CAT` is found in `SCATTER`)
+    /// # Arguments
+    /// * `word` - The word to look for
+    /// * `direction` - The `Direction` to read in (`Horizontal` scans each row, `Vertical` each column)
+    /// * `min_col` - Minimum occupied column index
+    /// * `max_col` - Maximum occupied column index
+    /// * `min_row` - Minimum occupied row index
+    /// * `max_row` - Maximum occupied row index
+    /// # Returns
+    /// `Vec` of the `(row, col)` indices of the first letter of each occurrence (empty if `word` isn't on the board)
+    pub(crate) fn find_word(&self, word: &Word, direction: Direction, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> Vec<(usize, usize)> {
+        let (row_step, col_step) = match direction {
+            Direction::Horizontal => (0, 1),
+            Direction::Vertical => (1, 0),
+            #[cfg(feature = "diagonal")]
+            Direction::Diagonal(dx, dy) => (dy as isize, dx as isize)
+        };
+        let mut found = Vec::new();
+        if word.is_empty() {
+            return found;
+        }
+        for row in min_row..=max_row {
+            for col in min_col..=max_col {
+                let matches = word.iter().enumerate().all(|(i, letter)| {
+                    let cell_row = row.checked_add_signed(row_step*i as isize).filter(|r| (min_row..=max_row).contains(r));
+                    let cell_col = col.checked_add_signed(col_step*i as isize).filter(|c| (min_col..=max_col).contains(c));
+                    match (cell_row, cell_col) {
+                        (Some(r), Some(c)) => self.get_val(r, c).letter() == Some(*letter),
+                        _ => false
+                    }
+                });
+                if matches {
+                    found.push((row, col));
+                }
+            }
+        }
+        found
+    }
+
+    /// Checks whether a word appears anywhere on the board within the given bounds, reading either horizontally or vertically (see `find_word`)
+    /// # Arguments
+    /// * `word` - The word to look for
+    /// * `min_col` - Minimum occupied column index
+    /// * `max_col` - Maximum occupied column index
+    /// * `min_row` - Minimum occupied row index
+    /// * `max_row` - Maximum occupied row index
+    /// # Returns
+    /// `bool` - Whether `word` is on the board
+    pub fn contains_word(&self, word: &Word, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> bool {
+        [Direction::Horizontal, Direction::Vertical].into_iter().any(|direction| !self.find_word(word, direction, min_col, max_col, min_row, max_row).is_empty())
+    }
+
+    /// Plays a word on the board
+    /// # Arguments
+    /// * `word` - The word to be played
+    /// * `row_idx` - The starting row at which to play the word
+    /// * `col_idx` - The starting column at which to play the word
+    /// * `direction` - The `Direction` in which to play the word
+    /// * `letters` - The number of each letter currently in the hand
+    /// * `letters_on_board` - The number of each letter on the board (is modified in-place)
+    /// # Returns
+    /// *`Result` with:*
+    /// * `bool` - Whether the word could be validly played
+    /// * `Vec<(usize, usize)>` - Vector of the indices played in `board`
+    /// * `Letters`- The remaining letters
+    /// * `LetterUsage` - How many letters were used
+    /// 
+    /// *or empty `Err` if out-of-bounds*
+    pub fn play_word(&mut self, word: &Word, row_idx: usize, col_idx: usize, direction: Direction, letters: &Letters, letters_on_board: &mut Letters) -> (bool, Vec<(usize, usize)>, Letters, LetterUsage) {
+        let mut played_indices: Vec<(usize, usize)> = Vec::with_capacity(MAX_WORD_LENGTH);
+        match direction {
+            Direction::Horizontal => {
+                let mut remaining_letters = *letters;
+                if col_idx + word.len() > self.size {
+                    return (false, played_indices, remaining_letters, LetterUsage::Remaining);
+                }
+                // Check if the word will start or end at a letter, or borders one top or bottom
+                let borders_start = !self.get_val(row_idx, col_idx).is_empty();
+                let borders_end = !self.get_val(row_idx, col_idx+word.len()-1).is_empty();
+                let borders_top_or_bottom = if row_idx == 0 {
+                    (col_idx..col_idx+word.len()).any(|c_idx| !self.get_val(1, c_idx).is_empty())
+                }
+                else if row_idx == self.size-1 {
+                    (col_idx..col_idx+word.len()).any(|c_idx| !self.get_val(self.size-2, c_idx).is_empty())
+                }
+                else {
+                    (col_idx..col_idx+word.len()).any(|c_idx| !self.get_val(row_idx-1, c_idx).is_empty() || !self.get_val(row_idx+1, c_idx).is_empty())
+                };
+                if !(borders_start || borders_end || borders_top_or_bottom) {
+                    (false, played_indices, remaining_letters, LetterUsage::Remaining)
+                }
+                else {
+                    let mut entirely_overlaps = true;
+                    for i in 0..word.len() {
+                        if self.get_val(row_idx, col_idx+i).is_empty() {
+                            letters_on_board[word[i]] += 1;
+                            played_indices.push((row_idx, col_idx+i));
+                            entirely_overlaps = false;
+                            if remaining_letters[word[i]] > 0 {
+                                self.set_val(row_idx, col_idx+i, word[i]);
+                                remaining_letters[word[i]] -= 1;
+                            }
+                            else if remaining_letters[BLANK] > 0 {
+                                // Use a blank in place of the missing letter
+                                self.set_blank(row_idx, col_idx+i, word[i]);
+                                remaining_letters[BLANK] -= 1;
+                            }
+                            else {
+                                self.set_val(row_idx, col_idx+i, word[i]);
+                                return (false, played_indices, remaining_letters, LetterUsage::Overused(word[i]));
+                            }
+                        }
+                        else if self.get_val(row_idx, col_idx+i).letter() != Some(word[i]) {
+                            return (false, played_indices, remaining_letters, LetterUsage::Remaining);
+                        }
+                    }
+                    if letters_count(&remaining_letters) == 0 && !entirely_overlaps {
+                        (true, played_indices, remaining_letters, LetterUsage::Finished)
+                    }
+                    else {
+                        (!entirely_overlaps, played_indices, remaining_letters, LetterUsage::Remaining)
+                    }
+                }
+            },
+            Direction::Vertical => {
+                let mut remaining_letters = *letters;
+                if row_idx + word.len() > self.size {
+                    return (false, played_indices, remaining_letters, LetterUsage::Remaining);
+                }
+                // Check if the word will start or end at a letter, or borders one left or right
+                let borders_start = !self.get_val(row_idx, col_idx).is_empty();
+                let borders_end = !self.get_val(row_idx+word.len()-1, col_idx).is_empty();
+                let borders_left_or_right = if col_idx == 0 {
+                    (row_idx..row_idx+word.len()).any(|r_idx| !self.get_val(r_idx, 1).is_empty())
+                }
+                else if col_idx == self.size-1 {
+                    (row_idx..row_idx+word.len()).any(|r_idx| !self.get_val(r_idx, self.size-2).is_empty())
+                }
+                else {
+                    (row_idx..row_idx+word.len()).any(|r_idx| !self.get_val(r_idx, col_idx-1).is_empty() || !self.get_val(r_idx, col_idx+1).is_empty())
+                };
+                if !(borders_start || borders_end || borders_left_or_right) {
+                    (false, played_indices, remaining_letters, LetterUsage::Remaining)
+                }
+                else {
+                    let mut entirely_overlaps = true;
+                    for i in 0..word.len() {
+                        if self.get_val(row_idx+i, col_idx).is_empty() {
+                            letters_on_board[word[i]] += 1;
+                            played_indices.push((row_idx+i, col_idx));
+                            entirely_overlaps = false;
+                            if remaining_letters[word[i]] > 0 {
+                                self.set_val(row_idx+i, col_idx, word[i]);
+                                remaining_letters[word[i]] -= 1;
+                            }
+                            else if remaining_letters[BLANK] > 0 {
+                                // Use a blank in place of the missing letter
+                                self.set_blank(row_idx+i, col_idx, word[i]);
+                                remaining_letters[BLANK] -= 1;
+                            }
+                            else {
+                                self.set_val(row_idx+i, col_idx, word[i]);
+                                return (false, played_indices, remaining_letters, LetterUsage::Overused(word[i]));
+                            }
+                        }
+                        else if self.get_val(row_idx+i, col_idx).letter() != Some(word[i]) {
+                            return (false, played_indices, remaining_letters, LetterUsage::Remaining);
+                        }
+                    }
+                    if letters_count(&remaining_letters) == 0 && !entirely_overlaps {
+                        (true, played_indices, remaining_letters, LetterUsage::Finished)
+                    }
+                    else {
+                        (!entirely_overlaps, played_indices, remaining_letters, LetterUsage::Remaining)
+                    }
+                }
+            },
+            #[cfg(feature = "diagonal")]
+            Direction::Diagonal(dx, dy) => {
+                let mut remaining_letters = *letters;
+                let cells: Option<Vec<(usize, usize)>> = (0..word.len()).map(|i| {
+                    let row = row_idx.checked_add_signed(dy as isize * i as isize)?;
+                    let col = col_idx.checked_add_signed(dx as isize * i as isize)?;
+                    if row < self.size && col < self.size {Some((row, col))} else {None}
+                }).collect();
+                let cells = match cells {
+                    Some(cells) => cells,
+                    None => {
+                        return (false, played_indices, remaining_letters, LetterUsage::Remaining);
+                    }
+                };
+                // Check if the word will overlap a letter, or touches one in any direction
+                if !cells.iter().any(|(row, col)| self.touches_tile(*row, *col)) {
+                    return (false, played_indices, remaining_letters, LetterUsage::Remaining);
+                }
+                let mut entirely_overlaps = true;
+                for (i, (row, col)) in cells.into_iter().enumerate() {
+                    if self.get_val(row, col).is_empty() {
+                        letters_on_board[word[i]] += 1;
+                        played_indices.push((row, col));
+                        entirely_overlaps = false;
+                        if remaining_letters[word[i]] > 0 {
+                            self.set_val(row, col, word[i]);
+                            remaining_letters[word[i]] -= 1;
+                        }
+                        else if remaining_letters[BLANK] > 0 {
+                            // Use a blank in place of the missing letter
+                            self.set_blank(row, col, word[i]);
+                            remaining_letters[BLANK] -= 1;
+                        }
+                        else {
+                            self.set_val(row, col, word[i]);
+                            return (false, played_indices, remaining_letters, LetterUsage::Overused(word[i]));
+                        }
+                    }
+                    else if self.get_val(row, col).letter() != Some(word[i]) {
+                        return (false, played_indices, remaining_letters, LetterUsage::Remaining);
+                    }
+                }
+                if letters_count(&remaining_letters) == 0 && !entirely_overlaps {
+                    (true, played_indices, remaining_letters, LetterUsage::Finished)
+                }
+                else {
+                    (!entirely_overlaps, played_indices, remaining_letters, LetterUsage::Remaining)
+                }
+            }
+        }
+    }
+
+    /// Gets whether a cell holds a tile or has a tile next to it (including diagonally)
+    /// # Arguments
+    /// * `row` - Row index of the cell (must be less than the board size)
+    /// * `col` - Column index of the cell (must be less than the board size)
+    /// # Returns
+    /// `bool` - Whether any cell within one row and column of `(row, col)` is occupied
+    #[cfg(feature = "diagonal")]
+    fn touches_tile(&self, row: usize, col: usize) -> bool {
+        (row.saturating_sub(1)..=(row+1).min(self.size-1)).any(|r| (col.saturating_sub(1)..=(col+1).min(self.size-1)).any(|c| !self.get_val(r, c).is_empty()))
+    }
+
+    /// Undoes a play on the `board`
+    /// # Arguments
+    /// * `board` - `Board` being undone (is modified in-place)
+    /// * `played_indices` - Vector of the indices in `board` that need to be reset
+    /// * `letters_on_board` - Length-26 array of the number of each letter on the board (is modified in place)
+    /// # Returns
+    /// * `Vec<(usize, bool)>` - Vector of the previous values on the `board` for each of `played_indices`, and whether each was a blank
+    pub fn undo_play(&mut self, played_indices: &[(usize, usize)], letters_on_board: &mut Letters) -> Vec<(usize, bool)> {
+        let mut old_letters: Vec<(usize, bool)> = Vec::with_capacity(played_indices.len());
+        for index in played_indices.iter() {
+            let old_val = self.get_val(index.0, index.1).letter().expect("Undoing an empty cell!");
+            letters_on_board[old_val] -= 1;
+            old_letters.push((old_val, self.is_blank(index.0, index.1)));
+            self.clear(index.0, index.1);
+        }
+        old_letters
+    }
+
+    /// Copies the occupied cells of the board to a new board, centered in the middle of the grid
+    /// # Arguments
+    /// * `min_col` - Minimum occupied column index
+    /// * `max_col` - Maximum occupied column index
+    /// * `min_row` - Minimum occupied row index
+    /// * `max_row` - Maximum occupied row index
+    /// # Returns
+    /// * `Board` - The re-centered board
+    /// * `usize` - Minimum occupied column index in the new board
+    /// * `usize` - Maximum occupied column index in the new board
+    /// * `usize` - Minimum occupied row index in the new board
+    /// * `usize` - Maximum occupied row index in the new board
+    pub(crate) fn compact(&self, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> BoardAndIdxs {
+        let new_min_col = self.size/2 - (max_col - min_col).div_ceil(2);
+        let new_min_row = self.size/2 - (max_row - min_row).div_ceil(2);
+        let mut compacted = Board::new(self.size);
+        compacted.max_span = self.max_span;
+        for row in min_row..=max_row {
+            for col in min_col..=max_col {
+                if let Some(letter) = self.get_val(row, col).letter() {
+                    if self.is_blank(row, col) {
+                        compacted.set_blank(new_min_row + row-min_row, new_min_col + col-min_col, letter);
+                    }
+                    else {
+                        compacted.set_val(new_min_row + row-min_row, new_min_col + col-min_col, letter);
+                    }
+                }
+            }
+        }
+        (compacted, new_min_col, new_min_col + max_col-min_col, new_min_row, new_min_row + max_row-min_row)
+    }
+}
+
+/// A `Board` as stored when serializing
+#[derive(Serialize, Deserialize)]
+struct SerializedBoard {
+    /// Number of rows/columns in the board
+    #[serde(default = "default_board_size")]
+    pub size: usize,
+    /// The occupied cells of the board
+    pub tiles: Vec<Tile>
+}
+
+/// Gets the default board size (for deserializing boards that don't specify one)
+fn default_board_size() -> usize {
+    BOARD_SIZE
+}
+
+/// A single occupied cell of a `Board`, as stored when serializing
+#[derive(Serialize, Deserialize)]
+pub struct Tile {
+    /// Row index of the tile
+    pub row: usize,
+    /// Column index of the tile
+    pub col: usize,
+    /// The uppercase letter on the tile
+    pub letter: char,
+    /// Whether the tile is a blank
+    #[serde(default)]
+    blank: bool
+}
+
+impl Serialize for Board {
+    /// Serializes the board as its size and a list of its occupied cells (rather than the full array)
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tiles: Vec<Tile> = Vec::new();
+        for row in 0..self.size {
+            for col in 0..self.size {
+                if let Some(letter) = self.get_val(row, col).letter() {
+                    tiles.push(Tile { row, col, letter: (letter as u8+65) as char, blank: self.is_blank(row, col) });
+                }
+            }
+        }
+        SerializedBoard { size: self.size, tiles }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    /// Deserializes a board from its size and a list of its occupied cells
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Board, D::Error> {
+        let saved = SerializedBoard::deserialize(deserializer)?;
+        if saved.size < MAX_WORD_LENGTH || saved.size > MAX_BOARD_SIZE {
+            return Err(de::Error::custom(format!("The board size must be between {} and {}, not {}", MAX_WORD_LENGTH, MAX_BOARD_SIZE, saved.size)));
+        }
+        let mut board = Board::new(saved.size);
+        for tile in saved.tiles {
+            if tile.row >= saved.size || tile.col >= saved.size {
+                return Err(de::Error::custom(format!("Tile at ({}, {}) is outside of the board", tile.row, tile.col)));
+            }
+            if !tile.letter.is_ascii_uppercase() {
+                return Err(de::Error::custom(format!("Tile at ({}, {}) has invalid letter {}", tile.row, tile.col, tile.letter)));
+            }
+            if tile.blank {
+                board.set_blank(tile.row, tile.col, (tile.letter as usize) - 65);
+            }
+            else {
+                board.set_val(tile.row, tile.col, (tile.letter as usize) - 65);
+            }
+        }
+        Ok(board)
+    }
+}
+
+/// Converts a `board` to a `String`
+/// # Arguments
+/// * `board` - Board to display
+/// * `min_col` - Minimum occupied column index
+/// * `max_col` - Maximum occupied column index
+/// * `min_row` - Minimum occupied row index
+/// * `max_row` - Maximum occupied row index
+/// # Returns
+/// * `String` - `board` in string form (with all numbers converted to letters, and blanks as lowercase letters)
+pub fn board_to_string(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> String {
+    let mut board_string: Vec<char> = Vec::with_capacity((max_row-min_row)*(max_col-min_col));
+    for row in min_row..=max_row {
+        for col in min_col..=max_col {
+            match board.get_val(row, col).letter() {
+                None => board_string.push(' '),
+                Some(letter) if board.is_blank(row, col) => board_string.push((letter as u8+97) as char),
+                Some(letter) => board_string.push((letter as u8+65) as char)
+            }
+        }
+        board_string.push('\n');
+    }
+    let s: String = board_string.iter().collect();
+    s.trim_end().to_owned()
+}
+
+/// Converts a `board` to comma-separated values, one line per row
+/// # Arguments
+/// * `board` - Board to display
+/// * `min_col` - Minimum occupied column index
+/// * `max_col` - Maximum occupied column index
+/// * `min_row` - Minimum occupied row index
+/// * `max_row` - Maximum occupied row index
+/// # Returns
+/// * `String` - `board` in CSV form (with empty cells as empty strings, and blanks as lowercase letters)
+pub fn board_to_csv(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> String {
+    let mut lines: Vec<String> = Vec::with_capacity(max_row-min_row+1);
+    for row in min_row..=max_row {
+        let cells: Vec<String> = (min_col..=max_col).map(|col| {
+            match board.get_val(row, col).letter() {
+                None => String::new(),
+                Some(letter) if board.is_blank(row, col) => ((letter as u8+97) as char).to_string(),
+                Some(letter) => ((letter as u8+65) as char).to_string()
+            }
+        }).collect();
+        lines.push(cells.join(","));
+    }
+    lines.join("\n")
+}
+
+/// Converts a `board` to an SVG image
+/// # Arguments
+/// * `board` - Board to display
+/// * `min_col` - Minimum occupied column index
+/// * `max_col` - Maximum occupied column index
+/// * `min_row` - Minimum occupied row index
+/// * `max_row` - Maximum occupied row index
+/// * `new_idxs` - Indices of tiles to highlight as newly played
+/// * `cell_size` - Width and height of each cell in pixels
+/// # Returns
+/// * `String` - `board` as an SVG document (with blanks as lowercase letters)
+pub fn board_to_svg(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, new_idxs: &HashSet<(usize, usize)>, cell_size: usize) -> String {
+    let width = (max_col-min_col+1)*cell_size;
+    let height = (max_row-min_row+1)*cell_size;
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n");
+    for row in min_row..=max_row {
+        for col in min_col..=max_col {
+            let Some(letter) = board.get_val(row, col).letter() else {
+                continue;
+            };
+            let letter = if board.is_blank(row, col) {(letter as u8+97) as char} else {(letter as u8+65) as char};
+            let fill = if new_idxs.contains(&(row, col)) {SVG_NEW_TILE_COLOR} else {SVG_TILE_COLOR};
+            let x = (col-min_col)*cell_size;
+            let y = (row-min_row)*cell_size;
+            svg.push_str(&format!("<g><rect x=\"{x}\" y=\"{y}\" width=\"{cell_size}\" height=\"{cell_size}\" fill=\"{fill}\" stroke=\"black\"/><text x=\"{}\" y=\"{}\" font-family=\"monospace\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">{letter}</text></g>\n", x+cell_size/2, y+cell_size/2, cell_size*3/5));
+        }
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Converts a `board` to a vector of vectors of strings
+/// # Arguments
+/// * `board` - Board to display
+/// * `min_col` - Minimum occupied column index
+/// * `max_col` - Maximum occupied column index
+/// * `min_row` - Minimum occupied row index
+/// * `max_row` - Maximum occupied row index
+/// # Returns
+/// * `Vec<Vec<String>>` - `board` in vector form (with all numbers converted to letters, and blanks as lowercase letters)
+pub fn board_to_vec(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> Vec<Vec<String>> {
+    let mut board_vec: Vec<Vec<String>> = Vec::with_capacity(max_row-min_row);
+    for row in min_row..=max_row {
+        let mut row_vec: Vec<String> = Vec::with_capacity(max_col-min_col);
+        for col in min_col..=max_col {
+            if let Some(letter) = board.get_val(row, col).letter() {
+                let letter = if board.is_blank(row, col) {(letter as u8+97) as char} else {(letter as u8+65) as char};
+                row_vec.push(letter.to_string());
+            }
+            else {
+                row_vec.push(' '.to_string());
+            }
+        }
+        board_vec.push(row_vec);
+    }
+    board_vec
+}
+
+/// Marks which cells of a board were carried over from the previous board, in the same layout as `board_to_vec`
+/// # Arguments
+/// * `min_col` - Minimum occupied column index
+/// * `max_col` - Maximum occupied column index
+/// * `min_row` - Minimum occupied row index
+/// * `max_row` - Maximum occupied row index
+/// * `previous_idxs` - Indices of the tiles that are in the same place as on the previous board (see `get_board_overlap`)
+/// # Returns
+/// * `Vec<Vec<bool>>` - Whether each cell between the minimum and maximum rows and columns is in `previous_idxs`
+pub fn carried_over_to_vec(min_col: usize, max_col: usize, min_row: usize, max_row: usize, previous_idxs: &HashSet<(usize, usize)>) -> Vec<Vec<bool>> {
+    (min_row..=max_row).map(|row| (min_col..=max_col).map(|col| previous_idxs.contains(&(row, col))).collect()).collect()
+}
+
+/// Converts a vector of vectors of strings (as from `board_to_vec`) to a `board` centered in the middle of the grid
+/// # Arguments
+/// * `grid` - Rows of cells, each either empty/whitespace or a single letter (lowercase for blanks, optionally followed by `*`)
+/// # Returns
+/// *`Result` with:*
+/// * `Board` - The parsed board
+/// * `usize` - Minimum occupied column index
+/// * `usize` - Maximum occupied column index
+/// * `usize` - Minimum occupied row index
+/// * `usize` - Maximum occupied row index
+/// 
+/// *or String `Err` if a cell is invalid, the grid is too large, or no tiles are present*
+pub fn board_from_vec(grid: &[Vec<String>], board_size: usize) -> Result<BoardAndIdxs, String> {
+    let height = grid.len();
+    let width = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+    if height > board_size || width > board_size {
+        return Err(format!("The board must be at most {} by {} cells!", board_size, board_size));
+    }
+    let row_offset = board_size/2 - height/2;
+    let col_offset = board_size/2 - width/2;
+    let mut board = Board::new(board_size);
+    for (i, row) in grid.iter().enumerate() {
+        for (j, cell) in row.iter().enumerate() {
+            let cell = cell.trim().trim_end_matches('*');
+            if cell.is_empty() {
+                continue;
+            }
+            let mut chars = cell.chars();
+            let (row_idx, col_idx) = (row_offset+i, col_offset+j);
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_uppercase() => board.set_val(row_idx, col_idx, (c as usize) - 65),
+                (Some(c), None) if c.is_ascii_lowercase() => board.set_blank(row_idx, col_idx, (c as usize) - 97),
+                _ => {
+                    return Err(format!("Unknown tile \"{}\" at row {}, column {}", cell, i+1, j+1));
+                }
+            }
+        }
+    }
+    let (min_col, max_col, min_row, max_row) = board.bounding_box().ok_or("The board has no tiles on it!")?;
+    Ok((board, min_col, max_col, min_row, max_row))
+}
+
+/// Gets every word (see `Board::iter_words`) on the `board`
+/// # Arguments
+/// * `board` - Board to search
+/// * `min_col` - Minimum occupied column index
+/// * `max_col` - Maximum occupied column index
+/// * `min_row` - Minimum occupied row index
+/// * `max_row` - Maximum occupied row index
+/// # Returns
+/// * `Vec<PlayedWord>` - Each word on `board` (with positions relative to `min_row` and `min_col`), horizontal words first
+pub fn get_words_on_board(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> Vec<PlayedWord> {
+    board.iter_words(min_col, max_col, min_row, max_row).map(|(word, direction, row, col)| {
+        PlayedWord { word: convert_array_to_word(&word), row: row - min_row, col: col - min_col, direction, obscure: false, required: false }
+    }).collect()
+}
+
+/// Checks whether a word is on the board as a whole word (rather than only as part of a longer one)
+/// # Arguments
+/// * `board` - Board to check
+/// * `min_col` - Minimum occupied column index
+/// * `max_col` - Maximum occupied column index
+/// * `min_row` - Minimum occupied row index
+/// * `max_row` - Maximum occupied row index
+/// * `word` - The word to look for
+/// # Returns
+/// * `bool` - Whether `word` is one of the words on `board`
+pub(crate) fn is_word_on_board(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, word: &Word) -> bool {
+    board.iter_words(min_col, max_col, min_row, max_row).any(|(played, _, _, _)| played == *word)
+}
+
+/// Hashes the tiles on the `board` relative to its minimum played row and column, so that the same layout played elsewhere on the board has the same hash
+/// # Arguments
+/// * `board` - Board to hash
+/// * `min_col` - Minimum occupied column index
+/// * `max_col` - Maximum occupied column index
+/// * `min_row` - Minimum occupied row index
+/// * `max_row` - Maximum occupied row index
+/// # Returns
+/// * `u64` - The hash of the layout of `board`
+pub(crate) fn board_layout_hash(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for row in min_row..=max_row {
+        for col in min_col..=max_col {
+            if let Some(letter) = board.get_val(row, col).letter() {
+                (row - min_row, col - min_col, letter).hash(&mut hasher);
+            }
+        }
+    }
+    hasher.finish()
+}
+
+/// Computes a Scrabble-style score for the `board`, as the sum of the letter values of every word on it (blanks are worth nothing)
+/// # Arguments
+/// * `board` - Board to score
+/// * `min_col` - Minimum occupied column index
+/// * `max_col` - Maximum occupied column index
+/// * `min_row` - Minimum occupied row index
+/// * `max_row` - Maximum occupied row index
+/// # Returns
+/// * `u32` - The total score of `board`
+pub fn score_board(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> u32 {
+    get_words_on_board(board, min_col, max_col, min_row, max_row).iter().map(|w| {
+        (0..w.word.len()).map(|i| {
+            let (row, col) = match w.direction {
+                Direction::Horizontal => (min_row + w.row, min_col + w.col + i),
+                Direction::Vertical => (min_row + w.row + i, min_col + w.col),
+                #[cfg(feature = "diagonal")]
+                Direction::Diagonal(dx, dy) => ((min_row + w.row).wrapping_add_signed(dy as isize * i as isize), (min_col + w.col).wrapping_add_signed(dx as isize * i as isize))
+            };
+            if board.is_blank(row, col) {0} else {board.get_val(row, col).letter().map_or(0, |letter| LETTER_VALUES[letter])}
+        }).sum::<u32>()
+    }).sum()
+}
+
+/// Computes statistics about the words and tiles on the `board`
+/// # Arguments
+/// * `board` - Board to summarize
+/// * `min_col` - Minimum occupied column index
+/// * `max_col` - Maximum occupied column index
+/// * `min_row` - Minimum occupied row index
+/// * `max_row` - Maximum occupied row index
+/// # Returns
+/// * `BoardStatistics` - The statistics of `board`
+pub fn board_statistics(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> BoardStatistics {
+    let mut tile_count = 0;
+    for row in min_row..=max_row {
+        for col in min_col..=max_col {
+            if !board.get_val(row, col).is_empty() {
+                tile_count += 1;
+            }
+        }
+    }
+    let mut distinct_words: HashSet<Word> = HashSet::new();
+    let mut horizontal_cells: HashSet<(usize, usize)> = HashSet::new();
+    let mut longest_word: Word = Vec::new();
+    let mut total_length = 0;
+    let mut word_count = 0;
+    let mut crossings = 0;
+    for (word, direction, row, col) in board.iter_words(min_col, max_col, min_row, max_row) {
+        for i in 0..word.len() {
+            match direction {
+                Direction::Horizontal => {horizontal_cells.insert((row, col + i));},
+                Direction::Vertical => if horizontal_cells.contains(&(row + i, col)) {crossings += 1;},
+                #[cfg(feature = "diagonal")]
+                Direction::Diagonal(..) => {}
+            }
+        }
+        total_length += word.len();
+        word_count += 1;
+        if word.len() > longest_word.len() {
+            longest_word = word.clone();
+        }
+        distinct_words.insert(word);
+    }
+    let area = (max_col - min_col + 1) * (max_row - min_row + 1);
+    BoardStatistics {
+        tile_count,
+        distinct_words: distinct_words.len(),
+        longest_word: convert_array_to_word(&longest_word),
+        average_word_length: if word_count == 0 {0.0} else {total_length as f64 / word_count as f64},
+        crossings,
+        area,
+        density: tile_count as f64 / area as f64
+    }
+}
+
+/// Scores how compact a solved board is
+/// # Arguments
+/// * `board` - Board to score
+/// * `min_col` - Minimum occupied column index
+/// * `max_col` - Maximum occupied column index
+/// * `min_row` - Minimum occupied row index
+/// * `max_row` - Maximum occupied row index
+/// # Returns
+/// * `SolutionScore` - The bounding-box area, number of words and average word length of `board`
+pub fn score_solution(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> SolutionScore {
+    let mut total_length = 0;
+    let mut word_count = 0;
+    for (word, _, _, _) in board.iter_words(min_col, max_col, min_row, max_row) {
+        total_length += word.len();
+        word_count += 1;
+    }
+    SolutionScore {
+        area: (max_col - min_col + 1) * (max_row - min_row + 1),
+        word_count,
+        average_word_length: if word_count == 0 {0.0} else {total_length as f64 / word_count as f64}
+    }
+}
+
+/// Gets which indices overlap between `previous_board` and `new_board`
+/// # Arguments
+/// * `previous_board` - The previous board
+/// * `new_board` - The new board
+/// * `previous_min_col` - The minimum played column in `previous_board`
+/// * `previous_max_col` - The maximum played column in `previous_board`
+/// * `previous_min_row` - The minimum played row in `previous_board`
+/// * `previous_max_row` - The maximum played row in `previous_board`
+/// * `new_min_col` - The minimum played column in `new_board`
+/// * `new_max_col` - The maximum played column in `new_board`
+/// * `new_min_row` - The minimum played row in `new_board`
+/// * `new_max_row` - The maximum played row in `new_board`
+/// # Returns
+/// `HashSet` - Set of the indices where `previous_board` and `new_board` have the same value
+pub fn get_board_overlap(previous_board: &Board, new_board: &Board, previous_min_col: usize, previous_max_col: usize, previous_min_row: usize, previous_max_row: usize, new_min_col: usize, new_max_col: usize, new_min_row: usize, new_max_row: usize) -> HashSet<(usize, usize)> {
+    let mut overlapping_idxs: HashSet<(usize, usize)> = HashSet::new();
+    for row in previous_min_row.max(new_min_row)..=previous_max_row.min(new_max_row) {
+        for col in previous_min_col.max(new_min_col)..=previous_max_col.min(new_max_col) {
+            if !previous_board.get_val(row, col).is_empty() && previous_board.get_val(row, col) == new_board.get_val(row, col) {
+                overlapping_idxs.insert((row, col));
+            }
+        }
+    }
+    overlapping_idxs
+}
+
+/// Gets the minimum and maximum occupied row and column from a `board` (assuming that tiles have only been removed)
+/// # Arguments
+/// * `board` - The `Board` to check
+/// * `old_min_col` - The previous mimimum occupied column
+/// * `old_max_col` - The previous maximum occupied column
+/// * `old_min_row` - The previous minimum occupied row
+/// * `old_max_row` - The previous maximum occupied row
+/// * `except_vec` - Vector of indices to ignore when checking if occupied
+/// # Returns
+/// `Option` - `None` if every occupied cell is in `except_vec`, otherwise `Some` tuple of:
+/// * `usize` - New minimum occupied column (never smaller than `old_min_col`)
+/// * `usize` - New maximum occupied column (never greater than `old_max_col`)
+/// * `usize` - New minimum occupied row (never smaller than `old_min_row`)
+/// * `usize` - New maximum occupied row (never greater than `old_max_row`)
+pub fn get_new_min_max(board: &Board, old_min_col: usize, old_max_col: usize, old_min_row: usize, old_max_row: usize, except_vec: &Vec<(usize, usize)>) -> Option<(usize, usize, usize, usize)> {
+    let mut except_idxs: HashSet<&(usize, usize)> = HashSet::with_capacity(except_vec.len());
+    for idxs in except_vec {
+        except_idxs.insert(idxs);
+    }
+    let occupied = |row: usize, col: usize| !except_idxs.contains(&(row, col)) && !board.get_val(row, col).is_empty();
+    // Find the first and last rows with a remaining tile (if there's no first row, then there's nothing left on the board)
+    let min_row = (old_min_row..=old_max_row).find(|row| (old_min_col..=old_max_col).any(|col| occupied(*row, col)))?;
+    let max_row = (min_row..=old_max_row).rev().find(|row| (old_min_col..=old_max_col).any(|col| occupied(*row, col))).unwrap_or(min_row);
+    // Then do the same for the columns, only looking within the new rows
+    let min_col = (old_min_col..=old_max_col).find(|col| (min_row..=max_row).any(|row| occupied(row, *col))).unwrap_or(old_min_col);
+    let max_col = (min_col..=old_max_col).rev().find(|col| (min_row..=max_row).any(|row| occupied(row, *col))).unwrap_or(min_col);
+    Some((min_col, max_col, min_row, max_row))
+}
+
+/// Checks whether the `board` is fully connected, using a breadth-first search within the given bounds
+/// # Arguments
+/// * `board` - Board to check
+/// * `min_col` - The minimum played column
+/// * `max_col` - The maximum played column
+/// * `min_row` - The minimum played row
+/// * `max_row` - The maximum played row
+/// * `ignore_cells` - Locations to ignore
+/// # Returns
+/// * `bool` - Whether every occupied cell of `board` (other than those ignored) is reachable from every other
+pub fn is_connected(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, ignored_cells: &Vec<(usize, usize)>) -> bool {
+    let max_col = max_col.min(board.size()-1);
+    let max_row = max_row.min(board.size()-1);
+    let width = max_col - min_col + 1;
+    // Ignored cells are marked as visited up front, so that the search never reaches them
+    let mut visited = vec![false; width*(max_row - min_row + 1)];
+    for (row, col) in ignored_cells {
+        if (min_row..=max_row).contains(row) && (min_col..=max_col).contains(col) {
+            visited[(row - min_row)*width + (col - min_col)] = true;
+        }
+    }
+    // Count the occupied cells, and find one to start the search from
+    let mut num_tiles = 0usize;
+    let mut start: Option<(usize, usize)> = None;
+    for row in min_row..=max_row {
+        for col in min_col..=max_col {
+            if !board.get_val(row, col).is_empty() && !visited[(row - min_row)*width + (col - min_col)] {
+                num_tiles += 1;
+                if start.is_none() {
+                    start = Some((row, col));
+                }
+            }
+        }
+    }
+    let (start_row, start_col) = match start {
+        Some(s) => s,
+        // An empty board is trivially connected
+        None => return true
+    };
+    let mut queue: VecDeque<(usize, usize)> = VecDeque::with_capacity(num_tiles);
+    queue.push_back((start_row, start_col));
+    visited[(start_row - min_row)*width + (start_col - min_col)] = true;
+    let mut num_visited = 1usize;
+    while let Some((row, col)) = queue.pop_front() {
+        for (r, c) in neighbours(board, row, col) {
+            if r >= min_row && r <= max_row && c >= min_col && c <= max_col {
+                let idx = (r - min_row)*width + (c - min_col);
+                if !visited[idx] && !board.get_val(r, c).is_empty() {
+                    visited[idx] = true;
+                    num_visited += 1;
+                    queue.push_back((r, c));
+                }
+            }
+        }
+    }
+    num_visited == num_tiles
+}
+
+/// Gets the cells that a tile is joined to when they're occupied
+/// # Arguments
+/// * `_board` - Board the tile is on
+/// * `row` - Row of the tile
+/// * `col` - Column of the tile
+/// # Returns
+/// Array of the neighbouring `(row, col)`s; those beyond the top or left edge of the board wrap around to `usize::MAX`, rather than underflowing
+#[cfg(not(feature = "diagonal"))]
+fn neighbours(_board: &Board, row: usize, col: usize) -> [(usize, usize); 4] {
+    [(row.wrapping_sub(1), col), (row+1, col), (row, col.wrapping_sub(1)), (row, col+1)]
+}
+
+/// Gets the cells that a tile is joined to when they're occupied, which include those along a diagonal word through the tile (but not those only touching its corners)
+/// # Arguments
+/// * `board` - Board the tile is on
+/// * `row` - Row of the tile
+/// * `col` - Column of the tile
+/// # Returns
+/// `Iterator` of the neighbouring `(row, col)`s; those beyond the top or left edge of the board wrap around to `usize::MAX`, rather than underflowing
+#[cfg(feature = "diagonal")]
+fn neighbours(board: &Board, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let diagonal = [(1, 1), (-1, 1)].into_iter()
+        .filter(move |(dr, dc)| run_length(board, row, col, *dr, *dc) >= MIN_DIAGONAL_WORD_LENGTH)
+        .flat_map(move |(dr, dc)| [(row.wrapping_add_signed(dr), col.wrapping_add_signed(dc)), (row.wrapping_add_signed(-dr), col.wrapping_add_signed(-dc))]);
+    [(row.wrapping_sub(1), col), (row+1, col), (row, col.wrapping_sub(1)), (row, col+1)].into_iter().chain(diagonal)
+}
+
+/// Checks whether a board that was connected before a word was played across or down still is, by only looking around the word (rather than searching the whole board like `is_connected`)
+/// # Arguments
+/// * `board` - Board to check
+/// * `min_col` - The minimum played column (including the word)
+/// * `max_col` - The maximum played column (including the word)
+/// * `min_row` - The minimum played row (including the word)
+/// * `max_row` - The maximum played row (including the word)
+/// * `start` - `(row, col)` of the first letter of the word
+/// * `end` - `(row, col)` of the last letter of the word
+/// # Returns
+/// * `bool` - Whether the word is all there is on the board or borders a tile outside of it
+fn is_word_connected(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, start: (usize, usize), end: (usize, usize)) -> bool {
+    if (min_row, min_col) == start && (max_row, max_col) == end {
+        return true;
+    }
+    let in_word = |row: usize, col: usize| (start.0..=end.0).contains(&row) && (start.1..=end.1).contains(&col);
+    (start.0..=end.0).flat_map(|row| (start.1..=end.1).map(move |col| (row, col))).any(|(row, col)| {
+        neighbours(board, row, col).into_iter().any(|(r, c)| r >= min_row && r <= max_row && c >= min_col && c <= max_col && !in_word(r, c) && !board.get_val(r, c).is_empty())
+    })
+}
+
+/// Gets a vector of vectors of each part of a word that can be validly removed from the `board`
+/// # Arguments
+/// * `board` - The board to check for removable word parts
+/// * `min_col` - The minimum played column
+/// * `max_col` - The maximum played column
+/// * `min_row` - The minimum played row
+/// * `max_row` - The maximum played row
+/// * `valid_words_trie` - `Trie` of all valid words (to check what's left of a board with only a single word)
+/// # Returns
+/// `Vec` - Vector of length-5 tuples of (vector of length-2 index tuples of the indices of `board` that can be validly removed, new_min_col, new_max_col, new_min_row, new_max_row)
+pub fn get_removable_indices(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words_trie: &Trie) -> Vec<Removable> {
+    // A board with a single row or column holds a single word with nothing crossing it, so the end of the word can be removed so long as what's left is a word (or a single tile)
+    if max_col <= min_col && max_row <= min_row {
+        return Vec::new();
+    }
+    else if max_row <= min_row {
+        return (min_col+1..=max_col)
+            .filter(|start_col| *start_col == min_col+1 || valid_words_trie.contains(&(min_col..*start_col).filter_map(|col| board.get_val(min_row, col).letter()).collect::<Word>()))
+            .map(|start_col| ((start_col..=max_col).map(|col| (min_row, col)).collect(), min_col, start_col-1, min_row, max_row))
+            .collect();
+    }
+    else if max_col <= min_col {
+        return (min_row+1..=max_row)
+            .filter(|start_row| *start_row == min_row+1 || valid_words_trie.contains(&(min_row..*start_row).filter_map(|row| board.get_val(row, min_col).letter()).collect::<Word>()))
+            .map(|start_row| ((start_row..=max_row).map(|row| (row, min_col)).collect(), min_col, max_col, min_row, start_row-1))
+            .collect();
+    }
+    let mut removable: Vec<Removable> = Vec::with_capacity((max_col - min_col) + (max_row - min_row));
+    let mut board_empty = true;
+    // First get horizontal removable word parts
+    for row in min_row..=max_row {
+        let mut current_word_part: Vec<(usize, usize)> = Vec::with_capacity(max_col-min_col);
+        for col in min_col..=max_col {
+            let touching = (row != 0 && !board.get_val(row-1, col).is_empty()) || (row != board.size()-1 && !board.get_val(row+1, col).is_empty());
+            // Tiles of a diagonal word can't be taken away without shortening it
+            #[cfg(feature = "diagonal")]
+            let touching = touching || is_on_diagonal_word(board, row, col);
+            if !board.get_val(row, col).is_empty() && !touching {
+                current_word_part.push((row, col));
+            }
+            else if board.get_val(row, col).is_empty() && !current_word_part.is_empty() {
+                if is_connected(board, min_col, max_col, min_row, max_row, &current_word_part) {
+                    if let Some(new_min_max) = get_new_min_max(board, min_col, max_col, min_row, max_row, &current_word_part) {
+                        removable.push((current_word_part.clone(), new_min_max.0, new_min_max.1, new_min_max.2, new_min_max.3));
+                    }
+                }
+                current_word_part.clear();
+            }
+            else {
+                // There's a non-empty value that didn't get removed (so there's still something on the board)
+                board_empty = false;
+            }
+        }
+        if !current_word_part.is_empty() {
+            if is_connected(board, min_col, max_col, min_row, max_row, &current_word_part) {
+                if let Some(new_min_max) = get_new_min_max(board, min_col, max_col, min_row, max_row, &current_word_part) {
+                    removable.push((current_word_part.clone(), new_min_max.0, new_min_max.1, new_min_max.2, new_min_max.3));
+                }
+            }
+            current_word_part.clear();
+        }
+    }
+    // If the board is empty, that means there was only a single horizontal word that got removed
+    if removable.len() == 1 && board_empty {
+        return Vec::new();
+    }
+    // Then get vertical removable word parts
+    for col in min_col..=max_col {
+        let mut current_word_part: Vec<(usize, usize)> = Vec::with_capacity(max_col-min_col);
+        for row in min_row..=max_row {
+            let touching = (col != 0 && !board.get_val(row, col-1).is_empty()) || (col != board.size()-1 && !board.get_val(row, col+1).is_empty());
+            #[cfg(feature = "diagonal")]
+            let touching = touching || is_on_diagonal_word(board, row, col);
+            if !board.get_val(row, col).is_empty() && !touching {
+                current_word_part.push((row, col));
+            }
+            else if board.get_val(row, col).is_empty() && !current_word_part.is_empty() {
+                if is_connected(board, min_col, max_col, min_row, max_row, &current_word_part) {
+                    if let Some(new_min_max) = get_new_min_max(board, min_col, max_col, min_row, max_row, &current_word_part) {
+                        removable.push((current_word_part.clone(), new_min_max.0, new_min_max.1, new_min_max.2, new_min_max.3));
+                    }
+                }
+                current_word_part.clear();
+            }
+            else {
+                board_empty = false;
+            }
+        }
+        if !current_word_part.is_empty() {
+            if is_connected(board, min_col, max_col, min_row, max_row, &current_word_part) {
+                if let Some(new_min_max) = get_new_min_max(board, min_col, max_col, min_row, max_row, &current_word_part) {
+                    removable.push((current_word_part.clone(), new_min_max.0, new_min_max.1, new_min_max.2, new_min_max.3));
+                }
+            }
+            current_word_part.clear();
+        }
+    }
+    if board_empty {
+        return Vec::new();
+    }
+    removable
+}
+
+/// Gets the letters in a hand that no playable word uses, since such a hand can never be solved
+/// # Arguments
+/// * `letters` - Array of the number of each letter (and blanks) in the hand
+/// * `playable_words` - The words that can be made from `letters` (see `is_makeable`)
+/// # Returns
+/// * `Vec<char>` - The letters in `letters` (in alphabetical order) that aren't in any of `playable_words` (blanks are never stranded, since they can stand in for any letter)
+pub(crate) fn stranded_letters(letters: &Letters, playable_words: &[IndexedWord]) -> Vec<char> {
+    let mut used = [false; 26];
+    for word in playable_words {
+        for letter in word.iter() {
+            used[*letter] = true;
+        }
+    }
+    (0..26).filter(|letter| letters[*letter] > 0 && !used[*letter]).map(|letter| (letter as u8+65) as char).collect()
+}
+
+/// Checks that a `board` is valid after a word is played horizontally, given the specified list of `valid_word`s
+/// Note that this assumes the board was connected before the word was played, and only checks that the word joins onto it (see `is_word_connected`).
+/// # Arguments
+/// * `board` - `Board` being checked
+/// * `min_col` - Minimum x (column) index of the subsection of the `board` to be checked
+/// * `max_col` - Maximum x (column) index of the subsection of the `board` to be checked
+/// * `min_row` - Minimum y (row) index of the subsection of the `board` to be checked
+/// * `max_row` - Maximum y (row) index of the subsection of the `board` to be checked
+/// * `row` - Row of the word played
+/// * `start_col` - Starting column of the word played
+/// * `end_col` - Ending column of the word played
+/// * `valid_words` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// # Returns
+/// `bool` - whether the given `board` is connected and made only of valid words
+pub fn is_board_valid_horizontal(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, row: usize, start_col: usize, end_col: usize, valid_words: &Trie, min_word_length: usize) -> bool {
+    if !is_word_connected(board, min_col, max_col, min_row, max_row, (row, start_col), (row, end_col)) {
+        return false;
+    }
+    let mut current_letters: Vec<usize> = Vec::with_capacity(MAX_WORD_LENGTH);
+    // Check across the row where the word was played
+    let (first_col, last_col) = run_extent(start_col, end_col, min_col, max_col, |col_idx| board.get_val(row, col_idx).is_empty());
+    if !are_runs_valid(&board.get_row(row, first_col, last_col), valid_words, min_word_length, &mut current_letters) {
+        return false;
+    }
+    // Check down each column where a letter was played
+    for col_idx in start_col..=end_col {
+        let (first_row, last_row) = run_extent(row, row, min_row, max_row, |row_idx| board.get_val(row_idx, col_idx).is_empty());
+        if !are_runs_valid(&board.get_col(col_idx, first_row, last_row), valid_words, min_word_length, &mut current_letters) {
+            return false;
+        }
+        #[cfg(feature = "diagonal")]
+        if !are_diagonals_valid(board, row, col_idx, valid_words, min_word_length) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Checks that a `board` is valid after a word is played vertically, given the specified list of `valid_word`s
+/// Note that this assumes the board was connected before the word was played, and only checks that the word joins onto it (see `is_word_connected`).
+/// # Arguments
+/// * `board` - `Board` being checked
+/// * `min_col` - Minimum x (column) index of the subsection of the `board` to be checked
+/// * `max_col` - Maximum x (column) index of the subsection of the `board` to be checked
+/// * `min_row` - Minimum y (row) index of the subsection of the `board` to be checked
+/// * `max_row` - Maximum y (row) index of the subsection of the `board` to be checked
+/// * `start_row` - Starting row of the word played
+/// * `end_row` - Ending row of the word played
+/// * `col` - Column of the word played
+/// * `valid_words` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// # Returns
+/// `bool` - whether the given `board` is connected and made only of valid words
+pub fn is_board_valid_vertical(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, start_row: usize, end_row: usize, col: usize, valid_words: &Trie, min_word_length: usize) -> bool {
+    if !is_word_connected(board, min_col, max_col, min_row, max_row, (start_row, col), (end_row, col)) {
+        return false;
+    }
+    let mut current_letters: Vec<usize> = Vec::with_capacity(MAX_WORD_LENGTH);
+    // Check down the column where the word was played
+    let (first_row, last_row) = run_extent(start_row, end_row, min_row, max_row, |row_idx| board.get_val(row_idx, col).is_empty());
+    if !are_runs_valid(&board.get_col(col, first_row, last_row), valid_words, min_word_length, &mut current_letters) {
+        return false;
+    }
+    // Check across each row where a letter was played
+    for row_idx in start_row..=end_row {
+        let (first_col, last_col) = run_extent(col, col, min_col, max_col, |col_idx| board.get_val(row_idx, col_idx).is_empty());
+        if !are_runs_valid(&board.get_row(row_idx, first_col, last_col), valid_words, min_word_length, &mut current_letters) {
+            return false;
+        }
+        #[cfg(feature = "diagonal")]
+        if !are_diagonals_valid(board, row_idx, col, valid_words, min_word_length) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Checks every word on a `board`, rather than only those through a newly played word (see `is_board_valid_horizontal`)
+/// Note that this does not check if all words are contiguous; this condition must be enforced elsewhere.
+/// # Arguments
+/// * `board` - `Board` being checked
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `valid_words` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// # Returns
+/// `bool` - Whether every word on the board (see `Board::iter_words`) is in `valid_words` and at least `min_word_length` long
+pub fn is_board_valid_full(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words: &Trie, min_word_length: usize) -> bool {
+    validate_entire_board(board, min_col, max_col, min_row, max_row, valid_words, min_word_length).is_ok()
+}
+
+/// A word on the board that isn't valid (see `validate_entire_board`)
+#[derive(Debug, PartialEq)]
+struct InvalidWord {
+    /// The word itself
+    pub word: String,
+    /// The direction the word runs in
+    pub direction: Direction,
+    /// The row of the word's first letter
+    pub row: usize,
+    /// The column of the word's first letter
+    pub col: usize
+}
+
+impl InvalidWord {
+    /// Checks whether the word runs through a cell
+    /// # Arguments
+    /// * `row` - Row of the cell
+    /// * `col` - Column of the cell
+    /// # Returns
+    /// `bool` - Whether one of the word's letters is at (`row`, `col`)
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        let len = self.word.len();
+        match self.direction {
+            Direction::Horizontal => row == self.row && col >= self.col && col < self.col+len,
+            Direction::Vertical => col == self.col && row >= self.row && row < self.row+len,
+            #[cfg(feature = "diagonal")]
+            Direction::Diagonal(dx, dy) => (0..len).any(|i| (self.row.wrapping_add_signed(dy as isize * i as isize), self.col.wrapping_add_signed(dx as isize * i as isize)) == (row, col))
+        }
+    }
+}
+
+/// Finds the invalid words on a `board` by reading off every run of letters, without any of the shortcuts taken by `is_board_valid_horizontal` and `is_board_valid_vertical`
+/// # Arguments
+/// * `board` - `Board` being checked
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `valid_words` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// # Returns
+/// `impl Iterator<Item = InvalidWord>` - Each word (see `Board::iter_words`) that isn't in `valid_words` or is shorter than `min_word_length`, horizontal words first
+fn find_invalid_words<'a>(board: &'a Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words: &'a Trie, min_word_length: usize) -> impl Iterator<Item = InvalidWord> + 'a {
+    board.iter_words(min_col, max_col, min_row, max_row)
+        .filter(move |(word, ..)| word.len() < min_word_length || !valid_words.contains(word))
+        .map(|(word, direction, row, col)| InvalidWord { word: convert_array_to_word(&word), direction, row, col })
+}
+
+/// Checks every word on a `board` (see `find_invalid_words`)
+/// Note that this does not check if all words are contiguous; this condition must be enforced elsewhere.
+/// # Arguments
+/// * `board` - `Board` being checked
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `valid_words` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// # Returns
+/// Empty `Result` if every word is valid
+///
+/// *or `InvalidWord` `Err` with the first invalid word found*
+fn validate_entire_board(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words: &Trie, min_word_length: usize) -> Result<(), InvalidWord> {
+    match find_invalid_words(board, min_col, max_col, min_row, max_row, valid_words, min_word_length).next() {
+        Some(invalid) => Err(invalid),
+        None => Ok(())
+    }
+}
+
+/// Checks that a play accepted by the incremental validity checks didn't form any invalid words, by checking the whole `board` (for debug assertions)
+/// 
+/// Only words through the newly played tiles are considered, since the board played off of may predate a change of dictionary or minimum word length.
+/// # Arguments
+/// * `board` - `Board` after the play
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `played_indices` - Indices of the tiles placed by the play
+/// * `valid_words` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// # Returns
+/// `bool` - Whether every word through `played_indices` is valid
+pub(crate) fn play_formed_valid_words(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, played_indices: &[(usize, usize)], valid_words: &Trie, min_word_length: usize) -> bool {
+    find_invalid_words(board, min_col, max_col, min_row, max_row, valid_words, min_word_length).all(|invalid| !played_indices.iter().any(|(row, col)| invalid.contains(*row, *col)))
+}
+
+/// Finds how far a run of letters along a row or column extends beyond the given (occupied) cells
+/// # Arguments
+/// * `start` - Index of the first occupied cell along the line
+/// * `end` - Index of the last occupied cell along the line
+/// * `min` - Minimum index along the line to extend to
+/// * `max` - Maximum index along the line to extend to
+/// * `is_empty` - Gets whether the cell at an index along the line is empty
+/// # Returns
+/// `(usize, usize)` - The first and last indices of the run
+fn run_extent(mut start: usize, mut end: usize, min: usize, max: usize, is_empty: impl Fn(usize) -> bool) -> (usize, usize) {
+    while start > min && !is_empty(start-1) {
+        start -= 1;
+    }
+    while end < max && !is_empty(end+1) {
+        end += 1;
+    }
+    (start, end)
+}
+
+/// Checks that each run of two or more letters in a line of the board is a valid word
+/// # Arguments
+/// * `line` - Cells along a row or column (see `Board::get_row` and `Board::get_col`)
+/// * `valid_words` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of a word (a single letter is always allowed, since it's just a tile crossing the line)
+/// * `current_letters` - Buffer to collect the letters of each run into (so that it doesn't need to be allocated for each line)
+/// # Returns
+/// `bool` - Whether every run of letters in `line` is in `valid_words` and at least `min_word_length` long
+pub(crate) fn are_runs_valid(line: &[Cell], valid_words: &Trie, min_word_length: usize, current_letters: &mut Vec<usize>) -> bool {
+    line.split(|cell| cell.is_empty()).filter(|run| run.len() > 1).all(|run| {
+        if run.len() < min_word_length {
+            return false;
+        }
+        current_letters.clear();
+        current_letters.extend(run.iter().filter_map(|cell| cell.letter()));
+        valid_words.contains(current_letters)
+    })
+}
+
+/// Gets the run of letters (i.e. the maximal line of occupied cells) through a cell in the given direction
+/// # Arguments
+/// * `board` - `Board` to read from
+/// * `row` - Row of the cell
+/// * `col` - Column of the cell
+/// * `dr` - Rows moved for each step along the run
+/// * `dc` - Columns moved for each step along the run
+/// # Returns
+/// `Word` - The letters of the run, in the order of the direction (empty if the cell is empty)
+#[cfg(feature = "diagonal")]
+fn run_through(board: &Board, row: usize, col: usize, dr: isize, dc: isize) -> Word {
+    let step = |(r, c): (usize, usize), sign: isize| -> Option<(usize, usize)> {
+        let r = r.checked_add_signed(dr*sign)?;
+        let c = c.checked_add_signed(dc*sign)?;
+        if r < board.size() && c < board.size() && !board.get_val(r, c).is_empty() {Some((r, c))} else {None}
+    };
+    // Walk back to the start of the run, then read forwards
+    let mut start = (row, col);
+    while let Some(prev) = step(start, -1) {
+        start = prev;
+    }
+    let mut run: Word = board.get_val(start.0, start.1).letter().into_iter().collect();
+    let mut cell = start;
+    while let Some(next) = step(cell, 1) {
+        run.extend(board.get_val(next.0, next.1).letter());
+        cell = next;
+    }
+    run
+}
+
+/// Gets the length of the run of letters through a cell in the given direction, without reading it (see `run_through`)
+/// # Arguments
+/// * `board` - `Board` to read from
+/// * `row` - Row of the cell
+/// * `col` - Column of the cell
+/// * `dr` - Rows moved for each step along the run
+/// * `dc` - Columns moved for each step along the run
+/// # Returns
+/// `usize` - The number of letters in the run (`0` if the cell is empty)
+#[cfg(feature = "diagonal")]
+fn run_length(board: &Board, row: usize, col: usize, dr: isize, dc: isize) -> usize {
+    if board.get_val(row, col).is_empty() {
+        return 0;
+    }
+    let count = |sign: isize| (1..).take_while(|i| {
+        match (row.checked_add_signed(dr*sign*i), col.checked_add_signed(dc*sign*i)) {
+            (Some(r), Some(c)) => r < board.size() && c < board.size() && !board.get_val(r, c).is_empty(),
+            _ => false
+        }
+    }).count();
+    1 + count(-1) + count(1)
+}
+
+/// Gets whether a tile is part of a diagonal word (i.e. a diagonal run of at least `MIN_DIAGONAL_WORD_LENGTH` letters)
+/// # Arguments
+/// * `board` - `Board` to read from
+/// * `row` - Row of the tile
+/// * `col` - Column of the tile
+/// # Returns
+/// `bool` - Whether either diagonal run through `(row, col)` is a word
+#[cfg(feature = "diagonal")]
+fn is_on_diagonal_word(board: &Board, row: usize, col: usize) -> bool {
+    run_length(board, row, col, 1, 1) >= MIN_DIAGONAL_WORD_LENGTH || run_length(board, row, col, -1, 1) >= MIN_DIAGONAL_WORD_LENGTH
+}
+
+/// Checks that both diagonal runs through a cell are valid words, if they're long enough to be words at all (see `MIN_DIAGONAL_WORD_LENGTH`)
+/// # Arguments
+/// * `board` - `Board` being checked
+/// * `row` - Row of the cell
+/// * `col` - Column of the cell
+/// * `valid_words` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// # Returns
+/// `bool` - Whether each diagonal run through `(row, col)` is either too short to be a word or in `valid_words` and at least `min_word_length` long
+#[cfg(feature = "diagonal")]
+fn are_diagonals_valid(board: &Board, row: usize, col: usize, valid_words: &Trie, min_word_length: usize) -> bool {
+    // Both diagonals are read from left to right, as they are by `Board::iter_words`
+    [(1, 1), (-1, 1)].into_iter().all(|(dr, dc)| {
+        let run = run_through(board, row, col, dr, dc);
+        run.len() < MIN_DIAGONAL_WORD_LENGTH || (run.len() >= min_word_length && valid_words.contains(&run))
+    })
+}
+
+/// Checks that a `board` is valid after a word is played diagonally, given the specified list of `valid_word`s
+/// Note that this does not check if all words are contiguous; this condition must be enforced elsewhere.
+/// 
+/// Every run through each played letter is checked, across, down and along both diagonals
+/// # Arguments
+/// * `board` - `Board` being checked
+/// * `played_indices` - `(row, col)` indices of the cells where letters were played
+/// * `valid_words` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// # Returns
+/// `bool` - whether the rows, columns and diagonals through each played letter are only made of valid words
+#[cfg(feature = "diagonal")]
+pub(crate) fn is_board_valid_diagonal(board: &Board, played_indices: &[(usize, usize)], valid_words: &Trie, min_word_length: usize) -> bool {
+    let run_valid = |(row, col): (usize, usize), dr: isize, dc: isize| {
+        let run = run_through(board, row, col, dr, dc);
+        run.len() < 2 || (run.len() >= min_word_length && valid_words.contains(&run))
+    };
+    played_indices.iter().all(|cell| run_valid(*cell, 0, 1) && run_valid(*cell, 1, 0) && are_diagonals_valid(board, cell.0, cell.1, valid_words, min_word_length))
+}
+
+/// Enumeration of how many letters have been used
+#[derive(Copy, Clone)]
+pub enum LetterUsage {
+    /// There are still unused letters
+    Remaining,
+    /// More of the given letter has been used than is available
+    Overused(usize),
+    /// All letters have been used
+    Finished
+}
+
+impl fmt::Display for LetterUsage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+       match self {
+            LetterUsage::Remaining => write!(f, "Remaining"),
+            LetterUsage::Overused(letter) => write!(f, "Overused({})", letter),
+            LetterUsage::Finished => write!(f, "Finished")
+        }
+    }
+}
+
+impl fmt::Debug for LetterUsage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LetterUsage::Remaining => write!(f, "Remaining"),
+            LetterUsage::Overused(letter) => write!(f, "Overused({})", letter),
+            LetterUsage::Finished => write!(f, "Finished")
+        }
+    }
+}
+
+/// Enumeration of the direction a word is played
+#[derive(Copy, Clone, PartialEq, Serialize)]
+pub enum Direction {
+    /// The word was played horizontally
+    Horizontal,
+    /// The word was played vertically
+    Vertical,
+    /// The word was played diagonally, each letter being `(dx, dy)` columns and rows on from the last
+    #[cfg(feature = "diagonal")]
+    Diagonal(i8, i8)
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Direction::Vertical => write!(f, "Horizontal"),
+            Direction::Horizontal => write!(f, "Vertical"),
+            #[cfg(feature = "diagonal")]
+            Direction::Diagonal(dx, dy) => write!(f, "Diagonal({}, {})", dx, dy)
+        }
+    }
+}
+
+impl fmt::Debug for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Direction::Vertical => write!(f, "Horizontal"),
+            Direction::Horizontal => write!(f, "Vertical"),
+            #[cfg(feature = "diagonal")]
+            Direction::Diagonal(dx, dy) => write!(f, "Diagonal({}, {})", dx, dy)
+        }
+    }
+}
+
+/// A word played on a solved board
+#[derive(Serialize)]
+pub struct PlayedWord {
+    /// The word itself
+    pub word: String,
+    /// The row of the word's first letter (relative to the top of the solved board)
+    pub row: usize,
+    /// The column of the word's first letter (relative to the left of the solved board)
+    pub col: usize,
+    /// The direction the word is played in
+    pub direction: Direction,
+    /// Whether the word isn't in the short dictionary of the current language (see `Solution::mark_obscure_words`)
+    pub obscure: bool,
+    /// Whether the word is the one the solution was required to contain (see `Solution::mark_required_word`)
+    pub(crate) required: bool
+}
+
+/// Statistics about the last solved board, as returned by `get_board_statistics`
+#[derive(Serialize)]
+pub struct BoardStatistics {
+    /// The number of tiles on the board
+    tile_count: usize,
+    /// The number of distinct words on the board
+    distinct_words: usize,
+    /// The longest word on the board (the first found if there is a tie)
+    longest_word: String,
+    /// The mean length of the words on the board
+    pub(crate) average_word_length: f64,
+    /// The number of cells shared by a horizontal and a vertical word
+    crossings: usize,
+    /// The area of the board's bounding box
+    pub(crate) area: usize,
+    /// The fraction of the bounding box filled with tiles
+    density: f64
+}
+
+/// How compact a solved board is, as computed by `score_solution`
+#[derive(Copy, Clone, Serialize)]
+pub struct SolutionScore {
+    /// The area of the board's bounding box
+    pub(crate) area: usize,
+    /// The number of words on the board
+    pub word_count: usize,
+    /// The mean length of the words on the board
+    pub(crate) average_word_length: f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+    use crate::{convert_word_to_array, letters_add};
+    use crate::test_helpers::{place, test_dictionary, test_words};
+
+    #[test]
+    fn crossing_words_reported_once() {
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        place(&mut board, "HAT", 69, 71, Direction::Vertical);
+        let words = get_words_on_board(&board, 70, 72, 69, 71);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words.iter().filter(|w| w.word == "CAT").count(), 1);
+        assert_eq!(words.iter().filter(|w| w.word == "HAT").count(), 1);
+        let cat = words.iter().find(|w| w.word == "CAT").unwrap();
+        assert!(cat.row == 1 && cat.col == 0 && cat.direction == Direction::Horizontal);
+        let hat = words.iter().find(|w| w.word == "HAT").unwrap();
+        assert!(hat.row == 0 && hat.col == 1 && hat.direction == Direction::Vertical);
+    }
+
+    #[test]
+    fn words_ending_at_bounds_are_reported() {
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "QUIXOTIC", 70, 70, Direction::Horizontal);
+        place(&mut board, "ZINC", 67, 77, Direction::Vertical);
+        let words: Vec<String> = get_words_on_board(&board, 70, 77, 67, 70).into_iter().map(|w| w.word).collect();
+        assert_eq!(words, vec!["QUIXOTIC", "ZINC"]);
+    }
+
+    #[test]
+    fn score_counts_crossing_letters_in_each_word() {
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        place(&mut board, "HAT", 69, 71, Direction::Vertical);
+        assert_eq!(score_board(&board, 70, 72, 69, 71), 11);
+        board.set_blank(69, 71, 7);
+        assert_eq!(score_board(&board, 70, 72, 69, 71), 7);
+    }
+
+    #[test]
+    fn statistics_count_tiles_words_and_crossings() {
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        place(&mut board, "CAT", 68, 72, Direction::Vertical);
+        let stats = board_statistics(&board, 70, 72, 68, 70);
+        assert_eq!(stats.tile_count, 5);
+        assert_eq!(stats.distinct_words, 1);
+        assert_eq!(stats.longest_word, "CAT");
+        assert_eq!(stats.average_word_length, 3.0);
+        assert_eq!(stats.crossings, 1);
+        assert_eq!(stats.area, 9);
+        assert_eq!(stats.density, 5.0 / 9.0);
+    }
+
+    #[test]
+    fn board_exported_as_text() {
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        place(&mut board, "HAT", 69, 71, Direction::Vertical);
+        assert_eq!(board_to_string(&board, 70, 72, 69, 71), " H \nCAT\n T");
+        board.set_blank(69, 71, 7);
+        assert_eq!(board_to_string(&board, 70, 72, 69, 71), " h \nCAT\n T");
+    }
+
+    #[test]
+    fn board_exported_as_csv() {
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        place(&mut board, "HAT", 69, 71, Direction::Vertical);
+        assert_eq!(board_to_csv(&board, 70, 72, 69, 71), ",H,\nC,A,T\n,T,");
+    }
+
+    #[test]
+    fn board_parsed_from_grid() {
+        let grid: Vec<Vec<String>> = vec![vec![" ", "H*", " "], vec!["C", "a", "T"], vec!["", "T"]].into_iter().map(|row| row.into_iter().map(String::from).collect()).collect();
+        let (board, min_col, max_col, min_row, max_row) = board_from_vec(&grid, BOARD_SIZE).unwrap();
+        assert_eq!((min_col, max_col, min_row, max_row), (71, 73, 71, 73));
+        assert_eq!(board_to_string(&board, min_col, max_col, min_row, max_row), " H \nCaT\n T");
+        assert!(board.is_blank(72, 72));
+        assert!(board_from_vec(&[vec!["1".to_owned()]], BOARD_SIZE).is_err());
+        assert!(board_from_vec(&[vec![" ".to_owned()]], BOARD_SIZE).is_err());
+    }
+
+    #[test]
+    fn board_exported_as_svg() {
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        place(&mut board, "HAT", 69, 71, Direction::Vertical);
+        let new_idxs: HashSet<(usize, usize)> = [(69, 71), (71, 71)].into_iter().collect();
+        let svg = board_to_svg(&board, 70, 72, 69, 71, &new_idxs, 10);
+        assert!(svg.starts_with("<svg") && svg.contains("viewBox=\"0 0 30 30\""));
+        assert_eq!(svg.matches("<rect").count(), 5);
+        assert_eq!(svg.matches(SVG_NEW_TILE_COLOR).count(), 2);
+        assert!(svg.contains("<rect x=\"10\" y=\"0\""));
+    }
+
+    #[test]
+    fn words_iterated_in_both_directions() {
+        // C A T
+        // . . O
+        // . U P
+        let mut board = Board::new(BOARD_SIZE);
+        for (row, col, letter) in [(10, 10, "C"), (10, 11, "A"), (10, 12, "T"), (11, 12, "O"), (12, 11, "U"), (12, 12, "P")] {
+            board.set_val(row, col, convert_word_to_array(letter)[0]);
+        }
+        let words: Vec<(String, Direction, usize, usize)> = board.iter_words(10, 13, 10, 12).map(|(word, direction, row, col)| (convert_array_to_word(&word), direction, row, col)).collect();
+        let expected = [("CAT".to_owned(), Direction::Horizontal, 10, 10),
+            ("UP".to_owned(), Direction::Horizontal, 12, 11),
+            ("TOP".to_owned(), Direction::Vertical, 10, 12)];
+        assert_eq!(words.len(), expected.len());
+        for (word, exp) in words.iter().zip(expected.iter()) {
+            assert!(word.0 == exp.0 && word.1 == exp.1 && word.2 == exp.2 && word.3 == exp.3, "{} at ({}, {})", word.0, word.2, word.3);
+        }
+        // Single letters aren't words, even at the edge of the bounds
+        assert_eq!(board.iter_words(11, 12, 12, 12).count(), 1);
+        assert_eq!(board.iter_words(10, 10, 10, 10).count(), 0);
+    }
+
+    #[test]
+    fn words_ending_on_board_edge_play_and_validate() {
+        let words: Vec<Word> = ["CAT", "SHOT"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("SHO") {
+            letters[letter] += 1;
+        }
+        // Ending on the last column
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, BOARD_SIZE-1, Direction::Vertical);
+        let (played, indices, _, usage) = board.play_word(&convert_word_to_array("SHOT"), 72, BOARD_SIZE-4, Direction::Horizontal, &letters, &mut [0; 27]);
+        assert!(played && matches!(usage, LetterUsage::Finished));
+        assert_eq!(indices, vec![(72, BOARD_SIZE-4), (72, BOARD_SIZE-3), (72, BOARD_SIZE-2)]);
+        assert!(is_board_valid_horizontal(&board, BOARD_SIZE-4, BOARD_SIZE-1, 70, 72, 72, BOARD_SIZE-4, BOARD_SIZE-1, &trie, 2));
+        // Ending on the last row
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", BOARD_SIZE-1, 70, Direction::Horizontal);
+        let (played, indices, _, usage) = board.play_word(&convert_word_to_array("SHOT"), BOARD_SIZE-4, 72, Direction::Vertical, &letters, &mut [0; 27]);
+        assert!(played && matches!(usage, LetterUsage::Finished));
+        assert_eq!(indices, vec![(BOARD_SIZE-4, 72), (BOARD_SIZE-3, 72), (BOARD_SIZE-2, 72)]);
+        assert!(is_board_valid_vertical(&board, 70, 72, BOARD_SIZE-4, BOARD_SIZE-1, BOARD_SIZE-4, BOARD_SIZE-1, 72, &trie, 2));
+    }
+
+    #[test]
+    fn new_bounds_include_tiles_on_old_bounds() {
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CATS", 70, 70, Direction::Horizontal);
+        place(&mut board, "SEA", 70, 73, Direction::Vertical);
+        // Nothing removed
+        assert_eq!(get_new_min_max(&board, 70, 73, 70, 72, &vec![]), Some((70, 73, 70, 72)));
+        // The E is on the maximum column, so the board still reaches row 71
+        assert_eq!(get_new_min_max(&board, 70, 73, 70, 72, &vec![(72, 73)]), Some((70, 73, 70, 71)));
+        // The A of SEA is alone on the maximum row, on the maximum column
+        assert_eq!(get_new_min_max(&board, 70, 73, 70, 72, &vec![(71, 73), (72, 73)]), Some((70, 73, 70, 70)));
+        // The C is on the minimum row and column
+        assert_eq!(get_new_min_max(&board, 70, 73, 70, 72, &vec![(70, 70)]), Some((71, 73, 70, 72)));
+        assert_eq!(get_new_min_max(&board, 70, 73, 70, 72, &vec![(70, 70), (70, 71), (70, 72)]), Some((73, 73, 70, 72)));
+        // Removing every tile leaves no bounds at all
+        let everything: Vec<(usize, usize)> = (70..=73).map(|col| (70, col)).chain([(71, 73), (72, 73)]).collect();
+        assert_eq!(get_new_min_max(&board, 70, 73, 70, 72, &everything), None);
+    }
+
+    #[test]
+    fn cells_only_hold_letters() {
+        assert_eq!(Cell::new(0).and_then(Cell::letter), Some(0));
+        assert_eq!(Cell::new(25).and_then(Cell::letter), Some(25));
+        assert!(Cell::new(BLANK).is_none());
+        assert!(Cell::new(30).is_none());
+        assert!(Cell::EMPTY.is_empty() && Cell::EMPTY.letter().is_none());
+        let mut board = Board::new(BOARD_SIZE);
+        assert!(board.get_val(70, 70).is_empty());
+        board.set_blank(70, 70, 7);
+        assert_eq!(board.get_val(70, 70).letter(), Some(7));
+        board.clear(70, 70);
+        assert!(board.get_val(70, 70).is_empty() && !board.is_blank(70, 70));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not a letter!")]
+    fn out_of_range_letter_not_placed() {
+        Board::new(BOARD_SIZE).set_val(70, 70, 30);
+    }
+
+    #[test]
+    fn compacting_keeps_tiles_and_words() {
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 1, 2, Direction::Horizontal);
+        place(&mut board, "ARE", 1, 3, Direction::Vertical);
+        board.set_blank(4, 3, 18);
+        let before = board_to_vec(&board, 2, 4, 1, 4);
+        let (compacted, min_col, max_col, min_row, max_row) = board.compact(2, 4, 1, 4);
+        assert_eq!((min_col, max_col, min_row, max_row), (BOARD_SIZE/2 - 1, BOARD_SIZE/2 + 1, BOARD_SIZE/2 - 2, BOARD_SIZE/2 + 1));
+        assert_eq!(board_to_vec(&compacted, min_col, max_col, min_row, max_row), before);
+        assert!(compacted.is_blank(max_row, min_col + 1));
+        // Nothing is left outside of the new bounds
+        assert_eq!(compacted.iter_words(0, BOARD_SIZE-1, 0, BOARD_SIZE-1).count(), 2);
+    }
+
+    #[test]
+    fn words_found_within_longer_runs() {
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "SCATTER", 70, 70, Direction::Horizontal);
+        place(&mut board, "TOE", 70, 73, Direction::Vertical);
+        let find = |word: &str, direction: Direction| board.find_word(&convert_word_to_array(word), direction, 70, 76, 70, 72);
+        assert_eq!(find("CAT", Direction::Horizontal), vec![(70, 71)]);
+        assert_eq!(find("TOE", Direction::Vertical), vec![(70, 73)]);
+        assert_eq!(find("TOE", Direction::Horizontal), vec![]);
+        assert_eq!(find("T", Direction::Horizontal), vec![(70, 73), (70, 74)]);
+        // Words running past the bounds aren't found
+        assert_eq!(board.find_word(&convert_word_to_array("TER"), Direction::Horizontal, 70, 75, 70, 72), vec![]);
+        assert!(board.contains_word(&convert_word_to_array("TOE"), 70, 76, 70, 72));
+        assert!(board.contains_word(&convert_word_to_array("ATT"), 70, 76, 70, 72));
+        assert!(!board.contains_word(&convert_word_to_array("TACT"), 70, 76, 70, 72));
+    }
+
+    #[test]
+    fn board_rows_and_columns_read() {
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 10, 10, Direction::Horizontal);
+        place(&mut board, "AX", 10, 11, Direction::Vertical);
+        let cells = |s: &str| s.chars().map(|c| if c == ' ' {Cell::EMPTY} else {Cell::new(convert_word_to_array(&c.to_string())[0]).unwrap()}).collect::<Vec<Cell>>();
+        assert_eq!(board.get_row(10, 9, 13), cells(" CAT "));
+        assert_eq!(board.get_row(11, 10, 11), cells(" X"));
+        assert_eq!(board.get_col(11, 9, 12), cells(" AX "));
+        assert_eq!(board.get_col(12, 10, 10), cells("T"));
+        // Each run of two or more letters is checked, but single letters aren't
+        let words: Vec<Word> = ["CAT", "AX"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        assert!(are_runs_valid(&cells("CAT A AX"), &trie, 2, &mut Vec::new()));
+        assert!(!are_runs_valid(&cells("CAT XA"), &trie, 2, &mut Vec::new()));
+        assert_eq!(run_extent(11, 11, 0, 20, |col| board.get_val(10, col).is_empty()), (10, 12));
+        assert_eq!(run_extent(11, 11, 11, 20, |col| board.get_val(10, col).is_empty()), (11, 12));
+    }
+
+    #[test]
+    fn empty_adjacent_cells_follow_the_tiles() {
+        let mut board = Board::new(BOARD_SIZE);
+        let brute_force = |board: &Board| {
+            let mut cells = Vec::new();
+            for row in 0..board.size() {
+                for col in 0..board.size() {
+                    if board.get_val(row, col).is_empty() && board.has_occupied_neighbour(row, col) {
+                        cells.push((row, col));
+                    }
+                }
+            }
+            cells
+        };
+        for (i, letter) in convert_word_to_array("CAT").into_iter().enumerate() {
+            board.set_val(0, i, letter);
+        }
+        board.set_blank(1, 2, 0);
+        assert_eq!(board.iter_empty_adjacent(0, 2, 0, 1).collect::<Vec<(usize, usize)>>(), vec![(0, 3), (1, 0), (1, 1), (1, 3), (2, 2)]);
+        board.clear(1, 2);
+        board.clear(0, 0);
+        assert_eq!(board.iter_empty_adjacent(0, 2, 0, 0).collect::<Vec<(usize, usize)>>(), brute_force(&board));
+        assert_eq!(brute_force(&board), vec![(0, 0), (0, 3), (1, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn incremental_validity_matches_full_board() {
+        let words: Vec<Word> = test_words().into_iter().filter(|word| (2..=6).contains(&word.len())).collect();
+        let trie = Trie::from_words(&words);
+        // A small board, so that plays often reach its edges
+        let size = 12;
+        let mut letters = [100usize; 27];
+        letters[BLANK] = 0;
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut board = Board::new(size);
+            let first = &words[rng.gen_range(0..words.len())];
+            let (row, col) = (rng.gen_range(0..size), rng.gen_range(0..=size-first.len()));
+            place(&mut board, &convert_array_to_word(first), row, col, Direction::Horizontal);
+            let (mut min_col, mut max_col, mut min_row, mut max_row) = (col, col+first.len()-1, row, row);
+            let mut letters_on_board = [0usize; 27];
+            for letter in first {
+                letters_on_board[*letter] += 1;
+            }
+            for _ in 0..500 {
+                let word = &words[rng.gen_range(0..words.len())];
+                let horizontal = rng.gen_bool(0.5);
+                let (row, col) = if horizontal {
+                    (rng.gen_range(min_row.saturating_sub(1)..=(size-1).min(max_row+1)), rng.gen_range(min_col.saturating_sub(word.len())..=(size-1).min(max_col+1)))
+                }
+                else {
+                    (rng.gen_range(min_row.saturating_sub(word.len())..=(size-1).min(max_row+1)), rng.gen_range(min_col.saturating_sub(1)..=(size-1).min(max_col+1)))
+                };
+                let direction = if horizontal {Direction::Horizontal} else {Direction::Vertical};
+                let res = board.play_word(word, row, col, direction, &letters, &mut letters_on_board);
+                if !res.0 {
+                    board.undo_play(&res.1, &mut letters_on_board);
+                    assert_eq!(board.bounding_box(), Some((min_col, max_col, min_row, max_row)));
+                    continue;
+                }
+                let (end_row, end_col) = if horizontal {(row, col+word.len()-1)} else {(row+word.len()-1, col)};
+                let (new_min_col, new_max_col, new_min_row, new_max_row) = (min_col.min(col), max_col.max(end_col), min_row.min(row), max_row.max(end_row));
+                let incremental = if horizontal {
+                    is_board_valid_horizontal(&board, new_min_col, new_max_col, new_min_row, new_max_row, row, col, end_col, &trie, 2)
+                }
+                else {
+                    is_board_valid_vertical(&board, new_min_col, new_max_col, new_min_row, new_max_row, row, end_row, col, &trie, 2)
+                };
+                let full = validate_entire_board(&board, new_min_col, new_max_col, new_min_row, new_max_row, &trie, 2);
+                assert_eq!(incremental, full.is_ok(), "Playing {} at ({}, {}) with seed {}: {:?}\n{:?}", convert_array_to_word(word), row, col, seed, full, board_to_vec(&board, new_min_col, new_max_col, new_min_row, new_max_row));
+                if incremental {
+                    (min_col, max_col, min_row, max_row) = (new_min_col, new_max_col, new_min_row, new_max_row);
+                }
+                else {
+                    board.undo_play(&res.1, &mut letters_on_board);
+                }
+                // The bounds tracked while playing should always be the true bounds of the tiles
+                assert_eq!(board.bounding_box(), Some((min_col, max_col, min_row, max_row)));
+            }
+            assert!(validate_entire_board(&board, min_col, max_col, min_row, max_row, &trie, 2).is_ok());
+        }
+        // The first invalid word found is reported with where it is
+        let mut board = Board::new(size);
+        place(&mut board, "CAT", 2, 2, Direction::Horizontal);
+        place(&mut board, "XQ", 3, 4, Direction::Vertical);
+        assert_eq!(validate_entire_board(&board, 2, 4, 2, 4, &trie, 2), Err(InvalidWord { word: "TXQ".to_owned(), direction: Direction::Vertical, row: 2, col: 4 }));
+        assert_eq!(validate_entire_board(&board, 2, 4, 2, 2, &trie, 4), Err(InvalidWord { word: "CAT".to_owned(), direction: Direction::Horizontal, row: 2, col: 2 }));
+    }
+
+    #[test]
+    fn plays_undone_exactly() {
+        // Every cell's tile (and whether it's a blank), plus the cells bordering the tiles
+        let snapshot = |board: &Board| {
+            let cells: Vec<(Option<usize>, bool)> = (0..board.size()*board.size()).map(|idx| (board.get_val(idx / board.size(), idx % board.size()).letter(), board.is_blank(idx / board.size(), idx % board.size()))).collect();
+            let mut border: Vec<(usize, usize)> = board.border.iter().copied().collect();
+            border.sort_unstable();
+            (cells, border)
+        };
+        // A small board, so that words often start or end at its edges
+        let size = 8;
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..5000 {
+            let mut board = Board::new(size);
+            let mut letters_on_board = [0usize; 27];
+            for row in 0..size {
+                for col in 0..size {
+                    if rng.gen_bool(0.3) {
+                        // Only a few different letters, so that words often match the tiles they cross
+                        let letter = rng.gen_range(0..4);
+                        if rng.gen_bool(0.1) {board.set_blank(row, col, letter)} else {board.set_val(row, col, letter)};
+                        letters_on_board[letter] += 1;
+                    }
+                }
+            }
+            let mut hand = [0usize; 27];
+            for count in hand.iter_mut().take(4) {
+                *count = rng.gen_range(0..4);
+            }
+            hand[BLANK] = rng.gen_range(0..2);
+            let word: Word = (0..rng.gen_range(2..=size)).map(|_| rng.gen_range(0..4)).collect();
+            let (row, col) = (rng.gen_range(0..size), rng.gen_range(0..size));
+            let direction = if rng.gen_bool(0.5) {Direction::Horizontal} else {Direction::Vertical};
+            let before = snapshot(&board);
+            let letters_before = letters_on_board;
+            let (_, played_indices, remaining, usage) = board.play_word(&word, row, col, direction, &hand, &mut letters_on_board);
+            // Each played tile came from the hand (as its letter or a blank), except the one the hand ran out of
+            let mut used = [0usize; 27];
+            for (i, (r, c)) in played_indices.iter().enumerate() {
+                assert!(before.0[r*size + c].0.is_none(), "Played over the tile at ({}, {})", r, c);
+                let overused = i == played_indices.len()-1 && matches!(usage, LetterUsage::Overused(_));
+                if !overused {
+                    used[if board.is_blank(*r, *c) {BLANK} else {board.get_val(*r, *c).letter().unwrap()}] += 1;
+                }
+            }
+            assert_eq!(letters_add(&remaining, &used), hand, "Playing {} at ({}, {})", convert_array_to_word(&word), row, col);
+            // Undoing the play, whether or not it succeeded, restores the board exactly
+            board.undo_play(&played_indices, &mut letters_on_board);
+            assert_eq!(letters_on_board, letters_before);
+            assert!(snapshot(&board) == before, "Undoing {} at ({}, {}) changed the board", convert_array_to_word(&word), row, col);
+        }
+    }
+
+    #[test]
+    fn bounding_box_found_from_tiles() {
+        let mut board = Board::new(BOARD_SIZE);
+        assert_eq!(board.bounding_box(), None);
+        board.set_val(10, 12, 0);
+        assert_eq!(board.bounding_box(), Some((12, 12, 10, 10)));
+        place(&mut board, "CAT", 8, 11, Direction::Vertical);
+        place(&mut board, "DOG", 0, BOARD_SIZE-3, Direction::Horizontal);
+        assert_eq!(board.bounding_box(), Some((11, BOARD_SIZE-1, 0, 10)));
+        board.undo_play(&[(0, BOARD_SIZE-3), (0, BOARD_SIZE-2), (0, BOARD_SIZE-1)], &mut [3usize; 27]);
+        assert_eq!(board.bounding_box(), Some((11, 12, 8, 10)));
+        // User-submitted layouts are centered, with their bounds worked out from the tiles
+        let grid: Vec<Vec<String>> = vec![vec![" ", " ", " "], vec![" ", "A", "T"], vec![" ", " ", " "]].into_iter().map(|row| row.into_iter().map(String::from).collect()).collect();
+        let (board, min_col, max_col, min_row, max_row) = board_from_vec(&grid, BOARD_SIZE).unwrap();
+        assert_eq!(board.bounding_box(), Some((min_col, max_col, min_row, max_row)));
+        assert_eq!((max_col-min_col, max_row-min_row), (1, 0));
+        assert!(board_from_vec(&[vec![" ".to_owned()]], BOARD_SIZE).is_err());
+    }
+
+    #[test]
+    fn split_boards_not_connected() {
+        let mut board = Board::new(BOARD_SIZE);
+        // Empty and single-tile boards are trivially connected
+        assert!(is_connected(&board, 10, 10, 10, 10, &Vec::new()));
+        board.set_val(10, 10, 0);
+        assert!(is_connected(&board, 10, 10, 10, 10, &Vec::new()));
+        for (row, col) in [(10, 11), (10, 12), (11, 12), (12, 12)] {
+            board.set_val(row, col, 0);
+        }
+        assert!(is_connected(&board, 10, 12, 10, 12, &Vec::new()));
+        // A second island, not touching the first even at a corner
+        for (row, col) in [(14, 10), (14, 11)] {
+            board.set_val(row, col, 0);
+        }
+        assert!(!is_connected(&board, 10, 12, 10, 14, &Vec::new()));
+        // Joining the islands makes the board connected again
+        for row in [13, 14] {
+            board.set_val(row, 12, 0);
+        }
+        assert!(is_connected(&board, 10, 12, 10, 14, &Vec::new()));
+        // Tiles at the edges of the board don't underflow
+        let mut board = Board::new(BOARD_SIZE);
+        board.set_val(0, 0, 0);
+        board.set_val(BOARD_SIZE-1, BOARD_SIZE-1, 0);
+        assert!(!is_connected(&board, 0, BOARD_SIZE-1, 0, BOARD_SIZE-1, &Vec::new()));
+    }
+
+    #[test]
+    fn disconnected_play_rejected() {
+        let (_, trie, _) = test_dictionary();
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        // DOG forms no invalid words, but a row apart from CAT it's a second island
+        let mut apart = board.clone();
+        place(&mut apart, "DOG", 72, 70, Direction::Horizontal);
+        assert!(is_board_valid_full(&apart, 70, 72, 70, 72, &trie, 2));
+        assert!(!is_board_valid_horizontal(&apart, 70, 72, 70, 72, 72, 70, 72, &trie, 2));
+        let mut apart = board.clone();
+        place(&mut apart, "DOG", 70, 74, Direction::Vertical);
+        assert!(!is_board_valid_vertical(&apart, 70, 74, 70, 72, 70, 72, 74, &trie, 2));
+        // Down from the T of CAT it's joined on
+        let mut joined = board.clone();
+        place(&mut joined, "TOE", 70, 72, Direction::Vertical);
+        assert!(is_board_valid_vertical(&joined, 70, 72, 70, 72, 70, 72, 72, &trie, 2));
+        // A word alone on the board is connected
+        assert!(is_board_valid_horizontal(&board, 70, 72, 70, 70, 70, 70, 72, &trie, 2));
+    }
+}
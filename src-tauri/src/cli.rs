@@ -0,0 +1,117 @@
+//! Solving a hand from the command line without launching the app, e.g. `bananagrams_solver --letters AABDEILNORSTU --json`
+
+use bananagrams_solver::{letter_index, string_to_letters, Letters};
+use bananagrams_solver::solver::{SolveConfig, Solver, SolverError};
+
+/// Usage shown when the arguments can't be parsed
+const USAGE: &str = "Usage: bananagrams_solver --letters <HAND> [--long-dict] [--max-words <N>] [--filter-board-letters <N>] [--json]";
+
+/// Options given on the command line
+#[derive(Debug, PartialEq)]
+pub(crate) struct CliOptions {
+    /// The number of each letter (and blanks) in the hand
+    pub(crate) letters: Letters,
+    /// Whether to use the long dictionary rather than the short one
+    pub(crate) long_dictionary: bool,
+    /// Maximum number of words to check before stopping (if `None`, then the app's default is used)
+    pub(crate) max_words_to_check: Option<usize>,
+    /// Maximum number of letters currently on the board that can be used in a newly played word (if `None`, then the app's default is used)
+    pub(crate) filter_letters_on_board: Option<usize>,
+    /// Whether to print the `Solution` as JSON rather than as a grid
+    pub(crate) json: bool
+}
+
+/// Checks whether the app was launched to solve from the command line rather than to open the window
+/// # Arguments
+/// * `args` - The command line arguments, excluding the program name
+/// # Returns
+/// `bool` - Whether a hand was given with `--letters`
+pub(crate) fn is_cli(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == "--letters")
+}
+
+/// Parses the command line arguments
+/// # Arguments
+/// * `args` - The command line arguments, excluding the program name
+/// # Returns
+/// `Result` with the parsed `CliOptions`
+///
+/// *or String `Err` describing the first invalid argument*
+pub(crate) fn parse_args(args: &[String]) -> Result<CliOptions, String> {
+    let mut letters = None;
+    let mut options = CliOptions { letters: [0usize; 27], long_dictionary: false, max_words_to_check: None, filter_letters_on_board: None, json: false };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--letters" => {
+                let hand = args.next().ok_or("--letters requires a hand of letters")?;
+                // Accented letters count as their base letter (see `letter_index`)
+                let hand: String = hand.to_uppercase().chars().map(|c| letter_index(c).map_or(c, |letter| (letter as u8+65) as char)).collect();
+                letters = Some(string_to_letters(&hand)?);
+            },
+            "--long-dict" => options.long_dictionary = true,
+            "--max-words" => {
+                let value = args.next().ok_or("--max-words requires a number")?;
+                options.max_words_to_check = Some(value.parse().or(Err(format!("Invalid maximum number of words: {}", value)))?);
+            },
+            "--filter-board-letters" => {
+                let value = args.next().ok_or("--filter-board-letters requires a number")?;
+                options.filter_letters_on_board = Some(value.parse().or(Err(format!("Invalid number of board letters: {}", value)))?);
+            },
+            "--json" => options.json = true,
+            _ => return Err(format!("Unknown argument: {}", arg))
+        }
+    }
+    options.letters = letters.ok_or("No hand given (use --letters)")?;
+    Ok(options)
+}
+
+/// Solves the hand given on the command line, printing the board (or an error) and the time taken
+/// # Arguments
+/// * `args` - The command line arguments, excluding the program name
+/// # Returns
+/// `i32` - Exit code: 0 if the hand was solved, 1 if no solution was found, or 2 if the arguments were invalid or solving failed
+pub(crate) fn run(args: &[String]) -> i32 {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return 2;
+        }
+    };
+    let dictionary = if options.long_dictionary {include_str!("dictionary.txt")} else {include_str!("updated_short_dictionary.txt")};
+    let solver = Solver::new(dictionary.lines().map(String::from).collect());
+    let mut config = SolveConfig::default();
+    if let Some(max_words_to_check) = options.max_words_to_check {
+        config.max_words_to_check = max_words_to_check;
+    }
+    if let Some(filter_letters_on_board) = options.filter_letters_on_board {
+        config.filter_letters_on_board = filter_letters_on_board;
+    }
+    let solution = match solver.solve(options.letters, config) {
+        Ok(solved) => solved.solutions().swap_remove(0),
+        Err(e) => {
+            eprintln!("{}", e);
+            return match e {
+                SolverError::NoSolution | SolverError::LettersLeftOver(_) | SolverError::NoPlayableWords | SolverError::StrandedLetters(_) | SolverError::ExceedsMaxSpan { .. } | SolverError::Timeout => 1,
+                _ => 2
+            };
+        }
+    };
+    if options.json {
+        match serde_json::to_string(&solution) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize solution: {}", e);
+                return 2;
+            }
+        }
+    }
+    else {
+        for row in solution.board.iter() {
+            println!("{}", row.concat());
+        }
+        println!("Solved in {} ms", solution.elapsed);
+    }
+    0
+}
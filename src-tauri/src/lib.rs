@@ -0,0 +1,239 @@
+//! Solving Bananagrams hands, shared by the app, its command line and its benchmarks
+//!
+//! The board is in `board`, the dictionary in `trie` and the search in `solver`; the types and letter conversions they all use are here
+
+use crate::board::Board;
+
+pub mod board;
+pub mod solver;
+pub mod trie;
+#[cfg(test)]
+mod test_helpers;
+
+// Lets `test_helpers` name this crate the same way from its tests as from the app's
+#[cfg(test)]
+extern crate self as bananagrams_solver;
+
+/// A numeric representation of a word
+pub type Word = Vec<usize>;
+/// Represents a hand of letters (with the number of blanks at index `BLANK`)
+pub type Letters = [usize; 27];
+/// Represents a board and its minimum and maximum played columns and rows
+pub type BoardAndIdxs = (Board, usize, usize, usize, usize);
+
+/// The maximum length of any word in the dictionary
+pub const MAX_WORD_LENGTH: usize = 17;
+/// Index of the number of blank tiles in a `Letters` array
+pub const BLANK: usize = 26;
+/// Character used to represent a blank tile when communicating with the frontend
+pub const BLANK_CHAR: char = '?';
+/// Default number of rows/columns in the board
+pub const BOARD_SIZE: usize = 144;
+/// Largest number of rows/columns a board can have, so that a corrupt save or setting can't ask for more memory than there is
+pub const MAX_BOARD_SIZE: usize = 1000;
+/// All uppercase letters in the Latin alphabet
+pub const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+/// The number of each letter present in regular Bananagrams
+pub const REGULAR_TILES: [u64; 26] = [13, 3, 3, 6, 18, 3, 4, 3, 12, 2, 2, 5, 3, 8, 11, 3, 2, 9, 6, 9, 6, 3, 3, 2, 3, 2];
+/// The Scrabble point value of each letter
+pub const LETTER_VALUES: [u32; 26] = [1, 3, 3, 2, 1, 4, 2, 4, 1, 8, 5, 1, 3, 1, 1, 3, 10, 1, 1, 1, 1, 4, 4, 8, 4, 10];
+/// Minimum length of the words formed on the board, unless changed in the settings
+pub const DEFAULT_MIN_WORD_LENGTH: usize = 2;
+
+/// Gets the bitmask of the letters in a `word`
+/// # Arguments
+/// * `word` - Word to get the mask of
+/// # Returns
+/// `usize` - Bitmask with bit `i` set if letter `i` is in `word`
+pub(crate) fn letter_mask(word: &Word) -> usize {
+    word.iter().fold(0, |mask, letter| mask | 1 << letter)
+}
+
+/// Converts a word into a numeric vector representation
+/// # Arguments
+/// * `word` - String word to convert
+/// # Returns
+/// `Word` - numeric representation of `word`, with each letter converted from 0 ('A') to 25 ('Z') (accented letters become their base letter; see `letter_index`)
+/// # See also
+/// `convert_array_to_word`
+pub fn convert_word_to_array(word: &str) -> Word {
+    word.chars().filter_map(letter_index).collect()
+}
+
+/// Gets the index of an uppercase letter, treating accented letters (such as `Ñ` or `É`) as their base letter
+/// # Arguments
+/// * `c` - The letter to convert
+/// # Returns
+/// `Option` - The index of `c` from 0 ('A') to 25 ('Z'), or `None` if `c` isn't an uppercase letter
+pub fn letter_index(c: char) -> Option<usize> {
+    let base = match c {
+        'Á' | 'À' | 'Â' | 'Ä' | 'Ã' | 'Å' => 'A',
+        'Ç' => 'C',
+        'É' | 'È' | 'Ê' | 'Ë' => 'E',
+        'Í' | 'Ì' | 'Î' | 'Ï' => 'I',
+        'Ñ' => 'N',
+        'Ó' | 'Ò' | 'Ô' | 'Ö' | 'Õ' => 'O',
+        'Ú' | 'Ù' | 'Û' | 'Ü' => 'U',
+        'Ÿ' => 'Y',
+        _ => c
+    };
+    if base.is_ascii_uppercase() {Some((base as usize) - 65)} else {None}
+}
+
+/// Converts a numeric vector representation into a `String`
+/// # Arguments
+/// * `arr` - Numeric vector of the word
+/// # Returns
+/// * `String` - `arr` converted into a `String`, with each number converted from 'A' (0) to 'Z' (25)
+/// # See also
+/// `convert_word_to_array`
+pub fn convert_array_to_word(arr: &Word) -> String {
+    arr.iter().map(|c| (*c as u8+65) as char).collect()
+}
+
+/// Lists the tiles in a hand
+/// # Arguments
+/// * `letters` - Array of the number of each letter (and blanks) in the hand
+/// # Returns
+/// * `Vec<char>` - Each tile in `letters` (in alphabetical order, followed by any blanks)
+pub(crate) fn letters_to_chars(letters: &Letters) -> Vec<char> {
+    (0..27).flat_map(|letter| std::iter::repeat_n(if letter == BLANK {BLANK_CHAR} else {(letter as u8+65) as char}, letters[letter])).collect()
+}
+
+/// Converts a hand into a `String`, e.g. "AABCDE"
+/// # Arguments
+/// * `letters` - Array of the number of each letter (and blanks) in the hand
+/// # Returns
+/// * `String` - Each tile in `letters` (in alphabetical order, followed by any blanks)
+/// # See also
+/// `string_to_letters`
+pub fn letters_to_string(letters: &Letters) -> String {
+    letters_to_chars(letters).into_iter().collect()
+}
+
+/// Converts a `String` such as "AABCDE" into a hand
+/// # Arguments
+/// * `s` - The tiles in the hand, as uppercase letters (with blanks as `BLANK_CHAR`)
+/// # Returns
+/// `Result` of the `Letters` in the hand
+///
+/// *or String `Err` if `s` contains anything other than uppercase letters and blanks*
+/// # See also
+/// `letters_to_string`
+pub fn string_to_letters(s: &str) -> Result<Letters, String> {
+    let mut letters = [0usize; 27];
+    for c in s.chars() {
+        if c == BLANK_CHAR {
+            letters[BLANK] += 1;
+        }
+        else if c.is_ascii_uppercase() {
+            letters[(c as usize) - 65] += 1;
+        }
+        else {
+            return Err(format!("Invalid letter in hand: {}", c));
+        }
+    }
+    Ok(letters)
+}
+
+/// Adds two hands together
+/// # Arguments
+/// * `a` - Array of the number of each letter (and blanks) in the first hand
+/// * `b` - Array of the number of each letter (and blanks) in the second hand
+/// # Returns
+/// * `Letters` - The number of each letter (and blanks) in both hands combined
+pub fn letters_add(a: &Letters, b: &Letters) -> Letters {
+    let mut sum = *a;
+    for (count, other) in sum.iter_mut().zip(b) {
+        *count += other;
+    }
+    sum
+}
+
+/// Takes the letters of one hand away from another
+/// # Arguments
+/// * `a` - Array of the number of each letter (and blanks) to take away from
+/// * `b` - Array of the number of each letter (and blanks) to take away
+/// # Returns
+/// * `Option<Letters>` - The number of each letter (and blanks) left over, or `None` if `b` has more of any letter than `a`
+pub fn letters_sub(a: &Letters, b: &Letters) -> Option<Letters> {
+    let mut difference = *a;
+    for (count, other) in difference.iter_mut().zip(b) {
+        *count = count.checked_sub(*other)?;
+    }
+    Some(difference)
+}
+
+/// Gets the most of each letter in either of two hands
+/// # Arguments
+/// * `a` - Array of the number of each letter (and blanks) in the first hand
+/// * `b` - Array of the number of each letter (and blanks) in the second hand
+/// # Returns
+/// * `Letters` - The greater of the two counts of each letter (and blanks)
+fn letters_max(a: &Letters, b: &Letters) -> Letters {
+    let mut max = *a;
+    for (count, other) in max.iter_mut().zip(b) {
+        *count = (*count).max(*other);
+    }
+    max
+}
+
+/// Counts the tiles in a hand
+/// # Arguments
+/// * `a` - Array of the number of each letter (and blanks) in the hand
+/// # Returns
+/// * `usize` - The total number of tiles (including blanks)
+pub(crate) fn letters_count(a: &Letters) -> usize {
+    a.iter().sum()
+}
+
+/// Checks whether a `word` can be made using the given `letters` (substituting blanks for missing letters)
+/// # Arguments
+/// * `word` - The vector form of the word to check
+/// * `letters` - Array of the number of each letter (and blanks) in the hand
+/// # Returns
+/// * `bool` - Whether `word` can be made using `letters`
+pub fn is_makeable(word: &Word, letters: &Letters) -> bool {
+    let mut needed = [0usize; 27];
+    for letter in word.iter() {
+        needed[*letter] += 1;
+    }
+    // Every letter the hand is short of has to be made up with a blank
+    let missing = letters_sub(&letters_max(&needed, letters), letters).expect("The maximum is at least the hand");
+    letters_count(&missing) <= letters[BLANK]
+}
+
+/// Checks whether a `candidate` word uses exactly the given `target` letters, with none left over
+/// # Arguments
+/// * `candidate` - The vector form of the word to check
+/// * `target` - Array of the number of each letter (and blanks) to use
+/// # Returns
+/// * `bool` - Whether `candidate` is an exact anagram of `target`
+pub fn is_exact_anagram(candidate: &Word, target: &Letters) -> bool {
+    candidate.len() == letters_count(target) && is_makeable(candidate, target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_arithmetic() {
+        let hand = |s: &str| string_to_letters(s).unwrap();
+        assert_eq!(letters_add(&hand("AB?"), &hand("BC")), hand("ABBC?"));
+        assert_eq!(letters_sub(&hand("ABBC?"), &hand("B?")), Some(hand("ABC")));
+        assert_eq!(letters_sub(&hand("ABC"), &hand("ABC")), Some([0usize; 27]));
+        // Taking away more of a letter (or blank) than there is fails rather than underflowing
+        assert_eq!(letters_sub(&hand("ABC"), &hand("AA")), None);
+        assert_eq!(letters_sub(&hand("ABC"), &hand("?")), None);
+        assert_eq!(letters_max(&hand("AAB"), &hand("ABBC")), hand("AABBC"));
+        assert_eq!(letters_count(&hand("AABC??")), 6);
+        assert_eq!(letters_count(&[0usize; 27]), 0);
+        // Blanks make up for missing letters
+        assert!(is_makeable(&convert_word_to_array("BANANA"), &hand("AAABNN")));
+        assert!(is_makeable(&convert_word_to_array("BANANA"), &hand("AABN??")));
+        assert!(is_makeable(&convert_word_to_array("BANANA"), &hand("AABNN?")));
+        assert!(!is_makeable(&convert_word_to_array("BANANA"), &hand("AABNNZ")));
+        assert!(!is_makeable(&convert_word_to_array("BANANA"), &hand("ABNN?")));
+    }
+}
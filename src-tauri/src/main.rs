@@ -1,1569 +1,618 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::num::NonZeroUsize;
-use std::{fmt, thread, usize, collections::HashMap};
+use std::cmp::Reverse;
+use std::hash::Hash;
+use std::{fmt, fs, io, thread, collections::HashMap, path::Path, path::PathBuf};
 use hashbrown::HashSet;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Instant;
 use rand::prelude::*;
 use rand::distributions::Uniform;
-use serde::Serialize;
-use tauri::State;
-
-/// A numeric representation of a word
-type Word = Vec<usize>;
-/// Represents a hand of letters
-type Letters = [usize; 26];
-/// Represents a board and its minimum and maximum played columns and rows
-type BoardAndIdxs = (Board, usize, usize, usize, usize);
-/// Represents a set of removable indices that will storm form a valid board, plus that new board's minimum and maximum played columns and rows
-type Removable = (Vec<(usize, usize)>, usize, usize, usize, usize);
-
-/// The maximum length of any word in the dictionary
-const MAX_WORD_LENGTH: usize = 17;
-/// Value of an empty cell on the board
-const EMPTY_VALUE: usize = 30;
-/// Number rows/columns in the board
-const BOARD_SIZE: usize = 144;
-/// All uppercase letters in the Latin alphabet
-const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
-/// The number of each letter present in regular Bananagrams
-const REGULAR_TILES: [u64; 26] = [13, 3, 3, 6, 18, 3, 4, 3, 12, 2, 2, 5, 3, 8, 11, 3, 2, 9, 6, 9, 6, 3, 3, 2, 3, 2];
-
-/// The current board
-#[derive(Clone)]
-struct Board {
-    /// The underlying vector of the board
-    arr: Vec<usize>
-}
-impl Board {
-    /// Creates a new board of dimensions `BOARD_SIZE`x`BOARD_SIZE` filled with the `EMPTY_VALUE`
-    fn new() -> Board {
-        return Board { arr: vec![EMPTY_VALUE; BOARD_SIZE*BOARD_SIZE] }
-    }
-
-    /// Gets a value from the board at the given index
-    /// # Arguments
-    /// * `row` - Row index of the value to get (must be less than `BOARD_SIZE`)
-    /// * `col` - Column index of the value to get (must be less than `BOARD_SIZE`)
-    /// # Returns
-    /// `usize` - The value in the board at `(row, col)`
-    /// # Panics
-    /// If `row` or `col` are out-of-bounds
-    fn get_val(&self, row: usize, col: usize) -> usize {
-        return *self.arr.get(row*BOARD_SIZE + col).expect("Index not in range!");
-    }
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tauri::{Manager, State};
+use bananagrams_solver::{convert_array_to_word, convert_word_to_array, is_exact_anagram, is_makeable, letter_index, letters_add, letters_sub, string_to_letters, Letters, Word, BLANK, BLANK_CHAR, BOARD_SIZE, DEFAULT_MIN_WORD_LENGTH, LETTER_VALUES, MAX_BOARD_SIZE, MAX_WORD_LENGTH, REGULAR_TILES, UPPERCASE};
+use bananagrams_solver::board::{board_from_vec, board_statistics, board_to_csv, board_to_string, board_to_svg, board_to_vec, carried_over_to_vec, get_board_overlap, get_new_min_max, get_removable_indices, get_words_on_board, is_board_valid_full, is_board_valid_horizontal, is_board_valid_vertical, is_connected, score_board, score_solution, Board, BoardStatistics, Direction, LetterUsage, SVG_CELL_SIZE};
+use bananagrams_solver::solver::{check_filter_after_play_later, get_col_limits, get_deadline, get_row_limits, past_deadline, play_existing, solve_hand, solve_with, solves_quickly, take_solver_stats, GameState, PartialCallback, Solution, SolutionPreference, SolveConfig, SolveMethod, SolverError, SolverStats};
+use bananagrams_solver::trie::{indexed_words, DictionaryIndex, IndexedWord, Trie};
 
-    /// Sets a value in the board at the given index
-    /// # Arguments
-    /// * `row` - Row index of the value to get (must be less than `BOARD_SIZE`)
-    /// * `col` - Column index of the value to get (must be less than `BOARD_SIZE`)
-    /// * `val` - Value to set at `(row, col)` in the board
-    /// # Panics
-    /// If `row` or `col` are out-of-bounds
-    fn set_val(&mut self, row: usize, col: usize, val: usize) {
-        let v = self.arr.get_mut(row*BOARD_SIZE + col).expect("Index not in range!");
-        *v = val;
-    }
+/// The number of blank tiles per 144 regular tiles
+const REGULAR_BLANKS: u64 = 2;
+/// Maximum number of words to check when quickly testing whether a hand is solvable after a dump
+const DUMP_WORDS_TO_CHECK: usize = 2_000;
+/// Seed used for deterministic solves when none is given
+const DEFAULT_SEED: u64 = 0;
+/// Number of previous games kept for undoing, and of solved boards kept in the board history, unless changed in the settings
+const DEFAULT_HISTORY_DEPTH: usize = 10;
+/// Version of the saved game file format (to be incremented whenever the format changes)
+const SAVE_VERSION: u32 = 1;
+/// Name of the file in the app data directory that the last game is automatically saved to
+const AUTOSAVE_FILE: &str = "last_game.json";
+/// Version of the settings file format (to be incremented only if a setting is removed or changes meaning, since new settings are read from older files with their defaults)
+const SETTINGS_VERSION: u32 = 1;
+/// Name of the file in the app config directory that the settings are saved to whenever they change
+const SETTINGS_FILE: &str = "settings.json";
+/// Folder (in the app data folder) containing the word lists of languages other than English (see `read_language_dictionaries`)
+const LANGUAGE_DICTIONARY_DIR: &str = "dictionaries";
+/// Minimum allowed value of the maximum number of words to check (fewer makes almost every solve fail)
+const MIN_WORDS_TO_CHECK: usize = 1_000;
+/// A hand is easy to solve (see `assess_hand`) if more than this many words can be formed from it
+const EASY_HAND_WORDS: usize = 50;
+/// Largest number of tiles a custom tile set can have (see `custom_tile_set`)
+const MAX_CUSTOM_TILES: u64 = 1_000;
 
-    /// Plays a word on the board
-    /// # Arguments
-    /// * `word` - The word to be played
-    /// * `row_idx` - The starting row at which to play the word
-    /// * `col_idx` - The starting column at which to play the word
-    /// * `direction` - The `Direction` in which to play the word
-    /// * `letters` - The number of each letter currently in the hand
-    /// * `letters_on_board` - The number of each letter on the board (is modified in-place)
-    /// # Returns
-    /// *`Result` with:*
-    /// * `bool` - Whether the word could be validly played
-    /// * `Vec<(usize, usize)>` - Vector of the indices played in `board`
-    /// * `[usize; 26]`- The remaining letters
-    /// * `LetterUsage` - How many letters were used
-    /// 
-    /// *or empty `Err` if out-of-bounds*
-    fn play_word(&mut self, word: &Word, row_idx: usize, col_idx: usize, direction: Direction, letters: &Letters, letters_on_board: &mut Letters) -> (bool, Vec<(usize, usize)>, [usize; 26], LetterUsage) {
-        let mut played_indices: Vec<(usize, usize)> = Vec::with_capacity(MAX_WORD_LENGTH);
-        match direction {
-            Direction::Horizontal => {
-                let mut remaining_letters = letters.clone();
-                if col_idx + word.len() > BOARD_SIZE {
-                    return (false, played_indices, remaining_letters, LetterUsage::Remaining);
-                }
-                // Check if the word will start or end at a letter, or borders one top or bottom
-                let borders_start = self.get_val(row_idx, col_idx) != EMPTY_VALUE;
-                let borders_end = self.get_val(row_idx, col_idx+word.len()-1) != EMPTY_VALUE;
-                let borders_top_or_bottom = if row_idx == 0 {
-                    (col_idx..col_idx+word.len()).any(|c_idx| self.get_val(1, c_idx) != EMPTY_VALUE)
-                }
-                else if row_idx == BOARD_SIZE-1 {
-                    (col_idx..col_idx+word.len()).any(|c_idx| self.get_val(BOARD_SIZE-2, c_idx) != EMPTY_VALUE)
-                }
-                else {
-                    (col_idx..col_idx+word.len()).any(|c_idx| self.get_val(row_idx-1, c_idx) != EMPTY_VALUE || self.get_val(row_idx+1, c_idx) != EMPTY_VALUE)
-                };
-                if !(borders_start || borders_end || borders_top_or_bottom) {
-                    return (false, played_indices, remaining_letters, LetterUsage::Remaining);
-                }
-                else {
-                    let mut entirely_overlaps = true;
-                    for i in 0..word.len() {
-                        if self.get_val(row_idx, col_idx+i) == EMPTY_VALUE {
-                            self.set_val(row_idx, col_idx+i, word[i]);
-                            letters_on_board[word[i]] += 1;
-                            played_indices.push((row_idx, col_idx+i));
-                            entirely_overlaps = false;
-                            let elem = remaining_letters.get_mut(word[i]).unwrap();
-                            if *elem == 0 {
-                                return (false, played_indices, remaining_letters, LetterUsage::Overused);
-                            }
-                            *elem -= 1;
-                        }
-                        else if self.get_val(row_idx, col_idx+i) != word[i] {
-                            return (false, played_indices, remaining_letters, LetterUsage::Remaining);
-                        }
-                    }
-                    if remaining_letters.iter().all(|count| *count == 0) && !entirely_overlaps {
-                        return (true, played_indices, remaining_letters, LetterUsage::Finished);
-                    }
-                    else {
-                        return (!entirely_overlaps, played_indices, remaining_letters, LetterUsage::Remaining);
-                    }
-                }
-            },
-            Direction::Vertical => {
-                let mut remaining_letters = letters.clone();
-                if row_idx + word.len() > BOARD_SIZE {
-                    return (false, played_indices, remaining_letters, LetterUsage::Remaining);
-                }
-                // Check if the word will start or end at a letter, or borders one left or right
-                let borders_start = self.get_val(row_idx, col_idx) != EMPTY_VALUE;
-                let borders_end = self.get_val(row_idx+word.len()-1, col_idx) != EMPTY_VALUE;
-                let borders_left_or_right = if col_idx == 0 {
-                    (row_idx..row_idx+word.len()).any(|r_idx| self.get_val(r_idx, 1) != EMPTY_VALUE)
-                }
-                else if col_idx == BOARD_SIZE-2 {
-                    (row_idx..row_idx+word.len()).any(|r_idx| self.get_val(r_idx, BOARD_SIZE-2) != EMPTY_VALUE)
-                }
-                else {
-                    (row_idx..row_idx+word.len()).any(|r_idx| self.get_val(r_idx, col_idx-1) != EMPTY_VALUE || self.get_val(r_idx, col_idx+1) != EMPTY_VALUE)
-                };
-                if !(borders_start || borders_end || borders_left_or_right) {
-                    return (false, played_indices, remaining_letters, LetterUsage::Remaining);
-                }
-                else {
-                    let mut entirely_overlaps = true;
-                    for i in 0..word.len() {
-                        if self.get_val(row_idx+i, col_idx) == EMPTY_VALUE {
-                            self.set_val(row_idx+i, col_idx, word[i]);
-                            letters_on_board[word[i]] += 1;
-                            played_indices.push((row_idx+i, col_idx));
-                            entirely_overlaps = false;
-                            let elem = remaining_letters.get_mut(word[i]).unwrap();
-                            if *elem == 0 {
-                                return (false, played_indices, remaining_letters, LetterUsage::Overused);
-                            }
-                            *elem -= 1;
-                        }
-                        else if self.get_val(row_idx+i, col_idx) != word[i] {
-                            return (false, played_indices, remaining_letters, LetterUsage::Remaining);
-                        }
-                    }
-                    if remaining_letters.iter().all(|count| *count == 0) && !entirely_overlaps {
-                        return (true, played_indices, remaining_letters, LetterUsage::Finished);
-                    }
-                    else {
-                        return (!entirely_overlaps, played_indices, remaining_letters, LetterUsage::Remaining);
-                    }
-                }
-            }
-        }
-    }
+mod cli;
+#[cfg(test)]
+mod test_helpers;
+
+/// Struct returned when getting playable words
+#[derive(Serialize)]
+struct PlayableWords {
+    /// Playable words using the shorter dictionary
+    short: Vec<String>,
+    /// Playable words using the whole Scrabble dictionary
+    long: Vec<String>,
+    /// Playable words using the custom dictionary (`None` if one hasn't been loaded)
+    custom: Option<Vec<String>>,
+    /// Number of words in `short` of each length
+    short_histogram: HashMap<usize, usize>,
+    /// Number of words in `long` of each length
+    long_histogram: HashMap<usize, usize>
+}
 
-    /// Undoes a play on the `board`
+impl PlayableWords {
+    /// Creates a `PlayableWords`, counting the words of each length in the short and long lists
     /// # Arguments
-    /// * `board` - `Board` being undone (is modified in-place)
-    /// * `played_indices` - Vector of the indices in `board` that need to be reset
-    /// * `letters_on_board` - Length-26 array of the number of each letter on the board (is modified in place)
+    /// * `short` - Playable words using the shorter dictionary
+    /// * `long` - Playable words using the whole Scrabble dictionary
+    /// * `custom` - Playable words using the custom dictionary (`None` if one hasn't been loaded)
     /// # Returns
-    /// * `Vec<usize>` - Vector of the previous values on the `board` for each of `played_indices`
-    fn undo_play(&mut self, played_indices: &Vec<(usize, usize)>, letters_on_board: &mut Letters) -> Vec<usize> {
-        let mut old_letters: Vec<usize> = Vec::with_capacity(played_indices.len());
-        for index in played_indices.iter() {
-            let old_val = self.get_val(index.0, index.1);
-            letters_on_board[old_val] -= 1;
-            old_letters.push(old_val);
-            self.set_val(index.0, index.1, EMPTY_VALUE);
-        }
-        old_letters
+    /// `PlayableWords` - The words and their histograms
+    fn new(short: Vec<String>, long: Vec<String>, custom: Option<Vec<String>>) -> PlayableWords {
+        let histogram = |words: &Vec<String>| words.iter().fold(HashMap::new(), |mut histogram, word| {
+            *histogram.entry(word.chars().count()).or_insert(0) += 1;
+            histogram
+        });
+        PlayableWords { short_histogram: histogram(&short), long_histogram: histogram(&long), short, long, custom }
     }
 }
 
-/// Converts a word into a numeric vector representation
-/// # Arguments
-/// * `word` - String word to convert
-/// # Returns
-/// `Word` - numeric representation of `word`, with each letter converted from 0 ('A') to 25 ('Z')
-/// # See also
-/// `convert_array_to_word`
-fn convert_word_to_array(word: &str) -> Word {
-    word.chars().filter(|c| c.is_ascii_uppercase()).map(|c| (c as usize) - 65).collect()
+/// Which dictionaries a word is in, as returned by `check_word_validity`
+#[derive(Serialize)]
+struct WordValidity {
+    /// Whether the word is in the shorter dictionary of the current language
+    in_short: bool,
+    /// Whether the word is in the longer dictionary of the current language
+    in_long: bool,
+    /// Whether the word is in the custom dictionary (`None` if one hasn't been loaded)
+    in_custom: Option<bool>
 }
 
-/// Converts a numeric vector representation into a `String`
-/// # Arguments
-/// * `arr` - Numeric vector of the word
-/// # Returns
-/// * `String` - `arr` converted into a `String`, with each number converted from 'A' (0) to 'Z' (25)
-/// # See also
-/// `convert_word_to_array`
-fn convert_array_to_word(arr: &Word) -> String {
-    arr.iter().map(|c| (*c as u8+65) as char).collect()
+/// A letter that could be dumped, as returned by `suggest_dump`
+#[derive(Serialize)]
+struct DumpCandidate {
+    /// The letter to dump
+    letter: String,
+    /// How many more words in the current dictionary are playable from the hand once `letter` is dumped than before (negative if words are lost)
+    words_gained: isize,
+    /// Whether a quick, bounded solve succeeded once `letter` is dumped
+    solvable: bool
 }
 
-/// Converts a `board` to a `String`
-/// # Arguments
-/// * `board` - Board to display
-/// * `min_col` - Minimum occupied column index
-/// * `max_col` - Maximum occupied column index
-/// * `min_row` - Minimum occupied row index
-/// * `max_row` - Maximum occupied row index
-/// # Returns
-/// * `String` - `board` in string form (with all numbers converted to letters)
-fn _board_to_string(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> String {
-    let mut board_string: Vec<char> = Vec::with_capacity((max_row-min_row)*(max_col-min_col));
-    for row in min_row..=max_row {
-        for col in min_col..=max_col {
-            if board.get_val(row, col) == EMPTY_VALUE {
-                board_string.push(' ');
-            }
-            else {
-                board_string.push((board.get_val(row, col) as u8+65) as char);
-            }
-        }
-        board_string.push('\n');
-    }
-    let s: String = board_string.iter().collect();
-    return s.trim_end().to_owned();
+/// The next word to play, as returned by `play_bananagrams_hint`
+#[derive(Serialize)]
+struct HintResult {
+    /// The word to play
+    word: String,
+    /// The direction to play the word in
+    direction: Direction,
+    /// The row of the full board that the word's first letter is in (see `Solution::min_row`)
+    row: usize,
+    /// The column of the full board that the word's first letter is in (see `Solution::min_col`)
+    col: usize
 }
 
-/// Converts a `board` to a vector of vectors of strings
-/// # Arguments
-/// * `board` - Board to display
-/// * `min_col` - Minimum occupied column index
-/// * `max_col` - Maximum occupied column index
-/// * `min_row` - Minimum occupied row index
-/// * `max_row` - Maximum occupied row index
-/// # Returns
-/// * `Vec<Vec<String>>` - `board` in vector form (with all numbers converted to letters)
-fn board_to_vec(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, previous_idxs: &HashSet<(usize, usize)>) -> Vec<Vec<String>> {
-    let mut board_vec: Vec<Vec<String>> = Vec::with_capacity(max_row-min_row);
-    for row in min_row..=max_row {
-        let mut row_vec: Vec<String> = Vec::with_capacity(max_col-min_col);
-        for col in min_col..=max_col {
-            if board.get_val(row, col) == EMPTY_VALUE {
-                row_vec.push(' '.to_string());
-            }
-            else {
-                if !previous_idxs.contains(&(row, col)) {
-                    row_vec.push(((board.get_val(row, col) as u8+65) as char).to_string());
-                }
-                else {
-                    row_vec.push(((board.get_val(row, col) as u8+65) as char).to_string() + "*");
-                }
-            }
-        }
-        board_vec.push(row_vec);
-    }
-    board_vec
+/// Tiles that can be taken off of the board without disconnecting it, as returned by `get_removable_words`
+#[derive(Serialize)]
+struct RemovableWord {
+    /// The letters of the tiles (blanks as lowercase letters)
+    word: String,
+    /// The direction the tiles run in (horizontal for a single tile)
+    direction: Direction,
+    /// The row of the full board that the first tile is in
+    row: usize,
+    /// The column of the full board that the first tile is in
+    col: usize,
+    /// The number of tiles
+    length: usize
 }
 
-/// Gets which indices overlap between `previous_board` and `new_board`
-/// # Arguments
-/// * `previous_board` - The previous board
-/// * `new_board` - The new board
-/// * `previous_min_col` - The minimum played column in `previous_board`
-/// * `previous_max_col` - The maximum played column in `previous_board`
-/// * `previous_min_row` - The minimum played row in `previous_board`
-/// * `previous_max_row` - The maximum played row in `previous_board`
-/// * `new_min_col` - The minimum played column in `new_board`
-/// * `new_max_col` - The maximum played column in `new_board`
-/// * `new_min_row` - The minimum played row in `new_board`
-/// * `new_max_row` - The maximum played row in `new_board`
-/// # Returns
-/// `HashSet` - Set of the indices where `previous_board` and `new_board` have the same value
-fn get_board_overlap(previous_board: &Board, new_board: &Board, previous_min_col: usize, previous_max_col: usize, previous_min_row: usize, previous_max_row: usize, new_min_col: usize, new_max_col: usize, new_min_row: usize, new_max_row: usize) -> HashSet<(usize, usize)> {
-    let mut overlapping_idxs: HashSet<(usize, usize)> = HashSet::new();
-    for row in previous_min_row.max(new_min_row)..=previous_max_row.min(new_max_row) {
-        for col in previous_min_col.max(new_min_col)..=previous_max_col.min(new_max_col) {
-            if previous_board.get_val(row, col) != EMPTY_VALUE && previous_board.get_val(row, col) == new_board.get_val(row, col) {
-                overlapping_idxs.insert((row, col));
-            }
-        }
-    }
-    overlapping_idxs
+/// A word on the last solved board, as returned by `words_on_board`
+#[derive(Serialize)]
+struct WordOnBoard {
+    /// The word itself
+    word: String,
+    /// "Horizontal" or "Vertical"
+    direction: String,
+    /// The row of the full board that the word's first letter is in
+    start_row: usize,
+    /// The column of the full board that the word's first letter is in
+    start_col: usize
 }
 
-/// Gets the minimum and maximum occupied row and column from a `board` (assuming that tiles have only been removed)
-/// # Arguments
-/// * `board` - The `Board` to check
-/// * `old_min_col` - The previous mimimum occupied column
-/// * `old_max_col` - The previous maximum occupied column
-/// * `old_min_row` - The previous minimum occupied row
-/// * `old_max_row` - The previous maximum occupied row
-/// * `except_vec` - Vector of indices to ignore when checking if occupied
-/// # Returns
-/// * `usize` - New minimum occupied column (never smaller than `old_min_col`)
-/// * `usize` - New maximum occupied column (never greater than `old_min_row`)
-/// * `usize` - New minimum occupied row (never smaller than `old_min_row`)
-/// * `usize` - New maximum occupied row (never greater than `old_max_row`)
-fn get_new_min_max(board: &Board, old_min_col: usize, old_max_col: usize, old_min_row: usize, old_max_row: usize, except_vec: &Vec<(usize, usize)>) -> (usize, usize, usize, usize) {
-    let mut except_idxs: HashSet<&(usize, usize)> = HashSet::with_capacity(except_vec.len());
-    for idxs in except_vec {
-        except_idxs.insert(idxs);
-    }
-    // Start at the old minimum row and check if that row or any subsequent ones have any non-empty values
-    let mut min_row = old_min_row;
-    for row in old_min_row..=old_max_row {
-        if (old_min_col..old_max_col).any(|col| !except_idxs.contains(&(row, col)) && board.get_val(row, col) != EMPTY_VALUE) {
-            break;
-        }
-        min_row += 1;
-    }
-    // Start at the test max_row and work our way down
-    let mut max_row = old_max_row;
-    while max_row > min_row {
-        if (old_min_col..old_max_col).any(|col| !except_idxs.contains(&(max_row, col)) && board.get_val(max_row, col) != EMPTY_VALUE) {
-            break;
-        }
-        max_row -= 1;
-    }
-    // Now do down columns
-    let mut min_col = old_min_col;
-    for col in old_min_col..=old_max_col {
-        if (min_row..max_row).any(|row| !except_idxs.contains(&(row, col)) && board.get_val(row, col) != EMPTY_VALUE) {
-            break;
+/// The result of taking a word off of the board, as returned by `remove_word`
+#[derive(Serialize)]
+struct RemovedWord {
+    /// Rows of the board that remains (see `board_to_vec`), which is empty if every tile was removed
+    board: Vec<Vec<String>>,
+    /// The letters of the removed tiles (blanks as `BLANK_CHAR`), to return to the hand
+    letters: Vec<char>
+}
+
+/// A `Solution` along with statistics of the search that found it
+#[derive(Serialize)]
+struct SolutionWithStats {
+    /// The solution itself
+    #[serde(flatten)]
+    solution: Solution,
+    /// Statistics of the search
+    stats: SolverStats
+}
+
+/// Why a word placed or removed by the user was rejected (see `place_word` and `remove_word`)
+#[derive(Clone, Debug, PartialEq)]
+enum PlacementError {
+    /// The word would run off the edge of the board
+    OutOfBounds,
+    /// There's no tile to remove at this row and column
+    EmptyTile(usize, usize),
+    /// The word would cover a different letter already on the board, at this row and column
+    ConflictingTile(usize, usize),
+    /// Every letter of the word is already on the board, so no tiles would be placed
+    NothingPlaced,
+    /// The hand doesn't have enough of this letter (or blanks to stand in for it)
+    OverusedLetter(char),
+    /// The change would leave these words on the board, which aren't in the dictionary or are shorter than the minimum word length
+    InvalidWords(Vec<String>),
+    /// The word wouldn't cross or touch the side of the existing board
+    Disconnected,
+    /// Removing the word would split the rest of the board into separate groups of tiles
+    SplitsBoard,
+    /// Anything else (e.g. an unknown direction or failing to get a lock), with its reason
+    Internal(String)
+}
+
+impl PlacementError {
+    /// Gets the name of the variant, which is sent to the frontend as the error's `kind`
+    /// # Returns
+    /// `&'static str` - The name of the variant
+    fn kind(&self) -> &'static str {
+        match self {
+            PlacementError::OutOfBounds => "OutOfBounds",
+            PlacementError::EmptyTile(_, _) => "EmptyTile",
+            PlacementError::ConflictingTile(_, _) => "ConflictingTile",
+            PlacementError::NothingPlaced => "NothingPlaced",
+            PlacementError::OverusedLetter(_) => "OverusedLetter",
+            PlacementError::InvalidWords(_) => "InvalidWords",
+            PlacementError::Disconnected => "Disconnected",
+            PlacementError::SplitsBoard => "SplitsBoard",
+            PlacementError::Internal(_) => "Internal"
         }
-        min_col += 1;
     }
-    let mut max_col = old_max_col;
-    while max_col > min_col {
-        if (min_row..max_row).any(|row| !except_idxs.contains(&(row, max_col)) && board.get_val(row, max_col) != EMPTY_VALUE) {
-            break;
+}
+
+impl fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlacementError::OutOfBounds => write!(f, "The word doesn't fit on the board there!"),
+            PlacementError::EmptyTile(row, col) => write!(f, "There's no tile at row {}, column {}!", row, col),
+            PlacementError::ConflictingTile(row, col) => write!(f, "The word would cover a different letter at row {}, column {}!", row, col),
+            PlacementError::NothingPlaced => write!(f, "The word is already on the board there!"),
+            PlacementError::OverusedLetter(letter) => write!(f, "There aren't enough {}s in the hand to place the word!", letter),
+            PlacementError::InvalidWords(words) => write!(f, "The board would have invalid words: {}", words.join(", ")),
+            PlacementError::Disconnected => write!(f, "The word must cross or touch a word already on the board!"),
+            PlacementError::SplitsBoard => write!(f, "Removing the word would split the board into separate groups!"),
+            PlacementError::Internal(reason) => write!(f, "{}", reason)
         }
-        max_col -= 1;
     }
-    (min_col, max_col, min_row, max_row)
 }
 
-/// Checks whether the `board` is fully connected; this code is mostly from ChatGPT
-/// # Arguments
-/// * `board` - Board to check
-/// * `min_col` - The minimum played column
-/// * `max_col` - The maximum played column
-/// * `min_row` - The minimum played row
-/// * `max_row` - The maximum played row
-/// * `ignore_cells` - Locations to ignore
-/// # Returns
-/// * `bool` - Whether `board` is fully connected
-fn is_connected(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, ignored_cells: &Vec<(usize, usize)>) -> bool {
-    // Create a HashSet from the ignored cells for efficient lookup
-    let ignored_cells: HashSet<_> = ignored_cells.into_iter().collect();
+impl From<&str> for PlacementError {
+    fn from(reason: &str) -> Self {
+        PlacementError::Internal(reason.to_owned())
+    }
+}
 
-    // Find the starting point
-    let mut start: Option<(usize, usize)> = None;
-    for row in min_row..=max_row {
-        for col in min_col..=max_col {
-            if board.get_val(row, col) != EMPTY_VALUE && !ignored_cells.contains(&(row, col)) {
-                start = Some((row, col));
-                break;
-            }
-        }
-        if start.is_some() {
-            break;
-        }
+impl From<String> for PlacementError {
+    fn from(reason: String) -> Self {
+        PlacementError::Internal(reason)
     }
+}
 
-    // If no starting point found, board is trivially connected
-    if start.is_none() {
-        return true;
+impl From<SolverError> for PlacementError {
+    fn from(error: SolverError) -> Self {
+        PlacementError::Internal(error.to_string())
     }
+}
 
-    let (start_row, start_col) = start.unwrap();
+/// How a `PlacementError` is sent to the frontend
+#[derive(Serialize)]
+struct SerializedPlacementError {
+    /// Name of the `PlacementError` variant
+    kind: &'static str,
+    /// Human-readable description of the error
+    message: String
+}
 
-    // Perform DFS to check connectivity
-    let mut visited = HashSet::new();
-    let mut stack = vec![(start_row, start_col)];
+impl Serialize for PlacementError {
+    /// Serializes the error as an object tagged with its `kind`, along with its `message`
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedPlacementError { kind: self.kind(), message: self.to_string() }.serialize(serializer)
+    }
+}
 
-    let directions = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+/// Summary of a board in the board history, as returned by `get_board_history`
+#[derive(Serialize)]
+struct BoardSummary {
+    /// The minimum played column
+    min_col: usize,
+    /// The maximum played column
+    max_col: usize,
+    /// The minimum played row
+    min_row: usize,
+    /// The maximum played row
+    max_row: usize,
+    /// The number of each letter (and `BLANK_CHAR`) in the hand
+    letters: HashMap<char, u64>,
+    /// The number of words on the board
+    word_count: usize
+}
 
-    while let Some((row, col)) = stack.pop() {
-        if !visited.insert((row, col)) {
-            continue;
-        }
+/// How often a letter appears in a hand compared to a tile set, as returned by `get_letter_distribution`
+#[derive(Serialize)]
+struct LetterStats {
+    /// The number of the letter in the hand
+    count_in_hand: i64,
+    /// The fraction of the tiles in the set that are the letter
+    expected_fraction: f64,
+    /// The fraction of the hand that is the letter, minus `expected_fraction`
+    deviation: f64
+}
 
-        for (dr, dc) in &directions {
-            let new_row = (row as isize + dr) as usize;
-            let new_col = (col as isize + dc) as usize;
+/// Summary of a simulated game, as returned by `simulate_game`
+#[derive(Serialize)]
+struct GameSimulation {
+    /// How long each solve took in milliseconds (the starting hand first, then one per peel)
+    elapsed: Vec<u128>,
+    /// How many peels required rebuilding the board from scratch
+    rebuilds: usize,
+    /// How many times a tile had to be dumped because a hand couldn't be solved
+    dumps: usize,
+    /// The final board
+    board: Vec<Vec<String>>
+}
 
-            if new_row >= min_row && new_row <= max_row && new_col >= min_col && new_col <= max_col {
-                if board.get_val(new_row, new_col) != EMPTY_VALUE && !visited.contains(&(new_row, new_col)) && !ignored_cells.contains(&(new_row, new_col)) {
-                    stack.push((new_row, new_col));
-                }
-            }
-        }
-    }
+/// A game and the settings it was played with, as saved to a file
+#[derive(Serialize, Deserialize)]
+struct SaveFile {
+    /// Version of the file format (see `SAVE_VERSION`)
+    version: u32,
+    /// The game
+    game: GameState,
+    /// The settings in use when the game was saved
+    settings: CurrentSettings
+}
 
-    // Check if all occupied cells are visited
-    for row in min_row..=max_row {
-        for col in min_col..=max_col {
-            if board.get_val(row, col) != EMPTY_VALUE && !ignored_cells.contains(&(row, col)) && !visited.contains(&(row, col)) {
-                return false;
-            }
-        }
-    }
-    true
+/// The settings, as saved to a file so that they're kept when the app is restarted
+#[derive(Serialize, Deserialize)]
+struct SettingsFile {
+    /// Version of the file format (see `SETTINGS_VERSION`)
+    version: u32,
+    /// The settings
+    settings: CurrentSettings
 }
 
-/// Gets a vector of vectors of each part of a word that can be validly removed from the `board`
-/// # Arguments
-/// * `board` - The board to check for removable word parts
-/// * `min_col` - The minimum played column
-/// * `max_col` - The maximum played column
-/// * `min_row` - The minimum played row
-/// * `max_row` - The maximum played row
-/// # Returns
-/// `Vec` - Vector of length-5 tuples of (vector of length-2 index tuples of the indices of `board` that can be validly removed, new_min_col, new_max_col, new_min_row, new_max_row)
-fn get_removable_indices(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> Vec<Removable> {
-    if max_col <= min_col || max_row <= min_row {
-        return Vec::new();
-    }
-    let mut removable: Vec<Removable> = Vec::with_capacity((max_col - min_col) + (max_row - min_row));
-    let mut board_empty = true;
-    // First get horizontal removable word parts
-    for row in min_row..=max_row {
-        let mut current_word_part: Vec<(usize, usize)> = Vec::with_capacity(max_col-min_col);
-        for col in min_col..=max_col {
-            let touching = (row != 0 && board.get_val(row-1, col) != EMPTY_VALUE) || (row != BOARD_SIZE-1 && board.get_val(row+1, col) != EMPTY_VALUE);
-            if board.get_val(row, col) != EMPTY_VALUE && !touching {
-                current_word_part.push((row, col));
-            }
-            else if board.get_val(row, col) == EMPTY_VALUE && current_word_part.len() > 0 {
-                if is_connected(board, min_col, max_col, min_row, max_row, &current_word_part) {
-                    let new_min_max = get_new_min_max(board, min_col, max_col, min_row, max_row, &current_word_part);
-                    removable.push((current_word_part.clone(), new_min_max.0, new_min_max.1, new_min_max.2, new_min_max.3));
-                }
-                current_word_part.clear();
-            }
-            else {
-                // There's a non-empty value that didn't get removed (so there's still something on the board)
-                board_empty = false;
-            }
-        }
-        if current_word_part.len() > 0 {
-            if is_connected(board, min_col, max_col, min_row, max_row, &current_word_part) {
-                let new_min_max = get_new_min_max(board, min_col, max_col, min_row, max_row, &current_word_part);
-                removable.push((current_word_part.clone(), new_min_max.0, new_min_max.1, new_min_max.2, new_min_max.3));
-            }
-            current_word_part.clear();
-        }
-    }
-    // If the board is empty, that means there was only a single horizontal word that got removed
-    if removable.len() == 1 && board_empty {
-        return Vec::new();
-    }
-    // Then get vertical removable word parts
-    for col in min_col..=max_col {
-        let mut current_word_part: Vec<(usize, usize)> = Vec::with_capacity(max_col-min_col);
-        for row in min_row..=max_row {
-            if board.get_val(row, col) != EMPTY_VALUE && !((col != 0 && board.get_val(row, col-1) != EMPTY_VALUE) || (col != BOARD_SIZE-1 && board.get_val(row, col+1) != EMPTY_VALUE)) {
-                current_word_part.push((row, col));
-            }
-            else if board.get_val(row, col) == EMPTY_VALUE && current_word_part.len() > 0 {
-                if is_connected(board, min_col, max_col, min_row, max_row, &current_word_part) {
-                    let new_min_max = get_new_min_max(board, min_col, max_col, min_row, max_row, &current_word_part);
-                    removable.push((current_word_part.clone(), new_min_max.0, new_min_max.1, new_min_max.2, new_min_max.3));
-                }
-                current_word_part.clear();
-            }
-            else {
-                board_empty = false;
-            }
-        }
-        if current_word_part.len() > 0 {
-            if is_connected(board, min_col, max_col, min_row, max_row, &current_word_part) {
-                let new_min_max = get_new_min_max(board, min_col, max_col, min_row, max_row, &current_word_part);
-                removable.push((current_word_part.clone(), new_min_max.0, new_min_max.1, new_min_max.2, new_min_max.3));
-            }
-            current_word_part.clear();
-        }
-    }
-    if board_empty {
-        return Vec::new();
-    }
-    removable
+/// Which dictionary the solver uses
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum DictionaryChoice {
+    /// The dictionary of the ~20k most common words in English
+    Short,
+    /// The complete Scrabble dictionary
+    Long,
+    /// A dictionary loaded by the user at runtime
+    Custom
 }
 
-/// Checks whether a `word` can be made using the given `letters`
-/// # Arguments
-/// * `word` - The vector form of the word to check
-/// * `letters` - Length-26 array of the number of each letter in the hand
-/// # Returns
-/// * `bool` - Whether `word` can be made using `letters`
-fn is_makeable(word: &Word, letters: &Letters) -> bool {
-    let mut available_letters = letters.clone();
-    for letter in word.iter() {
-        if available_letters.get(*letter).unwrap() == &0 {
-            return false;
-        }
-        let elem = available_letters.get_mut(*letter).unwrap();
-        *elem -= 1;
-    }
-    true
+/// Which language the dictionaries are in
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum Language {
+    /// English, using the bundled dictionaries
+    #[default]
+    English,
+    /// Spanish, using word lists loaded from the dictionaries folder
+    Spanish,
+    /// French, using word lists loaded from the dictionaries folder
+    French
 }
 
-/// Checks that a `board` is valid after a word is played horizontally, given the specified list of `valid_word`s
-/// Note that this does not check if all words are contiguous; this condition must be enforced elsewhere.
-/// # Arguments
-/// * `board` - `Board` being checked
-/// * `min_col` - Minimum x (column) index of the subsection of the `board` to be checked
-/// * `max_col` - Maximum x (column) index of the subsection of the `board` to be checked
-/// * `min_row` - Minimum y (row) index of the subsection of the `board` to be checked
-/// * `max_row` - Maximum y (row) index of the subsection of the `board` to be checked
-/// * `row` - Row of the word played
-/// * `start_col` - Starting column of the word played
-/// * `end_col` - Ending column of the word played
-/// * `valid_words` - HashSet of all valid words as `Vec<usize>`s
-/// # Returns
-/// `bool` - whether the given `board` is made only of valid words
-fn is_board_valid_horizontal(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, row: usize, start_col: usize, end_col: usize, valid_words: &HashSet<&Word>) -> bool {
-    let mut current_letters: Vec<usize> = Vec::with_capacity(MAX_WORD_LENGTH);
-    // Find the furthest left column that the new play is connected to
-    let mut minimum_col = start_col;
-    while minimum_col > min_col {
-        if board.get_val(row, minimum_col) == EMPTY_VALUE {
-            minimum_col += 1;
-            break;
-        }
-        minimum_col -= 1;
-    }
-    minimum_col = minimum_col.max(min_col);
-    // Check across the row where the word was played
-    for col_idx in minimum_col..=max_col {
-        // If we're not at an empty square, add it to the current word we're looking at
-        if board.get_val(row, col_idx) != EMPTY_VALUE {
-            current_letters.push(board.get_val(row, col_idx));
-        }
-        else {
-            // Turns out that checking with a set is faster than using a trie, at least for smaller hands
-            if current_letters.len() > 1 && !valid_words.contains(&current_letters) {
-                return false;
-            }
-            current_letters.clear();
-            if col_idx > end_col {
-                break;
-            }
-        }
-    }
-    if current_letters.len() > 1 && !valid_words.contains(&current_letters) {
-        return false;
-    }
-    // Check down each column where a letter was played
-    for col_idx in start_col..=end_col {
-        current_letters.clear();
-        // Find the furthest up row that the word is connected to
-        let mut minimum_row = row;
-        while minimum_row > min_row {
-            if board.get_val(minimum_row, col_idx) == EMPTY_VALUE {
-                minimum_row += 1;
-                break;
-            }
-            minimum_row -= 1;
-        }
-        minimum_row = minimum_row.max(min_row);
-        for row_idx in minimum_row..=max_row {
-            if board.get_val(row_idx, col_idx) != EMPTY_VALUE {
-                current_letters.push(board.get_val(row_idx, col_idx));
-            }
-            else {
-                if current_letters.len() > 1 && !valid_words.contains(&current_letters) {
-                    return false;
-                }
-                current_letters.clear();
-                if row_idx > row {
-                    break;
-                }
-            }
-        }
-        if current_letters.len() > 1 && !valid_words.contains(&current_letters) {
-            return false;
-        }
-    }
-    true
-}
-
-/// Checks that a `board` is valid after a word is played vertically, given the specified list of `valid_word`s
-/// Note that this does not check if all words are contiguous; this condition must be enforced elsewhere.
-/// # Arguments
-/// * `board` - `Board` being checked
-/// * `min_col` - Minimum x (column) index of the subsection of the `board` to be checked
-/// * `max_col` - Maximum x (column) index of the subsection of the `board` to be checked
-/// * `min_row` - Minimum y (row) index of the subsection of the `board` to be checked
-/// * `max_row` - Maximum y (row) index of the subsection of the `board` to be checked
-/// * `start_row` - Starting row of the word played
-/// * `end_row` - Ending row of the word played
-/// * `col` - Column of the word played
-/// * `valid_words` - HashSet of all valid words as `Vec<usize>`s
-/// # Returns
-/// `bool` - whether the given `board` is made only of valid words
-fn is_board_valid_vertical(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, start_row: usize, end_row: usize, col: usize, valid_words: &HashSet<&Word>) -> bool {
-    let mut current_letters: Vec<usize> = Vec::with_capacity(MAX_WORD_LENGTH);
-    // Find the furthest up row that the new play is connected to
-    let mut minimum_row = start_row;
-    while minimum_row > min_row {
-        if board.get_val(minimum_row, col) == EMPTY_VALUE {
-            minimum_row += 1;
-            break;
-        }
-        minimum_row -= 1;
-    }
-    minimum_row = minimum_row.max(min_row);
-    // Check down the column where the word was played
-    for row_idx in minimum_row..=max_row {
-        // If it's not an empty value, add it to the current word
-        if board.get_val(row_idx, col) != EMPTY_VALUE {
-            current_letters.push(board.get_val(row_idx, col));
-        }
-        else {
-            // Otherwise, check if we have more than one letter - if so, check if the word is valid
-            if current_letters.len() > 1 && !valid_words.contains(&current_letters) {
-                return false;
-            }
-            current_letters.clear();
-            // If we're past the end of the played word, no need to check farther
-            if row_idx > end_row {
-                break;
-            }
-        }
-    }
-    // In case we don't hit the `else` in the previous loop
-    if current_letters.len() > 1 {
-        if !valid_words.contains(&current_letters) {
-            return false;
-        }
-    }
-    // Check across each row where a letter was played
-    for row_idx in start_row..=end_row {
-        current_letters.clear();
-        // Find the furthest left column that the word is connected to
-        let mut minimum_col = col;
-        while minimum_col > min_col {
-            if board.get_val(row_idx, minimum_col) == EMPTY_VALUE {
-                minimum_col += 1;
-                break;
-            }
-            minimum_col -= 1;
-        }
-        minimum_col = minimum_col.max(min_col);
-        for col_idx in minimum_col..=max_col {
-            if board.get_val(row_idx, col_idx) != EMPTY_VALUE {
-                current_letters.push(board.get_val(row_idx, col_idx));
-            }
-            else {
-                if current_letters.len() > 1 && !valid_words.contains(&current_letters) {
-                    return false;
-                }
-                current_letters.clear();
-                if col_idx > col {
-                    break;
-                }
-            }
-        }
-        if current_letters.len() > 1 && !valid_words.contains(&current_letters) {
-            return false;
+impl Language {
+    /// Gets the start of the names of the word list files for the language (see `read_language_dictionaries`)
+    /// # Returns
+    /// `&str` - The lowercase name of the language
+    fn file_stem(&self) -> &'static str {
+        match self {
+            Language::English => "english",
+            Language::Spanish => "spanish",
+            Language::French => "french"
         }
     }
-    true
 }
 
-/// Enumeration of how many letters have been used
-#[derive(Copy, Clone)]
-enum LetterUsage {
-    /// There are still unused letters
-    Remaining,
-    /// More letters have been used than are available
-    Overused,
-    /// All letters have been used
-    Finished
-}
-impl fmt::Display for LetterUsage {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-       match self {
-            LetterUsage::Remaining => write!(f, "Remaining"),
-            LetterUsage::Overused => write!(f, "Overused"),
-            LetterUsage::Finished => write!(f, "Finished")
-        }
-    }
+/// How likely a hand is to be solvable, judged from the words that can be formed from it (see `assess_hand`)
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Solvability {
+    /// Fewer than two words can be formed, so no board can be built
+    Impossible,
+    /// Few words can be formed and none uses more than half the tiles
+    Difficult,
+    /// Either many words can be formed or one uses more than half the tiles, but not both
+    Moderate,
+    /// Many words can be formed and at least one uses more than half the tiles
+    Easy
 }
-impl fmt::Debug for LetterUsage {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+
+impl Solvability {
+    /// Gets the name of the category, to send to the frontend
+    /// # Returns
+    /// `&str` - The name of the variant
+    fn name(&self) -> &'static str {
         match self {
-            LetterUsage::Remaining => write!(f, "Remaining"),
-            LetterUsage::Overused => write!(f, "Overused"),
-            LetterUsage::Finished => write!(f, "Finished")
+            Solvability::Impossible => "Impossible",
+            Solvability::Difficult => "Difficult",
+            Solvability::Moderate => "Moderate",
+            Solvability::Easy => "Easy"
         }
     }
 }
 
-/// Enumeration of the direction a word is played
+/// Which set of tiles random letters are drawn from
 #[derive(Copy, Clone, PartialEq)]
-enum Direction {
-    /// The word was played horizontally
-    Horizontal,
-    /// The word was played vertically
-    Vertical
+#[allow(clippy::large_enum_variant)] // Only the one in `AppState` is ever kept, so boxing the custom counts wouldn't save anything
+enum TileSet {
+    /// Standard Bananagrams (144 tiles plus 2 blanks)
+    Standard,
+    /// Double Bananagrams (288 tiles plus 4 blanks)
+    Double,
+    /// Three standard sets combined (432 tiles plus 6 blanks)
+    Triple,
+    /// A set defined by the user, with the given number of each letter (and no blanks)
+    Custom([u64; 26])
 }
-impl fmt::Display for Direction {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Direction::Vertical => write!(f, "Horizontal"),
-            Direction::Horizontal => write!(f, "Vertical")
+
+impl TileSet {
+    /// Gets the tile set with the given name
+    /// # Arguments
+    /// * `name` - One of "standard Bananagrams", "double Bananagrams", or "triple Bananagrams"
+    /// # Returns
+    /// `Option` - The `TileSet`, or `None` if `name` is not recognized
+    fn from_name(name: &str) -> Option<TileSet> {
+        match name {
+            "standard Bananagrams" => Some(TileSet::Standard),
+            "double Bananagrams" => Some(TileSet::Double),
+            "triple Bananagrams" => Some(TileSet::Triple),
+            _ => None
         }
     }
-}
-impl fmt::Debug for Direction {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+
+    /// Gets how many standard sets the set is made of
+    /// # Returns
+    /// `Option` - The number of standard sets, or `None` for a custom set
+    fn scale(&self) -> Option<u64> {
         match self {
-            Direction::Vertical => write!(f, "Horizontal"),
-            Direction::Horizontal => write!(f, "Vertical")
+            TileSet::Standard => Some(1),
+            TileSet::Double => Some(2),
+            TileSet::Triple => Some(3),
+            TileSet::Custom(_) => None
         }
     }
-}
 
-/// Removes words that can't be played with `current_letters` plus a set number of `board_letters`
-/// # Arguments
-/// * `current_letters` - Letters currently available in the hand
-/// * `board_letters` - Letters played on the board
-/// * `word_being_checked` - Word to check if it contains the appropriate number of letters
-/// * `filter_letters_on_board` - Maximum number of letters from `board_letters` that can be used when checking if the word can be played
-/// # Returns
-/// * `bool` - Whether `word_being_checked` should pass the filter
-fn check_filter_after_play_later(mut current_letters: Letters, mut board_letters: Letters, word_being_checked: &Word, filter_letters_on_board: usize) -> bool {
-    let mut num_from_board = 0usize;
-    for letter in word_being_checked.iter() {
-        let num_in_hand = current_letters.get_mut(*letter).unwrap();
-        if *num_in_hand == 0 {
-            if num_from_board == filter_letters_on_board {
-                return false;
-            }
-            let num_on_board = board_letters.get_mut(*letter).unwrap();
-            if *num_on_board == 0 {
-                return false;
-            }
-            *num_on_board -= 1;
-            num_from_board += 1;
-        }
-        else {
-            *num_in_hand -= 1;
+    /// Gets the number of each letter tile in the set
+    /// # Arguments
+    /// * `standard_tiles` - The tiles of standard Bananagrams in the current language (see `language_tiles`)
+    /// # Returns
+    /// `[u64; 26]` - The number of tiles of each letter
+    fn tiles(&self, standard_tiles: &[u64; 26]) -> [u64; 26] {
+        match self {
+            TileSet::Custom(tiles) => *tiles,
+            _ => standard_tiles.map(|n| n*self.scale().unwrap_or(1))
         }
     }
-    return true;
-}
 
-/// Checks which words can be played after the first
-/// # Arguments
-/// * `letters` - Length-26 array of originally available letters
-/// * `word_being_checked` - Word that is being checked if playable
-/// * `played_on_board` - Set of the letters played on the board
-/// # Returns
-/// * `bool` - Whether the `word_being_checked` is playable
-fn check_filter_after_play(mut letters: Letters, word_being_checked: &Word, played_on_board: &HashSet<usize>) -> bool {
-    let mut already_seen_negative = false;
-    for letter in word_being_checked.iter() {
-        let elem = letters.get_mut(*letter).unwrap();
-        if *elem == 0 && !played_on_board.contains(letter) {
-            return false;
-        }
-        else if *elem <= 0 && already_seen_negative {
-            return false;
-        }
-        else if *elem == 0 {
-            already_seen_negative = true;
-        }
-        else {
-            *elem -= 1;
-        }
+    /// Gets the number of blank tiles in the set
+    /// # Returns
+    /// `u64` - The number of blanks
+    fn blanks(&self) -> u64 {
+        self.scale().map_or(0, |scale| REGULAR_BLANKS*scale)
     }
-    return true;
 }
 
-/// Gets the minimum and maximum columns where a word could be played at `row` on `board`
-/// # Arguments
-/// * `board` - Board to search
-/// * `row` - Row to check
-/// * `min_col` - Minimum occupied column on `board`
-/// * `max_col` - Maximum occupied column on `board`
-/// # Returns
-/// * `(usize, usize)` - Length-2 tuple of the (minimum column, maximum column) where a word could be played
-fn get_col_limits(board: &Board, row: usize, min_col: usize, max_col: usize) -> (usize, usize) {
-    let mut leftmost = max_col;
-    let mut rightmost = min_col;
-    if row == 0 {
-        for col in min_col..max_col {
-            if board.get_val(row, col) != EMPTY_VALUE || board.get_val(row+1, col) != EMPTY_VALUE {
-                leftmost = col;
-                break;
-            }
-        }
-        for col in (min_col..=max_col).rev() {
-            if board.get_val(row, col) != EMPTY_VALUE || board.get_val(row+1, col) != EMPTY_VALUE {
-                rightmost = col;
-                break;
-            }
-        }
-    }
-    else if row == BOARD_SIZE-1 {
-        for col in min_col..max_col {
-            if board.get_val(row, col) != EMPTY_VALUE || board.get_val(row-1, col) != EMPTY_VALUE {
-                leftmost = col;
-                break;
-            }
-        }
-        for col in (min_col..=max_col).rev() {
-            if board.get_val(row, col) != EMPTY_VALUE || board.get_val(row-1, col) != EMPTY_VALUE {
-                rightmost = col;
-                break;
-            }
-        }
-    }
-    else {
-        for col in min_col..max_col {
-            if board.get_val(row-1, col) != EMPTY_VALUE || board.get_val(row, col) != EMPTY_VALUE || board.get_val(row+1, col) != EMPTY_VALUE {
-                leftmost = col;
-                break;
-            }
-        }
-        for col in (min_col..=max_col).rev() {
-            if board.get_val(row-1, col) != EMPTY_VALUE || board.get_val(row, col) != EMPTY_VALUE || board.get_val(row+1, col) != EMPTY_VALUE {
-                rightmost = col;
-                break;
-            }
-        }
-    }
-    (leftmost, rightmost)
-}
-
-/// Gets the minimum and maximum rows where a word could be played at `col` on `board`
-/// # Arguments
-/// * `board` - Board to search
-/// * `col` - Column to check
-/// * `min_row` - Minimum occupied row on `board`
-/// * `max_row` - Maximum occupied row on `board`
-/// # Returns
-/// * `(usize, usize)` - Length-2 tuple of the (minimum row, maximum row) where a word could be played
-fn get_row_limits(board: &Board, col: usize, min_row: usize, max_row: usize) -> (usize, usize) {
-    let mut uppermost = min_row;
-    let mut lowermost = max_row;
-    if col == 0 {
-        for row in min_row..max_row {
-            if board.get_val(row, col) != EMPTY_VALUE || board.get_val(row, col+1) != EMPTY_VALUE {
-                uppermost = row;
-                break;
-            }
-        }
-        for row in (min_row..=max_row).rev() {
-            if board.get_val(row, col) != EMPTY_VALUE || board.get_val(row, col+1) != EMPTY_VALUE {
-                lowermost = row;
-                break;
-            }
-        }
-    }
-    else if col == BOARD_SIZE-1 {
-        for row in min_row..max_row {
-            if board.get_val(row, col) != EMPTY_VALUE || board.get_val(row, col-1) != EMPTY_VALUE {
-                uppermost = row;
-                break;
-            }
-        }
-        for row in (min_row..=max_row).rev() {
-            if board.get_val(row, col) != EMPTY_VALUE || board.get_val(row, col-1) != EMPTY_VALUE {
-                lowermost = row;
-                break;
-            }
-        }
-    }
-    else {
-        for row in min_row..max_row {
-            if board.get_val(row, col-1) != EMPTY_VALUE || board.get_val(row, col) != EMPTY_VALUE || board.get_val(row, col+1) != EMPTY_VALUE {
-                uppermost = row;
-                break;
-            }
-        }
-        for row in (min_row..=max_row).rev() {
-            if board.get_val(row, col-1) != EMPTY_VALUE || board.get_val(row, col) != EMPTY_VALUE || board.get_val(row, col+1) != EMPTY_VALUE {
-                lowermost = row;
-                break;
-            }
-        }
-    }
-    (uppermost, lowermost)
-}
-
-/// Tries to play a word horizontally anywhere on the `board`
-/// # Arguments
-/// * `board` - The `Board` to modify in-place
-/// * `word` - Word to try to play
-/// * `min_col` - Minimum occupied column index in `board`
-/// * `max_col` - Maximum occupied column index in `board`
-/// * `min_row` - Minimum occupied row index in `board`
-/// * `max_row` - Maximum occupied row index in `board`
-/// * `valid_words_vec` - Vector of vectors, each representing a word (see `convert_word_to_array`)
-/// * `valid_words_set` - HashSet of vectors, each representing a word (a HashSet version of `valid_words_vec` for faster membership checking)
-/// * `letters` - Length-26 array of the number of each letter in the hand
-/// * `depth` - Depth of the current recursive call
-/// * `words_checked` - The number of words checked in total
-/// * `letters_on_board` - Length-26 array of the number of each letter currently present on the `board`
-/// * `filter_letters_on_board` - Maximum number of letters currently on the board that can be used in a newly played word
-/// * `max_words_to_check` - Maximum number of words to check before stopping
-/// * `stop_t` - `AtomicBool` that, when set, indicates that processing should stop
-/// # Returns
-/// *`Result` with `Option` upon success with:*
-/// * `bool` - Whether the word could be validly played
-/// * `usize` - Minimum occupied column index in `board`
-/// * `usize` - Maximum occupied column index in `board`
-/// * `usize` - Minimum occupied row index in `board`
-/// * `usize` - Maximum occupied row index in `board`
-/// 
-/// *or `None` if no valid playing location was found, or empty `Err` another thread signalled to stop*
-fn try_play_word_horizontal(board: &mut Board, word: &Word, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words_vec: &Vec<&Word>, valid_words_set: &HashSet<&Word>, letters: Letters, depth: usize, words_checked: &mut usize, letters_on_board: &mut Letters, filter_letters_on_board: usize, max_words_to_check: usize, stop_t: &Arc<AtomicBool>) -> Result<Option<(bool, usize, usize, usize, usize)>, ()> {
-    // Try across all rows (starting from one before to one after)
-    for row_idx in min_row.saturating_sub(1)..=BOARD_SIZE.min(max_row+1) {
-        let (leftmost_col, rightmmost_col) = get_col_limits(board, row_idx, min_col, max_col);
-        // For each row, try across all columns (starting from the farthest out the word could be played)
-        for col_idx in leftmost_col.saturating_sub(word.len())..=BOARD_SIZE.min(rightmmost_col+1) {
-            let res = board.play_word(word, row_idx, col_idx, Direction::Horizontal, &letters, letters_on_board);
-            if res.0 {
-                // If the word was played successfully (i.e. it's not a complete overlap and it borders at least one existing tile), then check the validity of the new words it forms
-                let new_min_col = min_col.min(col_idx);
-                let new_max_col = max_col.max(col_idx+word.len());
-                let new_min_row = min_row.min(row_idx);
-                let new_max_row = max_row.max(row_idx);
-                if is_board_valid_horizontal(board, new_min_col, new_max_col, new_min_row, new_max_row, row_idx, col_idx, col_idx+word.len()-1, valid_words_set) {
-                    // If it's valid, go to the next recursive level (unless we've all the letters, at which point we're done)
-                    match res.3 {
-                        LetterUsage::Finished => {
-                            return Ok(Some((true, new_min_col, new_max_col, new_min_row, new_max_row)));
-                        },
-                        LetterUsage::Remaining => {
-                            let mut new_valid_words_vec: Vec<&Word> = Vec::with_capacity(valid_words_vec.len()/2);
-                            for i in 0..valid_words_vec.len() {
-                                if check_filter_after_play_later(letters.clone(), letters_on_board.clone(), valid_words_vec[i], filter_letters_on_board) {
-                                    new_valid_words_vec.push(valid_words_vec[i]);
-                                }
-                            }
-                            let res2 = play_further(board, new_min_col, new_max_col, new_min_row, new_max_row, &new_valid_words_vec, valid_words_set, res.2, depth+1, words_checked, letters_on_board, filter_letters_on_board, max_words_to_check, stop_t)?;
-                            if res2.0 {
-                                // If that recursive stack finishes successfully, we're done! (could have used another Result or Option rather than a bool in the returned tuple, but oh well)
-                                return Ok(Some(res2));
-                            }
-                            else {
-                                // Otherwise, undo the previous play (cloning the board before each play so we don't have to undo is *way* slower)
-                                board.undo_play(&res.1, letters_on_board);
-                            }
-                        },
-                        LetterUsage::Overused => unreachable!()
-                    }
-                }
-                else {
-                    // If the play formed some invalid words, undo the previous play
-                    board.undo_play(&res.1, letters_on_board);
-                }
-            }
-            else {
-                // If trying to play the board was invalid, undo the play
-                board.undo_play(&res.1, letters_on_board);
-            }
-        }
-    }
-    Ok(None)
-}
-
-/// Tries to play a word vertically anywhere on the `board`
-/// # Arguments
-/// * `board` - The `Board` to modify in-place
-/// * `word` - Word to try to play
-/// * `min_col` - Minimum occupied column index in `board`
-/// * `max_col` - Maximum occupied column index in `board`
-/// * `min_row` - Minimum occupied row index in `board`
-/// * `max_row` - Maximum occupied row index in `board`
-/// * `valid_words_vec` - Vector of vectors, each representing a word (see `convert_word_to_array`)
-/// * `valid_words_set` - HashSet of vectors, each representing a word (a HashSet version of `valid_words_vec` for faster membership checking)
-/// * `letters` - Length-26 array of the number of each letter in the hand
-/// * `depth` - Depth of the current recursive call
-/// * `words_checked` - The number of words checked in total
-/// * `letters_on_board` - Length-26 array of the number of each letter currently present on the `board`
-/// * `filter_letters_on_board` - Maximum number of letters currently on the board that can be used in a newly played word
-/// * `max_words_to_check` - Maximum number of words to check before stopping
-/// * `stop_t` - `AtomicBool` that, when set, indicates that processing should stop
-/// # Returns
-/// *`Result` with `Option` upon success with:*
-/// * `bool` - Whether the word could be validly played
-/// * `usize` - Minimum occupied column index in `board`
-/// * `usize` - Maximum occupied column index in `board`
-/// * `usize` - Minimum occupied row index in `board`
-/// * `usize` - Maximum occupied row index in `board`
-/// 
-/// *or `None` if no valid playing location was found, or empty `Err` if another thread signalled to stop*
-fn try_play_word_vertically(board: &mut Board, word: &Word, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words_vec: &Vec<&Word>, valid_words_set: &HashSet<&Word>, letters: Letters, depth: usize, words_checked: &mut usize, letters_on_board: &mut Letters, filter_letters_on_board: usize, max_words_to_check: usize, stop_t: &Arc<AtomicBool>) -> Result<Option<(bool, usize, usize, usize, usize)>, ()> {
-    // Try down all columns
-    for col_idx in min_col.saturating_sub(1)..=BOARD_SIZE.min(max_col+1) {
-        let (uppermost_row, lowermost_row) = get_row_limits(board, col_idx, min_row, max_row);
-        // This is analagous to the above
-        for row_idx in uppermost_row.saturating_sub(word.len())..=BOARD_SIZE.min(lowermost_row+1) {
-            let res = board.play_word(word, row_idx, col_idx, Direction::Vertical, &letters, letters_on_board);
-            if res.0 {
-                let new_min_col = min_col.min(col_idx);
-                let new_max_col = max_col.max(col_idx);
-                let new_min_row = min_row.min(row_idx);
-                let new_max_row = max_row.max(row_idx+word.len());
-                if is_board_valid_vertical(board, new_min_col, new_max_col, new_min_row, new_max_row, row_idx, row_idx+word.len()-1, col_idx, valid_words_set) {
-                    match res.3 {
-                        LetterUsage::Finished => {
-                            return Ok(Some((true, new_min_col, new_max_col, new_min_row, new_max_row)));
-                        },
-                        LetterUsage::Remaining => {
-                            let mut new_valid_words_vec: Vec<&Word> = Vec::with_capacity(valid_words_vec.len()/2);
-                            for i in 0..valid_words_vec.len() {
-                                if check_filter_after_play_later(letters.clone(), letters_on_board.clone(), valid_words_vec[i], filter_letters_on_board) {
-                                    new_valid_words_vec.push(valid_words_vec[i]);
-                                }
-                            }
-                            let res2 = play_further(board, new_min_col, new_max_col, new_min_row, new_max_row, &new_valid_words_vec, valid_words_set, res.2, depth+1, words_checked, letters_on_board, filter_letters_on_board, max_words_to_check, stop_t)?;
-                            if res2.0 {
-                                return Ok(Some(res2));
-                            }
-                            else {
-                                board.undo_play(&res.1, letters_on_board);
-                            }
-                        },
-                        LetterUsage::Overused => unreachable!()
-                    }
-                }
-                else {
-                    board.undo_play(&res.1, letters_on_board);
-                }
-            }
-            else {
-                board.undo_play(&res.1, letters_on_board);
-            }
-        }
-    }
-    Ok(None)
-}
-
-/// Recursively solves Bananagrams
-/// # Arguments
-/// * `board` - The `Board` to modify in-place
-/// * `min_col` - Minimum occupied column index in `board`
-/// * `max_col` - Maximum occupied column index in `board`
-/// * `min_row` - Minimum occupied row index in `board`
-/// * `max_row` - Maximum occupied row index in `board`
-/// * `valid_words_vec` - Vector of vectors, each representing a word (see `convert_word_to_array`)
-/// * `valid_words_set` - HashSet of vectors, each representing a word (a HashSet version of `valid_words_vec` for faster membership checking)
-/// * `letters` - Length-26 array of the number of each letter in the hand
-/// * `depth` - Depth of the current recursive call
-/// * `words_checked` - The number of words checked in total
-/// * `letters_on_board` - Length-26 array of the number of each letter currently present on the `board`
-/// * `filter_letters_on_board` - Maximum number of letters currently on the board that can be used in a newly played word
-/// * `max_words_to_check` - Maximum number of words to check before stopping
-/// * `stop_t` - `AtomicBool` that, when set, indicates that processing should stop
-/// # Returns
-/// *`Result` with:*
-/// * `bool` - Whether the word could be validly played
-/// * `usize` - Minimum occupied column index in `board`
-/// * `usize` - Maximum occupied column index in `board`
-/// * `usize` - Minimum occupied row index in `board`
-/// * `usize` - Maximum occupied row index in `board`
-/// 
-/// *or empty `Err` if out-of-bounds, past the maximum number of words to check, or another thread signalled to stop*
-fn play_further(board: &mut Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words_vec: &Vec<&Word>, valid_words_set: &HashSet<&Word>, letters: Letters, depth: usize, words_checked: &mut usize, letters_on_board: &mut Letters, filter_letters_on_board: usize, max_words_to_check: usize, stop_t: &Arc<AtomicBool>) -> Result<(bool, usize, usize, usize, usize), ()> {
-    if *words_checked > max_words_to_check || stop_t.load(Ordering::Relaxed) {
-        return Err(());
-    }
-    // If we're at an odd depth, play horizontally first (trying to alternate horizontal-vertical-horizontal as a heuristic to solve faster)
-    if depth % 2 == 1 {
-        for word in valid_words_vec.iter() {
-            *words_checked += 1;
-            if stop_t.load(Ordering::Relaxed) {
-                return Err(());
-            }
-            if let Some(r) = try_play_word_horizontal(board, word, min_col, max_col, min_row, max_row, valid_words_vec, valid_words_set, letters, depth, words_checked, letters_on_board, filter_letters_on_board, max_words_to_check, stop_t)? {
-                return Ok(r);
-            }
-        }
-        // If trying every word horizontally didn't work, try vertically instead
-        for word in valid_words_vec.iter() {
-            *words_checked += 1;
-            if stop_t.load(Ordering::Relaxed) {
-                return Err(());
-            }
-            if let Some(r) = try_play_word_vertically(board, word, min_col, max_col, min_row, max_row, valid_words_vec, valid_words_set, letters, depth, words_checked, letters_on_board, filter_letters_on_board, max_words_to_check, stop_t)? {
-                return Ok(r);
-            }
-        }
-        return Ok((false, min_col, max_col, min_row, max_row));
-    }
-    // If we're at an even depth, play vertically first. Otherwise this is analgous to the above.
-    else {
-        for word in valid_words_vec.iter() {
-            *words_checked += 1;
-            if stop_t.load(Ordering::Relaxed) {
-                return Err(());
-            }
-            if let Some(r) = try_play_word_vertically(board, word, min_col, max_col, min_row, max_row, valid_words_vec, valid_words_set, letters, depth, words_checked, letters_on_board, filter_letters_on_board, max_words_to_check, stop_t)? {
-                return Ok(r);
-            }
-        }
-        // No point in checking horizontally for the first depth, since it would have to form a vertical word that was already checked and failed
-        if depth == 0 {
-            return Ok((false, min_col, max_col, min_row, max_row));
-        }
-        for word in valid_words_vec.iter() {
-            *words_checked += 1;
-            if stop_t.load(Ordering::Relaxed) {
-                return Err(());
-            }
-            if let Some(r) = try_play_word_horizontal(board, word, min_col, max_col, min_row, max_row, valid_words_vec, valid_words_set, letters, depth, words_checked, letters_on_board, filter_letters_on_board, max_words_to_check, stop_t)? {
-                return Ok(r);
-            }
-        }
-        return Ok((false, min_col, max_col, min_row, max_row));
-    }
-}
-
-/// Tries to play a single letter on the board
-/// # Arguments
-/// * `board` - The `Board` on which to try to play the `letter`
-/// * `min_col` - Minimum occupied column index in `board`
-/// * `max_col` - Maximum occupied column index in `board`
-/// * `min_row` - Minimum occupied row index in `board`
-/// * `max_row` - Maximum occupied row index in `board`
-/// * `letter` - The numeric representation of the letter to play
-/// * `valid_words_set` - HashSet of all valid words
-/// # Returns
-/// `Option` - either `None` if no solution was found, or a `Some` tuple of `(row, col, new_min_col, new_max_col, new_min_row, new_max_row)` on success
-fn play_one_letter(board: &mut Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, letter: usize, valid_words_set: &HashSet<&Word>) -> Option<(usize, usize, usize, usize, usize, usize)> {
-    // Loop through all possible locations and check if the letter works there
-    for row in min_row.saturating_sub(1)..=BOARD_SIZE.min(max_row+1) {
-        for col in min_col.saturating_sub(1)..=BOARD_SIZE.min(max_col+1) {
-            if row < BOARD_SIZE && col < BOARD_SIZE && board.get_val(row, col) == EMPTY_VALUE {   // row/col don't need to be checked if they're greater than 0 since they'd underflow
-                if (col > 0 && board.get_val(row, col-1) != EMPTY_VALUE) || (col < BOARD_SIZE-1 && board.get_val(row, col+1) != EMPTY_VALUE) || (row > 0 && board.get_val(row-1, col) != EMPTY_VALUE) || (row < BOARD_SIZE-1 && board.get_val(row+1, col) != EMPTY_VALUE) {
-                    board.set_val(row, col, letter);
-                    let new_min_col = min_col.min(col);
-                    let new_max_col = max_col.max(col);
-                    let new_min_row = min_row.min(row);
-                    let new_max_row = max_row.max(row);
-                    // Could also use `is_board_valid_vertical`
-                    if is_board_valid_horizontal(board, new_min_col, new_max_col, new_min_row, new_max_row, row, col, col, valid_words_set) {
-                        // If it's valid, return the (potentially) new bounds, along with the location the letter was played
-                        return Some((row, col, new_min_col, new_max_col, new_min_row, new_max_row));
-                    }
-                    else {
-                        // If the board wasn't ok, reset this spot
-                        board.set_val(row, col, EMPTY_VALUE);
-                    }
-                }
-            }
-        }
-    }
-    // Return `None` if we don't find a solution
-    return None;
-}
-
-/// Recursively plays on an existing board by removing letters
-/// # Arguments
-/// * `board` - Existing board (will be modified in-place)
-/// * `letters_on_board` - Current letters on the `board`
-/// * `min_col` - Minimum occupied column index in `board`
-/// * `max_col` - Maximum occupied column index in `board`
-/// * `min_row` - Minimum occupied row index in `board`
-/// * `max_row` - Maximum occupied row index in `board`
-/// * `hand_letters` - Letters currently in the hand
-/// * `valid_words_vec` - Valid words that can be played on `board`
-/// * `valid_words_set` - Set of all valid words
-/// * `filter_letters_on_board` - Maximum number of letters from the board that can be used in a word
-/// * `max_words_to_check` - Maximum number of words to check
-/// * `stop_t` - AtomicBool for early stopping
-/// # Returns
-/// `Option` - either `None` if no solution was found, or a `Some` tuple of `(new_min_col, new_max_col, new_min_row, new_max_row)` on success
-fn play_removing(board: &mut Board, letters_on_board: &mut Letters, min_col: usize, max_col: usize, min_row: usize, max_row: usize, hand_letters: Letters, valid_words_vec: &Vec<&Word>, valid_words_set: &HashSet<&Word>, filter_letters_on_board: usize, max_words_to_check: usize, stop_t: &Arc<AtomicBool>) -> Option<(usize, usize, usize, usize)> {
-    let mut words_checked = 0usize;
-    // First try to play the words on the board, first horizontally and then vertically
-    for word in valid_words_vec {
-        match try_play_word_horizontal(board, word, min_col, max_col, min_row, max_row, valid_words_vec, valid_words_set, hand_letters, 0, &mut words_checked, &mut letters_on_board.clone(), filter_letters_on_board, max_words_to_check, stop_t) {
-            Ok(r) => {
-                // Ok + Some indicates that a solution was found
-                if let Some(rr) = r {
-                    // If another thread said we're done then just return None (although I *think* this isn't necessary since the return should then be an `Err`)
-                    if stop_t.load(Ordering::Relaxed) {
-                        return None;
-                    }
-                    // If we found a solution, set `stop_t` to true and return it
-                    else if rr.0 {
-                        stop_t.store(true, Ordering::Relaxed);
-                        return Some((rr.1, rr.2, rr.3, rr.4));
-                    }
-                    // Otherwise, try to play the word vertically
-                    else {
-                        match try_play_word_vertically(board, word, min_col, max_col, min_row, max_row, valid_words_vec, valid_words_set, hand_letters, 0, &mut words_checked, &mut letters_on_board.clone(), filter_letters_on_board, max_words_to_check, stop_t) {
-                            Ok(rrr) => {
-                                if let Some(rrrr) = rrr {
-                                    if stop_t.load(Ordering::Relaxed) {
-                                        return None;
-                                    }
-                                    else if rrrr.0 {
-                                        stop_t.store(true, Ordering::Relaxed);
-                                        return Some((rrrr.1, rrrr.2, rrrr.3, rrrr.4));
-                                    }
-                                }
-                            },
-                            Err(_) => {
-                                return None;
-                            }
-                        }
-                    }
-                }
-                else {
-                    match try_play_word_vertically(board, word, min_col, max_col, min_row, max_row, valid_words_vec, valid_words_set, hand_letters, 0, &mut words_checked, &mut letters_on_board.clone(), filter_letters_on_board, max_words_to_check, stop_t) {
-                        Ok(rrr) => {
-                            if let Some(rrrr) = rrr {
-                                if stop_t.load(Ordering::Relaxed) {
-                                    return None;
-                                }
-                                else if rrrr.0 {
-                                    stop_t.store(true, Ordering::Relaxed);
-                                    return Some((rrrr.1, rrrr.2, rrrr.3, rrrr.4));
-                                }
-                            }
-                        },
-                        Err(_) => {
-                            return None;
-                        }
-                    }
-                }
-            },
-            // An `Err` indicates that a thread signalled to stop
-            Err(_) => {
-                return None;
-            }
-        }
-    }
-    // If playing the word failed, find the new removable_indices and continue recursively
-    let mut removable_indices = get_removable_indices(board, min_col, max_col, min_row, max_row);
-    removable_indices.sort_unstable_by(|a, b| a.0.len().cmp(&b.0.len()));
-    for rmv in removable_indices {
-        let mut new_letters_on_board = letters_on_board.clone();
-        let prev_vals = board.undo_play(&rmv.0, &mut new_letters_on_board);
-        let mut new_hand_letters = hand_letters.clone();
-        prev_vals.iter().for_each(|p| {
-            new_hand_letters[*p] += 1;
-        });
-        // If we found a solution, return it
-        if let Some(res) = play_removing(board, &mut new_letters_on_board, rmv.1, rmv.2, rmv.3, rmv.4, new_hand_letters, valid_words_vec, valid_words_set, filter_letters_on_board, max_words_to_check, stop_t) {
-            return Some(res);
-        }
-        // If we didn't find a solution because another thread said to stop, then return None
-        else if stop_t.load(Ordering::Relaxed) {
-            return None;
-        }
-        // Otherwise undo the undo and continue to the next set of removable indices
-        else {
-            for (idx, val) in rmv.0.iter().zip(prev_vals) {
-                board.set_val(idx.0, idx.1, val);
-            }
-        }
-    }
-    None
-}
-
-/// Plays a new hand of `letters` on an existing `board`
-/// # Arguments
-/// * `old_board` - Previous board solution
-/// * `min_col` - Minimum occupied column index
-/// * `max_col` - Maximum occupied column index
-/// * `min_row` - Minimum occupied row index
-/// * `max_row` - Maximum occupied row index
-/// * `letters` - Letters in the new hand
-/// * `filter_letters_on_board` - Maximum number of letters from the board that can be used in a word
-/// * `max_words_to_check` - Maximum number of words to check
-fn play_existing(old_board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, letters: &Letters, valid_words_set: &HashSet<&Word>, dict_to_use: &Vec<Word>, filter_letters_on_board: usize, max_words_to_check: usize) -> Option<BoardAndIdxs> {
-    // First, try to play words that use only the new letters, plus one already present on the board
-    let mut hand_letters = letters.clone();
-    let mut old_letters_on_board = [0usize; 26];
-    let mut played_on_board: HashSet<usize> = HashSet::new();
-    for row in min_row..=max_row {
-        for col in min_col..=max_col {
-            if old_board.get_val(row, col) != EMPTY_VALUE {
-                hand_letters[old_board.get_val(row, col)] -= 1;
-                old_letters_on_board[old_board.get_val(row, col)] += 1;
-                played_on_board.insert(old_board.get_val(row, col));
-            }
-        }
-    }
-    let valid_words_vec: Vec<&Word> = dict_to_use.iter().filter(|w| check_filter_after_play_later(hand_letters.clone(), old_letters_on_board.clone(), w, filter_letters_on_board)).collect();
-    // Prepare for threading/early termination using `AtomicBool`
-    let stop = Arc::new(AtomicBool::new(false));
-    let arc_valid_words_set = Arc::new(valid_words_set);
-    let char_vec: Vec<(Board, usize, usize, usize, usize)> = Vec::new();
-    let ret_val = Arc::new(Mutex::new(char_vec));
-    if !valid_words_vec.is_empty() {
-        // Split the words to check up into appropriate chunks based on the available parallelism
-        let default_parallelism_approx =  thread::available_parallelism().unwrap_or(NonZeroUsize::new(1).unwrap()).get();
-        let chunk_size = (valid_words_vec.len() as f32)/(default_parallelism_approx as f32);
-        let mut chunks: Vec<Vec<&Word>> = vec![Vec::with_capacity(chunk_size.ceil() as usize); default_parallelism_approx];
-        for (i, word) in valid_words_vec.iter().enumerate() {
-            chunks[i % default_parallelism_approx].push(word);
-        }
-        let arc_valid_words_vec = Arc::new(valid_words_vec);
-        // For each thread (i.e. piece of available parallelism), spawn a new thread to check those words
-        // These threads check different sets of initial words in the board, and whichever finishes first signals the others to stop
-        thread::scope(|s| {
-            let mut handles: Vec<thread::ScopedJoinHandle<()>> = Vec::with_capacity(chunks.len());
-            for chunk in chunks {
-                let stop_t = stop.clone();
-                let new_letters = hand_letters.clone();
-                let copied_new_valid_words_vec = Arc::clone(&arc_valid_words_vec);
-                let copied_valid_words_set = Arc::clone(&arc_valid_words_set);
-                let conn = Arc::clone(&ret_val);
-                let board_cloned = old_board.clone();
-                let letters_on_board = old_letters_on_board.clone();
-                let handle = s.spawn(move || {
-                    // Loop through each word and play it on a new board
-                    let mut words_checked = 0;
-                    let mut board = board_cloned.clone();
-                    for word in chunk.iter() {
-                        match try_play_word_horizontal(&mut board, word, min_col, max_col, min_row, max_row, &copied_new_valid_words_vec, &copied_valid_words_set, new_letters, 0, &mut words_checked, &mut letters_on_board.clone(), filter_letters_on_board, max_words_to_check, &stop_t) {
-                            Ok(r) => {
-                                if let Some(rr) = r {
-                                    if stop_t.load(Ordering::Relaxed) {
-                                        break;
-                                    }
-                                    else if rr.0 {
-                                        stop_t.store(true, Ordering::Relaxed);
-                                        let mut ret = conn.lock().expect("Failed to get lock on shared ret_val");
-                                        ret.push((board, rr.1, rr.2, rr.3, rr.4));
-                                        break;
-                                    }
-                                    else {
-                                        match try_play_word_vertically(&mut board, word, min_col, max_col, min_row, max_row, &copied_new_valid_words_vec, &copied_valid_words_set, new_letters, 0, &mut words_checked, &mut letters_on_board.clone(), filter_letters_on_board, max_words_to_check, &stop_t) {
-                                            Ok(rrr) => {
-                                                if let Some(rrrr) = rrr {
-                                                    if rrrr.0 && !stop_t.load(Ordering::Relaxed) {
-                                                        stop_t.store(true, Ordering::Relaxed);
-                                                        let mut ret = conn.lock().expect("Failed to get lock on shared ret_val");
-                                                        ret.push((board, rrrr.1, rrrr.2, rrrr.3, rrrr.4));
-                                                        break;
-                                                    }
-                                                }
-                                            },
-                                            Err(_) => {
-                                                break;
-                                            }
-                                        }
-                                    }
-                                }
-                                else {
-                                    match try_play_word_vertically(&mut board, word, min_col, max_col, min_row, max_row, &copied_new_valid_words_vec, &copied_valid_words_set, new_letters, 0, &mut words_checked, &mut letters_on_board.clone(), filter_letters_on_board, max_words_to_check, &stop_t) {
-                                        Ok(rrr) => {
-                                            if let Some(rrrr) = rrr {
-                                                if rrrr.0 && !stop_t.load(Ordering::Relaxed) {
-                                                    stop_t.store(true, Ordering::Relaxed);
-                                                    let mut ret = conn.lock().expect("Failed to get lock on shared ret_val");
-                                                    ret.push((board, rrrr.1, rrrr.2, rrrr.3, rrrr.4));
-                                                    break;
-                                                }
-                                            }
-                                        },
-                                        Err(_) => {
-                                            break;
-                                        }
-                                    }
-                                }
-                            },
-                            Err(_) => {
-                                break;
-                            }
-                        }
-                    }
-                });
-                handles.push(handle);
-            }
-            // Wait for all the threads
-            for handle in handles {
-                let _res = handle.join();
-            }
-        });    
-        // If we're done, return the result
-        let ret = ret_val.lock().expect("Failed to get lock on shared ret_val when checking return");
-        if !ret.is_empty() {
-            return Some(ret[0].clone());
-        }
-    }
-
-    // If that didn't work (or there were no valid words), try recursively looping through indices that can be removed
-    let arc_dict_to_use = Arc::new(dict_to_use);
-    let mut removable_indices = get_removable_indices(old_board, min_col, max_col, min_row, max_row);
-    removable_indices.sort_unstable_by(|a, b| a.0.len().cmp(&b.0.len()));  // Try smaller sets of removable indices first
-    let default_parallelism_approx =  thread::available_parallelism().unwrap_or(NonZeroUsize::new(1).unwrap()).get();
-    let chunk_size = (removable_indices.len() as f32)/(default_parallelism_approx as f32);
-    let mut chunks: Vec<Vec<&Removable>> = vec![Vec::with_capacity(chunk_size.ceil() as usize); default_parallelism_approx];
-    for (i, r) in removable_indices.iter().enumerate() {
-        chunks[i % default_parallelism_approx].push(r);
-    }
-    thread::scope(|s| {
-        let mut handles: Vec<thread::ScopedJoinHandle<()>> = Vec::with_capacity(chunks.len());
-        for chunk in chunks {
-            let stop_t = stop.clone();
-            let mut cloned_board = old_board.clone();
-            let letters_on_board = old_letters_on_board.clone();
-            let new_letters = hand_letters.clone();
-            let copied_valid_words_vec = Arc::clone(&arc_dict_to_use);
-            let copied_valid_words_set = Arc::clone(&arc_valid_words_set);
-            let conn = Arc::clone(&ret_val);
-            let handle = s.spawn(move || {
-                for r in chunk {
-                    // "Undo" the letters that we want to remove
-                    let mut new_letters_on_board = letters_on_board.clone();
-                    let prev_letters = cloned_board.undo_play(&r.0, &mut new_letters_on_board);
-                    let mut new_hand_letters = new_letters.clone();
-                    prev_letters.iter().for_each(|p| {
-                        new_hand_letters[*p] += 1;
-                    });
-                    let valid_words_vec = copied_valid_words_vec.iter().filter(|w| check_filter_after_play_later(new_hand_letters.clone(), new_letters_on_board.clone(), w, filter_letters_on_board)).collect();
-                    // If we found a solution, set it as a solution and break (setting stop_t is performed in `play_removing`)
-                    if let Some(res) = play_removing(&mut cloned_board, &mut new_letters_on_board, r.1, r.2, r.3, r.4, new_hand_letters, &valid_words_vec, &copied_valid_words_set, filter_letters_on_board, max_words_to_check, &stop_t) {
-                        let mut ret = conn.lock().expect("Failed to get lock on shared ret_val");
-                        ret.push((cloned_board, res.0, res.1, res.2, res.3));
-                        break;
-                    }
-                    else {
-                        // Otherwise if stop_t was set, break (another thread has solved it)
-                        if stop_t.load(Ordering::Relaxed) {
-                            break;
-                        }
-                        // Othwerwise undo the undo and try with the next set of letters to remove
-                        else {
-                            for (idx, val) in r.0.iter().zip(prev_letters) {
-                                cloned_board.set_val(idx.0, idx.1, val);
-                            }
-                        }
-                    }
-                }
-            });
-            handles.push(handle);
-        }
-        // Wait for all the threads
-        for handle in handles {
-            let _res = handle.join();
-        }
-    });
-    // If we're done, return the result
-    let ret = ret_val.lock().expect("Failed to get lock on shared ret_val when checking return");
-    if !ret.is_empty() {
-        return Some(ret[0].clone());
-    }
-    // Otherwise, return None
-    None
-}
-
-/// For comparing a current hand of letters to a previous hand
-enum LetterComparison {
-    /// At least one letter has fewer than the previous letter
-    SomeLess,
-    /// All letters are the same except exactly one is greater by exactly one
-    GreaterByOne,
-    /// One or more letters are greater by one or more
-    GreaterByMoreThanOne,
-    /// The hand is the same as before
-    Same
-}
-impl fmt::Debug for LetterComparison {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            LetterComparison::SomeLess => write!(f, "Some less"),
-            LetterComparison::GreaterByOne => write!(f, "Greater by one"),
-            LetterComparison::GreaterByMoreThanOne => write!(f, "Greater by more than one"),
-            LetterComparison::Same => write!(f, "Same")
-        }
-     }
-}
-
-/// Struct returned when getting playable words
-#[derive(Serialize)]
-struct PlayableWords {
-    /// Playable words using the shorter dictionary
-    short: Vec<String>,
-    /// Playable words using the whole Scrabble dictionary
-    long: Vec<String>
-}
-
-/// Struct returned when a board is solved
-#[derive(Serialize)]
-struct Solution {
-    /// The solved board
-    board: Vec<Vec<String>>,
-    /// How long it took to solve the board in milliseconds
-    elapsed: u128
-}
-
-/// The previous game state
-#[derive(Clone)]
-struct GameState {
-    /// The previous board
-    board: Board,
-    /// The minimum played column in `board`
-    min_col: usize,
-    /// The maximum played column in `board`
-    max_col: usize,
-    /// The minimum played row in `board`
-    min_row: usize,
-    /// The maximum played row in `board`
-    max_row: usize,
-    /// The hand used to make `board`
-    letters: Letters
-}
+/// A dictionary's words (longest first), along with its `Trie` and `DictionaryIndex`
+type Dictionary = (Vec<Word>, Trie, DictionaryIndex);
+/// A borrowed `Dictionary`
+type DictionaryRef<'a> = (&'a Vec<Word>, &'a Trie, &'a DictionaryIndex);
+/// The short and long dictionaries of a language
+type LanguageDictionaries = (Dictionary, Dictionary);
 
 /// Controls the state of the app
 struct AppState {
-    /// Dictionary of the ~20k most common words in English
-    all_words_short: Vec<Word>,
-    /// Complete Scrabble dictionary
-    all_words_long: Vec<Word>,
-    /// Stack of previous solutions
+    /// Stack of previous solutions (at most `history_depth`, dropping the oldest)
     undo_stack: Mutex<Vec<Option<GameState>>>,
     /// Stack of undone solutions
     redo_stack: Mutex<Vec<Option<GameState>>>,
+    /// The most recently solved boards, oldest first (at most `history_depth`)
+    board_history: Mutex<Vec<GameState>>,
+    /// Maximum number of previous games that can be undone, and of solved boards kept in the board history
+    history_depth: Mutex<usize>,
     /// The last game state (if `None`, then no previous game has been played)
     last_game: Mutex<Option<GameState>>,
+    /// The tiles random letters are drawn from
+    tile_set: Mutex<TileSet>,
+    /// Number of each letter tile left in the bunch (if `None`, then no bunch has been started)
+    bunch: Mutex<Option<[u64; 26]>>,
     /// Number of letters present on the board that can be used in a word (higher will result in fewer words being filtered out)
     filter_letters_on_board: Mutex<usize>,
     /// Maximum number of words to check before stopping
     maximum_words_to_check: Mutex<usize>,
-    /// Whether to use the long dictionary or the short one
-    use_long_dictionary: Mutex<bool>
+    /// Maximum number of milliseconds to spend on a solve before stopping (if 0, then there is no limit)
+    max_solve_time_ms: Mutex<u64>,
+    /// Number of rows/columns in the board
+    board_size: Mutex<usize>,
+    /// Minimum length of the words formed on the board
+    min_word_length: Mutex<usize>,
+    /// Maximum number of columns the solved board can span (if `None`, then the board size)
+    max_width: Mutex<Option<usize>>,
+    /// Maximum number of rows the solved board can span (if `None`, then the board size)
+    max_height: Mutex<Option<usize>>,
+    /// Which dictionary to use
+    dictionary: Mutex<DictionaryChoice>,
+    /// Whether to retry with the long dictionary when the short one can't solve a hand
+    fallback_to_long_dictionary: Mutex<bool>,
+    /// Whether to try words containing the most common remaining letters first when solving
+    use_frequency_ordering: Mutex<bool>,
+    /// Whether to solve using a single thread so that the same hand always gives the same board
+    deterministic: Mutex<bool>,
+    /// Which language's dictionaries to use
+    language: Mutex<Language>,
+    /// Which solutions the solver prefers
+    solution_preference: Mutex<SolutionPreference>,
+    /// Dictionaries of each language; English's (the ~20k most common words and the complete Scrabble dictionary) are built in and can be replaced by `load_custom_dictionary`, while other languages' are loaded the first time each language is used
+    language_dictionaries: Mutex<HashMap<Language, LanguageDictionaries>>,
+    /// Folder containing the word lists of languages other than English (if `None`, then only English can be used)
+    language_dictionary_dir: Mutex<Option<PathBuf>>,
+    /// Dictionary loaded by the user (if any), along with its `Trie`
+    custom_dictionary: Mutex<Option<Arc<Dictionary>>>,
+    /// Words that the solver must not play
+    excluded_words: Mutex<HashSet<Word>>,
+    /// `Trie` of each dictionary without the excluded words, built the first time it's solved with (cleared whenever the excluded words or dictionaries change; see `excluded_words_and_trie`)
+    excluded_tries: Mutex<HashMap<(DictionaryChoice, Language), Arc<Trie>>>,
+    /// Flag that, when set, signals the current solve to stop
+    stop_solving: Arc<AtomicBool>,
+    /// Whether a solve is currently running (see `SolveInProgress`)
+    solving: AtomicBool,
+    /// File that the last game is automatically saved to after each solve (if `None`, then the game isn't saved)
+    autosave_path: Mutex<Option<PathBuf>>,
+    /// Why the game saved when the app was last open couldn't be restored, until the frontend is told (see `take_autosave_error`)
+    autosave_error: Mutex<Option<String>>,
+    /// File that the settings are saved to whenever they change (if `None`, then the settings aren't saved)
+    settings_path: Mutex<Option<PathBuf>>
 }
 
 /// Represents the current settings
-#[derive(Serialize)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 struct CurrentSettings {
     /// Number of letters present on the board that can be used in a word (higher will result in fewer words being filtered out)
     filter_letters_on_board: usize,
     /// Maximum number of words to check before stopping
     maximum_words_to_check: usize,
-    /// Whether to use the long dictionary or the short one
-    use_long_dictionary: bool
+    /// Maximum number of milliseconds to spend on a solve before stopping (if 0, then there is no limit); also read from `timeout_ms`, where `null` means no limit
+    #[serde(alias = "timeout_ms", deserialize_with = "deserialize_solve_time")]
+    max_solve_time_ms: u64,
+    /// Number of rows/columns in the board
+    board_size: usize,
+    /// Which dictionary to use
+    dictionary: DictionaryChoice,
+    /// Whether to try words containing the most common remaining letters first when solving
+    use_frequency_ordering: bool,
+    /// Whether to solve using a single thread so that the same hand always gives the same board
+    #[serde(default)]
+    deterministic: bool,
+    /// Which language's dictionaries to use
+    #[serde(default)]
+    language: Language,
+    /// Which solutions the solver prefers
+    #[serde(default)]
+    solution_preference: SolutionPreference,
+    /// Maximum number of previous games that can be undone, and of solved boards kept in the board history
+    #[serde(default = "default_history_depth")]
+    history_depth: usize,
+    /// Minimum length of the words formed on the board
+    #[serde(default = "default_min_word_length")]
+    min_word_length: usize,
+    /// Whether to retry with the long dictionary when the short one can't solve a hand (the retry gets its own budget of words to check and solve time)
+    #[serde(default)]
+    fallback_to_long_dictionary: bool,
+    /// Maximum number of columns the solved board can span (if `None`, then the board size)
+    #[serde(default)]
+    max_width: Option<usize>,
+    /// Maximum number of rows the solved board can span (if `None`, then the board size)
+    #[serde(default)]
+    max_height: Option<usize>
+}
+
+/// Gets the history depth of settings saved before it could be changed
+/// # Returns
+/// `usize` - `DEFAULT_HISTORY_DEPTH`
+fn default_history_depth() -> usize {
+    DEFAULT_HISTORY_DEPTH
+}
+
+/// Gets the minimum word length of settings saved before it could be changed
+/// # Returns
+/// `usize` - `DEFAULT_MIN_WORD_LENGTH`
+fn default_min_word_length() -> usize {
+    DEFAULT_MIN_WORD_LENGTH
+}
+
+/// Deserializes the maximum solve time, which may be given as `null` for no limit (as `timeout_ms` was)
+/// # Arguments
+/// * `deserializer` - The deserializer
+/// # Returns
+/// `Result` with the number of milliseconds (0, for no limit, if `null`)
+/// 
+/// *or `Err` if the value isn't a number or `null`*
+fn deserialize_solve_time<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    Ok(Option::<u64>::deserialize(deserializer)?.unwrap_or(0))
+}
+
+/// Settings to use for a single call instead of the stored ones, without changing them (any that aren't given keep their stored value)
+#[derive(Copy, Clone, Default, Deserialize)]
+struct SettingsOverrides {
+    /// Number of letters present on the board that can be used in a word
+    filter_letters_on_board: Option<usize>,
+    /// Maximum number of words to check before stopping
+    maximum_words_to_check: Option<usize>,
+    /// Maximum number of milliseconds to spend on the solve (if 0, then there is no limit); can also be given as `timeout_ms`
+    #[serde(alias = "timeout_ms")]
+    max_solve_time_ms: Option<u64>,
+    /// Which dictionary to use
+    dictionary: Option<DictionaryChoice>
+}
+
+impl SettingsOverrides {
+    /// Gets the settings to use for a call
+    /// # Arguments
+    /// * `settings` - The stored settings
+    /// # Returns
+    /// `Result` with `settings` with the overrides applied
+    /// 
+    /// *or String `Err` if an overridden setting is invalid*
+    fn apply(&self, mut settings: CurrentSettings) -> Result<CurrentSettings, String> {
+        if let Some(filter_letters_on_board) = self.filter_letters_on_board {
+            settings.filter_letters_on_board = filter_letters_on_board;
+        }
+        if let Some(maximum_words_to_check) = self.maximum_words_to_check {
+            settings.maximum_words_to_check = maximum_words_to_check;
+        }
+        if let Some(max_solve_time_ms) = self.max_solve_time_ms {
+            settings.max_solve_time_ms = max_solve_time_ms;
+        }
+        if let Some(dictionary) = self.dictionary {
+            settings.dictionary = dictionary;
+        }
+        validate_settings(&settings)?;
+        Ok(settings)
+    }
 }
 
 /// Represents a game undo or redo
@@ -1591,24 +640,25 @@ fn undo(state: State<'_, AppState>) -> Result<UndoRedo, String> {
         match prev_state {
             Some(p) => {
                 let mut letters = HashMap::new();
-                for (c, n) in UPPERCASE.chars().into_iter().zip(p.letters) {
+                for (c, n) in UPPERCASE.chars().zip(p.letters) {
                     letters.insert(c, n as u64);
                 }
-                return Ok(UndoRedo {
-                    solution: board_to_vec(&p.board, p.min_col, p.max_col, p.min_row, p.max_row, &HashSet::new()),
+                letters.insert(BLANK_CHAR, p.letters[BLANK] as u64);
+                Ok(UndoRedo {
+                    solution: board_to_vec(&p.board, p.min_col, p.max_col, p.min_row, p.max_row),
                     letters,
-                    undo_possible: undo_stack.len() > 0,
+                    undo_possible: !undo_stack.is_empty(),
                     redo_possible: true
-                });
+                })
             },
             None => {
-                let letters: HashMap<char, u64> = UPPERCASE.chars().into_iter().map(|c| (c, 0)).collect();
-                return Ok(UndoRedo { solution: Vec::new(), letters, undo_possible: undo_stack.len() > 0, redo_possible: true });
+                let letters: HashMap<char, u64> = UPPERCASE.chars().chain([BLANK_CHAR]).map(|c| (c, 0)).collect();
+                Ok(UndoRedo { solution: Vec::new(), letters, undo_possible: !undo_stack.is_empty(), redo_possible: true })
             }
         }
     }
     else {
-        return Err("No undos available!".to_owned());
+        Err("No undos available!".to_owned())
     }
 }
 
@@ -1624,451 +674,3458 @@ fn redo(state: State<'_, AppState>) -> Result<UndoRedo, String> {
         match prev_state {
             Some(p) => {
                 let mut letters = HashMap::new();
-                for (c, n) in UPPERCASE.chars().into_iter().zip(p.letters) {
+                for (c, n) in UPPERCASE.chars().zip(p.letters) {
                     letters.insert(c, n as u64);
                 }
-                return Ok(UndoRedo {
-                    solution: board_to_vec(&p.board, p.min_col, p.max_col, p.min_row, p.max_row, &HashSet::new()),
+                letters.insert(BLANK_CHAR, p.letters[BLANK] as u64);
+                Ok(UndoRedo {
+                    solution: board_to_vec(&p.board, p.min_col, p.max_col, p.min_row, p.max_row),
                     letters,
-                    undo_possible: undo_stack.len() > 0,
-                    redo_possible: redo_stack.len() > 0
-                });
+                    undo_possible: !undo_stack.is_empty(),
+                    redo_possible: !redo_stack.is_empty()
+                })
             },
             None => {
-                let letters: HashMap<char, u64> = UPPERCASE.chars().into_iter().map(|c| (c, 0)).collect();
-                return Ok(UndoRedo { solution: Vec::new(), letters, undo_possible: undo_stack.len() > 0, redo_possible: redo_stack.len() > 0 });
+                let letters: HashMap<char, u64> = UPPERCASE.chars().chain([BLANK_CHAR]).map(|c| (c, 0)).collect();
+                Ok(UndoRedo { solution: Vec::new(), letters, undo_possible: !undo_stack.is_empty(), redo_possible: !redo_stack.is_empty() })
             }
         }
     }
     else {
-        return Err("No redos available!".to_owned());
+        Err("No redos available!".to_owned())
     }
 }
 
-/// Generates random letters based on user input
+/// Adds a newly solved board to the board history, dropping the oldest board if the history is full
 /// # Arguments
-/// * `what` - Whether to generate characters from an "infinite set" (i.e. all are equal likelihood),
-/// or selected from "standard Bananagrams" (144 tiles) or "double Bananagrams" (288 tiles)
-/// * `how_many` - How many tiles to randomly generate; must be greater than 0, and less than 144 for regular Bananagrams,
-/// or 288 for double
+/// * `game` - The newly solved game
+/// * `state` - Current state of the app
 /// # Returns
-/// `Result` of mapping of each uppercase Latin character to the number of times it's present
+/// Empty `Result` upon success
 /// 
-/// *or String `Err` upon failure*
+/// *or String `Err` if the lock couldn't be obtained*
+fn record_history(game: &GameState, state: &AppState) -> Result<(), String> {
+    let history_depth = *state.history_depth.lock().or(Err("Failed to get lock on history depth!"))?;
+    let mut board_history = state.board_history.lock().or(Err("Failed to get lock on board history!"))?;
+    board_history.push(game.clone());
+    let excess = board_history.len().saturating_sub(history_depth);
+    board_history.drain(..excess);
+    Ok(())
+}
+
+/// Pushes the game being replaced onto the undo stack, dropping the oldest games if the stack is then deeper than the history depth
+/// # Arguments
+/// * `undo_stack` - The undo stack to push onto
+/// * `game` - The game being replaced (if `None`, then no game had been played)
+/// * `history_depth` - Maximum number of games to keep on the stack
+fn push_undo(undo_stack: &mut Vec<Option<GameState>>, game: Option<GameState>, history_depth: usize) {
+    undo_stack.push(game);
+    let excess = undo_stack.len().saturating_sub(history_depth);
+    undo_stack.drain(..excess);
+}
+
+/// Goes back to the board solved before the most recent one, discarding the most recent one from the board history
+/// 
+/// Unlike `undo`, this skips over resets and loaded games, returning to the previous arrangement of tiles so that a different peel can be tried
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` as a `Solution` with the restored board (and an elapsed time of 0)
+/// 
+/// *or String `Err` if there is no earlier board in the history*
 #[tauri::command]
-async fn get_random_letters(what: String, how_many: i64, _state: State<'_, AppState>) -> Result<HashMap<char, u64>, String> {
-    if how_many < 1 {
-        return Err("The number to choose should be greater than 0".to_owned());
+fn undo_last_solve(state: State<'_, AppState>) -> Result<Solution, String> {
+    // Lock the dictionaries before the game, in the same order as solving does
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let language_dictionaries = lock_language_dictionaries(&state, language)?;
+    let mut board_history = state.board_history.lock().or(Err("Failed to get lock on board history!"))?;
+    if board_history.len() < 2 {
+        return Err("There is no earlier board to go back to!".to_owned());
     }
-    let mut rng = thread_rng();
-    let mut return_chars: HashMap<char, u64> = HashMap::with_capacity(26);
-    UPPERCASE.chars().for_each(|c| {return_chars.insert(c, 0);});
-    if what == "infinite set" {
-        // For "infinite set", randomly generate characters
-        let uni = Uniform::new_inclusive(0, 25);
-        for _ in 0..how_many {
-            let random_num: u8 = uni.sample(&mut rng);
-            let random_char = (random_num+65) as char;
-            let old_val = return_chars.get(&random_char);
-            match old_val {
-                Some(v) => {
-                    return_chars.insert(random_char, v+1);
-                },
-                None => {
-                    return Err(format!("Missing value in return dictionary: {}", random_char));
-                }
-            }
+    board_history.pop();
+    let previous = board_history.last().expect("The history has at least one board").clone();
+    let history_depth = *state.history_depth.lock().or(Err("Failed to get lock on history depth!"))?;
+    let mut undo_stack = state.undo_stack.lock().or(Err("Failed to get lock on undo stack!"))?;
+    let mut redo_stack = state.redo_stack.lock().or(Err("Failed to get lock on redo stack!"))?;
+    let mut last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    push_undo(&mut undo_stack, last_game_state.clone(), history_depth);
+    redo_stack.clear();
+    let mut solution = Solution { board: board_to_vec(&previous.board, previous.min_col, previous.max_col, previous.min_row, previous.max_row), carried_over: carried_over_to_vec(previous.min_col, previous.max_col, previous.min_row, previous.max_row, &HashSet::new()), min_row: previous.min_row, min_col: previous.min_col, max_row: previous.max_row, max_col: previous.max_col, words: get_words_on_board(&previous.board, previous.min_col, previous.max_col, previous.min_row, previous.max_row), score: score_board(&previous.board, previous.min_col, previous.max_col, previous.min_row, previous.max_row), solution_score: score_solution(&previous.board, previous.min_col, previous.max_col, previous.min_row, previous.max_row), unplaced: Vec::new(), elapsed: 0, deterministic: false, used_long_dictionary: false, long_only_words: Vec::new() };
+    solution.mark_obscure_words(language_words(language, &language_dictionaries)?.0.1);
+    *last_game_state = Some(previous);
+    Ok(solution)
+}
+
+/// Gets summaries of the boards in the board history
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with a `BoardSummary` of each board, oldest first
+/// 
+/// *or String `Err` if the lock couldn't be obtained*
+#[tauri::command]
+fn get_board_history(state: State<'_, AppState>) -> Result<Vec<BoardSummary>, String> {
+    let board_history = state.board_history.lock().or(Err("Failed to get lock on board history!"))?;
+    Ok(board_history.iter().map(|game| {
+        let mut letters = HashMap::new();
+        for (c, n) in UPPERCASE.chars().zip(game.letters) {
+            letters.insert(c, n as u64);
         }
-    }
-    else if what == "standard Bananagrams" {
-        if how_many > 144 {
-            return Err("The number to choose must be less than 144 for standard Banangrams".to_owned());
+        letters.insert(BLANK_CHAR, game.letters[BLANK] as u64);
+        let word_count = get_words_on_board(&game.board, game.min_col, game.max_col, game.min_row, game.max_row).len();
+        BoardSummary { min_col: game.min_col, max_col: game.max_col, min_row: game.min_row, max_row: game.max_row, letters, word_count }
+    }).collect())
+}
+
+/// Gets statistics about the last solved board
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the `BoardStatistics` of the last board
+/// 
+/// *or String `Err` if no game has been played yet*
+#[tauri::command]
+fn get_board_statistics(state: State<'_, AppState>) -> Result<BoardStatistics, String> {
+    let last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    match &*last_game_state {
+        Some(game) => Ok(board_statistics(&game.board, game.min_col, game.max_col, game.min_row, game.max_row)),
+        None => Err("No game has been played yet!".to_owned())
+    }
+}
+
+/// Gets the words on a board, longest first
+/// # Arguments
+/// * `board` - The board to read
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// # Returns
+/// `Vec<WordOnBoard>` - Each word of two or more letters (see `Board::iter_words`), sorted by descending length and then alphabetically
+fn list_words_on_board(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize) -> Vec<WordOnBoard> {
+    let mut words: Vec<WordOnBoard> = board.iter_words(min_col, max_col, min_row, max_row).map(|(word, direction, start_row, start_col)| WordOnBoard {
+        word: convert_array_to_word(&word),
+        direction: if direction == Direction::Horizontal {"Horizontal"} else {"Vertical"}.to_owned(),
+        start_row,
+        start_col
+    }).collect();
+    words.sort_by(|a, b| b.word.len().cmp(&a.word.len()).then(a.word.cmp(&b.word)));
+    words
+}
+
+/// Command executed by the frontend to list the words on the last solved board
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with each `WordOnBoard`, sorted by descending length and then alphabetically
+/// 
+/// *or String `Err` if no game has been played yet*
+#[tauri::command]
+fn words_on_board(state: State<'_, AppState>) -> Result<Vec<WordOnBoard>, String> {
+    let last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    match &*last_game_state {
+        Some(game) => Ok(list_words_on_board(&game.board, game.min_col, game.max_col, game.min_row, game.max_row)),
+        None => Err("No board has been solved yet".to_owned())
+    }
+}
+
+/// Gets a tile distribution in proportion to how often each letter appears in `words`
+/// # Arguments
+/// * `words` - Words to count the letters of
+/// * `total` - Total number of tiles
+/// # Returns
+/// `[u64; 26]` - The number of tiles of each letter, summing to `total` (unless `words` is empty)
+fn tiles_from_words(words: &[Word], total: u64) -> [u64; 26] {
+    let mut counts = [0u64; 26];
+    for letter in words.iter().flatten() {
+        counts[*letter] += 1;
+    }
+    let num_letters: u64 = counts.iter().sum();
+    let mut tiles = [0u64; 26];
+    if num_letters == 0 {
+        return tiles;
+    }
+    for i in 0..26 {
+        tiles[i] = counts[i]*total/num_letters;
+    }
+    // Hand out the tiles lost to rounding down to the letters that lost the most
+    let mut by_remainder: Vec<usize> = (0..26).collect();
+    by_remainder.sort_by_key(|i| Reverse(counts[*i]*total % num_letters));
+    let assigned: u64 = tiles.iter().sum();
+    for i in by_remainder.into_iter().take((total - assigned) as usize) {
+        tiles[i] += 1;
+    }
+    tiles
+}
+
+/// Gets the standard Bananagrams tile distribution for the current language
+/// 
+/// English uses the real distribution (`REGULAR_TILES`); other languages have the same number of tiles, split in proportion to how often each letter appears in their long dictionary
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the number of tiles of each letter
+/// 
+/// *or String `Err` if the lock couldn't be obtained or the language's dictionaries couldn't be loaded*
+fn language_tiles(state: &AppState) -> Result<[u64; 26], String> {
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    if language == Language::English {
+        return Ok(REGULAR_TILES);
+    }
+    let language_dictionaries = lock_language_dictionaries(state, language)?;
+    let (_, (all_words_long, _, _)) = language_words(language, &language_dictionaries)?;
+    Ok(tiles_from_words(all_words_long, REGULAR_TILES.iter().sum()))
+}
+
+/// Draws tiles from the `bunch` without replacement
+/// # Arguments
+/// * `bunch` - Number of each letter tile left in the bunch; the drawn tiles are removed from it
+/// * `how_many` - How many tiles to draw
+/// # Returns
+/// `Result` with the drawn letters
+/// 
+/// *or String `Err` if the bunch doesn't have `how_many` tiles left*
+fn draw_from_bunch(bunch: &mut [u64; 26], how_many: usize) -> Result<Vec<char>, String> {
+    let remaining: u64 = bunch.iter().sum();
+    if remaining == 0 {
+        return Err("The bunch is empty!".to_owned());
+    }
+    if how_many as u64 > remaining {
+        return Err(format!("Only {} tiles are left in the bunch", remaining));
+    }
+    let mut to_choose_from: Vec<usize> = Vec::with_capacity(remaining as usize);
+    for (i, count) in bunch.iter().enumerate() {
+        for _num_letter in 0..*count {
+            to_choose_from.push(i);
         }
-        // For regular Bananagrams, first make the vector of characters to choose form
-        let mut to_choose_from: Vec<char> = Vec::with_capacity(144);
-        for (i, c) in UPPERCASE.chars().enumerate() {
-            for _num_letter in 0..REGULAR_TILES[i] {
-                to_choose_from.push(c);
-            }
+    }
+    let selected: Vec<usize> = to_choose_from.choose_multiple(&mut thread_rng(), how_many).cloned().collect();
+    for i in selected.iter() {
+        bunch[*i] -= 1;
+    }
+    Ok(selected.into_iter().map(|i| (i as u8 + 65) as char).collect())
+}
+
+/// Gets the tile set to draw from
+/// # Arguments
+/// * `what` - Either "tile set" (the set chosen with `set_tile_set`) or the name of a set (see `TileSet::from_name`)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the `TileSet`
+/// 
+/// *or String `Err` if `what` is not recognized or the lock couldn't be obtained*
+fn selected_tile_set(what: &str, state: &AppState) -> Result<TileSet, String> {
+    if what == "tile set" {
+        return Ok(*state.tile_set.lock().or(Err("Failed to get lock on tile set!"))?);
+    }
+    TileSet::from_name(what).ok_or(format!("`what` must be \"infinite set\", \"tile set\", \"standard Bananagrams\", \"double Bananagrams\", or \"triple Bananagrams\", not {}", what))
+}
+
+/// Creates a custom tile set
+/// # Arguments
+/// * `counts` - Mapping of each uppercase Latin character to the number of tiles of it (missing letters have none)
+/// # Returns
+/// `Result` with the `TileSet`
+/// 
+/// *or String `Err` if `counts` has no tiles, more than `MAX_CUSTOM_TILES` tiles, or contains something other than a letter*
+fn custom_tile_set(counts: &HashMap<String, u64>) -> Result<TileSet, String> {
+    let too_many = || format!("A custom tile set can't have more than {} tiles!", MAX_CUSTOM_TILES);
+    let mut tiles = [0u64; 26];
+    let mut total = 0u64;
+    for (key, number) in counts.iter() {
+        let mut chars = key.chars();
+        match (chars.next().and_then(letter_index), chars.next()) {
+            (Some(letter), None) => {
+                tiles[letter] = tiles[letter].checked_add(*number).ok_or_else(too_many)?;
+                total = total.checked_add(*number).filter(|total| *total <= MAX_CUSTOM_TILES).ok_or_else(too_many)?;
+            },
+            _ => return Err(format!("Custom tile sets can only contain letters, not {}", key))
         }
-        // Then selecte `how_many` characters from that vector
-        let selected_chars: Vec<char> = to_choose_from.choose_multiple(&mut rng, how_many as usize).cloned().collect();
-        for i in 0..selected_chars.len() {
-            let old_val = return_chars.get(&selected_chars[i]);
+    }
+    if total == 0 {
+        return Err("A custom tile set must contain at least one tile!".to_owned());
+    }
+    Ok(TileSet::Custom(tiles))
+}
+
+/// Sets the tile set that random letters are drawn from when asking for "tile set"
+/// # Arguments
+/// * `set_name` - One of "standard Bananagrams", "double Bananagrams", "triple Bananagrams", or "custom"
+/// * `custom_counts` - For "custom", mapping of each uppercase Latin character to the number of tiles of it (see `custom_tile_set`)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the number of tiles in the set
+/// 
+/// *or String `Err` if `set_name` is not recognized or the custom counts are missing or invalid (see `custom_tile_set`)*
+#[tauri::command]
+fn set_tile_set(set_name: String, custom_counts: Option<HashMap<String, u64>>, state: State<'_, AppState>) -> Result<u64, String> {
+    let tile_set = match (set_name.as_str(), custom_counts) {
+        ("custom", Some(counts)) => custom_tile_set(&counts)?,
+        ("custom", None) => return Err("The number of each letter must be given for a custom tile set!".to_owned()),
+        (name, _) => TileSet::from_name(name).ok_or(format!("`set_name` must be \"standard Bananagrams\", \"double Bananagrams\", \"triple Bananagrams\", or \"custom\", not {}", name))?
+    };
+    let total = tile_set.tiles(&language_tiles(&state)?).iter().sum::<u64>() + tile_set.blanks();
+    *state.tile_set.lock().or(Err("Failed to get lock on tile set!"))? = tile_set;
+    Ok(total)
+}
+
+/// Compares how often each letter appears in a hand with how often it appears in a tile set
+/// # Arguments
+/// * `letters` - The hand to compare
+/// * `tiles` - The number of tiles of each letter in the set
+/// * `blanks` - The number of blank tiles in the set
+/// # Returns
+/// `HashMap` - Mapping of each uppercase Latin character (and `BLANK_CHAR`) to its `LetterStats`
+fn letter_distribution(letters: &Letters, tiles: &[u64; 26], blanks: u64) -> HashMap<String, LetterStats> {
+    let hand_size = letters.iter().sum::<usize>() as f64;
+    let set_size = (tiles.iter().sum::<u64>() + blanks) as f64;
+    UPPERCASE.chars().zip(tiles.iter()).chain(std::iter::once((BLANK_CHAR, &blanks))).enumerate().map(|(i, (c, in_set))| {
+        let expected_fraction = *in_set as f64 / set_size;
+        (c.to_string(), LetterStats { count_in_hand: letters[i] as i64, expected_fraction, deviation: letters[i] as f64 / hand_size - expected_fraction })
+    }).collect()
+}
+
+/// Gets how the letters in a hand compare to those in a tile set
+/// # Arguments
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
+/// * `tile_set` - The tile set to compare against: "infinite set" (where every letter is equally likely), or any tile set accepted by `selected_tile_set`
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` of mapping of each uppercase Latin character (and `BLANK_CHAR`) to its `LetterStats`
+/// 
+/// *or String `Err` if the hand is invalid or empty, or `tile_set` is not recognized*
+#[tauri::command]
+fn get_letter_distribution(available_letters: HashMap<String, i64>, tile_set: String, state: State<'_, AppState>) -> Result<HashMap<String, LetterStats>, String> {
+    let letters = letters_from_map(&available_letters)?;
+    if letters.iter().sum::<usize>() == 0 {
+        return Err("The hand has no letters!".to_owned());
+    }
+    if tile_set == "infinite set" {
+        return Ok(letter_distribution(&letters, &[1; 26], 0));
+    }
+    let selected = selected_tile_set(&tile_set, &state)?;
+    Ok(letter_distribution(&letters, &selected.tiles(&language_tiles(&state)?), selected.blanks()))
+}
+
+/// Starts a new bunch, from which `get_random_letters` and `peel_from_bunch` can then draw without replacement
+/// # Arguments
+/// * `what` - The tile set to fill the bunch from (see `selected_tile_set`), using the current language (see `language_tiles`); blanks are not included
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the number of tiles in the bunch
+/// 
+/// *or String `Err` if `what` is not recognized or the lock couldn't be obtained*
+#[tauri::command]
+fn start_bunch(what: String, state: State<'_, AppState>) -> Result<u64, String> {
+    let tiles = selected_tile_set(&what, &state)?.tiles(&language_tiles(&state)?);
+    let mut bunch = state.bunch.lock().or(Err("Failed to get lock on bunch!"))?;
+    *bunch = Some(tiles);
+    Ok(tiles.iter().sum())
+}
+
+/// Draws tiles from the bunch started by `start_bunch`, such as when peeling
+/// # Arguments
+/// * `count` - How many tiles to draw
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` of mapping of each drawn character to the number of times it was drawn
+/// 
+/// *or String `Err` if no bunch has been started or it doesn't have `count` tiles left*
+#[tauri::command]
+fn peel_from_bunch(count: usize, state: State<'_, AppState>) -> Result<HashMap<char, u64>, String> {
+    let mut bunch = state.bunch.lock().or(Err("Failed to get lock on bunch!"))?;
+    let drawn = match &mut *bunch {
+        Some(remaining) => draw_from_bunch(remaining, count)?,
+        None => return Err("No bunch has been started!".to_owned())
+    };
+    let mut return_chars: HashMap<char, u64> = HashMap::new();
+    for c in drawn {
+        *return_chars.entry(c).or_insert(0) += 1;
+    }
+    Ok(return_chars)
+}
+
+/// Generates random letters based on user input
+/// # Arguments
+/// * `what` - Whether to generate characters from an "infinite set" (i.e. all are equal likelihood), or selected from the "tile set" chosen with `set_tile_set`, "standard Bananagrams" (144 tiles plus 2 blanks), "double Bananagrams" (288 tiles plus 4 blanks), or "triple Bananagrams" (432 tiles plus 6 blanks)
+/// * `how_many` - How many tiles to randomly generate; must be greater than 0, and no more than the number of tiles in the tile set
+/// * `from_bunch` - Whether to instead draw from (and remove from) the bunch started by `start_bunch`, ignoring `what` (defaults to `false`)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` of mapping of each uppercase Latin character (and `BLANK_CHAR`) to the number of times it's present
+/// 
+/// *or String `Err` upon failure*
+#[tauri::command]
+async fn get_random_letters(what: String, how_many: i64, from_bunch: Option<bool>, state: State<'_, AppState>) -> Result<HashMap<char, u64>, String> {
+    if how_many < 1 {
+        return Err("The number to choose should be greater than 0".to_owned());
+    }
+    let mut rng = thread_rng();
+    let mut return_chars: HashMap<char, u64> = HashMap::with_capacity(26);
+    UPPERCASE.chars().for_each(|c| {return_chars.insert(c, 0);});
+    return_chars.insert(BLANK_CHAR, 0);
+    if from_bunch.unwrap_or(false) {
+        let mut bunch = state.bunch.lock().or(Err("Failed to get lock on bunch!"))?;
+        match &mut *bunch {
+            Some(remaining) => {
+                for c in draw_from_bunch(remaining, how_many as usize)? {
+                    *return_chars.entry(c).or_insert(0) += 1;
+                }
+            },
+            None => return Err("No bunch has been started!".to_owned())
+        }
+    }
+    else if what == "infinite set" {
+        // For "infinite set", randomly generate characters
+        let uni = Uniform::new_inclusive(0, 25);
+        for _ in 0..how_many {
+            let random_num: u8 = uni.sample(&mut rng);
+            let random_char = (random_num+65) as char;
+            let old_val = return_chars.get(&random_char);
             match old_val {
                 Some(v) => {
-                    return_chars.insert(selected_chars[i], v+1);
+                    return_chars.insert(random_char, v+1);
                 },
                 None => {
-                    return Err(format!("Missing value in return dictionary: {}", selected_chars[i]));
+                    return Err(format!("Missing value in return dictionary: {}", random_char));
                 }
             }
         }
     }
-    else if what == "double Bananagrams" {
-        // "double Bananagrams" is just like regular, except with twice as many pieces
-        if how_many > 288 {
-            return Err("The number to choose must be less than 288 for double Banangrams".to_owned());
+    else {
+        // Otherwise, first make the vector of characters to choose from
+        let tile_set = selected_tile_set(&what, &state)?;
+        let tiles = tile_set.tiles(&language_tiles(&state)?);
+        let total = tiles.iter().sum::<u64>() + tile_set.blanks();
+        if how_many as u64 > total {
+            return Err(format!("The number to choose must be at most {} for {}", total, what));
         }
-        let mut to_choose_from: Vec<char> = Vec::with_capacity(288);
+        let mut to_choose_from: Vec<char> = Vec::with_capacity(total as usize);
         for (i, c) in UPPERCASE.chars().enumerate() {
-            for _num_letter in 0..REGULAR_TILES[i]*2 {
+            for _num_letter in 0..tiles[i] {
                 to_choose_from.push(c);
             }
         }
+        for _num_blank in 0..tile_set.blanks() {
+            to_choose_from.push(BLANK_CHAR);
+        }
+        // Then select `how_many` characters from that vector
         let selected_chars: Vec<char> = to_choose_from.choose_multiple(&mut rng, how_many as usize).cloned().collect();
-        for i in 0..selected_chars.len() {
-            let old_val = return_chars.get(&selected_chars[i]);
+        for c in selected_chars.iter() {
+            let old_val = return_chars.get(c);
             match old_val {
                 Some(v) => {
-                    return_chars.insert(selected_chars[i], v+1);
+                    return_chars.insert(*c, v+1);
                 },
                 None => {
-                    return Err(format!("Missing value in return dictionary: {}", selected_chars[i]));
+                    return Err(format!("Missing value in return dictionary: {}", c));
                 }
             }
         }
     }
-    else {
-        return Err(format!("`what` must be \"infinite set\", \"standard Bananagrams\", or \"double Bananagrams\", not {}", what))
-    }
-    return Ok(return_chars);
+    Ok(return_chars)
 }
 
-/// Async command executed by the frontend to get the playable words for a given hand of letters
+/// Converts the hand of letters sent by the frontend into a `Letters` array
 /// # Arguments
-/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
-/// * `state` - Current state of the app
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter (blanks, as `BLANK_CHAR`, are optional)
 /// # Returns
-/// `Result` of `PlayableWords` with two keys - "short" (common words playable using `available_letters`) and "long" (Scrabble words playable using `available_letters`)
+/// `Result` of the `Letters` in the hand
 /// 
-/// *or String `Err` upon failure*
-#[tauri::command]
-async fn get_playable_words(available_letters: HashMap<String, i64>, state: State<'_, AppState>) -> Result<PlayableWords, String> {
+/// *or `SolverError` if a letter is missing or negative*
+fn letters_from_map(available_letters: &HashMap<String, i64>) -> Result<Letters, SolverError> {
     // Check if we have all the letters from the frontend
-    let mut letters = [0usize; 26];
+    let mut letters = [0usize; 27];
     for c in UPPERCASE.chars() {
         let num = available_letters.get(&c.to_string());
         match num {
             Some(number) => {
                 if *number < 0 {
-                    return Err(format!("Number of letter {} is {}, but must be greater than or equal to 0!", c, number));
+                    return Err(SolverError::InvalidLetterCount { letter: c, count: *number });
                 }
                 letters[(c as usize) - 65] = *number as usize;
             },
             None => {
-                return Err(format!("Missing letter: {}", c));
+                return Err(SolverError::MissingLetter(c));
+            }
+        }
+    }
+    // Blanks are optional
+    if let Some(number) = available_letters.get(&BLANK_CHAR.to_string()) {
+        if *number < 0 {
+            return Err(SolverError::InvalidLetterCount { letter: BLANK_CHAR, count: *number });
+        }
+        letters[BLANK] = *number as usize;
+    }
+    // So are accented letters, which are counted as their base letter
+    for (key, number) in available_letters.iter() {
+        let mut chars = key.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if let Some(letter) = letter_index(c).filter(|_| !c.is_ascii_uppercase()) {
+                if *number < 0 {
+                    return Err(SolverError::InvalidLetterCount { letter: c, count: *number });
+                }
+                letters[letter] += *number as usize;
             }
         }
     }
-    let playable_short: Vec<String> = state.all_words_short.iter().filter(|word| is_makeable(word, &letters)).map(convert_array_to_word).collect();
-    let playable_long: Vec<String> = state.all_words_long.iter().filter(|word| is_makeable(word, &letters)).map(convert_array_to_word).collect();
-    return Ok(PlayableWords { short: playable_short, long: playable_long });
+    Ok(letters)
 }
 
-/// Updates the settings
+/// Reads the short and long word lists of a language from `<language>_short.txt` and `<language>_long.txt` (see `Language::file_stem`)
 /// # Arguments
-/// * `filter_letters_on_board` - Maximum number of letters on the board that can be used when forming a word
-/// * `maximum_words_to_check` - Maximum number of iterations to perform
-/// * `use_long_dictionary` - Whether to use the long dictionary instead of the short one
-/// Empty `Result` upon success
+/// * `language` - The language to read the word lists of
+/// * `dir` - Folder containing the word lists
+/// # Returns
+/// `Result` with the `LanguageDictionaries` (if there is no short word list, the long one is used for both)
 /// 
-/// *or String `Err` upon failure*
-#[tauri::command]
-fn set_settings(filter_letters_on_board: usize, maximum_words_to_check: usize, use_long_dictionary: bool, state: State<'_, AppState>) -> Result<(), String> {
-    let mut to_change = state.filter_letters_on_board.lock().or(Err("Failed to get lock on state!"))?;
-    *to_change = filter_letters_on_board;
-    let mut to_change = state.maximum_words_to_check.lock().or(Err("Failed to get lock on state!"))?;
-    *to_change = maximum_words_to_check;
-    let mut to_change = state.use_long_dictionary.lock().or(Err("Failed to get lock on state!"))?;
-    *to_change = use_long_dictionary;
-    Ok(())
+/// *or String `Err` if the long word list couldn't be read (see `read_word_list`)*
+fn read_language_dictionaries(language: Language, dir: &Path) -> Result<LanguageDictionaries, String> {
+    let long = read_word_list(&dir.join(format!("{}_long.txt", language.file_stem())), MAX_WORD_LENGTH)?;
+    let short_path = dir.join(format!("{}_short.txt", language.file_stem()));
+    let short = if short_path.exists() {read_word_list(&short_path, MAX_WORD_LENGTH)?} else {long.clone()};
+    let (short_trie, short_index) = (Trie::from_words(&short), DictionaryIndex::from_words(&short));
+    let (long_trie, long_index) = (Trie::from_words(&long), DictionaryIndex::from_words(&long));
+    Ok(((short, short_trie, short_index), (long, long_trie, long_index)))
 }
 
-/// Gets the current settings
+/// Locks the dictionaries of each language, first loading those of `language` if they haven't been yet
+/// # Arguments
+/// * `state` - Current state of the app
+/// * `language` - The language whose dictionaries should be loaded
 /// # Returns
-/// `Results` with struct containing the current settings
+/// `Result` with the locked dictionaries of each loaded language (English's are always loaded)
+/// 
+/// *or String `Err` if the lock couldn't be obtained or the word lists of `language` couldn't be read*
+fn lock_language_dictionaries(state: &AppState, language: Language) -> Result<MutexGuard<'_, HashMap<Language, LanguageDictionaries>>, String> {
+    let mut language_dictionaries = state.language_dictionaries.lock().or(Err("Failed to get lock on language dictionaries!"))?;
+    if language != Language::English && !language_dictionaries.contains_key(&language) {
+        let dir = state.language_dictionary_dir.lock().or(Err("Failed to get lock on language dictionary folder!"))?.clone();
+        match dir {
+            Some(dir) => {
+                language_dictionaries.insert(language, read_language_dictionaries(language, &dir)?);
+            },
+            None => return Err(format!("No word lists are available for {}", language.file_stem()))
+        }
+    }
+    Ok(language_dictionaries)
+}
+
+/// Gets the short and long dictionaries of a language
+/// # Arguments
+/// * `language` - The language to get the dictionaries of
+/// * `language_dictionaries` - The dictionaries of each loaded language (see `lock_language_dictionaries`)
+/// # Returns
+/// `Result` of the short and long dictionaries, each with its `Trie` and `DictionaryIndex`
+/// 
+/// *or String `Err` if the dictionaries of `language` haven't been loaded*
+fn language_words(language: Language, language_dictionaries: &HashMap<Language, LanguageDictionaries>) -> Result<(DictionaryRef<'_>, DictionaryRef<'_>), String> {
+    match language_dictionaries.get(&language) {
+        Some(((short, short_trie, short_index), (long, long_trie, long_index))) => Ok(((short, short_trie, short_index), (long, long_trie, long_index))),
+        None => Err(format!("The {} dictionaries haven't been loaded!", language.file_stem()))
+    }
+}
+
+/// Gets the custom dictionary, releasing the lock on it straight away so that another can be loaded while this one is in use
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the custom dictionary, or `None` if one hasn't been loaded
+/// 
+/// *or String `Err` if the lock couldn't be obtained*
+fn get_custom_dictionary(state: &AppState) -> Result<Option<Arc<Dictionary>>, String> {
+    Ok(state.custom_dictionary.lock().or(Err("Failed to get lock on custom dictionary!"))?.clone())
+}
+
+/// Selects the dictionary (and its `Trie`) to use
+/// # Arguments
+/// * `dictionary` - Which dictionary to use
+/// * `language` - Which language's short or long dictionary to use
+/// * `custom_dictionary` - The custom dictionary, if one has been loaded
+/// * `language_dictionaries` - The dictionaries of each loaded language (see `lock_language_dictionaries`)
+/// # Returns
+/// `Result` of the words in the dictionary and their `Trie`
+/// 
+/// *or String `Err` if the custom dictionary is requested but hasn't been loaded, or the dictionaries of `language` haven't been loaded*
+fn select_dictionary<'a>(dictionary: DictionaryChoice, language: Language, custom_dictionary: &'a Option<Arc<Dictionary>>, language_dictionaries: &'a HashMap<Language, LanguageDictionaries>) -> Result<DictionaryRef<'a>, String> {
+    match dictionary {
+        DictionaryChoice::Short => Ok(language_words(language, language_dictionaries)?.0),
+        DictionaryChoice::Long => Ok(language_words(language, language_dictionaries)?.1),
+        DictionaryChoice::Custom => match custom_dictionary {
+            Some(custom) => Ok((&custom.0, &custom.1, &custom.2)),
+            None => Err("No custom dictionary has been loaded!".to_owned())
+        }
+    }
+}
+
+/// Suggests the longest words playable from a hand that aren't already on the board
+/// # Arguments
+/// * `letters` - The number of each letter (and blanks) in the hand
+/// * `n` - Maximum number of words to suggest
+/// * `dictionary` - Which dictionary to suggest words from (if `None`, then the stored choice is used)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with up to `n` words from the dictionary, sorted by descending length and then alphabetically
 /// 
 /// *or String `Err` upon failure*
-#[tauri::command]
-fn get_settings(state: State<'_, AppState>) -> Result<CurrentSettings, String> {
-    let filter_letters_on_board = *state.filter_letters_on_board.lock().or(Err("Failed to get lock on state!"))?;
-    let use_long_dictionary = *state.use_long_dictionary.lock().or(Err("Failed to get lock on state!"))?;
-    let maximum_words_to_check = *state.maximum_words_to_check.lock().or(Err("Failed to get lock on state!"))?;
-    Ok(CurrentSettings { filter_letters_on_board, use_long_dictionary, maximum_words_to_check })
+fn word_suggestions(letters: &Letters, n: usize, dictionary: Option<DictionaryChoice>, state: &AppState) -> Result<Vec<String>, String> {
+    let dictionary = match dictionary {
+        Some(dictionary) => dictionary,
+        None => *state.dictionary.lock().or(Err("Failed to get lock on dictionary!"))?
+    };
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let custom_dictionary = get_custom_dictionary(state)?;
+    let language_dictionaries = lock_language_dictionaries(state, language)?;
+    let (dict_to_use, _, _) = select_dictionary(dictionary, language, &custom_dictionary, &language_dictionaries)?;
+    let excluded_words = state.excluded_words.lock().or(Err("Failed to get lock on excluded words!"))?;
+    let last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    // A word is already on the board if it is part of any horizontal or vertical run of letters
+    let mut suggestions: Vec<String> = dict_to_use.iter()
+        .filter(|word| !excluded_words.contains(*word) && is_makeable(word, letters))
+        .filter(|word| !last_game_state.as_ref().is_some_and(|game| game.board.contains_word(word, game.min_col, game.max_col, game.min_row, game.max_row)))
+        .map(convert_array_to_word)
+        .collect();
+    suggestions.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    suggestions.dedup();
+    suggestions.truncate(n);
+    Ok(suggestions)
 }
 
-/// Async command executed by the frontend to reset the Banangrams board
+/// Async command executed by the frontend to suggest the longest words playable from a hand that aren't already on the board
 /// # Arguments
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
+/// * `n` - Maximum number of words to suggest
+/// * `overrides` - Settings to use for this call only, instead of the stored ones (only the dictionary is used)
 /// * `state` - Current state of the app
 /// # Returns
-/// Empty `Result` upon success
+/// `Result` with up to `n` words from the current dictionary, sorted by descending length and then alphabetically
 /// 
 /// *or String `Err` upon failure*
 #[tauri::command]
-async fn reset(state: State<'_, AppState>) -> Result<(), String> {
-    let mut last_game_state = state.last_game.lock().or(Err("Failed to get lock on the last game state"))?;
-    let mut undo_stack = state.undo_stack.lock().or(Err("Failed to get lock on undo stack!"))?;
-    let mut redo_stack = state.redo_stack.lock().or(Err("Failed to get lock on redo stack!"))?;
-    undo_stack.push(last_game_state.clone());
-    redo_stack.clear();
-    *last_game_state = None;
-    Ok(())
+async fn get_word_suggestions(available_letters: HashMap<String, i64>, n: usize, overrides: Option<SettingsOverrides>, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let letters = letters_from_map(&available_letters)?;
+    word_suggestions(&letters, n, overrides.and_then(|overrides| overrides.dictionary), &state)
 }
 
-/// Async command executed by the frontend to solve a Bananagrams board
+/// Async command executed by the frontend to get the playable words for a given hand of letters
 /// # Arguments
 /// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
 /// * `state` - Current state of the app
 /// # Returns
-/// `Result` as a `Solution` with a vector of vector of chars of the solution and the elapsed time
+/// `Result` of `PlayableWords` with keys "short" (common words playable using `available_letters`), "long" (Scrabble words playable using `available_letters`), "custom" (custom dictionary words playable using `available_letters`), each without words shorter than the minimum word length, and "short_histogram" and "long_histogram" (the number of short and long words of each length)
 /// 
-/// *or String `Err` upon failure or not finding a tile (with the reason indicated in the String)*
+/// *or `SolverError` upon failure (e.g. a letter is missing from the hand)*
 #[tauri::command]
-async fn play_bananagrams(available_letters: HashMap<String, i64>, state: State<'_, AppState>) -> Result<Solution, String> {
-    let now = Instant::now();
-    // Check if we have all the letters from the frontend
-    let mut letters = [0usize; 26];
-    for c in UPPERCASE.chars() {
-        let num = available_letters.get(&c.to_string());
-        match num {
-            Some(number) => {
-                if *number < 0 {
-                    return Err(format!("Number of letter {} is {}, but must be greater than or equal to 0!", c, number));
-                }
-                letters[(c as usize) - 65] = *number as usize;
-            },
-            None => {
-                return Err(format!("Missing letter: {}", c));
-            }
-        }
+async fn get_playable_words(available_letters: HashMap<String, i64>, state: State<'_, AppState>) -> Result<PlayableWords, SolverError> {
+    let letters = letters_from_map(&available_letters)?;
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let language_dictionaries = lock_language_dictionaries(&state, language)?;
+    let ((all_words_short, _, _), (all_words_long, _, _)) = language_words(language, &language_dictionaries)?;
+    let excluded_words = state.excluded_words.lock().or(Err("Failed to get lock on excluded words!"))?;
+    let min_word_length = *state.min_word_length.lock().or(Err("Failed to get lock on minimum word length!"))?;
+    let is_playable = |word: &&Word| word.len() >= min_word_length && !excluded_words.contains(*word) && is_makeable(word, &letters);
+    let playable_short: Vec<String> = all_words_short.iter().filter(is_playable).map(convert_array_to_word).collect();
+    let playable_long: Vec<String> = all_words_long.iter().filter(is_playable).map(convert_array_to_word).collect();
+    let playable_custom: Option<Vec<String>> = get_custom_dictionary(&state)?.map(|custom| custom.0.iter().filter(is_playable).map(convert_array_to_word).collect());
+    Ok(PlayableWords::new(playable_short, playable_long, playable_custom))
+}
+
+/// Checks which dictionaries a word is in
+/// # Arguments
+/// * `word` - The word to look up (case-insensitive; accented letters are read as their base letter)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the `WordValidity` of `word`
+/// 
+/// *or String `Err` if `word` contains anything other than letters or upon failure*
+fn word_validity(word: &str, state: &AppState) -> Result<WordValidity, String> {
+    let word = word.trim().to_uppercase();
+    if word.is_empty() || !word.chars().all(|c| letter_index(c).is_some()) {
+        return Err(format!("\"{}\" isn't a word!", word));
     }
-    // Check whether a board has been played already
-    let mut last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
-    let mut undo_stack = state.undo_stack.lock().or(Err("Failed to get lock on undo stack!"))?;
-    let mut redo_stack = state.redo_stack.lock().or(Err("Failed to get lock on redo stack!"))?;
-    let max_words_to_check = *state.maximum_words_to_check.lock().or(Err("Failed to get lock on maximum words!"))?;
-    let filter_letters_on_board = *state.filter_letters_on_board.lock().or(Err("Failed to get lock on maximum board letters!"))?;
-    let mut previous_board: Option<BoardAndIdxs> = None;
-    match &*last_game_state {   // I don't like &*
-        Some(prev_state) => {
-            previous_board = Some((prev_state.board.clone(), prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row));
-            let mut seen_greater: usize = EMPTY_VALUE;
-            let mut comparison = LetterComparison::Same;
-            for i in 0..26 {
-                if letters[i] < prev_state.letters[i] {
-                    // Any less means we re-do the board, so we can break here
-                    comparison = LetterComparison::SomeLess;
-                    break;
-                }
-                else if letters[i] > prev_state.letters[i] && (seen_greater != EMPTY_VALUE || letters[i] - prev_state.letters[i] != 1) {
-                    comparison = LetterComparison::GreaterByMoreThanOne;
-                }
-                else if letters[i] > prev_state.letters[i] {
-                    comparison = LetterComparison::GreaterByOne;
-                    seen_greater = i;
-                }
-            }
-            let dict_to_use = if *state.use_long_dictionary.lock().or(Err("Failed to get lock on using long dictionary!"))? {&state.all_words_long} else {&state.all_words_short};
-            match comparison {
-                LetterComparison::Same => {
-                    // If the hand is the same then no need to do anything
-                    return Ok(Solution { board: board_to_vec(&prev_state.board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, &HashSet::new()), elapsed: now.elapsed().as_millis() });
-                },
-                LetterComparison::GreaterByOne => {
-                    // If only a single letter has increased by one, then first check just that letter
-                    let valid_words_set: HashSet<&Word> = HashSet::from_iter(dict_to_use.iter().filter(|word| is_makeable(word, &letters)));
-                    let mut board = prev_state.board.clone();
-                    let res = play_one_letter(&mut board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, seen_greater, &valid_words_set);
-                    match res {
-                        Some(result) => {
-                            let previous_idxs = get_board_overlap(&prev_state.board, &board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, result.2, result.3, result.4, result.5);
-                            undo_stack.push(last_game_state.clone());
-                            redo_stack.clear();
-                            *last_game_state = Some(GameState { board: board.clone(), min_col: result.2, max_col: result.3, min_row: result.4, max_row: result.5, letters });
-                            return Ok(Solution { board: board_to_vec(&board, result.2, result.3, result.4, result.5, &previous_idxs), elapsed: now.elapsed().as_millis() });
-                        },
-                        None => {
-                            // If we failed when playing one letter, try playing off the existing board
-                            let attempt = play_existing(&prev_state.board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, &letters, &valid_words_set, dict_to_use, filter_letters_on_board, max_words_to_check);
-                            match attempt {
-                                Some(result) => {
-                                    let previous_idxs = get_board_overlap(&prev_state.board, &result.0, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, result.1, result.2, result.3, result.4);
-                                    undo_stack.push(last_game_state.clone());
-                                    redo_stack.clear();
-                                    *last_game_state = Some(GameState { board: result.0.clone(), min_col: result.1, max_col: result.2, min_row: result.3, max_row: result.4, letters });
-                                    return Ok(Solution { board: board_to_vec(&result.0, result.1, result.2, result.3, result.4, &previous_idxs), elapsed: now.elapsed().as_millis() });
-                                },
-                                None => { /* We want to continue with the code that builds from scratch */ }
-                            }
-                        }
-                    }
-                },
-                LetterComparison::GreaterByMoreThanOne => {
-                    // If a letter has increased by more than one, or multiple have increased by one or more, then try playing off the existing board
-                    let valid_words_set: HashSet<&Word> = HashSet::from_iter(dict_to_use.iter().filter(|word| is_makeable(word, &letters)));
-                    let attempt = play_existing(&prev_state.board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, &letters, &valid_words_set, dict_to_use, filter_letters_on_board, max_words_to_check);
-                    match attempt {
-                        Some(result) => {
-                            let previous_idxs = get_board_overlap(&prev_state.board, &result.0, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, result.1, result.2, result.3, result.4);
-                            undo_stack.push(last_game_state.clone());
-                            redo_stack.clear();
-                            *last_game_state = Some(GameState { board: result.0.clone(), min_col: result.1, max_col: result.2, min_row: result.3, max_row: result.4, letters });
-                            return Ok(Solution { board: board_to_vec(&result.0, result.1, result.2, result.3, result.4, &previous_idxs), elapsed: now.elapsed().as_millis() });
-                        },
-                        None => { /* We want to continue with the code that builds from scratch */ }
-                    }
-                },
-                LetterComparison::SomeLess => {/* We just want to continue to the code that starts from scratch */}
-            }
-        },
-        None => {/* We just want to continue to the code that starts from scratch */}
-    }
-    // Play from scratch
-    // Get a vector of all valid words
-    let dict_to_use = if *state.use_long_dictionary.lock().or(Err("Failed to get lock on using long dictionary!"))? {&state.all_words_long} else {&state.all_words_short};
-    let valid_words_vec: Vec<&Word> = dict_to_use.iter().filter(|word| is_makeable(word, &letters)).collect();
-    if valid_words_vec.is_empty() {
-        return Err("No valid words can be formed from the current letters - dump and try again!".to_owned());
-    }
-    let valid_words_set: HashSet<&Word> = HashSet::from_iter(valid_words_vec.iter().map(|w| *w));
-    // Split the words to check up into appropriate chunks based on the available parallelism
-    let default_parallelism_approx =  thread::available_parallelism().unwrap_or(NonZeroUsize::new(1).unwrap()).get();
-    let chunk_size = (valid_words_vec.len() as f32)/(default_parallelism_approx as f32);
-    let mut chunks: Vec<Vec<&Word>> = vec![Vec::with_capacity(chunk_size.ceil() as usize); default_parallelism_approx];
-    for (i, word) in valid_words_vec.iter().enumerate() {
-        chunks[i % default_parallelism_approx].push(*word);
-    }
-    // Prepare for threading/early termination using `AtomicBool`
-    let stop = Arc::new(AtomicBool::new(false));
-    let char_vec: Vec<(Vec<Vec<String>>, Board, usize, usize, usize, usize)> = Vec::new();
-    let ret_val = Arc::new(Mutex::new(char_vec));
-    let tried: Arc<Mutex<HashSet<&Word>>> = Arc::new(Mutex::new(HashSet::new()));
-    let valid_words_vec_len = valid_words_vec.len();
-    let arc_valid_words_vec = Arc::new(valid_words_vec);
-    let arc_valid_words_set = Arc::new(valid_words_set);
-    // For each thread (i.e. piece of available parallelism), spawn a new thread to check those words
-    // These threads check different sets of initial words in the board, and whichever finishes first signals the others to stop
-    thread::scope(|s| {
-        let mut handles: Vec<thread::ScopedJoinHandle<()>> = Vec::with_capacity(chunks.len());
-        for chunk in chunks {
-            let stop_t = stop.clone();
-            let new_letters = letters.clone();
-            let copied_new_valid_words_vec = Arc::clone(&arc_valid_words_vec);
-            let copied_valid_words_set = Arc::clone(&arc_valid_words_set);
-            let conn = Arc::clone(&ret_val);
-            let cloned_previous_board = previous_board.clone();
-            let tried_words = Arc::clone(&tried);
-            let handle = s.spawn(move || {
-                // Loop through each word and play it on a new board
-                let mut words_checked = 0;
-                let mut board = Board::new();
-                for word in chunk.iter() {
-                    let col_start = BOARD_SIZE/2 - word.len()/2;
-                    let row = BOARD_SIZE/2;
-                    let mut use_letters: [usize; 26] = new_letters.clone();
-                    let mut letters_on_board = [0usize; 26];
-                    for i in 0..word.len() {
-                        board.set_val(row, col_start+i, word[i]);
-                        letters_on_board[word[i]] += 1;
-                        use_letters[word[i]] -= 1;  // Should never underflow because we've verified that every word is playable with these letters
-                    }
-                    let min_col = col_start;
-                    let min_row = row;
-                    let max_col = col_start + (word.len()-1);
-                    let max_row = row;
-                    if use_letters.iter().all(|count| *count == 0) {
-                        if !stop_t.load(Ordering::Relaxed) {
-                            stop_t.store(true, Ordering::Relaxed);
-                            let mut ret = conn.lock().expect("Failed to get lock on shared ret_val");
-                            let previous_idxs: HashSet<(usize, usize)>;
-                            match cloned_previous_board {
-                                Some(prev) => {
-                                    previous_idxs = get_board_overlap(&prev.0, &board, prev.1, prev.2, prev.3, prev.4, min_col, max_col, min_row, max_row);
-                                },
-                                None => {previous_idxs = HashSet::new();}
-                            }
-                            ret.push((board_to_vec(&board, min_col, max_col, min_row, max_row, &previous_idxs), board.clone(), min_col, max_col, min_row, max_row));
-                            break;
-                        }
-                    }
-                    else {
-                        // Reduce the set of remaining words to check to those that can be played with the letters not in the first word (plus only one of the tiles played in the first word)
-                        let word_letters: HashSet<usize> = HashSet::from_iter(word.iter().map(|c| c.clone()));
-                        let mut new_valid_words_vec: Vec<&Word> = Vec::with_capacity(valid_words_vec_len);
-                        for w in copied_new_valid_words_vec.iter() {
-                            if check_filter_after_play(use_letters.clone(), w, &word_letters) && !tried_words.lock().expect("Failed to get lock on tried_words").contains(w) {
-                                new_valid_words_vec.push(w);
-                            }
-                        }
-                        // Begin the recursive processing
-                        let result = play_further(&mut board, min_col, max_col, min_row, max_row, &new_valid_words_vec, &copied_valid_words_set, use_letters, 0, &mut words_checked, &mut letters_on_board, filter_letters_on_board, max_words_to_check, &stop_t);
-                        match result {
-                            // If the result was good, then store it and signal other threads to finish (so long as another thread isn't doing so)
-                            Ok(res) => {
-                                if res.0 && !stop_t.load(Ordering::Relaxed) {
-                                    stop_t.store(true, Ordering::Relaxed);
-                                    // The expect will panic the thread but I think that's ok
-                                    let mut ret = conn.lock().expect("Failed to get lock on shared ret_val");
-                                    let previous_idxs: HashSet<(usize, usize)>;
-                                    match cloned_previous_board {
-                                        Some(prev) => {
-                                            previous_idxs = get_board_overlap(&prev.0, &board, prev.1, prev.2, prev.3, prev.4, res.1, res.2, res.3, res.4);
-                                        },
-                                        None => {previous_idxs = HashSet::new();}
-                                    }
-                                    ret.push((board_to_vec(&board, res.1, res.2, res.3, res.4, &previous_idxs), board.clone(), res.1, res.2, res.3, res.4));
-                                    break;
-                                }
-                                else {
-                                    tried_words.lock().expect("Failed to get lock on tried words").insert(word);
-                                }
-                            },
-                            // If an error (we're out of bounds or another thread signalled to stop) then we're done
-                            Err(()) => {
-                                break;
-                            }
-                        }
-                    }
-                    for col in min_col..=max_col {
-                        board.set_val(row, col, EMPTY_VALUE);
-                    }
-                }
-            });
-            handles.push(handle);
+    let word = convert_word_to_array(&word);
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let language_dictionaries = lock_language_dictionaries(state, language)?;
+    let ((_, trie_short, _), (_, trie_long, _)) = language_words(language, &language_dictionaries)?;
+    let in_custom = get_custom_dictionary(state)?.map(|custom| custom.1.contains(&word));
+    Ok(WordValidity { in_short: trie_short.contains(&word), in_long: trie_long.contains(&word), in_custom })
+}
+
+/// Async command executed by the frontend to look up whether a word is in the dictionaries
+/// # Arguments
+/// * `word` - The word to look up (case-insensitive)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the `WordValidity` of `word`
+/// 
+/// *or String `Err` if `word` contains anything other than letters or upon failure*
+#[tauri::command]
+async fn check_word_validity(word: String, state: State<'_, AppState>) -> Result<WordValidity, String> {
+    word_validity(&word, &state)
+}
+
+/// Command executed by the frontend to read a hand typed as a string, e.g. "AABCDE"
+/// # Arguments
+/// * `s` - The tiles in the hand (case-insensitive; blanks are `BLANK_CHAR`)
+/// # Returns
+/// `Result` with a `HashMap` mapping each letter to the number of it in the hand (blanks, as `BLANK_CHAR`, are only included if there are any)
+///
+/// *or String `Err` if `s` contains anything other than letters and blanks*
+#[tauri::command]
+fn parse_letters(s: String) -> Result<HashMap<String, usize>, String> {
+    let letters = string_to_letters(&s.to_uppercase())?;
+    let mut counts: HashMap<String, usize> = UPPERCASE.chars().zip(letters).map(|(c, count)| (c.to_string(), count)).collect();
+    if letters[BLANK] > 0 {
+        counts.insert(BLANK_CHAR.to_string(), letters[BLANK]);
+    }
+    Ok(counts)
+}
+
+/// A single position of a crossword-style pattern (see `parse_pattern`)
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum PatternAtom {
+    /// Matches any letter
+    Any,
+    /// Matches only the given letter (0 for 'A' through 25 for 'Z')
+    Exact(usize)
+}
+
+/// Parses a crossword-style pattern such as "C.T", where `.` matches any letter and a letter matches only itself
+/// # Arguments
+/// * `pattern` - The pattern to parse (uppercase; accented letters are read as their base letter)
+/// # Returns
+/// `Result` with a `PatternAtom` for each character of `pattern`
+///
+/// *or String `Err` if `pattern` is empty, longer than the longest word, or contains anything other than letters and `.`*
+fn parse_pattern(pattern: &str) -> Result<Vec<PatternAtom>, String> {
+    let atoms = pattern.chars().map(|c| match c {
+        '.' => Ok(PatternAtom::Any),
+        _ => letter_index(c).map(PatternAtom::Exact).ok_or(format!("Invalid character in pattern: {}", c))
+    }).collect::<Result<Vec<PatternAtom>, String>>()?;
+    if atoms.is_empty() {
+        return Err("The pattern must not be empty!".to_owned());
+    }
+    if atoms.len() > MAX_WORD_LENGTH {
+        return Err(format!("The pattern can be at most {} letters long (the length of the longest word)!", MAX_WORD_LENGTH));
+    }
+    Ok(atoms)
+}
+
+/// Checks whether a word matches a pattern (see `parse_pattern`)
+/// # Arguments
+/// * `pattern` - The parsed pattern
+/// * `word` - The word to check
+/// # Returns
+/// `bool` - Whether `word` is the same length as `pattern` and has each of its exact letters in place
+fn match_pattern(pattern: &[PatternAtom], word: &Word) -> bool {
+    pattern.len() == word.len() && pattern.iter().zip(word).all(|(atom, letter)| match atom {
+        PatternAtom::Any => true,
+        PatternAtom::Exact(exact) => exact == letter
+    })
+}
+
+/// Finds the words in the short and long dictionaries matching a pattern
+/// # Arguments
+/// * `pattern` - The crossword-style pattern (see `parse_pattern`; case-insensitive)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the matching words, without duplicates and sorted alphabetically
+///
+/// *or String `Err` if the pattern is invalid or upon failure*
+fn words_matching(pattern: &str, state: &AppState) -> Result<Vec<String>, String> {
+    let pattern = parse_pattern(&pattern.trim().to_uppercase())?;
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let language_dictionaries = lock_language_dictionaries(state, language)?;
+    let ((all_words_short, _, _), (all_words_long, _, _)) = language_words(language, &language_dictionaries)?;
+    let mut matches: Vec<String> = all_words_short.iter().chain(all_words_long.iter()).filter(|word| match_pattern(&pattern, word)).map(convert_array_to_word).collect();
+    matches.sort();
+    matches.dedup();
+    Ok(matches)
+}
+
+/// Async command executed by the frontend to search the dictionaries for words matching a crossword-style pattern, e.g. "C.T"
+/// # Arguments
+/// * `pattern` - The pattern, where `.` matches any letter (case-insensitive)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the matching words, without duplicates and sorted alphabetically
+///
+/// *or String `Err` if the pattern is invalid or upon failure*
+#[tauri::command]
+async fn search_dictionary(pattern: String, state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    words_matching(&pattern, &state)
+}
+
+/// Finds all the anagrams of a string
+/// # Arguments
+/// * `word` - The letters to anagram (case-insensitive; `BLANK_CHAR` is treated as a blank and other characters are ignored)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` of `PlayableWords` with keys "short" (common words), "long" (Scrabble words), and "custom" (custom dictionary words) that use exactly the letters in `word` and aren't already on the board, sorted alphabetically
+/// 
+/// *or String `Err` upon failure*
+fn anagrams_of(word: &str, state: &AppState) -> Result<PlayableWords, String> {
+    let mut letters = [0usize; 27];
+    for c in word.to_uppercase().chars() {
+        if let Some(letter) = letter_index(c) {
+            letters[letter] += 1;
+        }
+        else if c == BLANK_CHAR {
+            letters[BLANK] += 1;
+        }
+    }
+    if letters.iter().all(|count| *count == 0) {
+        return Err("No letters were given to anagram!".to_owned());
+    }
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let language_dictionaries = lock_language_dictionaries(state, language)?;
+    let ((all_words_short, _, _), (all_words_long, _, _)) = language_words(language, &language_dictionaries)?;
+    // Anagrams that are already on the board (see `Board::contains_word`) can't be played again, so they're left out
+    let last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    let is_new_anagram = |w: &Word| is_exact_anagram(w, &letters) && !last_game_state.as_ref().is_some_and(|game| game.board.contains_word(w, game.min_col, game.max_col, game.min_row, game.max_row));
+    let mut anagrams_short: Vec<String> = all_words_short.iter().filter(|w| is_new_anagram(w)).map(convert_array_to_word).collect();
+    anagrams_short.sort();
+    anagrams_short.dedup();
+    let mut anagrams_long: Vec<String> = all_words_long.iter().filter(|w| is_new_anagram(w)).map(convert_array_to_word).collect();
+    anagrams_long.sort();
+    anagrams_long.dedup();
+    let anagrams_custom: Option<Vec<String>> = get_custom_dictionary(state)?.map(|custom| {
+        let mut anagrams: Vec<String> = custom.0.iter().filter(|w| is_new_anagram(w)).map(convert_array_to_word).collect();
+        anagrams.sort();
+        anagrams.dedup();
+        anagrams
+    });
+    Ok(PlayableWords::new(anagrams_short, anagrams_long, anagrams_custom))
+}
+
+/// Async command executed by the frontend to get all the anagrams of a string
+/// # Arguments
+/// * `word` - The letters to anagram (case-insensitive; `BLANK_CHAR` is treated as a blank and other characters are ignored)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` of `PlayableWords` (see `anagrams_of`)
+/// 
+/// *or String `Err` upon failure*
+#[tauri::command]
+async fn get_anagrams(word: String, state: State<'_, AppState>) -> Result<PlayableWords, String> {
+    anagrams_of(&word, &state)
+}
+
+/// Evaluates dumping one copy of each distinct letter (or blank) in a hand, with a thread for each letter
+/// # Arguments
+/// * `letters` - The number of each letter (and blanks) in the hand
+/// * `dict_to_use` - The words of the dictionary to play from
+/// * `excluded_words` - Words that must not be played
+/// * `min_word_length` - Minimum length of a word that can be played
+/// * `board_size` - Number of rows/columns in the board
+/// * `quick_solve` - The `Trie`, `DictionaryIndex` and maximum number of board letters to use in a bounded solve of each dumped hand (see `solves_quickly`), or `None` to only count the playable words
+/// # Returns
+/// `Vec` of `(letter, words_gained, solvable)` for each letter in the hand, where `words_gained` is how many more words are playable once `letter` is dumped than before (negative if words are lost) and `solvable` is whether the bounded solve succeeded (always `false` without `quick_solve`)
+fn evaluate_dumps(letters: &Letters, dict_to_use: &[Word], excluded_words: &HashSet<Word>, min_word_length: usize, board_size: usize, quick_solve: Option<(&Trie, &DictionaryIndex, usize)>) -> Vec<(usize, isize, bool)> {
+    let is_playable = |word: &Word, letters: &Letters| (min_word_length..=board_size).contains(&word.len()) && !excluded_words.contains(word) && is_makeable(word, letters);
+    let playable_before = dict_to_use.iter().filter(|word| is_playable(word, letters)).count() as isize;
+    // Each candidate gets its own thread, which bounds its own search
+    let stop = Arc::new(AtomicBool::new(false));
+    let mut results: Vec<(usize, isize, bool)> = Vec::with_capacity(27);
+    thread::scope(|s| {
+        let mut handles: Vec<(usize, thread::ScopedJoinHandle<(isize, bool)>)> = Vec::with_capacity(27);
+        for letter in (0..27).filter(|l| letters[*l] > 0) {
+            let mut dumped_letters = *letters;
+            dumped_letters[letter] -= 1;
+            let stop_t = Arc::clone(&stop);
+            let handle = s.spawn(move || {
+                let valid_words_vec: Vec<IndexedWord> = indexed_words(dict_to_use).filter(|word| is_playable(word, &dumped_letters)).collect();
+                let solvable = quick_solve.is_some_and(|(valid_words_trie, dictionary_index, filter_letters_on_board)| solves_quickly(&dumped_letters, &valid_words_vec, valid_words_trie, dictionary_index, &SolveConfig { filter_letters_on_board, max_words_to_check: DUMP_WORDS_TO_CHECK, board_size, min_word_length, ..SolveConfig::default() }, &stop_t));
+                (valid_words_vec.len() as isize - playable_before, solvable)
+            });
+            handles.push((letter, handle));
+        }
+        // Wait for all the threads
+        for (letter, handle) in handles {
+            if let Ok((words_gained, solvable)) = handle.join() {
+                results.push((letter, words_gained, solvable));
+            }
+        }
+    });
+    results
+}
+
+/// Ranks which letters are best to dump
+/// # Arguments
+/// * `letters` - The number of each letter (and blanks) in the hand
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with a `DumpCandidate` for each distinct letter in the hand, best first (those that could be solved quickly, then those gaining the most playable words, then those with the fewest copies in the hand)
+/// 
+/// *or String `Err` upon failure*
+fn rank_dumps(letters: &Letters, state: &AppState) -> Result<Vec<DumpCandidate>, String> {
+    let filter_letters_on_board = *state.filter_letters_on_board.lock().or(Err("Failed to get lock on maximum board letters!"))?;
+    let min_word_length = *state.min_word_length.lock().or(Err("Failed to get lock on minimum word length!"))?;
+    let board_size = *state.board_size.lock().or(Err("Failed to get lock on board size!"))?;
+    let dictionary = *state.dictionary.lock().or(Err("Failed to get lock on dictionary!"))?;
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let custom_dictionary = get_custom_dictionary(state)?;
+    let language_dictionaries = lock_language_dictionaries(state, language)?;
+    let (dict_to_use, valid_words_trie, dictionary_index) = select_dictionary(dictionary, language, &custom_dictionary, &language_dictionaries)?;
+    let excluded_words = state.excluded_words.lock().or(Err("Failed to get lock on excluded words!"))?.clone();
+    let mut results = evaluate_dumps(letters, dict_to_use, &excluded_words, min_word_length, board_size, Some((valid_words_trie, dictionary_index, filter_letters_on_board)));
+    if results.is_empty() {
+        return Err("There are no letters in the hand to dump!".to_owned());
+    }
+    results.sort_by(|a, b| b.2.cmp(&a.2).then(b.1.cmp(&a.1)).then(letters[a.0].cmp(&letters[b.0])));
+    Ok(results.into_iter().map(|(letter, words_gained, solvable)| DumpCandidate {
+        letter: if letter == BLANK {BLANK_CHAR.to_string()} else {((letter as u8+65) as char).to_string()},
+        words_gained,
+        solvable
+    }).collect())
+}
+
+/// Async command executed by the frontend to rank which letters are best to dump
+/// # Arguments
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with a `DumpCandidate` for each distinct letter in the hand, best first (see `rank_dumps`)
+/// 
+/// *or String `Err` upon failure*
+#[tauri::command]
+async fn suggest_dump(available_letters: HashMap<String, i64>, state: State<'_, AppState>) -> Result<Vec<DumpCandidate>, String> {
+    rank_dumps(&letters_from_map(&available_letters)?, &state)
+}
+
+/// Recommends which letter to dump
+/// # Arguments
+/// * `letters` - The number of each letter (and blanks) in the hand
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the letter whose removal from the hand leaves the most playable words in the current dictionary (ties going to the letter with the fewest copies in the hand)
+/// 
+/// *or String `Err` upon failure*
+fn recommend_dump(letters: &Letters, state: &AppState) -> Result<String, String> {
+    let min_word_length = *state.min_word_length.lock().or(Err("Failed to get lock on minimum word length!"))?;
+    let board_size = *state.board_size.lock().or(Err("Failed to get lock on board size!"))?;
+    let dictionary = *state.dictionary.lock().or(Err("Failed to get lock on dictionary!"))?;
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let custom_dictionary = get_custom_dictionary(state)?;
+    let language_dictionaries = lock_language_dictionaries(state, language)?;
+    let (dict_to_use, _, _) = select_dictionary(dictionary, language, &custom_dictionary, &language_dictionaries)?;
+    let excluded_words = state.excluded_words.lock().or(Err("Failed to get lock on excluded words!"))?.clone();
+    let results = evaluate_dumps(letters, dict_to_use, &excluded_words, min_word_length, board_size, None);
+    // Prefer the most playable words, then the rarest letter in the hand (blanks are never worth dumping)
+    let best = results.iter().filter(|(letter, _, _)| *letter != BLANK).max_by(|a, b| a.1.cmp(&b.1).then(letters[b.0].cmp(&letters[a.0])));
+    match best {
+        Some((letter, _, _)) => Ok(((*letter as u8+65) as char).to_string()),
+        None => Err("There are no letters in the hand to dump!".to_owned())
+    }
+}
+
+/// Async command executed by the frontend to recommend which letter to dump
+/// # Arguments
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the recommended letter (see `recommend_dump`)
+/// 
+/// *or String `Err` upon failure*
+#[tauri::command]
+async fn dump_simulate(available_letters: HashMap<String, i64>, state: State<'_, AppState>) -> Result<String, String> {
+    recommend_dump(&letters_from_map(&available_letters)?, &state)
+}
+
+/// Quickly judges how likely a hand is to be solvable, without running the solver
+/// # Arguments
+/// * `letters` - The number of each letter (and blanks) in the hand
+/// * `valid_words` - The words that can be played
+/// # Returns
+/// `Solvability` - `Impossible` if fewer than 2 words can be formed, `Easy` if more than `EASY_HAND_WORDS` can be formed and one of them uses more than half the tiles, `Moderate` if only one of those holds, otherwise `Difficult`
+fn assess_hand(letters: &Letters, valid_words: &[&Word]) -> Solvability {
+    let num_tiles: usize = letters.iter().sum();
+    let mut num_makeable = 0;
+    let mut uses_half = false;
+    for word in valid_words.iter().filter(|word| is_makeable(word, letters)) {
+        num_makeable += 1;
+        uses_half |= word.len()*2 > num_tiles;
+    }
+    match (num_makeable, num_makeable > EASY_HAND_WORDS, uses_half) {
+        (0..=1, _, _) => Solvability::Impossible,
+        (_, true, true) => Solvability::Easy,
+        (_, true, false) | (_, false, true) => Solvability::Moderate,
+        _ => Solvability::Difficult
+    }
+}
+
+/// Command executed by the frontend to quickly judge how likely a hand is to be solvable before solving it
+/// # Arguments
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with "Impossible", "Difficult", "Moderate" or "Easy" (see `assess_hand`), judged from the words of the current dictionary that are at least the minimum word length and aren't excluded
+/// 
+/// *or String `Err` upon failure*
+#[tauri::command]
+fn assess_hand_command(available_letters: HashMap<String, i64>, state: State<'_, AppState>) -> Result<String, String> {
+    let letters = letters_from_map(&available_letters)?;
+    let dictionary = *state.dictionary.lock().or(Err("Failed to get lock on dictionary!"))?;
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let min_word_length = *state.min_word_length.lock().or(Err("Failed to get lock on minimum word length!"))?;
+    let custom_dictionary = get_custom_dictionary(&state)?;
+    let language_dictionaries = lock_language_dictionaries(&state, language)?;
+    let (dict_to_use, _, _) = select_dictionary(dictionary, language, &custom_dictionary, &language_dictionaries)?;
+    let excluded_words = state.excluded_words.lock().or(Err("Failed to get lock on excluded words!"))?;
+    let valid_words: Vec<&Word> = dict_to_use.iter().filter(|word| word.len() >= min_word_length && !excluded_words.contains(*word)).collect();
+    Ok(assess_hand(&letters, &valid_words).name().to_owned())
+}
+
+/// Updates the settings
+/// # Arguments
+/// * `filter_letters_on_board` - Maximum number of letters on the board that can be used when forming a word
+/// * `maximum_words_to_check` - Maximum number of iterations to perform
+/// * `max_solve_time_ms` - Maximum number of milliseconds to spend on a solve (if 0, then there is no limit)
+/// * `board_size` - Number of rows/columns in the board (must be at least the length of the longest word, and at most `MAX_BOARD_SIZE`)
+/// * `dictionary` - Which dictionary to use (a custom dictionary must already have been loaded to use it)
+/// * `use_frequency_ordering` - Whether to try words containing the most common remaining letters first
+/// * `deterministic` - Whether to solve using a single thread so that the same hand always gives the same board
+/// * `language` - Which language's dictionaries to use (its word lists must be in the dictionaries folder unless it's English)
+/// * `solution_preference` - Which solutions the solver prefers
+/// * `history_depth` - Maximum number of previous games that can be undone (must be at least 1)
+/// * `min_word_length` - Minimum length of the words formed on the board (from 2 up to the length of the longest word)
+/// * `fallback_to_long_dictionary` - Whether to retry with the long dictionary when the short one can't solve a hand
+/// * `max_width` - Maximum number of columns the solved board can span (if `None`, then the board size)
+/// * `max_height` - Maximum number of rows the solved board can span (if `None`, then the board size)
+/// # Returns
+/// `Result` with the settings now in use
+/// 
+/// *or String `Err` if a setting is invalid (in which case none are changed) or upon failure*
+#[tauri::command]
+fn set_settings(filter_letters_on_board: usize, maximum_words_to_check: usize, max_solve_time_ms: u64, board_size: usize, dictionary: DictionaryChoice, use_frequency_ordering: bool, deterministic: bool, language: Language, solution_preference: SolutionPreference, history_depth: usize, min_word_length: usize, fallback_to_long_dictionary: bool, max_width: Option<usize>, max_height: Option<usize>, state: State<'_, AppState>) -> Result<CurrentSettings, String> {
+    apply_settings(&state, &CurrentSettings { filter_letters_on_board, maximum_words_to_check, max_solve_time_ms, board_size, dictionary, use_frequency_ordering, deterministic, language, solution_preference, history_depth, min_word_length, fallback_to_long_dictionary, max_width, max_height })?;
+    // Failing to save the settings shouldn't stop them being used
+    let _ = save_settings(&state);
+    current_settings(&state)
+}
+
+/// Checks that the numeric settings are within their allowed ranges
+/// # Arguments
+/// * `settings` - The settings to check
+/// # Returns
+/// Empty `Result` if the settings are valid
+/// 
+/// *or String `Err` explaining which setting is invalid*
+fn validate_settings(settings: &CurrentSettings) -> Result<(), String> {
+    if settings.maximum_words_to_check < MIN_WORDS_TO_CHECK {
+        return Err(format!("The maximum number of words to check must be at least {}!", MIN_WORDS_TO_CHECK));
+    }
+    if settings.filter_letters_on_board > MAX_WORD_LENGTH {
+        return Err(format!("The number of usable letters on the board can be at most {} (the length of the longest word)!", MAX_WORD_LENGTH));
+    }
+    if settings.board_size < MAX_WORD_LENGTH || settings.board_size > MAX_BOARD_SIZE {
+        return Err(format!("The board size must be between {} (the length of the longest word) and {}!", MAX_WORD_LENGTH, MAX_BOARD_SIZE));
+    }
+    if settings.history_depth == 0 {
+        return Err("At least one previous game must be kept for undoing!".to_owned());
+    }
+    if settings.min_word_length < 2 || settings.min_word_length > MAX_WORD_LENGTH {
+        return Err(format!("The minimum word length must be between 2 and {} (the length of the longest word)!", MAX_WORD_LENGTH));
+    }
+    // The first word is played across, so the board must be at least wide enough for a word
+    if settings.max_width.is_some_and(|width| width < settings.min_word_length || width > settings.board_size) {
+        return Err(format!("The maximum width must be between {} (the minimum word length) and {} (the board size)!", settings.min_word_length, settings.board_size));
+    }
+    if settings.max_height.is_some_and(|height| height == 0 || height > settings.board_size) {
+        return Err(format!("The maximum height must be between 1 and {} (the board size)!", settings.board_size));
+    }
+    Ok(())
+}
+
+/// Updates the settings of the app
+/// # Arguments
+/// * `state` - State of the app to update
+/// * `settings` - The new settings
+/// # Returns
+/// Empty `Result` upon success
+/// 
+/// *or String `Err` if the settings are invalid or a lock couldn't be obtained*
+fn apply_settings(state: &AppState, settings: &CurrentSettings) -> Result<(), String> {
+    let CurrentSettings { filter_letters_on_board, maximum_words_to_check, max_solve_time_ms, board_size, dictionary, use_frequency_ordering, deterministic, language, solution_preference, history_depth, min_word_length, fallback_to_long_dictionary, max_width, max_height } = *settings;
+    validate_settings(settings)?;
+    if dictionary == DictionaryChoice::Custom && state.custom_dictionary.lock().or(Err("Failed to get lock on custom dictionary!"))?.is_none() {
+        return Err("No custom dictionary has been loaded!".to_owned());
+    }
+    // Load the language's dictionaries now, so that a missing word list is reported when it's chosen rather than when solving
+    drop(lock_language_dictionaries(state, language)?);
+    // Get every lock before changing anything, so that failing to get one doesn't leave the settings half-applied
+    let mut filter_to_change = state.filter_letters_on_board.lock().or(Err("Failed to get lock on state!"))?;
+    let mut maximum_to_change = state.maximum_words_to_check.lock().or(Err("Failed to get lock on state!"))?;
+    let mut time_to_change = state.max_solve_time_ms.lock().or(Err("Failed to get lock on state!"))?;
+    let mut size_to_change = state.board_size.lock().or(Err("Failed to get lock on state!"))?;
+    let mut dictionary_to_change = state.dictionary.lock().or(Err("Failed to get lock on state!"))?;
+    let mut ordering_to_change = state.use_frequency_ordering.lock().or(Err("Failed to get lock on state!"))?;
+    let mut deterministic_to_change = state.deterministic.lock().or(Err("Failed to get lock on state!"))?;
+    let mut language_to_change = state.language.lock().or(Err("Failed to get lock on state!"))?;
+    let mut preference_to_change = state.solution_preference.lock().or(Err("Failed to get lock on state!"))?;
+    let mut depth_to_change = state.history_depth.lock().or(Err("Failed to get lock on state!"))?;
+    let mut length_to_change = state.min_word_length.lock().or(Err("Failed to get lock on state!"))?;
+    let mut fallback_to_change = state.fallback_to_long_dictionary.lock().or(Err("Failed to get lock on state!"))?;
+    let mut width_to_change = state.max_width.lock().or(Err("Failed to get lock on state!"))?;
+    let mut height_to_change = state.max_height.lock().or(Err("Failed to get lock on state!"))?;
+    *filter_to_change = filter_letters_on_board;
+    *maximum_to_change = maximum_words_to_check;
+    *time_to_change = max_solve_time_ms;
+    *size_to_change = board_size;
+    *dictionary_to_change = dictionary;
+    *ordering_to_change = use_frequency_ordering;
+    *deterministic_to_change = deterministic;
+    *language_to_change = language;
+    *preference_to_change = solution_preference;
+    *depth_to_change = history_depth;
+    *length_to_change = min_word_length;
+    *fallback_to_change = fallback_to_long_dictionary;
+    *width_to_change = max_width;
+    *height_to_change = max_height;
+    Ok(())
+}
+
+/// Gets the current settings
+/// # Returns
+/// `Results` with struct containing the current settings
+/// 
+/// *or String `Err` upon failure*
+#[tauri::command]
+fn get_settings(state: State<'_, AppState>) -> Result<CurrentSettings, String> {
+    current_settings(&state)
+}
+
+/// Gets the settings of the app
+/// # Arguments
+/// * `state` - State of the app
+/// # Returns
+/// `Result` with the current settings
+/// 
+/// *or String `Err` if a lock couldn't be obtained*
+fn current_settings(state: &AppState) -> Result<CurrentSettings, String> {
+    let filter_letters_on_board = *state.filter_letters_on_board.lock().or(Err("Failed to get lock on state!"))?;
+    let dictionary = *state.dictionary.lock().or(Err("Failed to get lock on state!"))?;
+    let maximum_words_to_check = *state.maximum_words_to_check.lock().or(Err("Failed to get lock on state!"))?;
+    let max_solve_time_ms = *state.max_solve_time_ms.lock().or(Err("Failed to get lock on state!"))?;
+    let board_size = *state.board_size.lock().or(Err("Failed to get lock on state!"))?;
+    let use_frequency_ordering = *state.use_frequency_ordering.lock().or(Err("Failed to get lock on state!"))?;
+    let deterministic = *state.deterministic.lock().or(Err("Failed to get lock on state!"))?;
+    let language = *state.language.lock().or(Err("Failed to get lock on state!"))?;
+    let solution_preference = *state.solution_preference.lock().or(Err("Failed to get lock on state!"))?;
+    let history_depth = *state.history_depth.lock().or(Err("Failed to get lock on state!"))?;
+    let min_word_length = *state.min_word_length.lock().or(Err("Failed to get lock on state!"))?;
+    let fallback_to_long_dictionary = *state.fallback_to_long_dictionary.lock().or(Err("Failed to get lock on state!"))?;
+    let max_width = *state.max_width.lock().or(Err("Failed to get lock on state!"))?;
+    let max_height = *state.max_height.lock().or(Err("Failed to get lock on state!"))?;
+    Ok(CurrentSettings { filter_letters_on_board, dictionary, maximum_words_to_check, max_solve_time_ms, board_size, use_frequency_ordering, deterministic, language, solution_preference, history_depth, min_word_length, fallback_to_long_dictionary, max_width, max_height })
+}
+
+/// Reads a newline-delimited word list (accented letters are read as their base letter; see `letter_index`)
+/// # Arguments
+/// * `path` - Path of the word list to read
+/// * `max_word_length` - Maximum allowed length of a word
+/// # Returns
+/// `Result` with the words, sorted by descending length
+/// 
+/// *or String `Err` if the file couldn't be read, has no words, or has a word that is too long or contains something other than letters*
+fn read_word_list(path: &Path, max_word_length: usize) -> Result<Vec<Word>, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut words: Vec<Word> = Vec::new();
+    for (line_num, line) in contents.lines().enumerate() {
+        let word = line.trim().to_uppercase();
+        if word.is_empty() {
+            continue;
+        }
+        if !word.chars().all(|c| letter_index(c).is_some()) {
+            return Err(format!("Word \"{}\" on line {} contains characters that aren't letters", line.trim(), line_num+1));
+        }
+        if word.chars().count() > max_word_length {
+            return Err(format!("Word \"{}\" on line {} is longer than the maximum of {} letters", line.trim(), line_num+1, max_word_length));
+        }
+        words.push(convert_word_to_array(&word));
+    }
+    if words.is_empty() {
+        return Err(format!("{} doesn't contain any words", path.display()));
+    }
+    words.sort_by_key(|word| Reverse(word.len()));
+    Ok(words)
+}
+
+/// Command executed by the frontend to load a dictionary from a newline-delimited word list (see `load_dictionary`)
+/// # Arguments
+/// * `path` - Path of the word list to read
+/// * `which` - Which dictionary to replace: `"short"` or `"long"` for the English short or long dictionary, or `"custom"` (the default) for the custom dictionary
+/// * `max_word_length` - Maximum allowed length of a word (defaults to `MAX_WORD_LENGTH`)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the number of words loaded
+/// 
+/// *or String `Err` upon failure (in which case the previous dictionary is left untouched)*
+#[tauri::command]
+fn load_custom_dictionary(path: String, which: Option<String>, max_word_length: Option<usize>, state: State<'_, AppState>) -> Result<usize, String> {
+    load_dictionary(&path, which, max_word_length, &state)
+}
+
+/// Loads a custom dictionary from a newline-delimited word list, replacing any previously loaded custom dictionary (or, if `which` is given, one of the English dictionaries)
+/// # Arguments
+/// * `path` - Path of the word list to read
+/// * `which` - Which dictionary to replace: `"short"` or `"long"` for the English short or long dictionary, or `"custom"` (the default) for the custom dictionary
+/// * `max_word_length` - Maximum allowed length of a word (defaults to `MAX_WORD_LENGTH`)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the number of words loaded
+/// 
+/// *or String `Err` upon failure (in which case the previous dictionary is left untouched)*
+fn load_dictionary(path: &str, which: Option<String>, max_word_length: Option<usize>, state: &AppState) -> Result<usize, String> {
+    let which = match which.map(|which| which.to_lowercase()).as_deref() {
+        None | Some("custom") => DictionaryChoice::Custom,
+        Some("short") => DictionaryChoice::Short,
+        Some("long") => DictionaryChoice::Long,
+        Some(other) => return Err(format!("Unknown dictionary \"{}\" (expected \"short\", \"long\" or \"custom\")", other))
+    };
+    let words = read_word_list(Path::new(path), max_word_length.unwrap_or(MAX_WORD_LENGTH))?;
+    let trie = Trie::from_words(&words);
+    let index = DictionaryIndex::from_words(&words);
+    let num_words = words.len();
+    match which {
+        DictionaryChoice::Custom => {
+            let mut custom_dictionary = state.custom_dictionary.lock().or(Err("Failed to get lock on custom dictionary!"))?;
+            *custom_dictionary = Some(Arc::new((words, trie, index)));
+        },
+        DictionaryChoice::Short | DictionaryChoice::Long => {
+            // This waits for any solve in progress, since solving holds the lock on the dictionaries
+            let mut language_dictionaries = state.language_dictionaries.lock().or(Err("Failed to get lock on language dictionaries!"))?;
+            let english = language_dictionaries.get_mut(&Language::English).ok_or("The English dictionaries haven't been loaded!")?;
+            if which == DictionaryChoice::Short {
+                english.0 = (words, trie, index);
+            }
+            else {
+                english.1 = (words, trie, index);
+            }
+        }
+    }
+    state.excluded_tries.lock().or(Err("Failed to get lock on excluded word tries!"))?.clear();
+    Ok(num_words)
+}
+
+/// Writes a game and the current settings to a JSON file
+/// # Arguments
+/// * `path` - Path of the file to write
+/// * `game` - The game to save
+/// * `state` - Current state of the app
+/// # Returns
+/// Empty `Result` upon success
+/// 
+/// *or String `Err` upon failure*
+fn write_save_file(path: &PathBuf, game: &GameState, state: &AppState) -> Result<(), String> {
+    let save = SaveFile { version: SAVE_VERSION, game: game.clone(), settings: current_settings(state)? };
+    let json = serde_json::to_string(&save).map_err(|e| format!("Failed to serialize the game: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Reads a game and its settings from a file written by `write_save_file`, making it the current game
+/// # Arguments
+/// * `path` - Path of the file to read
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` of `UndoRedo` with the loaded board and hand
+/// 
+/// *or String `Err` upon failure*
+fn read_save_file(path: &PathBuf, state: &AppState) -> Result<UndoRedo, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let save = parse_save_file(&contents).map_err(|e| format!("Failed to load the game: {}", e))?;
+    restore_settings(state, save.settings)?;
+    let game = save.game;
+    let history_depth = *state.history_depth.lock().or(Err("Failed to get lock on history depth!"))?;
+    let mut undo_stack = state.undo_stack.lock().or(Err("Failed to get lock on undo stack!"))?;
+    let mut redo_stack = state.redo_stack.lock().or(Err("Failed to get lock on redo stack!"))?;
+    let mut last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    push_undo(&mut undo_stack, last_game_state.clone(), history_depth);
+    redo_stack.clear();
+    let mut letters = HashMap::new();
+    for (c, n) in UPPERCASE.chars().zip(game.letters) {
+        letters.insert(c, n as u64);
+    }
+    letters.insert(BLANK_CHAR, game.letters[BLANK] as u64);
+    let solution = board_to_vec(&game.board, game.min_col, game.max_col, game.min_row, game.max_row);
+    *last_game_state = Some(game);
+    Ok(UndoRedo { solution, letters, undo_possible: true, redo_possible: false })
+}
+
+/// Parses the contents of a file written by `write_save_file`
+/// # Arguments
+/// * `contents` - Contents of the file
+/// # Returns
+/// `Result` with the `SaveFile`
+/// 
+/// *or String `Err` describing why the file couldn't be read*
+fn parse_save_file(contents: &str) -> Result<SaveFile, String> {
+    // Check the version first, so that files from other versions aren't reported as corrupt
+    let value: serde_json::Value = serde_json::from_str(contents).map_err(|e| format!("The file is not valid JSON: {}", e))?;
+    match value.get("version").and_then(|v| v.as_u64()) {
+        Some(version) if version == SAVE_VERSION as u64 => {},
+        Some(version) if version > SAVE_VERSION as u64 => {
+            return Err(format!("The game was saved by a newer version of the program (file version {}, but only up to {} is supported)", version, SAVE_VERSION));
+        },
+        Some(version) => {
+            return Err(format!("The game was saved in an unsupported format (file version {})", version));
+        },
+        None => {
+            return Err("The file is not a saved game (it has no version)".to_owned());
+        }
+    }
+    serde_json::from_value(value).map_err(|e| format!("The saved game is corrupt: {}", e))
+}
+
+/// Restores the settings from a saved game
+/// # Arguments
+/// * `state` - State of the app to update
+/// * `settings` - The saved settings (if these use a custom dictionary that isn't loaded, then the current dictionary is kept, and out-of-range numbers are clamped)
+/// # Returns
+/// Empty `Result` upon success
+/// 
+/// *or String `Err` if the settings are invalid or a lock couldn't be obtained*
+fn restore_settings(state: &AppState, mut settings: CurrentSettings) -> Result<(), String> {
+    if settings.dictionary == DictionaryChoice::Custom && state.custom_dictionary.lock().or(Err("Failed to get lock on custom dictionary!"))?.is_none() {
+        settings.dictionary = *state.dictionary.lock().or(Err("Failed to get lock on dictionary!"))?;
+    }
+    if lock_language_dictionaries(state, settings.language).is_err() {
+        settings.language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    }
+    // Games saved before the settings were validated may have values that are now out of range
+    settings.maximum_words_to_check = settings.maximum_words_to_check.max(MIN_WORDS_TO_CHECK);
+    settings.filter_letters_on_board = settings.filter_letters_on_board.min(MAX_WORD_LENGTH);
+    settings.history_depth = settings.history_depth.max(1);
+    settings.min_word_length = settings.min_word_length.clamp(2, MAX_WORD_LENGTH);
+    settings.board_size = settings.board_size.clamp(MAX_WORD_LENGTH, MAX_BOARD_SIZE);
+    settings.max_width = settings.max_width.map(|width| width.max(settings.min_word_length).min(settings.board_size));
+    settings.max_height = settings.max_height.map(|height| height.max(1).min(settings.board_size));
+    apply_settings(state, &settings)?;
+    // Failing to save the settings shouldn't stop them being used
+    let _ = save_settings(state);
+    Ok(())
+}
+
+/// Saves the current settings to the settings file (if there is one), so that they're restored when the app is next opened
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// Empty `Result` upon success or if there's nowhere to save to
+/// 
+/// *or String `Err` upon failure*
+fn save_settings(state: &AppState) -> Result<(), String> {
+    let settings_path = state.settings_path.lock().or(Err("Failed to get lock on settings path!"))?;
+    match &*settings_path {
+        Some(path) => {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+            }
+            let json = serde_json::to_string(&SettingsFile { version: SETTINGS_VERSION, settings: current_settings(state)? }).map_err(|e| format!("Failed to serialize the settings: {}", e))?;
+            fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+        },
+        None => Ok(())
+    }
+}
+
+/// Parses the contents of a settings file written by `save_settings`
+/// # Arguments
+/// * `contents` - Contents of the file
+/// # Returns
+/// `Result` with the saved settings (any settings added since the file was written have their defaults)
+/// 
+/// *or String `Err` describing why the file couldn't be read*
+fn parse_settings_file(contents: &str) -> Result<CurrentSettings, String> {
+    let value: serde_json::Value = serde_json::from_str(contents).map_err(|e| format!("The settings file is not valid JSON: {}", e))?;
+    match value.get("version").and_then(|v| v.as_u64()) {
+        Some(version) if version == SETTINGS_VERSION as u64 => {},
+        Some(version) => {
+            return Err(format!("The settings were saved in an unsupported format (file version {}, but only {} is supported)", version, SETTINGS_VERSION));
+        },
+        None => {
+            return Err("The file is not a settings file (it has no version)".to_owned());
+        }
+    }
+    let file: SettingsFile = serde_json::from_value(value).map_err(|e| format!("The settings file is corrupt: {}", e))?;
+    Ok(file.settings)
+}
+
+/// Restores the settings from the settings file
+/// # Arguments
+/// * `state` - State of the app to update
+/// * `path` - Path of the settings file
+/// # Returns
+/// Empty `Result` upon success
+/// 
+/// *or String `Err` if the file couldn't be read or is corrupt (in which case the settings are unchanged)*
+fn load_settings(state: &AppState, path: &PathBuf) -> Result<(), String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    restore_settings(state, parse_settings_file(&contents)?)
+}
+
+/// Restores the game automatically saved when the app was last open, along with its settings
+/// # Arguments
+/// * `state` - State of the app to update
+/// * `path` - Path of the autosave file
+/// # Returns
+/// Empty `Result` upon success or if there's no autosave (which just means starting a new game)
+/// 
+/// *or String `Err` if the file couldn't be read or is corrupt (in which case the game isn't restored)*
+fn restore_autosave(state: &AppState, path: &PathBuf) -> Result<(), String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e))
+    };
+    let save = parse_save_file(&contents).map_err(|e| format!("The last game couldn't be restored: {}", e))?;
+    // Failing to restore the settings shouldn't stop the game being restored
+    let _ = restore_settings(state, save.settings);
+    *state.last_game.lock().or(Err("Failed to get lock on last game state!"))? = Some(save.game);
+    Ok(())
+}
+
+/// Gets why the last game couldn't be restored when the app was opened, so that the frontend can show it
+/// # Returns
+/// `Result` with the reason (or `None` if the game was restored, there was nothing to restore, or the reason has already been taken)
+/// 
+/// *or String `Err` if a lock couldn't be obtained*
+#[tauri::command]
+fn take_autosave_error(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    Ok(state.autosave_error.lock().or(Err("Failed to get lock on autosave error!"))?.take())
+}
+
+/// Automatically saves a game to the app data directory (if available), so that it can be restored if the app is closed
+/// # Arguments
+/// * `game` - The game to save
+/// * `state` - Current state of the app
+/// # Returns
+/// Empty `Result` upon success or if there's nowhere to save to
+/// 
+/// *or String `Err` upon failure*
+fn autosave(game: &GameState, state: &AppState) -> Result<(), String> {
+    let autosave_path = state.autosave_path.lock().or(Err("Failed to get lock on autosave path!"))?;
+    match &*autosave_path {
+        Some(path) => {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+            }
+            write_save_file(path, game, state)
+        },
+        None => Ok(())
+    }
+}
+
+/// Saves the current game and settings to a JSON file
+/// # Arguments
+/// * `path` - Path of the file to write
+/// * `state` - Current state of the app
+/// # Returns
+/// Empty `Result` upon success
+/// 
+/// *or String `Err` upon failure*
+#[tauri::command]
+fn save_game(path: String, state: State<'_, AppState>) -> Result<(), String> {
+    let last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    match &*last_game_state {
+        Some(game) => write_save_file(&PathBuf::from(path), game, &state),
+        None => Err("No game has been played yet!".to_owned())
+    }
+}
+
+/// Loads a game and its settings from a JSON file (as written by `save_game`), making it the current game
+/// # Arguments
+/// * `path` - Path of the file to read
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` of `UndoRedo` with the loaded board and hand
+/// 
+/// *or String `Err` upon failure*
+#[tauri::command]
+fn load_game(path: String, state: State<'_, AppState>) -> Result<UndoRedo, String> {
+    read_save_file(&PathBuf::from(path), &state)
+}
+
+/// Gets the current game (such as one restored from when the app was last open)
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` of `UndoRedo` with the current board and hand (both empty if no game has been played)
+/// 
+/// *or String `Err` upon failure*
+#[tauri::command]
+fn get_last_game(state: State<'_, AppState>) -> Result<UndoRedo, String> {
+    let undo_stack = state.undo_stack.lock().or(Err("Failed to get lock on undo stack!"))?;
+    let redo_stack = state.redo_stack.lock().or(Err("Failed to get lock on redo stack!"))?;
+    let last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    match &*last_game_state {
+        Some(game) => {
+            let mut letters = HashMap::new();
+            for (c, n) in UPPERCASE.chars().zip(game.letters) {
+                letters.insert(c, n as u64);
+            }
+            letters.insert(BLANK_CHAR, game.letters[BLANK] as u64);
+            let solution = board_to_vec(&game.board, game.min_col, game.max_col, game.min_row, game.max_row);
+            Ok(UndoRedo { solution, letters, undo_possible: !undo_stack.is_empty(), redo_possible: !redo_stack.is_empty() })
+        },
+        None => {
+            let letters: HashMap<char, u64> = UPPERCASE.chars().chain([BLANK_CHAR]).map(|c| (c, 0)).collect();
+            Ok(UndoRedo { solution: Vec::new(), letters, undo_possible: !undo_stack.is_empty(), redo_possible: !redo_stack.is_empty() })
+        }
+    }
+}
+
+/// Exports the last solved board for pasting elsewhere or saving to a file
+/// # Arguments
+/// * `format` - Either `"text"` for a monospace grid or `"csv"` for comma-separated cells (defaults to `"text"`)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the rendered board
+/// 
+/// *or String `Err` if no game has been played yet or `format` is not recognized*
+#[tauri::command]
+fn export_board(format: Option<String>, state: State<'_, AppState>) -> Result<String, String> {
+    let last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    match &*last_game_state {
+        Some(game) => {
+            match format.as_deref().unwrap_or("text") {
+                "text" => Ok(board_to_string(&game.board, game.min_col, game.max_col, game.min_row, game.max_row)),
+                "csv" => Ok(board_to_csv(&game.board, game.min_col, game.max_col, game.min_row, game.max_row)),
+                other => Err(format!("Unknown export format: {}", other))
+            }
+        },
+        None => Err("No game has been played yet!".to_owned())
+    }
+}
+
+/// Exports the last solved board as an SVG image, highlighting the tiles added since the previous board
+/// # Arguments
+/// * `cell_size` - Width and height of each cell in pixels (defaults to `SVG_CELL_SIZE`)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the SVG document
+/// 
+/// *or String `Err` if no game has been played yet or `cell_size` is zero*
+#[tauri::command]
+fn export_board_svg(cell_size: Option<usize>, state: State<'_, AppState>) -> Result<String, String> {
+    let cell_size = cell_size.unwrap_or(SVG_CELL_SIZE);
+    if cell_size == 0 {
+        return Err("Cell size must be positive".to_owned());
+    }
+    let undo_stack = state.undo_stack.lock().or(Err("Failed to get lock on undo stack!"))?;
+    let last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    match &*last_game_state {
+        Some(game) => {
+            // Only highlight tiles when this board was built on top of a previous one
+            let new_idxs: HashSet<(usize, usize)> = match undo_stack.last() {
+                Some(Some(prev)) => {
+                    let previous_idxs = get_board_overlap(&prev.board, &game.board, prev.min_col, prev.max_col, prev.min_row, prev.max_row, game.min_col, game.max_col, game.min_row, game.max_row);
+                    let mut new_idxs = HashSet::new();
+                    for row in game.min_row..=game.max_row {
+                        for col in game.min_col..=game.max_col {
+                            if !game.board.get_val(row, col).is_empty() && !previous_idxs.contains(&(row, col)) {
+                                new_idxs.insert((row, col));
+                            }
+                        }
+                    }
+                    new_idxs
+                },
+                _ => HashSet::new()
+            };
+            Ok(board_to_svg(&game.board, game.min_col, game.max_col, game.min_row, game.max_row, &new_idxs, cell_size))
+        },
+        None => Err("No game has been played yet!".to_owned())
+    }
+}
+
+/// Excludes words from being played by the solver (or shown as playable words)
+/// # Arguments
+/// * `words` - Words to exclude (case-insensitive)
+/// * `state` - Current state of the app
+/// # Returns
+/// Empty `Result` upon success
+/// 
+/// *or String `Err` upon failure*
+#[tauri::command]
+fn add_excluded_words(words: Vec<String>, state: State<'_, AppState>) -> Result<(), String> {
+    exclude_words(&words, &state)
+}
+
+/// Excludes words from being played by the solver, dropping the `Trie`s built without the previously excluded words
+/// # Arguments
+/// * `words` - Words to exclude (case-insensitive)
+/// * `state` - Current state of the app
+/// # Returns
+/// Empty `Result` upon success
+/// 
+/// *or String `Err` if a lock couldn't be obtained*
+fn exclude_words(words: &[String], state: &AppState) -> Result<(), String> {
+    let mut excluded_words = state.excluded_words.lock().or(Err("Failed to get lock on excluded words!"))?;
+    for word in words {
+        let word_vec = convert_word_to_array(&word.to_ascii_uppercase());
+        if !word_vec.is_empty() {
+            excluded_words.insert(word_vec);
+        }
+    }
+    state.excluded_tries.lock().or(Err("Failed to get lock on excluded word tries!"))?.clear();
+    Ok(())
+}
+
+/// Clears all excluded words
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// Empty `Result` upon success
+/// 
+/// *or String `Err` upon failure*
+#[tauri::command]
+fn clear_excluded_words(state: State<'_, AppState>) -> Result<(), String> {
+    unexclude_words(&state)
+}
+
+/// Clears all excluded words, along with the `Trie`s built without them
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// Empty `Result` upon success
+/// 
+/// *or String `Err` if a lock couldn't be obtained*
+fn unexclude_words(state: &AppState) -> Result<(), String> {
+    let mut excluded_words = state.excluded_words.lock().or(Err("Failed to get lock on excluded words!"))?;
+    excluded_words.clear();
+    state.excluded_tries.lock().or(Err("Failed to get lock on excluded word tries!"))?.clear();
+    Ok(())
+}
+
+/// Gets the excluded words, along with the `Trie` of a dictionary without them (building it if it hasn't been since the excluded words or dictionaries last changed)
+/// # Arguments
+/// * `dictionary` - Which dictionary `dict_to_use` is
+/// * `language` - Which language's dictionary `dict_to_use` is
+/// * `dict_to_use` - The words of the dictionary (see `select_dictionary`)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with a copy of the excluded words and the `Trie` (`None` if no words are excluded, in which case the dictionary's own `Trie` is used)
+/// 
+/// *or String `Err` if a lock couldn't be obtained*
+fn excluded_words_and_trie(dictionary: DictionaryChoice, language: Language, dict_to_use: &[Word], state: &AppState) -> Result<(HashSet<Word>, Option<Arc<Trie>>), String> {
+    // The excluded words stay locked so that they can't change while the `Trie` is built
+    let excluded_words = state.excluded_words.lock().or(Err("Failed to get lock on excluded words!"))?;
+    if excluded_words.is_empty() {
+        return Ok((HashSet::new(), None));
+    }
+    let mut excluded_tries = state.excluded_tries.lock().or(Err("Failed to get lock on excluded word tries!"))?;
+    let trie = excluded_tries.entry((dictionary, language)).or_insert_with(|| Arc::new(Trie::from_words(dict_to_use.iter().filter(|word| !excluded_words.contains(*word)))));
+    Ok((excluded_words.clone(), Some(Arc::clone(trie))))
+}
+
+/// Cancels the in-progress solve, if any
+/// # Arguments
+/// * `state` - Current state of the app
+#[tauri::command]
+fn cancel_solve(state: State<'_, AppState>) {
+    state.stop_solving.store(true, Ordering::Relaxed);
+}
+
+/// Async command executed by the frontend to reset the Banangrams board
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// Empty `Result` upon success
+/// 
+/// *or String `Err` upon failure*
+#[tauri::command]
+async fn reset(state: State<'_, AppState>) -> Result<(), String> {
+    reset_game(&state)
+}
+
+/// Resets the Bananagrams board (this can be done while a solve is running, whose result will then replace the reset board)
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// Empty `Result` upon success
+/// 
+/// *or String `Err` if a lock couldn't be obtained*
+fn reset_game(state: &AppState) -> Result<(), String> {
+    let mut last_game_state = state.last_game.lock().or(Err("Failed to get lock on the last game state"))?;
+    let history_depth = *state.history_depth.lock().or(Err("Failed to get lock on history depth!"))?;
+    let mut undo_stack = state.undo_stack.lock().or(Err("Failed to get lock on undo stack!"))?;
+    let mut redo_stack = state.redo_stack.lock().or(Err("Failed to get lock on redo stack!"))?;
+    let mut bunch = state.bunch.lock().or(Err("Failed to get lock on bunch!"))?;
+    push_undo(&mut undo_stack, last_game_state.clone(), history_depth);
+    redo_stack.clear();
+    *last_game_state = None;
+    *bunch = None;
+    Ok(())
+}
+
+/// Marks a solve as running for as long as it is held, so that only one solve runs at a time (see `AppState::solving`)
+struct SolveInProgress<'a>(&'a AtomicBool);
+
+impl<'a> SolveInProgress<'a> {
+    /// Marks a solve as started
+    /// # Arguments
+    /// * `state` - Current state of the app
+    /// # Returns
+    /// `Result` with the `SolveInProgress`, which marks the solve as finished when dropped
+    /// 
+    /// *or `SolverError` if another solve is already running*
+    fn start(state: &'a AppState) -> Result<SolveInProgress<'a>, SolverError> {
+        if state.solving.swap(true, Ordering::Relaxed) {
+            return Err("A solve is already in progress!".into());
+        }
+        Ok(SolveInProgress(&state.solving))
+    }
+}
+
+impl Drop for SolveInProgress<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Gets the seed to solve with when no seed is given explicitly
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with `DEFAULT_SEED` if the deterministic setting is on, otherwise `None`
+/// 
+/// *or String `Err` if the lock couldn't be obtained*
+fn deterministic_seed(state: &AppState) -> Result<Option<u64>, String> {
+    let deterministic = *state.deterministic.lock().or(Err("Failed to get lock on deterministic!"))?;
+    Ok(if deterministic {Some(DEFAULT_SEED)} else {None})
+}
+
+/// Solves a Bananagrams board for the current game, storing the result in the `state`
+/// # Arguments
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
+/// * `seed` - If given, solve deterministically using this seed (see `order_for_seed`); otherwise solve deterministically with `DEFAULT_SEED` only if that setting is on
+/// * `num_solutions` - Maximum number of distinct solutions to find (see `solve_hand`)
+/// * `seed_count` - If given, instead build from scratch by racing this many randomly seeded solves (see `solve_hand_multiseed`), ignoring `seed`, `num_solutions`, `allow_partial` and `on_partial`
+/// * `allow_partial` - Whether to keep the closest attempt if no board uses the whole hand (see `solve_hand`), storing it as the current game with only the placed letters in its hand
+/// * `required_word` - If given, a word the board must contain (case-insensitive), which is marked in each `Solution`'s words
+/// * `from_scratch` - Whether to build a new board rather than playing off of the current game (which is still replaced by the result)
+/// * `overrides` - Settings to use for this solve instead of the stored ones
+/// * `state` - Current state of the app
+/// * `on_partial` - Callback that is sent the board partway through solving (see `report_partial`)
+/// # Returns
+/// `Result` with at least one `Solution`, best first; only the first is stored as the current game (if the short dictionary couldn't solve the hand and falling back is on, these are from the long dictionary)
+/// 
+/// *or `SolverError` upon failure, not finding a board, or if another solve is already in progress (with the reason indicated by the variant)*
+fn solve_and_store(available_letters: HashMap<String, i64>, seed: Option<u64>, num_solutions: usize, seed_count: Option<usize>, allow_partial: bool, required_word: Option<String>, from_scratch: bool, overrides: &SettingsOverrides, state: &AppState, on_partial: Option<&PartialCallback>) -> Result<Vec<Solution>, SolverError> {
+    let _solving = SolveInProgress::start(state)?;
+    let letters = letters_from_map(&available_letters)?;
+    let required_word = match required_word {
+        Some(word) => {
+            let word = word.trim().to_uppercase();
+            if word.is_empty() || !word.chars().all(|c| letter_index(c).is_some()) {
+                return Err(format!("\"{}\" isn't a word!", word).into());
+            }
+            Some(convert_word_to_array(&word))
+        },
+        None => None
+    };
+    // Racing seeds isn't deterministic, since the result depends on which finishes first
+    let seed = if seed_count.is_some() {None} else {seed.or(deterministic_seed(state)?)};
+    // Check whether a board has been played already (copying it so that the game isn't locked while solving)
+    let previous = if from_scratch {None} else {state.last_game.lock().or(Err("Failed to get lock on last game state"))?.clone()};
+    let settings = overrides.apply(current_settings(state)?)?;
+    let config = SolveConfig { filter_letters_on_board: settings.filter_letters_on_board, max_words_to_check: settings.maximum_words_to_check, threads: None, max_solve_time_ms: settings.max_solve_time_ms, use_frequency_ordering: settings.use_frequency_ordering, seed, seed_count, num_solutions, solution_preference: settings.solution_preference, allow_partial, board_size: settings.board_size, max_width: settings.max_width, max_height: settings.max_height, min_word_length: settings.min_word_length, required_word, recenter: true };
+    let custom_dictionary = get_custom_dictionary(state)?;
+    let language_dictionaries = lock_language_dictionaries(state, settings.language)?;
+    let (dict_to_use, dictionary_trie, dictionary_index) = select_dictionary(settings.dictionary, settings.language, &custom_dictionary, &language_dictionaries)?;
+    let (excluded_words, excluded_trie) = excluded_words_and_trie(settings.dictionary, settings.language, dict_to_use, state)?;
+    let dictionary_trie = excluded_trie.as_deref().unwrap_or(dictionary_trie);
+    // Prepare for threading/early termination using `AtomicBool` (resetting it so that a stale cancel doesn't stop this solve)
+    let stop = Arc::clone(&state.stop_solving);
+    stop.store(false, Ordering::Relaxed);
+    let (solved, used_long_dictionary) = match solve_with(dict_to_use, dictionary_trie, dictionary_index, &previous, letters, &config, &excluded_words, &stop, on_partial) {
+        // Only retry when the short dictionary ran out of words, not when the solve was cancelled, timed out or failed
+        Err(SolverError::NoSolution | SolverError::LettersLeftOver(_) | SolverError::NoPlayableWords | SolverError::StrandedLetters(_) | SolverError::RequiredWordUnplayable(_) | SolverError::ExceedsMaxSpan { .. }) if settings.fallback_to_long_dictionary && settings.dictionary == DictionaryChoice::Short => {
+            let (long_words, long_trie, long_index) = language_words(settings.language, &language_dictionaries)?.1;
+            let excluded_long_trie = excluded_words_and_trie(DictionaryChoice::Long, settings.language, long_words, state)?.1;
+            let long_trie = excluded_long_trie.as_deref().unwrap_or(long_trie);
+            stop.store(false, Ordering::Relaxed);
+            // `solve_with` starts its own count of words checked and its own deadline, so the retry isn't starved by the failed solve
+            (solve_with(long_words, long_trie, long_index, &previous, letters, &config, &excluded_words, &stop, on_partial)?, true)
+        },
+        result => (result?, false)
+    };
+    // Store the result in the `State` (unless nothing changed) and return the result to the frontend
+    if solved.method != SolveMethod::Unchanged {
+        let mut last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+        let history_depth = *state.history_depth.lock().or(Err("Failed to get lock on history depth!"))?;
+        let mut undo_stack = state.undo_stack.lock().or(Err("Failed to get lock on undo stack!"))?;
+        let mut redo_stack = state.redo_stack.lock().or(Err("Failed to get lock on redo stack!"))?;
+        push_undo(&mut undo_stack, last_game_state.clone(), history_depth);
+        redo_stack.clear();
+        // Failing to autosave shouldn't fail the solve itself
+        let _ = autosave(&solved.game, state);
+        record_history(&solved.game, state)?;
+        *last_game_state = Some(solved.game.clone());
+    }
+    let (_, short_trie, _) = language_words(settings.language, &language_dictionaries)?.0;
+    let mut solutions = solved.solutions();
+    for solution in solutions.iter_mut() {
+        solution.mark_obscure_words(short_trie);
+        if let Some(ref required_word) = config.required_word {
+            solution.mark_required_word(required_word);
+        }
+        if used_long_dictionary {
+            solution.used_long_dictionary = true;
+            solution.long_only_words = solution.words.iter().filter(|played| played.obscure).map(|played| played.word.clone()).collect();
+            solution.long_only_words.sort();
+            solution.long_only_words.dedup();
+        }
+    }
+    Ok(solutions)
+}
+
+/// Async command executed by the frontend to solve a Bananagrams board
+/// # Arguments
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
+/// * `seed` - If given, solve deterministically so that the same hand and seed always give the same board (this uses a single thread, so is usually slower)
+/// * `num_solutions` - Maximum number of distinct solutions to return (defaults to 1); more than one can only be found when the board is built from scratch, and looking for them is slower
+/// * `allow_partial` - Whether to return the board that placed the most tiles, with the rest in `Solution::unplaced`, if no board uses the whole hand (defaults to `false`)
+/// * `required_word` - If given, a word from the current dictionary that the board must contain (marked as `required` in each `Solution`'s words)
+/// * `overrides` - Settings to use for this solve only, instead of the stored ones (which aren't changed)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with up to `num_solutions` `Solution`s, sorted by the area of the board and then by the number of words; the first is stored as the current game
+/// 
+/// *or `SolverError` upon failure or not finding a board (with the reason indicated by the variant)*
+#[tauri::command]
+async fn play_bananagrams(available_letters: HashMap<String, i64>, seed: Option<u64>, num_solutions: Option<usize>, allow_partial: Option<bool>, required_word: Option<String>, overrides: Option<SettingsOverrides>, state: State<'_, AppState>) -> Result<Vec<Solution>, SolverError> {
+    let num_solutions = num_solutions.unwrap_or(1);
+    if num_solutions == 0 {
+        return Err("The number of solutions must be greater than 0".into());
+    }
+    solve_and_store(available_letters, seed, num_solutions, None, allow_partial.unwrap_or(false), required_word, false, &overrides.unwrap_or_default(), &state, None)
+}
+
+/// Async command executed by the frontend to solve a Bananagrams board, emitting a `"partial-solution"` event with a `PartialSolution` each time a word is played at the top level of the search
+/// # Arguments
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
+/// * `seed` - If given, solve deterministically (see `play_bananagrams`)
+/// * `window` - The window to send the events to
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` as a `Solution` with a vector of vector of chars of the solution and the elapsed time
+/// 
+/// *or `SolverError` upon failure or not finding a board (with the reason indicated by the variant)*
+#[tauri::command]
+async fn play_bananagrams_stream(available_letters: HashMap<String, i64>, seed: Option<u64>, window: tauri::Window, state: State<'_, AppState>) -> Result<Solution, SolverError> {
+    let on_partial: PartialCallback = Arc::new(move |partial| {
+        // A missed update isn't a problem since the final solution is still returned
+        let _ = window.emit("partial-solution", partial);
+    });
+    Ok(solve_and_store(available_letters, seed, 1, None, false, None, false, &SettingsOverrides::default(), &state, Some(&on_partial))?.remove(0))
+}
+
+/// Async command executed by the frontend to solve a Bananagrams board from scratch, racing several solves that each try the words in a different random order
+/// # Arguments
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
+/// * `seed_count` - How many differently ordered solves to race (each uses its own thread)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` as a `Solution` from whichever solve finished first (this is stored as the current game)
+/// 
+/// *or `SolverError` upon failure or not finding a board (with the reason indicated by the variant)*
+#[tauri::command]
+async fn play_bananagrams_multiseed(available_letters: HashMap<String, i64>, seed_count: usize, state: State<'_, AppState>) -> Result<Solution, SolverError> {
+    if seed_count == 0 {
+        return Err("The number of seeds must be greater than 0".into());
+    }
+    Ok(solve_and_store(available_letters, None, 1, Some(seed_count), false, None, false, &SettingsOverrides::default(), &state, None)?.remove(0))
+}
+
+/// Async command executed by the frontend to solve a Bananagrams board from scratch, never playing off of the current game (some prefer a fresh board to one built up over several peels)
+/// # Arguments
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` as a `Solution` with a newly built board (this replaces the current game)
+/// 
+/// *or `SolverError` upon failure or not finding a board (with the reason indicated by the variant)*
+#[tauri::command]
+async fn play_bananagrams_fresh(available_letters: HashMap<String, i64>, state: State<'_, AppState>) -> Result<Solution, SolverError> {
+    Ok(solve_and_store(available_letters, None, 1, None, false, None, true, &SettingsOverrides::default(), &state, None)?.remove(0))
+}
+
+/// Async command executed by the frontend to solve a Bananagrams board, also returning statistics of the search (to help choose a maximum number of words to check)
+/// # Arguments
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
+/// * `seed` - If given, solve deterministically (see `play_bananagrams`)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` as a `SolutionWithStats` with the `Solution` (which is stored as the current game) and the `SolverStats` of every search run to find it
+/// 
+/// *or `SolverError` upon failure or not finding a board (with the reason indicated by the variant)*
+#[tauri::command]
+async fn play_bananagrams_with_stats(available_letters: HashMap<String, i64>, seed: Option<u64>, state: State<'_, AppState>) -> Result<SolutionWithStats, SolverError> {
+    // Discard anything left over from an earlier solve that failed on this thread
+    take_solver_stats();
+    let solution = solve_and_store(available_letters, seed, 1, None, false, None, false, &SettingsOverrides::default(), &state, None)?.remove(0);
+    Ok(SolutionWithStats { solution, stats: take_solver_stats() })
+}
+
+/// Finds the first valid placement of any of the given words on a board, without playing any further words
+/// # Arguments
+/// * `board` - The board to play on (left unchanged)
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `valid_words_vec` - The words to try, in order
+/// * `valid_words_trie` - `Trie` of the dictionary that the words formed on the board must be in
+/// * `letters` - The letters in the hand that aren't on the board
+/// * `letters_on_board` - The number of each letter on the board
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// # Returns
+/// `Option` with the `HintResult` of the first valid placement, or `None` if no word can be played
+fn first_valid_play(board: &mut Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words_vec: &[&Word], valid_words_trie: &Trie, letters: &Letters, letters_on_board: &mut Letters, min_word_length: usize) -> Option<HintResult> {
+    for word in valid_words_vec {
+        // Try across and then down, over the same positions as `try_play_word_horizontal` and `try_play_word_vertically`
+        for row_idx in min_row.saturating_sub(1)..=(board.size()-1).min(max_row+1) {
+            let (leftmost_col, rightmost_col) = get_col_limits(board, row_idx, min_col, max_col);
+            for col_idx in leftmost_col.saturating_sub(word.len())..=(board.size()-1).min(rightmost_col+1) {
+                let res = board.play_word(word, row_idx, col_idx, Direction::Horizontal, letters, letters_on_board);
+                let (new_min_col, new_max_col, new_min_row, new_max_row) = (min_col.min(col_idx), max_col.max(col_idx+word.len()-1), min_row.min(row_idx), max_row.max(row_idx));
+                let valid = res.0 && is_board_valid_horizontal(board, new_min_col, new_max_col, new_min_row, new_max_row, row_idx, col_idx, col_idx+word.len()-1, valid_words_trie, min_word_length);
+                board.undo_play(&res.1, letters_on_board);
+                if valid {
+                    return Some(HintResult { word: convert_array_to_word(word), direction: Direction::Horizontal, row: row_idx, col: col_idx });
+                }
+            }
+        }
+        for col_idx in min_col.saturating_sub(1)..=(board.size()-1).min(max_col+1) {
+            let (uppermost_row, lowermost_row) = get_row_limits(board, col_idx, min_row, max_row);
+            for row_idx in uppermost_row.saturating_sub(word.len())..=(board.size()-1).min(lowermost_row+1) {
+                let res = board.play_word(word, row_idx, col_idx, Direction::Vertical, letters, letters_on_board);
+                let (new_min_col, new_max_col, new_min_row, new_max_row) = (min_col.min(col_idx), max_col.max(col_idx), min_row.min(row_idx), max_row.max(row_idx+word.len()-1));
+                let valid = res.0 && is_board_valid_vertical(board, new_min_col, new_max_col, new_min_row, new_max_row, row_idx, row_idx+word.len()-1, col_idx, valid_words_trie, min_word_length);
+                board.undo_play(&res.1, letters_on_board);
+                if valid {
+                    return Some(HintResult { word: convert_array_to_word(word), direction: Direction::Vertical, row: row_idx, col: col_idx });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Finds the next word to play, either off of the previous board or as the first word of a new one
+/// # Arguments
+/// * `letters` - The number of each letter (and blanks) in the hand, including those already on the previous board
+/// * `previous` - The previous game state (if any)
+/// * `dict_to_use` - Dictionary of words to play, sorted by descending length
+/// * `dictionary_trie` - `Trie` of `dict_to_use`
+/// * `excluded_words` - Words that must not be played
+/// * `filter_letters_on_board` - Maximum number of letters currently on the board that can be used in the word
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// * `board_size` - Number of rows/columns in a new board
+/// # Returns
+/// `Result` with the `HintResult` of the longest word that can be played (in the middle of the board if there is no previous board, or if the hand no longer includes its tiles)
+/// 
+/// *or String `Err` if every letter in the hand is already on the board or no word can be played*
+fn next_word_hint(letters: Letters, previous: &Option<GameState>, dict_to_use: &[Word], dictionary_trie: &Trie, excluded_words: &HashSet<Word>, filter_letters_on_board: usize, min_word_length: usize, board_size: usize) -> Result<HintResult, String> {
+    if let Some(game) = previous {
+        let mut hand_letters = letters;
+        let mut letters_on_board = [0usize; 27];
+        let mut hand_has_board_tiles = true;
+        for row in game.min_row..=game.max_row {
+            for col in game.min_col..=game.max_col {
+                if let Some(letter) = game.board.get_val(row, col).letter() {
+                    let in_hand = &mut hand_letters[if game.board.is_blank(row, col) {BLANK} else {letter}];
+                    hand_has_board_tiles &= *in_hand > 0;
+                    *in_hand = in_hand.saturating_sub(1);
+                    letters_on_board[letter] += 1;
+                }
+            }
+        }
+        // Otherwise solving would start a new board, so the hint is the first word of that instead
+        if hand_has_board_tiles {
+            if hand_letters.iter().all(|count| *count == 0) {
+                return Err("Every letter in the hand is already on the board!".to_owned());
+            }
+            let valid_words_vec: Vec<&Word> = dict_to_use.iter().filter(|w| w.len() >= min_word_length && !excluded_words.contains(*w) && check_filter_after_play_later(hand_letters, letters_on_board, w, filter_letters_on_board, Some(dictionary_trie))).collect();
+            let mut board = game.board.clone();
+            return first_valid_play(&mut board, game.min_col, game.max_col, game.min_row, game.max_row, &valid_words_vec, dictionary_trie, &hand_letters, &mut letters_on_board, min_word_length).ok_or("No word can be played off of the board with these letters!".to_owned());
+        }
+    }
+    let word = dict_to_use.iter().find(|w| (min_word_length..=board_size).contains(&w.len()) && !excluded_words.contains(*w) && is_makeable(w, &letters)).ok_or("No word can be made from these letters!")?;
+    Ok(HintResult { word: convert_array_to_word(word), direction: Direction::Horizontal, row: board_size/2, col: board_size/2 - word.len()/2 })
+}
+
+/// Async command executed by the frontend to get a hint of the next word to play, rather than solving the whole board
+/// # Arguments
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the `HintResult` of the next word to play off of the current game's board (or the first word, if there is no board); the game isn't changed
+/// 
+/// *or String `Err` if no word can be played or upon failure*
+#[tauri::command]
+async fn play_bananagrams_hint(available_letters: HashMap<String, i64>, state: State<'_, AppState>) -> Result<HintResult, String> {
+    let letters = letters_from_map(&available_letters)?;
+    let previous = state.last_game.lock().or(Err("Failed to get lock on last game state"))?.clone();
+    let settings = current_settings(&state)?;
+    let custom_dictionary = get_custom_dictionary(&state)?;
+    let language_dictionaries = lock_language_dictionaries(&state, settings.language)?;
+    let (dict_to_use, dictionary_trie, _) = select_dictionary(settings.dictionary, settings.language, &custom_dictionary, &language_dictionaries)?;
+    let excluded_words = state.excluded_words.lock().or(Err("Failed to get lock on excluded words!"))?;
+    next_word_hint(letters, &previous, dict_to_use, dictionary_trie, &excluded_words, settings.filter_letters_on_board, settings.min_word_length, settings.board_size)
+}
+
+/// Simulates a full single-player game, peeling one tile at a time until the bunch is empty
+/// 
+/// When a hand can't be solved, its highest-scoring letter is dumped in exchange for three tiles from the bunch. This uses the current settings and tile set (without blanks), but does not affect the current game.
+/// Cancelling (see `cancel_solve`) is checked between peels.
+/// # Arguments
+/// * `seed` - Seed for shuffling the bunch (the same seed and settings will play the same game)
+/// * `starting_tiles` - Number of tiles to start with
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with a `GameSimulation` summarizing the game
+/// 
+/// *or String `Err` if `starting_tiles` is invalid, the simulation was cancelled, or a hand could not be solved with too few tiles left to dump*
+fn simulate(seed: u64, starting_tiles: usize, state: &AppState) -> Result<GameSimulation, String> {
+    let mut bunch: Vec<usize> = selected_tile_set("tile set", state)?.tiles(&language_tiles(state)?).iter().enumerate().flat_map(|(letter, count)| std::iter::repeat_n(letter, *count as usize)).collect();
+    if starting_tiles == 0 || starting_tiles > bunch.len() {
+        return Err(format!("The number of starting tiles must be between 1 and {}", bunch.len()));
+    }
+    let max_words_to_check = *state.maximum_words_to_check.lock().or(Err("Failed to get lock on maximum words!"))?;
+    let max_solve_time_ms = *state.max_solve_time_ms.lock().or(Err("Failed to get lock on maximum solve time!"))?;
+    let use_frequency_ordering = *state.use_frequency_ordering.lock().or(Err("Failed to get lock on frequency ordering!"))?;
+    let filter_letters_on_board = *state.filter_letters_on_board.lock().or(Err("Failed to get lock on maximum board letters!"))?;
+    let min_word_length = *state.min_word_length.lock().or(Err("Failed to get lock on minimum word length!"))?;
+    let board_size = *state.board_size.lock().or(Err("Failed to get lock on board size!"))?;
+    let dictionary = *state.dictionary.lock().or(Err("Failed to get lock on dictionary!"))?;
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let custom_dictionary = get_custom_dictionary(state)?;
+    let language_dictionaries = lock_language_dictionaries(state, language)?;
+    let (dict_to_use, dictionary_trie, dictionary_index) = select_dictionary(dictionary, language, &custom_dictionary, &language_dictionaries)?;
+    let (excluded_words, excluded_trie) = excluded_words_and_trie(dictionary, language, dict_to_use, state)?;
+    let dictionary_trie = excluded_trie.as_deref().unwrap_or(dictionary_trie);
+    state.stop_solving.store(false, Ordering::Relaxed);
+    let solve_seed = if *state.deterministic.lock().or(Err("Failed to get lock on deterministic!"))? {Some(seed)} else {None};
+    let config = SolveConfig { filter_letters_on_board, max_words_to_check, max_solve_time_ms, use_frequency_ordering, seed: solve_seed, board_size, min_word_length, ..SolveConfig::default() };
+    let mut rng = StdRng::seed_from_u64(seed);
+    bunch.shuffle(&mut rng);
+    let mut letters = [0usize; 27];
+    for letter in bunch.drain(bunch.len()-starting_tiles..) {
+        letters[letter] += 1;
+    }
+    let mut previous: Option<GameState> = None;
+    let mut elapsed: Vec<u128> = Vec::new();
+    let mut rebuilds = 0;
+    let mut dumps = 0;
+    loop {
+        if state.stop_solving.load(Ordering::Relaxed) {
+            return Err(format!("Cancelled after {} peels", elapsed.len().saturating_sub(1)));
         }
-        // Wait for all the threads
-        for handle in handles {
-            let _res = handle.join();
+        let now = Instant::now();
+        // Each solve gets its own flag, since finding a solution sets it
+        let stop = Arc::new(AtomicBool::new(false));
+        let deadline = get_deadline(now, config.max_solve_time_ms);
+        let solved = match solve_hand(letters, &previous, dict_to_use, dictionary_trie, dictionary_index, &excluded_words, &config, deadline, &stop, None) {
+            Ok(solved) => solved,
+            Err(e) => {
+                if bunch.len() < 3 {
+                    return Err(format!("Failed after {} peels: {}", elapsed.len().saturating_sub(1), e));
+                }
+                // Dump the hardest letter to play and try again
+                let dumped = (0..26).filter(|l| letters[*l] > 0).max_by_key(|l| LETTER_VALUES[*l]).ok_or(e)?;
+                letters[dumped] -= 1;
+                for letter in bunch.drain(bunch.len()-3..) {
+                    letters[letter] += 1;
+                }
+                let idx = rng.gen_range(0..=bunch.len());
+                bunch.insert(idx, dumped);
+                dumps += 1;
+                continue;
+            }
+        };
+        elapsed.push(now.elapsed().as_millis());
+        // The first solve is always from scratch, so it isn't a rebuild
+        if solved.method == SolveMethod::Scratch && previous.is_some() {
+            rebuilds += 1;
         }
-    });
-    // If we're done, store the result in the `State` and return the result to the frontend
-    let ret: std::sync::MutexGuard<'_, Vec<(Vec<Vec<String>>, Board, usize, usize, usize, usize)>>;
-    match ret_val.lock() {
-        Ok(locked) => {
-            ret = locked;
+        let (board, min_col, max_col, min_row, max_row) = solved.board;
+        debug_assert!(is_connected(&board, min_col, max_col, min_row, max_row, &Vec::new()), "Solved board is split into more than one group of tiles");
+        previous = Some(GameState { board, min_col, max_col, min_row, max_row, letters });
+        // Peel
+        match bunch.pop() {
+            Some(letter) => letters[letter] += 1,
+            None => break
+        }
+    }
+    let last = previous.expect("At least one hand was solved");
+    Ok(GameSimulation { elapsed, rebuilds, dumps, board: board_to_vec(&last.board, last.min_col, last.max_col, last.min_row, last.max_row) })
+}
+
+/// Async command executed by the frontend to simulate a full single-player game (see `simulate`)
+/// # Arguments
+/// * `seed` - Seed for shuffling the bunch (the same seed and settings will play the same game)
+/// * `starting_tiles` - Number of tiles to start with
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with a `GameSimulation` summarizing the game
+/// 
+/// *or String `Err` upon failure (see `simulate`)*
+#[tauri::command]
+async fn simulate_game(seed: u64, starting_tiles: usize, state: State<'_, AppState>) -> Result<GameSimulation, String> {
+    simulate(seed, starting_tiles, &state)
+}
+
+/// Checks whether a board laid out by the user is a complete, valid solution for a hand
+/// # Arguments
+/// * `grid` - Rows of the board (see `board_from_vec`)
+/// * `letters` - The number of each letter in the hand
+/// * `valid_words_trie` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// * `board_size` - Number of rows/columns in the board
+/// # Returns
+/// Empty `Result` if the board is valid
+/// 
+/// *or String `Err` explaining why the board is invalid*
+fn check_solution(grid: &[Vec<String>], letters: &Letters, valid_words_trie: &Trie, min_word_length: usize, board_size: usize) -> Result<(), String> {
+    let (board, min_col, max_col, min_row, max_row) = board_from_vec(grid, board_size)?;
+    // The tiles on the board must be exactly those in the hand
+    let mut letters_on_board = [0usize; 27];
+    for row in min_row..=max_row {
+        for col in min_col..=max_col {
+            if let Some(letter) = board.get_val(row, col).letter() {
+                letters_on_board[if board.is_blank(row, col) {BLANK} else {letter}] += 1;
+            }
+        }
+    }
+    let mismatches: Vec<String> = (0..27).filter(|i| letters_on_board[*i] != letters[*i]).map(|i| {
+        let c = if i == BLANK {BLANK_CHAR} else {(i as u8+65) as char};
+        format!("{} on the board but {} in the hand for {}", letters_on_board[i], letters[i], c)
+    }).collect();
+    if !mismatches.is_empty() {
+        return Err(format!("The tiles don't match the hand: {}", mismatches.join(", ")));
+    }
+    if !is_connected(&board, min_col, max_col, min_row, max_row, &Vec::new()) {
+        return Err("The board must be a single connected group of tiles!".to_owned());
+    }
+    if !is_board_valid_full(&board, min_col, max_col, min_row, max_row, valid_words_trie, min_word_length) {
+        let invalid: Vec<String> = board.iter_words(min_col, max_col, min_row, max_row).filter(|(word, ..)| !valid_words_trie.contains(word)).map(|(word, ..)| convert_array_to_word(&word)).collect();
+        if !invalid.is_empty() {
+            return Err(format!("Not in the dictionary: {}", invalid.join(", ")));
+        }
+        let short: Vec<String> = board.iter_words(min_col, max_col, min_row, max_row).filter(|(word, ..)| word.len() < min_word_length).map(|(word, ..)| convert_array_to_word(&word)).collect();
+        return Err(format!("Shorter than the minimum word length of {}: {}", min_word_length, short.join(", ")));
+    }
+    Ok(())
+}
+
+/// Command executed by the frontend to check whether a board laid out by the user is a valid solution
+/// # Arguments
+/// * `board_layout` - Rows of the board, with empty cells as `" "` (see `board_from_vec`)
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter in the hand
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with `true` if the board uses exactly the hand, is connected, and contains only valid words
+/// 
+/// *or String `Err` explaining why the board is invalid*
+#[tauri::command]
+fn validate_solution(board_layout: Vec<Vec<String>>, available_letters: HashMap<String, i64>, state: State<'_, AppState>) -> Result<bool, String> {
+    let letters = letters_from_map(&available_letters)?;
+    let board_size = *state.board_size.lock().or(Err("Failed to get lock on board size!"))?;
+    let dictionary = *state.dictionary.lock().or(Err("Failed to get lock on dictionary!"))?;
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let custom_dictionary = get_custom_dictionary(&state)?;
+    let language_dictionaries = lock_language_dictionaries(&state, language)?;
+    let (_, valid_words_trie, _) = select_dictionary(dictionary, language, &custom_dictionary, &language_dictionaries)?;
+    let min_word_length = *state.min_word_length.lock().or(Err("Failed to get lock on minimum word length!"))?;
+    check_solution(&board_layout, &letters, valid_words_trie, min_word_length, board_size)?;
+    Ok(true)
+}
+
+/// Extends a board the user has already laid out, making it the current game
+/// # Arguments
+/// * `board` - Rows of the existing board (see `board_from_vec`)
+/// * `hand_letters` - The number of each letter (and blanks) in the hand, *not* including those on `board`
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` as a `Solution` with the extended board (the stored hand includes the tiles from `board`, so later peels should too)
+/// 
+/// *or `SolverError` if `board` is invalid or no solution was found (with the reason indicated by the variant)*
+fn solve_from_board(board: &[Vec<String>], hand_letters: Letters, state: &AppState) -> Result<Solution, SolverError> {
+    let now = Instant::now();
+    let _solving = SolveInProgress::start(state)?;
+    let board_size = *state.board_size.lock().or(Err("Failed to get lock on board size!"))?;
+    let max_words_to_check = *state.maximum_words_to_check.lock().or(Err("Failed to get lock on maximum words!"))?;
+    let max_solve_time_ms = *state.max_solve_time_ms.lock().or(Err("Failed to get lock on maximum solve time!"))?;
+    let use_frequency_ordering = *state.use_frequency_ordering.lock().or(Err("Failed to get lock on frequency ordering!"))?;
+    let filter_letters_on_board = *state.filter_letters_on_board.lock().or(Err("Failed to get lock on maximum board letters!"))?;
+    let min_word_length = *state.min_word_length.lock().or(Err("Failed to get lock on minimum word length!"))?;
+    let seed = deterministic_seed(state)?;
+    let config = SolveConfig { filter_letters_on_board, max_words_to_check, max_solve_time_ms, use_frequency_ordering, seed, board_size, min_word_length, ..SolveConfig::default() };
+    let (existing_board, min_col, max_col, min_row, max_row) = board_from_vec(board, board_size)?;
+    let dictionary = *state.dictionary.lock().or(Err("Failed to get lock on dictionary!"))?;
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let custom_dictionary = get_custom_dictionary(state)?;
+    let language_dictionaries = lock_language_dictionaries(state, language)?;
+    let (dict_to_use, valid_words_trie, dictionary_index) = select_dictionary(dictionary, language, &custom_dictionary, &language_dictionaries)?;
+    let (excluded_words, excluded_trie) = excluded_words_and_trie(dictionary, language, dict_to_use, state)?;
+    // Check that the existing board is itself valid
+    if !is_connected(&existing_board, min_col, max_col, min_row, max_row, &Vec::new()) {
+        return Err("The board must be a single connected group of tiles!".into());
+    }
+    for played_word in get_words_on_board(&existing_board, min_col, max_col, min_row, max_row) {
+        if !valid_words_trie.contains(&convert_word_to_array(&played_word.word)) {
+            return Err(format!("{} is not in the dictionary!", played_word.word).into());
+        }
+    }
+    // The solver works with the whole hand, including the tiles already on the board
+    let mut letters = hand_letters;
+    for row in min_row..=max_row {
+        for col in min_col..=max_col {
+            if let Some(letter) = existing_board.get_val(row, col).letter() {
+                letters[if existing_board.is_blank(row, col) {BLANK} else {letter}] += 1;
+            }
+        }
+    }
+    let result = if hand_letters.iter().all(|count| *count == 0) {
+        // Nothing to add, so the board is already a solution
+        (existing_board.clone(), min_col, max_col, min_row, max_row)
+    }
+    else {
+        let stop = Arc::clone(&state.stop_solving);
+        stop.store(false, Ordering::Relaxed);
+        let deadline = get_deadline(now, config.max_solve_time_ms);
+        // The words added can't form any excluded words either
+        match play_existing(&existing_board, min_col, max_col, min_row, max_row, &letters, excluded_trie.as_deref().unwrap_or(valid_words_trie), dictionary_index, dict_to_use, &excluded_words, &config, deadline, &stop, None) {
+            Some(result) => result,
+            None => {
+                if past_deadline(deadline) {
+                    return Err(SolverError::Timeout);
+                }
+                if stop.load(Ordering::Relaxed) {
+                    return Err(SolverError::Cancelled);
+                }
+                return Err(SolverError::NoSolution);
+            }
+        }
+    };
+    let previous_idxs = get_board_overlap(&existing_board, &result.0, min_col, max_col, min_row, max_row, result.1, result.2, result.3, result.4);
+    // The game is only locked once the solve is done, so that it can be reset in the meantime
+    let mut last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    let history_depth = *state.history_depth.lock().or(Err("Failed to get lock on history depth!"))?;
+    let mut undo_stack = state.undo_stack.lock().or(Err("Failed to get lock on undo stack!"))?;
+    let mut redo_stack = state.redo_stack.lock().or(Err("Failed to get lock on redo stack!"))?;
+    push_undo(&mut undo_stack, last_game_state.clone(), history_depth);
+    redo_stack.clear();
+    let game = GameState { board: result.0.clone(), min_col: result.1, max_col: result.2, min_row: result.3, max_row: result.4, letters };
+    let _ = autosave(&game, state);
+    record_history(&game, state)?;
+    *last_game_state = Some(game);
+    let mut solution = Solution { board: board_to_vec(&result.0, result.1, result.2, result.3, result.4), carried_over: carried_over_to_vec(result.1, result.2, result.3, result.4, &previous_idxs), min_row: result.3, min_col: result.1, max_row: result.4, max_col: result.2, words: get_words_on_board(&result.0, result.1, result.2, result.3, result.4), score: score_board(&result.0, result.1, result.2, result.3, result.4), solution_score: score_solution(&result.0, result.1, result.2, result.3, result.4), unplaced: Vec::new(), elapsed: now.elapsed().as_millis(), deterministic: seed.is_some(), used_long_dictionary: false, long_only_words: Vec::new() };
+    solution.mark_obscure_words(language_words(language, &language_dictionaries)?.0.1);
+    Ok(solution)
+}
+
+/// Async command executed by the frontend to extend a board the user has already laid out
+/// # Arguments
+/// * `board` - Rows of the existing board (see `board_from_vec`)
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter in the hand, *not* including those on `board`
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` as a `Solution` with the extended board (see `solve_from_board`)
+/// 
+/// *or `SolverError` if `board` is invalid or no solution was found (with the reason indicated by the variant)*
+#[tauri::command]
+async fn play_from_board(board: Vec<Vec<String>>, available_letters: HashMap<String, i64>, state: State<'_, AppState>) -> Result<Solution, SolverError> {
+    solve_from_board(&board, letters_from_map(&available_letters)?, &state)
+}
+
+/// Places a word on a board, using tiles from the hand for any letters not already on the board
+/// # Arguments
+/// * `previous` - The game to place the word on (if `None`, then the word is placed on an empty board as the first word)
+/// * `word` - The word to place
+/// * `row` - Row of the word's first letter
+/// * `col` - Column of the word's first letter
+/// * `direction` - The `Direction` to place the word in
+/// * `hand` - The number of each letter (and blanks) in the hand, *not* including those on the board
+/// * `valid_words_trie` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of the words formed on the board
+/// * `board_size` - Number of rows/columns in the board (only used if there is no previous game)
+/// # Returns
+/// `Result` with the new `GameState`, whose letters include the tiles taken from `hand`
+///
+/// *or `PlacementError` with why the word can't be placed there*
+fn place_word_on_board(previous: Option<&GameState>, word: &Word, row: usize, col: usize, direction: Direction, hand: &Letters, valid_words_trie: &Trie, min_word_length: usize, board_size: usize) -> Result<GameState, PlacementError> {
+    let (mut board, mut letters) = match previous {
+        Some(game) => (game.board.clone(), game.letters),
+        None => (Board::new(board_size), [0usize; 27])
+    };
+    let cells: Vec<(usize, usize)> = match direction {
+        Direction::Horizontal if col+word.len() <= board.size() && row < board.size() => (col..col+word.len()).map(|c| (row, c)).collect(),
+        Direction::Vertical if row+word.len() <= board.size() && col < board.size() => (row..row+word.len()).map(|r| (r, col)).collect(),
+        _ => return Err(PlacementError::OutOfBounds)
+    };
+    for (&(r, c), letter) in cells.iter().zip(word) {
+        if board.get_val(r, c).letter().is_some_and(|existing| existing != *letter) {
+            return Err(PlacementError::ConflictingTile(r, c));
+        }
+    }
+    if cells.iter().all(|(r, c)| !board.get_val(*r, *c).is_empty()) {
+        return Err(PlacementError::NothingPlaced);
+    }
+    let remaining = match previous {
+        Some(_) => {
+            let mut letters_on_board = [0usize; 27];
+            match board.play_word(word, row, col, direction, hand, &mut letters_on_board) {
+                (_, _, _, LetterUsage::Overused(letter)) => return Err(PlacementError::OverusedLetter((letter as u8+65) as char)),
+                (false, _, _, _) => return Err(PlacementError::Disconnected),
+                (true, _, remaining, _) => remaining
+            }
         },
-        _ => {
-            return Err("Failed to get lock on shared ret_val when checking return".to_owned());
+        None => {
+            // The first word doesn't need to border anything, so it's placed directly
+            let mut remaining = *hand;
+            for (&(r, c), &letter) in cells.iter().zip(word) {
+                if remaining[letter] > 0 {
+                    board.set_val(r, c, letter);
+                    remaining[letter] -= 1;
+                }
+                else if remaining[BLANK] > 0 {
+                    board.set_blank(r, c, letter);
+                    remaining[BLANK] -= 1;
+                }
+                else {
+                    return Err(PlacementError::OverusedLetter((letter as u8+65) as char));
+                }
+            }
+            remaining
         }
+    };
+    let (first, last) = (cells[0], cells[cells.len()-1]);
+    let (min_col, max_col, min_row, max_row) = match previous {
+        Some(game) => (game.min_col.min(first.1), game.max_col.max(last.1), game.min_row.min(first.0), game.max_row.max(last.0)),
+        None => (first.1, last.1, first.0, last.0)
+    };
+    let is_valid = match direction {
+        Direction::Horizontal => is_board_valid_horizontal(&board, min_col, max_col, min_row, max_row, row, col, last.1, valid_words_trie, min_word_length),
+        _ => is_board_valid_vertical(&board, min_col, max_col, min_row, max_row, row, last.0, col, valid_words_trie, min_word_length)
+    };
+    if !is_valid {
+        // The rest of the board was already valid, so the invalid words are the ones formed by the placement
+        return Err(PlacementError::InvalidWords(get_invalid_words(&board, min_col, max_col, min_row, max_row, valid_words_trie, min_word_length)));
     }
-    if ret.len() > 0 {
-        undo_stack.push(last_game_state.clone());
-        redo_stack.clear();
-        *last_game_state = Some(GameState { board: ret[0].1.clone(), min_col: ret[0].2, max_col: ret[0].3, min_row: ret[0].4, max_row: ret[0].5, letters });
-        return Ok(Solution { board: ret[0].0.clone(), elapsed: now.elapsed().as_millis() });
+    letters = letters_add(&letters, &letters_sub(hand, &remaining).expect("Only letters in the hand are used"));
+    Ok(GameState { board, min_col, max_col, min_row, max_row, letters })
+}
+
+/// Gets the words on a board that aren't valid
+/// # Arguments
+/// * `board` - The board to check
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `valid_words_trie` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of the words formed on the board
+/// # Returns
+/// `Vec<String>` - The words that aren't in `valid_words_trie` or are shorter than `min_word_length`, horizontal words first
+fn get_invalid_words(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words_trie: &Trie, min_word_length: usize) -> Vec<String> {
+    get_words_on_board(board, min_col, max_col, min_row, max_row).into_iter()
+        .map(|played_word| played_word.word)
+        .filter(|word| word.len() < min_word_length || !valid_words_trie.contains(&convert_word_to_array(word)))
+        .collect()
+}
+
+/// Command executed by the frontend to place a word chosen by the user, so that later solves play off of it
+/// # Arguments
+/// * `word` - The word to place (case-insensitive)
+/// * `row` - Row of the word's first letter on the full board
+/// * `col` - Column of the word's first letter on the full board
+/// * `direction` - "Horizontal" or "Vertical"
+/// * `available_letters` - `HashMap` (from JavaScript object) mapping string letters to numeric quantity of each letter in the hand, *not* including those on the board
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the rows of the updated board (see `board_to_vec`); the tiles used are added to the stored game, so the frontend should remove them from the hand
+///
+/// *or `PlacementError` with why the word can't be placed there (in which case nothing is changed)*
+#[tauri::command]
+fn place_word(word: String, row: usize, col: usize, direction: String, available_letters: HashMap<String, i64>, state: State<'_, AppState>) -> Result<Vec<Vec<String>>, PlacementError> {
+    let word = word.trim().to_uppercase();
+    if word.is_empty() || !word.chars().all(|c| letter_index(c).is_some()) {
+        return Err(format!("\"{}\" isn't a word!", word).into());
+    }
+    let word = convert_word_to_array(&word);
+    let direction = match direction.as_str() {
+        "Horizontal" => Direction::Horizontal,
+        "Vertical" => Direction::Vertical,
+        _ => return Err(format!("Unknown direction: {}", direction).into())
+    };
+    let hand = letters_from_map(&available_letters)?;
+    let board_size = *state.board_size.lock().or(Err("Failed to get lock on board size!"))?;
+    let min_word_length = *state.min_word_length.lock().or(Err("Failed to get lock on minimum word length!"))?;
+    let dictionary = *state.dictionary.lock().or(Err("Failed to get lock on dictionary!"))?;
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let custom_dictionary = get_custom_dictionary(&state)?;
+    let language_dictionaries = lock_language_dictionaries(&state, language)?;
+    let (_, valid_words_trie, _) = select_dictionary(dictionary, language, &custom_dictionary, &language_dictionaries)?;
+    let mut last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    let game = place_word_on_board(last_game_state.as_ref(), &word, row, col, direction, &hand, valid_words_trie, min_word_length, board_size)?;
+    let history_depth = *state.history_depth.lock().or(Err("Failed to get lock on history depth!"))?;
+    let mut undo_stack = state.undo_stack.lock().or(Err("Failed to get lock on undo stack!"))?;
+    let mut redo_stack = state.redo_stack.lock().or(Err("Failed to get lock on redo stack!"))?;
+    push_undo(&mut undo_stack, last_game_state.clone(), history_depth);
+    redo_stack.clear();
+    let _ = autosave(&game, &state);
+    let board = board_to_vec(&game.board, game.min_col, game.max_col, game.min_row, game.max_row);
+    *last_game_state = Some(game);
+    Ok(board)
+}
+
+/// Takes tiles off of a board, returning their letters
+/// # Arguments
+/// * `game` - The game to remove the tiles from
+/// * `cells` - The row and column of each tile to remove
+/// * `valid_words_trie` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of the words formed on the board
+/// * `force` - Whether to remove the tiles even if the rest of the board would then be disconnected or have invalid words
+/// # Returns
+/// *`Result` with:*
+/// * `Option<GameState>` - The game without the tiles, whose letters no longer include them (or `None` if no tiles are left)
+/// * `Vec<char>` - The letters of the removed tiles (blanks as `BLANK_CHAR`)
+///
+/// *or `PlacementError` with why the tiles can't be removed*
+fn remove_tiles_from_board(game: &GameState, cells: &Vec<(usize, usize)>, valid_words_trie: &Trie, min_word_length: usize, force: bool) -> Result<(Option<GameState>, Vec<char>), PlacementError> {
+    let mut board = game.board.clone();
+    let mut letters = game.letters;
+    let mut removed = Vec::with_capacity(cells.len());
+    for &(row, col) in cells.iter() {
+        if row >= board.size() || col >= board.size() {
+            return Err(PlacementError::OutOfBounds);
+        }
+        match board.get_val(row, col).letter() {
+            Some(letter) => {
+                let is_blank = board.is_blank(row, col);
+                letters[if is_blank {BLANK} else {letter}] -= 1;
+                removed.push(if is_blank {BLANK_CHAR} else {(letter as u8+65) as char});
+                board.clear(row, col);
+            },
+            None => return Err(PlacementError::EmptyTile(row, col))
+        }
+    }
+    let (min_col, max_col, min_row, max_row) = match get_new_min_max(&game.board, game.min_col, game.max_col, game.min_row, game.max_row, cells) {
+        Some(new_min_max) => new_min_max,
+        None => return Ok((None, removed))
+    };
+    if !force {
+        if !is_connected(&board, min_col, max_col, min_row, max_row, &Vec::new()) {
+            return Err(PlacementError::SplitsBoard);
+        }
+        if !is_board_valid_full(&board, min_col, max_col, min_row, max_row, valid_words_trie, min_word_length) {
+            return Err(PlacementError::InvalidWords(get_invalid_words(&board, min_col, max_col, min_row, max_row, valid_words_trie, min_word_length)));
+        }
     }
-    return Err("No solution found - dump and try again!".to_owned());
+    Ok((Some(GameState { board, min_col, max_col, min_row, max_row, letters }), removed))
+}
+
+/// Command executed by the frontend to take a word (or part of one) off of the stored board
+/// # Arguments
+/// * `row` - Row of the first tile to remove on the full board
+/// * `col` - Column of the first tile to remove on the full board
+/// * `direction` - "Horizontal" or "Vertical"
+/// * `length` - Number of tiles to remove
+/// * `force` - Whether to remove the tiles even if the rest of the board would then be disconnected or have invalid words (if `None`, then `false`)
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with the `RemovedWord`; the removed tiles are taken out of the stored game, so the frontend should add them back to the hand
+///
+/// *or `PlacementError` with why the tiles can't be removed (in which case nothing is changed)*
+#[tauri::command]
+fn remove_word(row: usize, col: usize, direction: String, length: usize, force: Option<bool>, state: State<'_, AppState>) -> Result<RemovedWord, PlacementError> {
+    let cells: Vec<(usize, usize)> = match direction.as_str() {
+        "Horizontal" => (0..length).map(|i| (row, col+i)).collect(),
+        "Vertical" => (0..length).map(|i| (row+i, col)).collect(),
+        _ => return Err(format!("Unknown direction: {}", direction).into())
+    };
+    let min_word_length = *state.min_word_length.lock().or(Err("Failed to get lock on minimum word length!"))?;
+    let dictionary = *state.dictionary.lock().or(Err("Failed to get lock on dictionary!"))?;
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let custom_dictionary = get_custom_dictionary(&state)?;
+    let language_dictionaries = lock_language_dictionaries(&state, language)?;
+    let (_, valid_words_trie, _) = select_dictionary(dictionary, language, &custom_dictionary, &language_dictionaries)?;
+    let mut last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    let (game, letters) = match last_game_state.as_ref() {
+        Some(game) => remove_tiles_from_board(game, &cells, valid_words_trie, min_word_length, force.unwrap_or(false))?,
+        None => return Err("There's no board to remove a word from!".into())
+    };
+    let history_depth = *state.history_depth.lock().or(Err("Failed to get lock on history depth!"))?;
+    let mut undo_stack = state.undo_stack.lock().or(Err("Failed to get lock on undo stack!"))?;
+    let mut redo_stack = state.redo_stack.lock().or(Err("Failed to get lock on redo stack!"))?;
+    push_undo(&mut undo_stack, last_game_state.clone(), history_depth);
+    redo_stack.clear();
+    let board = match &game {
+        Some(game) => {
+            let _ = autosave(game, &state);
+            board_to_vec(&game.board, game.min_col, game.max_col, game.min_row, game.max_row)
+        },
+        None => Vec::new()
+    };
+    *last_game_state = game;
+    Ok(RemovedWord { board, letters })
+}
+
+/// Gets the groups of tiles that can be taken off of a board without disconnecting it (see `get_removable_indices`)
+/// # Arguments
+/// * `game` - The game to check
+/// * `valid_words_trie` - `Trie` of all valid words
+/// # Returns
+/// `Vec<RemovableWord>` - Each group of tiles, which can be passed to `remove_word`
+fn removable_words(game: &GameState, valid_words_trie: &Trie) -> Vec<RemovableWord> {
+    get_removable_indices(&game.board, game.min_col, game.max_col, game.min_row, game.max_row, valid_words_trie).into_iter().map(|(cells, _, _, _, _)| {
+        let (row, col) = cells[0];
+        let direction = if cells.len() > 1 && cells[1].1 == col {Direction::Vertical} else {Direction::Horizontal};
+        let word = cells.iter().filter_map(|(r, c)| game.board.get_val(*r, *c).letter().map(|letter| if game.board.is_blank(*r, *c) {(letter as u8+97) as char} else {(letter as u8+65) as char})).collect();
+        RemovableWord { word, direction, row, col, length: cells.len() }
+    }).collect()
+}
+
+/// Command executed by the frontend to list the groups of tiles on the stored board that can be removed with `remove_word`
+/// # Arguments
+/// * `state` - Current state of the app
+/// # Returns
+/// `Result` with each `RemovableWord` (empty if there's no board)
+///
+/// *or String `Err` upon failure*
+#[tauri::command]
+fn get_removable_words(state: State<'_, AppState>) -> Result<Vec<RemovableWord>, String> {
+    let dictionary = *state.dictionary.lock().or(Err("Failed to get lock on dictionary!"))?;
+    let language = *state.language.lock().or(Err("Failed to get lock on language!"))?;
+    let custom_dictionary = get_custom_dictionary(&state)?;
+    let language_dictionaries = lock_language_dictionaries(&state, language)?;
+    let (_, valid_words_trie, _) = select_dictionary(dictionary, language, &custom_dictionary, &language_dictionaries)?;
+    let last_game_state = state.last_game.lock().or(Err("Failed to get lock on last game state"))?;
+    Ok(last_game_state.as_ref().map_or(Vec::new(), |game| removable_words(game, valid_words_trie)))
 }
 
 fn main() {
+    // Solve from the command line instead of opening the window if a hand was given
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if cli::is_cli(&args) {
+        std::process::exit(cli::run(&args));
+    }
     let mut all_words_short: Vec<Word> = include_str!("updated_short_dictionary.txt").lines().map(convert_word_to_array).collect();
-    all_words_short.sort_by(|a, b| b.len().cmp(&a.len()));
+    all_words_short.sort_by_key(|word| Reverse(word.len()));
     let mut all_words_long: Vec<Word> = include_str!("dictionary.txt").lines().map(convert_word_to_array).collect();
-    all_words_long.sort_by(|a, b| b.len().cmp(&a.len()));
+    all_words_long.sort_by_key(|word| Reverse(word.len()));
+    let trie_short = Trie::from_words(&all_words_short);
+    let trie_long = Trie::from_words(&all_words_long);
+    let index_short = DictionaryIndex::from_words(&all_words_short);
+    let index_long = DictionaryIndex::from_words(&all_words_long);
+    let english = ((all_words_short, trie_short, index_short), (all_words_long, trie_long, index_long));
     tauri::Builder::default()
-        .manage(AppState { all_words_short, all_words_long, last_game: None.into(), undo_stack: Vec::new().into(), redo_stack: Vec::new().into(), filter_letters_on_board: 2.into(), maximum_words_to_check: 50_000.into(), use_long_dictionary: false.into() })
-        .invoke_handler(tauri::generate_handler![play_bananagrams, reset, get_playable_words, get_random_letters, get_settings, set_settings, undo, redo])
+        .manage(AppState { last_game: None.into(), tile_set: TileSet::Standard.into(), bunch: None.into(), undo_stack: Vec::new().into(), redo_stack: Vec::new().into(), board_history: Vec::new().into(), history_depth: DEFAULT_HISTORY_DEPTH.into(), min_word_length: DEFAULT_MIN_WORD_LENGTH.into(), max_width: None.into(), max_height: None.into(), filter_letters_on_board: 2.into(), maximum_words_to_check: 50_000.into(), max_solve_time_ms: 0.into(), board_size: BOARD_SIZE.into(), dictionary: DictionaryChoice::Short.into(), fallback_to_long_dictionary: false.into(), use_frequency_ordering: false.into(), deterministic: false.into(), language: Language::English.into(), solution_preference: SolutionPreference::Fastest.into(), language_dictionaries: HashMap::from([(Language::English, english)]).into(), language_dictionary_dir: None.into(), custom_dictionary: None.into(), excluded_words: HashSet::new().into(), excluded_tries: HashMap::new().into(), stop_solving: Arc::new(AtomicBool::new(false)), solving: AtomicBool::new(false), autosave_path: None.into(), autosave_error: None.into(), settings_path: None.into() })
+        .setup(|app| {
+            let state = app.state::<AppState>();
+            // Restore the game from when the app was last open (if it can't be, the frontend is told why once it's loaded, and a new game is started)
+            if let Some(dir) = app.path_resolver().app_data_dir() {
+                *state.language_dictionary_dir.lock().expect("Failed to get lock on language dictionary folder") = Some(dir.join(LANGUAGE_DICTIONARY_DIR));
+                let path = dir.join(AUTOSAVE_FILE);
+                if let Err(e) = restore_autosave(&state, &path) {
+                    *state.autosave_error.lock().expect("Failed to get lock on autosave error") = Some(e);
+                }
+                *state.autosave_path.lock().expect("Failed to get lock on autosave path") = Some(path);
+            }
+            // Then restore the settings, which may have been changed since the game was saved (a missing or corrupt file just means using the defaults)
+            if let Some(dir) = app.path_resolver().app_config_dir() {
+                let path = dir.join(SETTINGS_FILE);
+                let _ = load_settings(&state, &path);
+                *state.settings_path.lock().expect("Failed to get lock on settings path") = Some(path);
+            }
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![play_bananagrams, play_bananagrams_stream, play_bananagrams_multiseed, play_bananagrams_with_stats, play_bananagrams_hint, reset, get_playable_words, get_random_letters, get_settings, set_settings, undo, redo, cancel_solve, save_game, load_game, take_autosave_error, get_last_game, get_anagrams, check_word_validity, parse_letters, search_dictionary, load_custom_dictionary, dump_simulate, add_excluded_words, clear_excluded_words, export_board, export_board_svg, get_word_suggestions, play_from_board, suggest_dump, simulate_game, validate_solution, undo_last_solve, get_board_history, get_board_statistics, start_bunch, peel_from_bunch, set_tile_set, get_letter_distribution, assess_hand_command, place_word, remove_word, get_removable_words, words_on_board, play_bananagrams_fresh])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, thread, collections::HashMap};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use bananagrams_solver::letters_to_string;
+    use bananagrams_solver::solver::Solver;
+    use crate::test_helpers::{place, test_dictionary, test_solver, test_words};
+
+    #[test]
+    fn bunch_drawn_without_replacement() {
+        let mut bunch = REGULAR_TILES;
+        let mut drawn = draw_from_bunch(&mut bunch, 21).unwrap();
+        drawn.extend(draw_from_bunch(&mut bunch, 123).unwrap());
+        for (i, c) in UPPERCASE.chars().enumerate() {
+            assert_eq!(drawn.iter().filter(|d| **d == c).count() as u64, REGULAR_TILES[i]);
+        }
+        assert_eq!(bunch, [0; 26]);
+        assert!(draw_from_bunch(&mut bunch, 1).is_err());
+    }
+
+    #[test]
+    fn spanish_hand_with_accents_solves() {
+        let dir = std::env::temp_dir().join("bananagrams_solver_language_test");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("spanish_long.txt"), "año\nniño\nojo\n").unwrap();
+        let ((words, trie, index), _) = read_language_dictionaries(Language::Spanish, &dir).unwrap();
+        assert_eq!(words.len(), 3);
+        let mut available_letters: HashMap<String, i64> = UPPERCASE.chars().map(|c| (c.to_string(), 0)).collect();
+        for (c, n) in [("N", 1), ("I", 1), ("O", 2), ("A", 1), ("Ñ", 1)] {
+            available_letters.insert(c.to_owned(), n);
+        }
+        let letters = letters_from_map(&available_letters).unwrap();
+        assert_eq!(letters[13], 2);
+        let solved = solve_hand(letters, &None, &words, &trie, &index, &HashSet::new(), &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), None).unwrap();
+        let (board, min_col, max_col, min_row, max_row) = solved.board;
+        let played: Vec<String> = get_words_on_board(&board, min_col, max_col, min_row, max_row).into_iter().map(|w| w.word).collect();
+        assert!(played.contains(&"NINO".to_owned()) && played.contains(&"ANO".to_owned()));
+        let tiles = tiles_from_words(&words, 144);
+        assert_eq!(tiles.iter().sum::<u64>(), 144);
+        assert_eq!(tiles[25], 0);
+    }
+
+    #[test]
+    fn tile_sets_have_expected_counts() {
+        assert_eq!(TileSet::Standard.tiles(&REGULAR_TILES).iter().sum::<u64>() + TileSet::Standard.blanks(), 146);
+        assert_eq!(TileSet::Double.tiles(&REGULAR_TILES).iter().sum::<u64>() + TileSet::Double.blanks(), 292);
+        assert_eq!(TileSet::Triple.tiles(&REGULAR_TILES).iter().sum::<u64>() + TileSet::Triple.blanks(), 438);
+        assert_eq!(TileSet::Triple.tiles(&REGULAR_TILES)[0], REGULAR_TILES[0]*3);
+        let junior = TileSet::Custom([2; 26]);
+        assert_eq!(junior.tiles(&REGULAR_TILES).iter().sum::<u64>(), 52);
+        assert_eq!(junior.blanks(), 0);
+        assert!(TileSet::from_name("double Bananagrams") == Some(TileSet::Double));
+    }
+
+    #[test]
+    fn custom_tile_set_limited() {
+        let counts = |pairs: &[(&str, u64)]| pairs.iter().map(|(key, number)| (key.to_string(), *number)).collect::<HashMap<String, u64>>();
+        assert!(custom_tile_set(&counts(&[("A", 3), ("B", 1)])) == Ok(TileSet::Custom({
+            let mut tiles = [0u64; 26];
+            tiles[0] = 3;
+            tiles[1] = 1;
+            tiles
+        })));
+        assert!(custom_tile_set(&counts(&[("A", MAX_CUSTOM_TILES)])).is_ok());
+        assert!(custom_tile_set(&counts(&[("A", MAX_CUSTOM_TILES), ("B", 1)])).is_err());
+        // Counts so large that adding them up would overflow are rejected rather than wrapping around
+        assert!(custom_tile_set(&counts(&[("A", u64::MAX), ("B", 1)])).is_err());
+        assert!(custom_tile_set(&counts(&[("A", 0)])).is_err());
+        assert!(custom_tile_set(&counts(&[("AB", 1)])).is_err());
+    }
+
+    #[test]
+    fn save_file_round_trips_and_rejects_bad_files() {
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        board.set_blank(69, 71, 7);
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("CAT") {
+            letters[letter] += 1;
+        }
+        letters[BLANK] = 1;
+        let game = GameState { board, min_col: 70, max_col: 72, min_row: 69, max_row: 70, letters };
+        let settings = CurrentSettings { filter_letters_on_board: 2, maximum_words_to_check: 50_000, max_solve_time_ms: 0, board_size: BOARD_SIZE, dictionary: DictionaryChoice::Short, use_frequency_ordering: false, deterministic: false, language: Language::English, solution_preference: SolutionPreference::Fastest, history_depth: DEFAULT_HISTORY_DEPTH, min_word_length: DEFAULT_MIN_WORD_LENGTH, fallback_to_long_dictionary: false, max_width: None, max_height: None };
+        let json = serde_json::to_string(&SaveFile { version: SAVE_VERSION, game, settings }).unwrap();
+        let save = parse_save_file(&json).unwrap();
+        assert_eq!((save.game.min_col, save.game.max_col, save.game.min_row, save.game.max_row), (70, 72, 69, 70));
+        assert!(save.game.board.is_blank(69, 71));
+        assert_eq!(save.game.letters, letters);
+        assert!(parse_save_file(&json.replacen(&format!("\"version\":{}", SAVE_VERSION), &format!("\"version\":{}", SAVE_VERSION+1), 1)).err().unwrap().contains("newer version"));
+        assert!(parse_save_file(&json.replacen(&format!("\"version\":{},", SAVE_VERSION), "", 1)).err().unwrap().contains("no version"));
+        assert!(parse_save_file(&json.replacen("\"letter\":\"C\"", "\"letter\":\"D\"", 1)).err().unwrap().contains("does not match"));
+        assert!(parse_save_file("{\"version\":").err().unwrap().contains("not valid JSON"));
+        // Boards too big to allocate, or with tiles off the board, are rejected rather than crashing
+        let oversized = json.replacen(&format!("\"size\":{}", BOARD_SIZE), "\"size\":100000000000", 1);
+        assert_ne!(oversized, json);
+        assert!(parse_save_file(&oversized).err().unwrap().contains("board size must be between"));
+        assert!(parse_save_file(&json.replacen("\"row\":70", &format!("\"row\":{}", BOARD_SIZE), 1)).err().unwrap().contains("outside of the board"));
+    }
+
+    #[test]
+    fn saved_game_loaded_with_its_settings() {
+        let path = std::env::temp_dir().join("bananagrams_solver_save_game_test.json");
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        board.set_blank(71, 71, 0);
+        let mut letters = string_to_letters("CT?").unwrap();
+        letters[0] += 1;
+        let game = GameState { board, min_col: 70, max_col: 72, min_row: 70, max_row: 71, letters };
+        let state = test_state();
+        *state.maximum_words_to_check.lock().unwrap() = 12_345;
+        write_save_file(&path, &game, &state).unwrap();
+        // Loading into a fresh app restores the game and the settings it was saved with, and can be undone
+        let loaded = test_state();
+        let undo_redo = read_save_file(&path, &loaded).unwrap();
+        assert_eq!(undo_redo.solution, board_to_vec(&game.board, 70, 72, 70, 71));
+        assert_eq!(undo_redo.solution[1][1], "a");
+        assert_eq!(undo_redo.letters[&'A'], 1);
+        assert_eq!(undo_redo.letters[&BLANK_CHAR], 1);
+        assert!(undo_redo.undo_possible && !undo_redo.redo_possible);
+        let last_game = loaded.last_game.lock().unwrap().clone().unwrap();
+        assert_eq!((last_game.min_col, last_game.max_col, last_game.min_row, last_game.max_row), (70, 72, 70, 71));
+        assert_eq!(last_game.letters, letters);
+        assert_eq!(last_game.board.occupied_cells().collect::<Vec<_>>(), game.board.occupied_cells().collect::<Vec<_>>());
+        assert!(last_game.board.is_blank(71, 71));
+        assert_eq!(*loaded.maximum_words_to_check.lock().unwrap(), 12_345);
+        assert_eq!(loaded.undo_stack.lock().unwrap().len(), 1);
+        fs::write(&path, "not a saved game").unwrap();
+        assert!(read_save_file(&path, &loaded).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn corrupt_autosave_reported_rather_than_restored() {
+        let path = std::env::temp_dir().join("bananagrams_solver_autosave_test.json");
+        let _ = fs::remove_file(&path);
+        // No autosave just means a new game
+        let state = test_state();
+        assert!(restore_autosave(&state, &path).is_ok());
+        assert!(state.last_game.lock().unwrap().is_none());
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        let game = GameState { board, min_col: 70, max_col: 72, min_row: 70, max_row: 70, letters: string_to_letters("CAT").unwrap() };
+        write_save_file(&path, &game, &state).unwrap();
+        assert!(restore_autosave(&state, &path).is_ok());
+        assert!(state.last_game.lock().unwrap().is_some());
+        // A board too big to allocate is an error, rather than crashing the app on every launch
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::write(&path, contents.replacen(&format!("\"size\":{}", BOARD_SIZE), "\"size\":100000000000", 1)).unwrap();
+        let state = test_state();
+        assert!(restore_autosave(&state, &path).err().unwrap().contains("board size must be between"));
+        assert!(state.last_game.lock().unwrap().is_none());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn solution_checked_against_hand_and_dictionary() {
+        let words: Vec<Word> = ["CAT", "HAT"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let grid = |rows: Vec<Vec<&str>>| -> Vec<Vec<String>> {rows.into_iter().map(|row| row.into_iter().map(String::from).collect()).collect()};
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("CATHT") {
+            letters[letter] += 1;
+        }
+        assert!(check_solution(&grid(vec![vec![" ", "H", " "], vec!["C", "A", "T"], vec![" ", "T", " "]]), &letters, &trie, 2, BOARD_SIZE).is_ok());
+        assert_eq!(check_solution(&grid(vec![vec![" ", "H", " "], vec!["C", "A", "T"], vec![" ", "T", " "]]), &letters, &trie, 4, BOARD_SIZE).unwrap_err(), "Shorter than the minimum word length of 4: CAT, HAT");
+        assert!(check_solution(&grid(vec![vec![" ", "H", " "], vec!["C", "A", "T"], vec![" ", "T", "T"]]), &letters, &trie, 2, BOARD_SIZE).unwrap_err().contains("don't match"));
+        assert!(check_solution(&grid(vec![vec!["C", "A", "T", " ", "H"], vec![" ", " ", " ", " ", "T"]]), &letters, &trie, 2, BOARD_SIZE).unwrap_err().contains("connected"));
+        letters[2] -= 1;
+        letters[BLANK] += 1;
+        assert!(check_solution(&grid(vec![vec![" ", "H", " "], vec!["c", "A", "T"], vec![" ", "T", " "]]), &letters, &trie, 2, BOARD_SIZE).is_ok());
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("CATHTS") {
+            letters[letter] += 1;
+        }
+        assert_eq!(check_solution(&grid(vec![vec![" ", "H", " ", " "], vec!["C", "A", "T", "S"], vec![" ", "T", " ", " "]]), &letters, &trie, 2, BOARD_SIZE).unwrap_err(), "Not in the dictionary: CATS");
+    }
+
+    #[test]
+    fn letter_distribution_compared_to_custom_set() {
+        let mut tiles = [0u64; 26];
+        tiles[0] = 3;
+        tiles[1] = 1;
+        let mut letters = [0usize; 27];
+        letters[0] = 1;
+        letters[1] = 1;
+        let distribution = letter_distribution(&letters, &TileSet::Custom(tiles).tiles(&REGULAR_TILES), TileSet::Custom(tiles).blanks());
+        assert_eq!(distribution.len(), 27);
+        assert_eq!(distribution["A"].count_in_hand, 1);
+        assert_eq!(distribution["A"].expected_fraction, 0.75);
+        assert_eq!(distribution["A"].deviation, -0.25);
+        assert_eq!(distribution["B"].deviation, 0.25);
+        assert_eq!(distribution["Z"].expected_fraction, 0.0);
+        assert_eq!(distribution[&BLANK_CHAR.to_string()].deviation, 0.0);
+        // Blanks count towards the total of the standard set
+        let distribution = letter_distribution(&letters, &REGULAR_TILES, REGULAR_BLANKS);
+        assert_eq!(distribution["E"].expected_fraction, 18.0 / 146.0);
+    }
+
+    #[test]
+    fn out_of_range_settings_rejected() {
+        let valid = CurrentSettings { filter_letters_on_board: 2, maximum_words_to_check: 50_000, max_solve_time_ms: 0, board_size: BOARD_SIZE, dictionary: DictionaryChoice::Short, use_frequency_ordering: false, deterministic: false, language: Language::English, solution_preference: SolutionPreference::Fastest, history_depth: DEFAULT_HISTORY_DEPTH, min_word_length: DEFAULT_MIN_WORD_LENGTH, fallback_to_long_dictionary: false, max_width: None, max_height: None };
+        assert!(validate_settings(&valid).is_ok());
+        assert!(validate_settings(&CurrentSettings { maximum_words_to_check: MIN_WORDS_TO_CHECK, filter_letters_on_board: MAX_WORD_LENGTH, board_size: MAX_WORD_LENGTH, ..valid }).is_ok());
+        let too_few_words = validate_settings(&CurrentSettings { maximum_words_to_check: 0, ..valid }).unwrap_err();
+        assert!(too_few_words.contains("maximum number of words"));
+        assert!(validate_settings(&CurrentSettings { maximum_words_to_check: MIN_WORDS_TO_CHECK-1, ..valid }).is_err());
+        let too_many_letters = validate_settings(&CurrentSettings { filter_letters_on_board: 10_000, ..valid }).unwrap_err();
+        assert!(too_many_letters.contains("usable letters"));
+        let too_small = validate_settings(&CurrentSettings { board_size: MAX_WORD_LENGTH-1, ..valid }).unwrap_err();
+        assert!(too_small.contains("board size"));
+        assert!(validate_settings(&CurrentSettings { board_size: MAX_BOARD_SIZE, ..valid }).is_ok());
+        let too_big = validate_settings(&CurrentSettings { board_size: MAX_BOARD_SIZE+1, ..valid }).unwrap_err();
+        assert!(too_big.contains("board size"));
+        assert!(validate_settings(&CurrentSettings { board_size: usize::MAX, ..valid }).is_err());
+        assert!(validate_settings(&CurrentSettings { history_depth: 0, ..valid }).is_err());
+        assert!(validate_settings(&CurrentSettings { min_word_length: 1, ..valid }).is_err());
+        assert!(validate_settings(&CurrentSettings { min_word_length: MAX_WORD_LENGTH+1, ..valid }).is_err());
+    }
+
+    #[test]
+    fn solver_errors_distinguished_by_kind() {
+        let (words, trie, index) = test_dictionary();
+        // A hand without any vowels can't make a single word
+        let mut available_letters: HashMap<String, i64> = UPPERCASE.chars().map(|c| (c.to_string(), 0)).collect();
+        for c in "BCDFGKLMNPRST".chars() {
+            available_letters.insert(c.to_string(), 1);
+        }
+        let letters = letters_from_map(&available_letters).unwrap();
+        let no_words = solve_hand(letters, &None, &words, &trie, &index, &HashSet::new(), &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), None).err();
+        assert_eq!(no_words, Some(SolverError::NoPlayableWords));
+        // Whereas a negative count is rejected before solving
+        available_letters.insert("E".to_owned(), -1);
+        let negative = letters_from_map(&available_letters).unwrap_err();
+        assert_eq!(negative, SolverError::InvalidLetterCount { letter: 'E', count: -1 });
+        assert_eq!(negative.to_string(), "Number of letter E is -1, but must be greater than or equal to 0!");
+        available_letters.remove("E");
+        assert_eq!(letters_from_map(&available_letters).unwrap_err(), SolverError::MissingLetter('E'));
+        // The frontend branches on the kind, and shows the message
+        assert_eq!(serde_json::to_value(&negative).unwrap(), serde_json::json!({"kind": "InvalidLetterCount", "message": negative.to_string(), "letter": "E", "count": -1}));
+        assert_eq!(serde_json::to_value(SolverError::NoPlayableWords).unwrap(), serde_json::json!({"kind": "NoPlayableWords", "message": "No valid words can be formed from the current letters - dump and try again!"}));
+    }
+
+    /// Creates an `AppState` with the default settings, using the short dictionary as both dictionaries
+    fn test_state() -> AppState {
+        let english = (test_dictionary(), test_dictionary());
+        AppState { last_game: None.into(), tile_set: TileSet::Standard.into(), bunch: None.into(), undo_stack: Vec::new().into(), redo_stack: Vec::new().into(), board_history: Vec::new().into(), history_depth: DEFAULT_HISTORY_DEPTH.into(), min_word_length: DEFAULT_MIN_WORD_LENGTH.into(), max_width: None.into(), max_height: None.into(), filter_letters_on_board: 2.into(), maximum_words_to_check: 50_000.into(), max_solve_time_ms: 0.into(), board_size: BOARD_SIZE.into(), dictionary: DictionaryChoice::Short.into(), fallback_to_long_dictionary: false.into(), use_frequency_ordering: false.into(), deterministic: false.into(), language: Language::English.into(), solution_preference: SolutionPreference::Fastest.into(), language_dictionaries: HashMap::from([(Language::English, english)]).into(), language_dictionary_dir: None.into(), custom_dictionary: None.into(), excluded_words: HashSet::new().into(), excluded_tries: HashMap::new().into(), stop_solving: Arc::new(AtomicBool::new(false)), solving: AtomicBool::new(false), autosave_path: None.into(), autosave_error: None.into(), settings_path: None.into() }
+    }
+
+    #[test]
+    fn game_reset_while_solving() {
+        let state = test_state();
+        *state.maximum_words_to_check.lock().unwrap() = usize::MAX;
+        let mut available_letters: HashMap<String, i64> = UPPERCASE.chars().map(|c| (c.to_string(), 0)).collect();
+        for c in "HELLOWORLD".chars() {
+            *available_letters.get_mut(&c.to_string()).unwrap() += 1;
+        }
+        // Hold the solve partway through until the test is done with it
+        let started = Arc::new(AtomicBool::new(false));
+        let release = Arc::new(AtomicBool::new(false));
+        let on_partial: PartialCallback = {
+            let (started, release) = (Arc::clone(&started), Arc::clone(&release));
+            Arc::new(move |_| {
+                started.store(true, Ordering::Relaxed);
+                while !release.load(Ordering::Relaxed) {
+                    thread::sleep(Duration::from_millis(1));
+                }
+            })
+        };
+        thread::scope(|s| {
+            let solve = s.spawn(|| solve_and_store(available_letters.clone(), None, 1, None, false, None, false, &SettingsOverrides::default(), &state, Some(&on_partial)));
+            while !started.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+            }
+            // The game isn't locked while solving, so it can be reset straight away...
+            reset_game(&state).unwrap();
+            assert!(state.last_game.lock().unwrap().is_none());
+            // ...but a second solve is refused rather than waiting for the first
+            assert_eq!(solve_and_store(available_letters.clone(), None, 1, None, false, None, false, &SettingsOverrides::default(), &state, None).err(), Some(SolverError::Internal("A solve is already in progress!".to_owned())));
+            state.stop_solving.store(true, Ordering::Relaxed);
+            release.store(true, Ordering::Relaxed);
+            let _ = solve.join().unwrap();
+        });
+        assert!(!state.solving.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn cli_arguments_parsed() {
+        let args = |args: &str| args.split(' ').map(String::from).collect::<Vec<String>>();
+        let options = cli::parse_args(&args("--letters abé? --long-dict --max-words 100 --json")).unwrap();
+        let mut letters = [0usize; 27];
+        letters[0] = 1;
+        letters[1] = 1;
+        letters[4] = 1;
+        letters[BLANK] = 1;
+        assert_eq!(options, cli::CliOptions { letters, long_dictionary: true, max_words_to_check: Some(100), filter_letters_on_board: None, json: true });
+        assert!(cli::is_cli(&args("--json --letters AB")));
+        assert!(!cli::is_cli(&args("--json")));
+        assert!(cli::parse_args(&args("--letters A1")).is_err());
+        assert!(cli::parse_args(&args("--max-words many --letters AB")).is_err());
+        assert!(cli::parse_args(&args("--json")).is_err());
+        assert!(cli::parse_args(&args("--letters")).is_err());
+    }
+
+    #[test]
+    fn english_dictionary_replaced_at_runtime() {
+        let state = test_state();
+        let path = std::env::temp_dir().join("bananagrams_solver_replace_dictionary_test.txt");
+        fs::write(&path, "cat\n\nhorse\ndog\n").unwrap();
+        let path = path.to_str().unwrap();
+        assert_eq!(load_dictionary(path, Some("short".to_owned()), None, &state), Ok(3));
+        {
+            let language_dictionaries = lock_language_dictionaries(&state, Language::English).unwrap();
+            let ((short, short_trie, _), (long, _, _)) = language_words(Language::English, &language_dictionaries).unwrap();
+            assert_eq!(short.iter().map(convert_array_to_word).collect::<Vec<String>>(), vec!["HORSE", "CAT", "DOG"]);
+            assert!(short_trie.contains(&convert_word_to_array("DOG")));
+            // The long dictionary is untouched
+            assert!(long.len() > 3);
+        }
+        assert!(load_dictionary(path, Some("medium".to_owned()), None, &state).is_err());
+        assert!(state.custom_dictionary.lock().unwrap().is_none());
+        assert_eq!(load_dictionary(path, None, None, &state), Ok(3));
+        assert!(state.custom_dictionary.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn playable_words_histogram_counts_lengths() {
+        let words = test_words();
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("ACT") {
+            letters[letter] += 1;
+        }
+        let playable: Vec<String> = words.iter().filter(|word| is_makeable(word, &letters)).map(convert_array_to_word).collect();
+        assert!(playable.contains(&"CAT".to_owned()));
+        let playable_words = PlayableWords::new(playable.clone(), Vec::new(), None);
+        assert!(playable_words.short_histogram[&3] >= 1);
+        assert_eq!(playable_words.short_histogram.values().sum::<usize>(), playable.len());
+        assert!(playable_words.long_histogram.is_empty());
+    }
+
+    #[test]
+    fn undo_stack_capped_at_history_depth() {
+        let mut undo_stack = Vec::new();
+        for i in 0..5 {
+            push_undo(&mut undo_stack, Some(GameState { board: Board::new(BOARD_SIZE), min_col: i, max_col: i, min_row: 0, max_row: 0, letters: [0usize; 27] }), 3);
+        }
+        assert_eq!(undo_stack.len(), 3);
+        // The oldest games are the ones dropped
+        assert_eq!(undo_stack.iter().map(|game| game.as_ref().unwrap().min_col).collect::<Vec<usize>>(), vec![2, 3, 4]);
+        let old_settings: CurrentSettings = serde_json::from_str(r#"{"filter_letters_on_board": 2, "maximum_words_to_check": 50000, "max_solve_time_ms": 0, "board_size": 144, "dictionary": "Short", "use_frequency_ordering": false}"#).unwrap();
+        assert_eq!(old_settings.history_depth, DEFAULT_HISTORY_DEPTH);
+    }
+
+    #[test]
+    fn word_looked_up_in_dictionaries() {
+        let state = test_state();
+        let validity = word_validity("hello", &state).unwrap();
+        assert!(validity.in_short && validity.in_long);
+        assert_eq!(validity.in_custom, None);
+        let validity = word_validity(" Héllo ", &state).unwrap();
+        assert!(validity.in_short);
+        // Letters that only begin words aren't a word themselves
+        assert!(!word_validity("HEL", &state).unwrap().in_long);
+        assert!(!word_validity("XQZV", &state).unwrap().in_short);
+        assert!(word_validity("HELLO WORLD", &state).is_err());
+        assert!(word_validity("", &state).is_err());
+    }
+
+    #[test]
+    fn letters_round_trip_through_strings() {
+        let letters = string_to_letters("BADCA?E").unwrap();
+        assert_eq!(letters[0], 2);
+        assert_eq!(letters[BLANK], 1);
+        assert_eq!(letters_to_string(&letters), "AABCDE?");
+        assert_eq!(string_to_letters("").unwrap(), [0usize; 27]);
+        assert_eq!(string_to_letters("abc").unwrap_err(), "Invalid letter in hand: a");
+        assert!(string_to_letters("AB C").is_err());
+        // The command is case-insensitive, and only includes blanks if there are any
+        let counts = parse_letters("aab".to_string()).unwrap();
+        assert_eq!(counts.len(), 26);
+        assert_eq!(counts["A"], 2);
+        assert_eq!(counts["Z"], 0);
+        assert_eq!(parse_letters("Q?".to_string()).unwrap()[&BLANK_CHAR.to_string()], 1);
+        assert!(parse_letters("É".to_string()).is_err());
+    }
+
+    #[test]
+    fn dictionary_searched_by_pattern() {
+        let state = test_state();
+        let matches = words_matching("c.t", &state).unwrap();
+        assert!(matches.contains(&"CAT".to_owned()));
+        assert!(matches.contains(&"CUT".to_owned()));
+        assert!(matches.iter().all(|word| word.len() == 3 && word.starts_with('C') && word.ends_with('T')));
+        let mut sorted = matches.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(matches, sorted);
+        assert_eq!(parse_pattern("A.Z"), Ok(vec![PatternAtom::Exact(0), PatternAtom::Any, PatternAtom::Exact(25)]));
+        assert!(words_matching("", &state).is_err());
+        assert!(words_matching("C?T", &state).is_err());
+        assert!(words_matching(&".".repeat(MAX_WORD_LENGTH + 1), &state).is_err());
+    }
+
+    #[test]
+    fn long_dictionary_used_when_short_fails() {
+        let state = test_state();
+        // Make "ZA" a long-only word
+        let mut long_words = test_words();
+        long_words.push(convert_word_to_array("ZA"));
+        long_words.sort_by_key(|word| Reverse(word.len()));
+        state.language_dictionaries.lock().unwrap().get_mut(&Language::English).unwrap().1 = (long_words.clone(), Trie::from_words(&long_words), DictionaryIndex::from_words(&long_words));
+        let mut available_letters: HashMap<String, i64> = UPPERCASE.chars().map(|c| (c.to_string(), 0)).collect();
+        available_letters.insert("Z".to_owned(), 1);
+        available_letters.insert("A".to_owned(), 1);
+        assert!(solve_and_store(available_letters.clone(), None, 1, None, false, None, false, &SettingsOverrides::default(), &state, None).is_err());
+        *state.fallback_to_long_dictionary.lock().unwrap() = true;
+        let solution = solve_and_store(available_letters, None, 1, None, false, None, false, &SettingsOverrides::default(), &state, None).unwrap().remove(0);
+        assert!(solution.used_long_dictionary);
+        assert_eq!(solution.long_only_words, vec!["ZA".to_owned()]);
+    }
+
+    #[test]
+    fn fresh_solve_ignores_current_game() {
+        let state = test_state();
+        let mut available_letters: HashMap<String, i64> = UPPERCASE.chars().map(|c| (c.to_string(), 0)).collect();
+        for c in "HELLOWORLD".chars() {
+            *available_letters.get_mut(&c.to_string()).unwrap() += 1;
+        }
+        solve_and_store(available_letters.clone(), None, 1, None, false, None, false, &SettingsOverrides::default(), &state, None).unwrap();
+        let undo_depth = state.undo_stack.lock().unwrap().len();
+        // Solving the same hand again normally leaves the game unchanged
+        solve_and_store(available_letters.clone(), None, 1, None, false, None, false, &SettingsOverrides::default(), &state, None).unwrap();
+        assert_eq!(state.undo_stack.lock().unwrap().len(), undo_depth);
+        // A fresh solve builds a new board, which replaces the game
+        let solution = solve_and_store(available_letters, None, 1, None, false, None, true, &SettingsOverrides::default(), &state, None).unwrap().remove(0);
+        assert_eq!(state.undo_stack.lock().unwrap().len(), undo_depth+1);
+        assert!(solution.carried_over.iter().flatten().all(|carried_over| !carried_over));
+        let last_game = state.last_game.lock().unwrap();
+        let game = last_game.as_ref().unwrap();
+        assert_eq!(board_to_vec(&game.board, game.min_col, game.max_col, game.min_row, game.max_row), solution.board);
+    }
+
+    #[test]
+    fn hint_is_a_valid_next_word() {
+        let (words, trie, _) = test_dictionary();
+        let hand = |s: &str| string_to_letters(s).unwrap();
+        // With no board, the hint is the longest word that can be made, in the middle of the board
+        let hint = next_word_hint(hand("CATS"), &None, &words, &trie, &HashSet::new(), 2, 2, BOARD_SIZE).unwrap();
+        assert_eq!(hint.word.len(), 4);
+        assert!(is_makeable(&convert_word_to_array(&hint.word), &hand("CATS")));
+        assert_eq!((hint.row, hint.col), (BOARD_SIZE/2, BOARD_SIZE/2 - 2));
+        // With a board, the hint must validly extend it using only the new letters
+        let mut board = Board::new(BOARD_SIZE);
+        let mut letters_on_board = [0usize; 27];
+        for (i, letter) in convert_word_to_array("CAT").into_iter().enumerate() {
+            board.set_val(72, 71+i, letter);
+            letters_on_board[letter] += 1;
+        }
+        let previous = Some(GameState { board: board.clone(), min_col: 71, max_col: 73, min_row: 72, max_row: 72, letters: hand("CAT") });
+        let hint = next_word_hint(hand("CATDOG"), &previous, &words, &trie, &HashSet::new(), 2, 2, BOARD_SIZE).unwrap();
+        let direction = hint.direction;
+        let word = convert_word_to_array(&hint.word);
+        let res = board.play_word(&word, hint.row, hint.col, direction, &hand("DOG"), &mut letters_on_board);
+        assert!(res.0);
+        let (min_col, max_col, min_row, max_row) = res.1.iter().fold((71, 73, 72, 72), |(a, b, c, d), (row, col)| (a.min(*col), b.max(*col), c.min(*row), d.max(*row)));
+        assert!(is_connected(&board, min_col, max_col, min_row, max_row, &Vec::new()));
+        assert!(match direction {
+            Direction::Horizontal => is_board_valid_horizontal(&board, min_col, max_col, min_row, max_row, hint.row, hint.col, hint.col+word.len()-1, &trie, 2),
+            _ => is_board_valid_vertical(&board, min_col, max_col, min_row, max_row, hint.row, hint.row+word.len()-1, hint.col, &trie, 2)
+        });
+        assert!(next_word_hint(hand("CAT"), &previous, &words, &trie, &HashSet::new(), 2, 2, BOARD_SIZE).is_err());
+    }
+
+    #[test]
+    fn words_missing_from_short_dictionary_marked_obscure() {
+        let state = test_state();
+        // Make "ZA" a long-only word
+        let mut long_words = test_words();
+        long_words.push(convert_word_to_array("ZA"));
+        long_words.sort_by_key(|word| Reverse(word.len()));
+        state.language_dictionaries.lock().unwrap().get_mut(&Language::English).unwrap().1 = (long_words.clone(), Trie::from_words(&long_words), DictionaryIndex::from_words(&long_words));
+        *state.dictionary.lock().unwrap() = DictionaryChoice::Long;
+        let solve = |hand: &str| {
+            let mut available_letters: HashMap<String, i64> = UPPERCASE.chars().map(|c| (c.to_string(), 0)).collect();
+            for c in hand.chars() {
+                *available_letters.get_mut(&c.to_string()).unwrap() += 1;
+            }
+            solve_and_store(available_letters, None, 1, None, false, None, false, &SettingsOverrides::default(), &state, None).unwrap().remove(0)
+        };
+        let solution = solve("ZA");
+        assert!(solution.words.iter().all(|played| played.word == "ZA" && played.obscure));
+        // Played off of the previous board
+        let solution = solve("ZAT");
+        assert!(solution.words.iter().all(|played| played.obscure == (played.word == "ZA")));
+        reset_game(&state).unwrap();
+        let solution = solve("CAT");
+        assert!(solution.words.iter().all(|played| !played.obscure));
+    }
+
+    #[test]
+    fn settings_saved_and_restored() {
+        let path = std::env::temp_dir().join("bananagrams_solver_settings_test.json");
+        let state = test_state();
+        *state.settings_path.lock().unwrap() = Some(path.clone());
+        let mut settings = current_settings(&state).unwrap();
+        settings.maximum_words_to_check = 12_345;
+        settings.filter_letters_on_board = 3;
+        settings.dictionary = DictionaryChoice::Long;
+        apply_settings(&state, &settings).unwrap();
+        save_settings(&state).unwrap();
+        let restored = test_state();
+        load_settings(&restored, &path).unwrap();
+        assert_eq!(serde_json::to_string(&current_settings(&restored).unwrap()).unwrap(), serde_json::to_string(&settings).unwrap());
+        // Settings added after the file was written get their defaults
+        let old_json = format!("{{\"version\":{},\"settings\":{{\"filter_letters_on_board\":4,\"maximum_words_to_check\":20000,\"max_solve_time_ms\":0,\"board_size\":{},\"dictionary\":\"Short\",\"use_frequency_ordering\":false}}}}", SETTINGS_VERSION, BOARD_SIZE);
+        let old_settings = parse_settings_file(&old_json).unwrap();
+        assert_eq!((old_settings.filter_letters_on_board, old_settings.min_word_length, old_settings.history_depth), (4, DEFAULT_MIN_WORD_LENGTH, DEFAULT_HISTORY_DEPTH));
+        // A corrupt file or one from another version leaves the settings as they were
+        let corrupted = test_state();
+        fs::write(&path, "{\"version\":").unwrap();
+        assert!(load_settings(&corrupted, &path).is_err());
+        fs::write(&path, old_json.replacen(&format!("\"version\":{}", SETTINGS_VERSION), &format!("\"version\":{}", SETTINGS_VERSION+1), 1)).unwrap();
+        assert!(load_settings(&corrupted, &path).err().unwrap().contains("unsupported format"));
+        assert_eq!(current_settings(&corrupted).unwrap().maximum_words_to_check, 50_000);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn overrides_used_for_one_call_only() {
+        let state = test_state();
+        // Make "ZA" a long-only word
+        let mut long_words = test_words();
+        long_words.push(convert_word_to_array("ZA"));
+        long_words.sort_by_key(|word| Reverse(word.len()));
+        state.language_dictionaries.lock().unwrap().get_mut(&Language::English).unwrap().1 = (long_words.clone(), Trie::from_words(&long_words), DictionaryIndex::from_words(&long_words));
+        let za = string_to_letters("ZA").unwrap();
+        let (short, long) = thread::scope(|s| {
+            let short = s.spawn(|| word_suggestions(&za, 5, Some(DictionaryChoice::Short), &state));
+            let long = s.spawn(|| word_suggestions(&za, 5, Some(DictionaryChoice::Long), &state));
+            (short.join().unwrap().unwrap(), long.join().unwrap().unwrap())
+        });
+        assert!(!short.contains(&"ZA".to_owned()));
+        assert!(long.contains(&"ZA".to_owned()));
+        let mut available_letters: HashMap<String, i64> = UPPERCASE.chars().map(|c| (c.to_string(), 0)).collect();
+        available_letters.insert("Z".to_owned(), 1);
+        available_letters.insert("A".to_owned(), 1);
+        assert!(solve_and_store(available_letters.clone(), None, 1, None, false, None, false, &SettingsOverrides::default(), &state, None).is_err());
+        let overrides = SettingsOverrides { filter_letters_on_board: Some(3), maximum_words_to_check: Some(10_000), max_solve_time_ms: None, dictionary: Some(DictionaryChoice::Long) };
+        assert!(solve_and_store(available_letters, None, 1, None, false, None, false, &overrides, &state, None).is_ok());
+        let settings = current_settings(&state).unwrap();
+        assert!(matches!(settings.dictionary, DictionaryChoice::Short));
+        assert_eq!((settings.filter_letters_on_board, settings.maximum_words_to_check), (2, 50_000));
+        // Invalid overrides are rejected rather than used
+        assert!(SettingsOverrides { maximum_words_to_check: Some(0), ..Default::default() }.apply(settings).is_err());
+    }
+
+    #[test]
+    fn words_placed_by_hand_validated() {
+        let hand = |s: &str| string_to_letters(s).unwrap();
+        let (_, trie, _) = test_dictionary();
+        let place = |previous: Option<&GameState>, word: &str, row: usize, col: usize, direction: Direction, letters: &str| place_word_on_board(previous, &convert_word_to_array(word), row, col, direction, &hand(letters), &trie, DEFAULT_MIN_WORD_LENGTH, BOARD_SIZE);
+        // The first word can go anywhere
+        let first = place(None, "CAT", 10, 10, Direction::Horizontal, "CATS").unwrap();
+        assert_eq!((first.min_col, first.max_col, first.min_row, first.max_row), (10, 12, 10, 10));
+        assert_eq!(first.letters, hand("CAT"));
+        // Crossing words only use tiles for the letters not already on the board
+        let second = place(Some(&first), "BAD", 9, 11, Direction::Vertical, "BD").unwrap();
+        assert_eq!((second.min_col, second.max_col, second.min_row, second.max_row), (10, 12, 9, 11));
+        assert_eq!(second.letters, hand("CATBD"));
+        assert_eq!(board_to_vec(&second.board, second.min_col, second.max_col, second.min_row, second.max_row), vec![vec![" ", "B", " "], vec!["C", "A", "T"], vec![" ", "D", " "]]);
+        // Each way a placement can be rejected gives its own error
+        assert_eq!(place(Some(&first), "BAD", 9, 11, Direction::Vertical, "B").err(), Some(PlacementError::OverusedLetter('D')));
+        assert_eq!(place(Some(&first), "AT", 11, 10, Direction::Horizontal, "AT").err(), Some(PlacementError::InvalidWords(vec!["CA".to_owned()])));
+        assert_eq!(place(Some(&first), "DOG", 20, 20, Direction::Horizontal, "DOG").err(), Some(PlacementError::Disconnected));
+        assert_eq!(place(Some(&first), "DOG", 10, 10, Direction::Vertical, "OG").err(), Some(PlacementError::ConflictingTile(10, 10)));
+        assert_eq!(place(Some(&first), "CAT", 10, 10, Direction::Horizontal, "").err(), Some(PlacementError::NothingPlaced));
+        assert_eq!(place(None, "CAT", 0, BOARD_SIZE-2, Direction::Horizontal, "CAT").err(), Some(PlacementError::OutOfBounds));
+    }
+
+    #[test]
+    fn words_on_board_listed_longest_first() {
+        let hand = |s: &str| string_to_letters(s).unwrap();
+        let (_, trie, _) = test_dictionary();
+        let place = |previous: Option<&GameState>, word: &str, row: usize, col: usize, direction: Direction, letters: &str| place_word_on_board(previous, &convert_word_to_array(word), row, col, direction, &hand(letters), &trie, DEFAULT_MIN_WORD_LENGTH, BOARD_SIZE).unwrap();
+        let sad = place(None, "SAD", 10, 13, Direction::Vertical, "SAD");
+        let game = place(Some(&sad), "CATS", 10, 10, Direction::Horizontal, "CAT");
+        let game = place(Some(&game), "ICE", 9, 10, Direction::Vertical, "IE");
+        let listed: Vec<(String, String, usize, usize)> = list_words_on_board(&game.board, game.min_col, game.max_col, game.min_row, game.max_row).into_iter().map(|w| (w.word, w.direction, w.start_row, w.start_col)).collect();
+        assert_eq!(listed, vec![
+            ("CATS".to_owned(), "Horizontal".to_owned(), 10, 10),
+            ("ICE".to_owned(), "Vertical".to_owned(), 9, 10),
+            ("SAD".to_owned(), "Vertical".to_owned(), 10, 13)
+        ]);
+    }
+
+    #[test]
+    fn words_removed_by_hand_keep_board_valid() {
+        let hand = |s: &str| string_to_letters(s).unwrap();
+        let (_, trie, _) = test_dictionary();
+        let place = |previous: Option<&GameState>, word: &str, row: usize, col: usize, direction: Direction, letters: &str| place_word_on_board(previous, &convert_word_to_array(word), row, col, direction, &hand(letters), &trie, DEFAULT_MIN_WORD_LENGTH, BOARD_SIZE).unwrap();
+        let cat = place(None, "CAT", 10, 10, Direction::Horizontal, "CAT");
+        let game = place(Some(&cat), "CUE", 10, 10, Direction::Vertical, "UE");
+        let game = place(Some(&game), "TO", 10, 12, Direction::Vertical, "O");
+        // Removing a word that others cross would leave their tiles stranded
+        let crossing = vec![(10, 10), (10, 11), (10, 12)];
+        assert_eq!(remove_tiles_from_board(&game, &crossing, &trie, DEFAULT_MIN_WORD_LENGTH, false).err(), Some(PlacementError::SplitsBoard));
+        let (forced, letters) = remove_tiles_from_board(&game, &crossing, &trie, DEFAULT_MIN_WORD_LENGTH, true).unwrap();
+        assert_eq!(letters, vec!['C', 'A', 'T']);
+        assert_eq!(forced.unwrap().letters, hand("UEO"));
+        // The end of a word that nothing crosses can be removed, shrinking the bounds
+        let removable = removable_words(&game, &trie);
+        assert!(removable.iter().any(|word| word.word == "O" && (word.row, word.col, word.length) == (11, 12, 1)));
+        let (game, letters) = remove_tiles_from_board(&game, &vec![(11, 12)], &trie, DEFAULT_MIN_WORD_LENGTH, false).unwrap();
+        let game = game.unwrap();
+        assert_eq!(letters, vec!['O']);
+        assert_eq!((game.min_col, game.max_col, game.min_row, game.max_row), (10, 12, 10, 12));
+        assert_eq!(game.letters, hand("CATUE"));
+        // Removing part of a word can leave an invalid word behind
+        assert_eq!(remove_tiles_from_board(&game, &vec![(10, 12)], &trie, DEFAULT_MIN_WORD_LENGTH, false).err(), Some(PlacementError::InvalidWords(vec!["CA".to_owned()])));
+        assert_eq!(remove_tiles_from_board(&game, &vec![(12, 12)], &trie, DEFAULT_MIN_WORD_LENGTH, false).err(), Some(PlacementError::EmptyTile(12, 12)));
+        // Removing every tile leaves no game
+        let (empty, _) = remove_tiles_from_board(&cat, &crossing, &trie, DEFAULT_MIN_WORD_LENGTH, false).unwrap();
+        assert!(empty.is_none());
+    }
+
+    #[test]
+    fn hands_assessed_by_playable_words() {
+        let hand = |s: &str| string_to_letters(s).unwrap();
+        let words = test_words();
+        let valid_words: Vec<&Word> = words.iter().collect();
+        assert_eq!(assess_hand(&hand("QQ"), &valid_words), Solvability::Impossible);
+        assert_eq!(assess_hand(&hand("AEINRST"), &valid_words), Solvability::Easy);
+        // Many words can be formed from a large hand, but none uses half of it
+        assert_eq!(assess_hand(&hand(&UPPERCASE.repeat(2)), &valid_words), Solvability::Moderate);
+        let few_words = [convert_word_to_array("AB"), convert_word_to_array("BA"), convert_word_to_array("CAB")];
+        let few_words: Vec<&Word> = few_words.iter().collect();
+        assert_eq!(assess_hand(&hand("ABCDEFGH"), &few_words), Solvability::Difficult);
+        assert_eq!(assess_hand(&hand("ABCD"), &few_words), Solvability::Moderate);
+    }
+
+    #[test]
+    fn solution_kept_within_max_span() {
+        let hand = |s: &str| string_to_letters(s).unwrap();
+        // The only word is wider than the limit, so the first word can't be played
+        let solver = Solver::new(vec!["BANANAGRAMS".to_owned()]);
+        assert!(solver.solve(hand("BANANAGRAMS"), SolveConfig { threads: Some(1), ..SolveConfig::default() }).is_ok());
+        assert_eq!(solver.solve(hand("BANANAGRAMS"), SolveConfig { threads: Some(1), max_width: Some(8), ..SolveConfig::default() }).err(), Some(SolverError::ExceedsMaxSpan { max_width: 8, max_height: BOARD_SIZE }));
+        // Otherwise every word is played within the limits
+        let solver = test_solver();
+        let solved = solver.solve(hand("HELLOWORLDQUEEN"), SolveConfig { threads: Some(1), max_width: Some(10), max_height: Some(10), ..SolveConfig::default() }).unwrap();
+        let game = &solved.game;
+        assert!(game.max_col-game.min_col < 10 && game.max_row-game.min_row < 10);
+        let words = test_words();
+        assert!(is_board_valid_full(&game.board, game.min_col, game.max_col, game.min_row, game.max_row, &Trie::from_words(&words), DEFAULT_MIN_WORD_LENGTH));
+        // The limits can't be narrower than a word or wider than the board
+        let mut settings = CurrentSettings { filter_letters_on_board: 2, maximum_words_to_check: 50_000, max_solve_time_ms: 0, board_size: BOARD_SIZE, dictionary: DictionaryChoice::Short, use_frequency_ordering: false, deterministic: false, language: Language::English, solution_preference: SolutionPreference::Fastest, history_depth: DEFAULT_HISTORY_DEPTH, min_word_length: DEFAULT_MIN_WORD_LENGTH, fallback_to_long_dictionary: false, max_width: Some(10), max_height: Some(1) };
+        assert!(validate_settings(&settings).is_ok());
+        settings.max_width = Some(1);
+        assert!(validate_settings(&settings).is_err());
+        settings.max_width = None;
+        settings.max_height = Some(BOARD_SIZE+1);
+        assert!(validate_settings(&settings).is_err());
+    }
+
+    #[test]
+    fn anagrams_use_exactly_the_letters_given() {
+        let hand = |s: &str| string_to_letters(s).unwrap();
+        let word = |s: &str| convert_word_to_array(s);
+        assert!(is_exact_anagram(&word("ACT"), &hand("TCA")));
+        assert!(!is_exact_anagram(&word("CAT"), &hand("CATS")));
+        assert!(!is_exact_anagram(&word("CATS"), &hand("CAT")));
+        // A blank stands in for any one letter, but still has to be used
+        assert!(is_exact_anagram(&word("CATS"), &hand("CAT?")));
+        assert!(is_exact_anagram(&word("CAT"), &hand("??T")));
+        assert!(!is_exact_anagram(&word("CAT"), &hand("CAT?")));
+        assert!(!is_exact_anagram(&word("DOGS"), &hand("CAT?")));
+        let state = test_state();
+        let anagrams = anagrams_of("tca", &state).unwrap();
+        assert!(anagrams.short.contains(&"CAT".to_owned()) && anagrams.short.contains(&"ACT".to_owned()));
+        assert!(anagrams.short.iter().all(|anagram| is_exact_anagram(&word(anagram), &hand("CAT"))));
+        assert!(anagrams.short.windows(2).all(|pair| pair[0] < pair[1]));
+        let with_blank = anagrams_of("CA?", &state).unwrap();
+        assert!(with_blank.short.contains(&"CAT".to_owned()) && with_blank.short.contains(&"CAB".to_owned()));
+        assert!(with_blank.short.iter().all(|anagram| anagram.len() == 3 && anagram.contains('C') && anagram.contains('A')));
+        // Words already on the board are left out
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        *state.last_game.lock().unwrap() = Some(GameState { board, min_col: 70, max_col: 72, min_row: 70, max_row: 70, letters: hand("CAT") });
+        let anagrams = anagrams_of("TCA", &state).unwrap();
+        assert!(!anagrams.short.contains(&"CAT".to_owned()) && anagrams.short.contains(&"ACT".to_owned()));
+        assert!(anagrams_of("123", &state).is_err());
+    }
+
+    #[test]
+    fn word_suggestions_longest_first_and_not_on_board() {
+        let hand = string_to_letters("CATS").unwrap();
+        let state = test_state();
+        let suggestions = word_suggestions(&hand, 100, None, &state).unwrap();
+        assert!(suggestions.contains(&"CATS".to_owned()) && suggestions.contains(&"CAT".to_owned()));
+        assert!(suggestions.iter().all(|word| is_makeable(&convert_word_to_array(word), &hand)));
+        assert!(suggestions.windows(2).all(|pair| pair[0].len() > pair[1].len() || (pair[0].len() == pair[1].len() && pair[0] < pair[1])));
+        // Only the first `n` are kept
+        let top = word_suggestions(&hand, 2, None, &state).unwrap();
+        assert_eq!(top, suggestions[..2]);
+        // Excluded words and words already on the board aren't suggested
+        state.excluded_words.lock().unwrap().insert(convert_word_to_array("CATS"));
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        *state.last_game.lock().unwrap() = Some(GameState { board, min_col: 70, max_col: 72, min_row: 70, max_row: 70, letters: string_to_letters("CAT").unwrap() });
+        let suggestions = word_suggestions(&hand, 100, None, &state).unwrap();
+        assert!(!suggestions.contains(&"CATS".to_owned()) && !suggestions.contains(&"CAT".to_owned()));
+        assert!(suggestions.contains(&"ACT".to_owned()));
+        assert!(word_suggestions(&string_to_letters("QQ").unwrap(), 10, None, &state).unwrap().is_empty());
+    }
+
+    #[test]
+    fn solve_continues_from_supplied_board() {
+        let grid: Vec<Vec<String>> = [["C", "A", "T"], [" ", "T", " "]].iter().map(|row| row.iter().map(|cell| cell.to_string()).collect()).collect();
+        let (existing, ..) = board_from_vec(&grid, BOARD_SIZE).unwrap();
+        let state = test_state();
+        let solution = solve_from_board(&grid, string_to_letters("SHOE").unwrap(), &state).unwrap();
+        let game = state.last_game.lock().unwrap().clone().unwrap();
+        // Every tile of the supplied board is still in the same place, and marked as carried over
+        for (row, col) in existing.occupied_cells() {
+            assert_eq!(game.board.get_val(row, col), existing.get_val(row, col));
+            assert!(solution.carried_over[row-game.min_row][col-game.min_col]);
+        }
+        assert_eq!(solution.carried_over.iter().flatten().filter(|carried| **carried).count(), 4);
+        // The hand is used up, and the stored hand includes the supplied tiles
+        assert_eq!(game.board.occupied_cells().count(), 8);
+        assert_eq!(game.letters, string_to_letters("CATTSHOE").unwrap());
+        let (_, trie, _) = test_dictionary();
+        assert!(is_board_valid_full(&game.board, game.min_col, game.max_col, game.min_row, game.max_row, &trie, DEFAULT_MIN_WORD_LENGTH));
+        assert!(is_connected(&game.board, game.min_col, game.max_col, game.min_row, game.max_row, &Vec::new()));
+        // Boards that aren't connected or contain invalid words are rejected before solving
+        let split: Vec<Vec<String>> = vec![vec!["A".to_owned(), "T".to_owned(), " ".to_owned(), "A".to_owned(), "T".to_owned()]];
+        assert!(solve_from_board(&split, string_to_letters("S").unwrap(), &state).is_err());
+        let not_word: Vec<Vec<String>> = vec![vec!["Q".to_owned(), "X".to_owned()]];
+        assert!(solve_from_board(&not_word, string_to_letters("S").unwrap(), &state).is_err());
+    }
+
+    #[test]
+    fn dump_recommended_by_playable_words_lost() {
+        let words: Vec<Word> = ["CAT", "ACT", "AT", "TA", "ZA", "CAB"].iter().map(|w| convert_word_to_array(w)).collect();
+        let (trie, index) = (Trie::from_words(&words), DictionaryIndex::from_words(&words));
+        let hand = string_to_letters("CATZ").unwrap();
+        // CATZ makes CAT, ACT, AT, TA and ZA; dumping Z only loses ZA, while every other letter loses more
+        let mut results = evaluate_dumps(&hand, &words, &HashSet::new(), 2, BOARD_SIZE, None);
+        results.sort();
+        let [a, c, t, z] = ["A", "C", "T", "Z"].map(|letter| convert_word_to_array(letter)[0]);
+        assert_eq!(results, vec![(a, -5, false), (c, -2, false), (t, -4, false), (z, -1, false)]);
+        let state = test_state();
+        *state.custom_dictionary.lock().unwrap() = Some(Arc::new((words, trie, index)));
+        *state.dictionary.lock().unwrap() = DictionaryChoice::Custom;
+        assert_eq!(recommend_dump(&hand, &state).unwrap(), "Z");
+        // Ties go to the letter with the fewest copies, and blanks are never recommended
+        assert_eq!(recommend_dump(&string_to_letters("CCAATTQ?").unwrap(), &state).unwrap(), "Q");
+        assert!(recommend_dump(&string_to_letters("?").unwrap(), &state).is_err());
+    }
+
+    #[test]
+    fn dumps_ranked_by_quick_solve_then_words_gained() {
+        let words: Vec<Word> = ["CAT", "ACT", "AT", "TA"].iter().map(|w| convert_word_to_array(w)).collect();
+        let (trie, index) = (Trie::from_words(&words), DictionaryIndex::from_words(&words));
+        let state = test_state();
+        *state.custom_dictionary.lock().unwrap() = Some(Arc::new((words, trie, index)));
+        *state.dictionary.lock().unwrap() = DictionaryChoice::Custom;
+        // Only dumping the Q leaves a hand that can be solved, without losing any words
+        let ranked = rank_dumps(&string_to_letters("CATQ").unwrap(), &state).unwrap();
+        let summary: Vec<(&str, isize, bool)> = ranked.iter().map(|candidate| (candidate.letter.as_str(), candidate.words_gained, candidate.solvable)).collect();
+        assert_eq!(summary, vec![("Q", 0, true), ("C", -2, false), ("A", -4, false), ("T", -4, false)]);
+        assert!(rank_dumps(&[0usize; 27], &state).is_err());
+    }
+
+    #[test]
+    fn custom_dictionary_loaded_and_solved_with() {
+        let state = test_state();
+        let path = std::env::temp_dir().join("bananagrams_solver_custom_dictionary_test.txt");
+        fs::write(&path, "cat\ntag\nact\n").unwrap();
+        assert_eq!(load_dictionary(path.to_str().unwrap(), None, None, &state), Ok(3));
+        *state.dictionary.lock().unwrap() = DictionaryChoice::Custom;
+        let mut available_letters: HashMap<String, i64> = UPPERCASE.chars().map(|c| (c.to_string(), 0)).collect();
+        for c in "CATAG".chars() {
+            *available_letters.get_mut(&c.to_string()).unwrap() += 1;
+        }
+        let solutions = solve_and_store(available_letters, None, 1, None, false, None, false, &SettingsOverrides::default(), &state, None).unwrap();
+        let mut words: Vec<String> = solutions[0].words.iter().map(|word| word.word.clone()).collect();
+        words.sort();
+        assert!(words == vec!["CAT", "TAG"] || words == vec!["ACT", "TAG"]);
+        // A solve only holds its own copy of the dictionary, so another can be loaded in the meantime
+        let in_use = get_custom_dictionary(&state).unwrap().unwrap();
+        assert!(state.custom_dictionary.try_lock().is_ok());
+        fs::write(&path, "dog\n").unwrap();
+        assert_eq!(load_dictionary(path.to_str().unwrap(), None, None, &state), Ok(1));
+        assert_eq!(in_use.0.len(), 3);
+        assert_eq!(get_custom_dictionary(&state).unwrap().unwrap().0, vec![convert_word_to_array("DOG")]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn excluded_words_added_and_cleared() {
+        let state = test_state();
+        let (words, ..) = test_dictionary();
+        exclude_words(&["cat".to_owned(), "".to_owned()], &state).unwrap();
+        assert_eq!(*state.excluded_words.lock().unwrap(), HashSet::from([convert_word_to_array("CAT")]));
+        let (excluded, trie) = excluded_words_and_trie(DictionaryChoice::Short, Language::English, &words, &state).unwrap();
+        assert_eq!(excluded.len(), 1);
+        let trie = trie.unwrap();
+        assert!(!trie.contains(&convert_word_to_array("CAT")) && trie.contains(&convert_word_to_array("ACT")));
+        // The `Trie` is only built once until the excluded words change
+        assert!(Arc::ptr_eq(&trie, &excluded_words_and_trie(DictionaryChoice::Short, Language::English, &words, &state).unwrap().1.unwrap()));
+        exclude_words(&["Act".to_owned()], &state).unwrap();
+        assert!(state.excluded_tries.lock().unwrap().is_empty());
+        let trie = excluded_words_and_trie(DictionaryChoice::Short, Language::English, &words, &state).unwrap().1.unwrap();
+        assert!(!trie.contains(&convert_word_to_array("CAT")) && !trie.contains(&convert_word_to_array("ACT")));
+        unexclude_words(&state).unwrap();
+        assert!(state.excluded_words.lock().unwrap().is_empty() && state.excluded_tries.lock().unwrap().is_empty());
+        assert!(excluded_words_and_trie(DictionaryChoice::Short, Language::English, &words, &state).unwrap().1.is_none());
+    }
+
+    #[test]
+    fn excluded_words_never_played() {
+        let state = test_state();
+        let mut available_letters: HashMap<String, i64> = UPPERCASE.chars().map(|c| (c.to_string(), 0)).collect();
+        for c in "HELLOWORLD".chars() {
+            *available_letters.get_mut(&c.to_string()).unwrap() += 1;
+        }
+        let first = solve_and_store(available_letters.clone(), None, 1, None, false, None, true, &SettingsOverrides::default(), &state, None).unwrap().remove(0);
+        let first_words: Vec<String> = first.words.iter().map(|word| word.word.clone()).collect();
+        exclude_words(&first_words, &state).unwrap();
+        // Neither playing off of the previous board (which contains them) nor building from scratch uses the excluded words
+        for from_scratch in [false, true] {
+            let solution = solve_and_store(available_letters.clone(), None, 1, None, false, None, from_scratch, &SettingsOverrides::default(), &state, None).unwrap().remove(0);
+            assert!(solution.words.iter().all(|word| !first_words.contains(&word.word)));
+        }
+    }
+
+    #[test]
+    fn solve_time_read_from_timeout_setting() {
+        let settings = |json: &str| serde_json::from_str::<CurrentSettings>(&format!(r#"{{"filter_letters_on_board": 2, "maximum_words_to_check": 50000, {}, "board_size": 144, "dictionary": "Short", "use_frequency_ordering": false}}"#, json)).unwrap().max_solve_time_ms;
+        assert_eq!(settings(r#""max_solve_time_ms": 500"#), 500);
+        assert_eq!(settings(r#""timeout_ms": 500"#), 500);
+        assert_eq!(settings(r#""timeout_ms": null"#), 0);
+        // A time limit can also be given for a single solve
+        let overrides: SettingsOverrides = serde_json::from_str(r#"{"timeout_ms": 250}"#).unwrap();
+        assert_eq!(overrides.apply(current_settings(&test_state()).unwrap()).unwrap().max_solve_time_ms, 250);
+    }
+
+    #[test]
+    fn seeded_game_played_to_completion() {
+        let state = test_state();
+        // A small set of common letters keeps the game short
+        //                 A  B  C  D  E  F  G  H  I  J  K  L  M  N  O  P  Q  R  S  T  U  V  W  X  Y  Z
+        let tile_counts = [3, 1, 1, 2, 6, 1, 1, 1, 3, 0, 0, 2, 1, 3, 3, 1, 0, 3, 2, 3, 1, 0, 1, 0, 1, 0];
+        let total = tile_counts.iter().sum::<u64>() as usize;
+        *state.tile_set.lock().unwrap() = TileSet::Custom(tile_counts);
+        *state.deterministic.lock().unwrap() = true;
+        *state.max_solve_time_ms.lock().unwrap() = 5_000;
+        let game = simulate(7, 21, &state).unwrap();
+        // Every peel was solved, and the final board holds the whole set
+        assert_eq!(game.elapsed.len(), total-21+1-2*game.dumps);
+        let tiles = game.board.iter().flatten().filter(|cell| !cell.trim().is_empty()).count();
+        assert_eq!(tiles, total);
+        // The same seed plays the same game
+        assert_eq!(simulate(7, 21, &state).unwrap().board, game.board);
+    }
+
+    #[test]
+    fn simulation_cancelled_between_peels() {
+        let state = Arc::new(test_state());
+        // Cancelling only takes effect once the current solve ends
+        *state.max_solve_time_ms.lock().unwrap() = 500;
+        // A cancel from before the simulation started is ignored
+        state.stop_solving.store(true, Ordering::Relaxed);
+        let state_t = Arc::clone(&state);
+        let handle = thread::spawn(move || simulate(7, 21, &state_t));
+        thread::sleep(Duration::from_millis(200));
+        state.stop_solving.store(true, Ordering::Relaxed);
+        let result = handle.join().unwrap();
+        assert!(matches!(result, Err(e) if e.starts_with("Cancelled after")));
+    }
+}
@@ -0,0 +1,3226 @@
+//! The recursive search that plays a hand onto a board (see `solve_hand`), along with `Solver` and `solve_with` for solving without the Tauri app state, so that full solves can be run from the app, tests, benchmarks or the command line
+
+use std::cmp::Reverse;
+use std::num::NonZeroUsize;
+use std::{fmt, thread};
+use hashbrown::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use std::cell::RefCell;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize, Serializer};
+use crate::{convert_array_to_word, convert_word_to_array, is_makeable, letter_index, letters_add, letters_count, letters_sub, letters_to_chars, BoardAndIdxs, Letters, Word, BLANK, BLANK_CHAR, BOARD_SIZE, DEFAULT_MIN_WORD_LENGTH, LETTER_VALUES, MAX_WORD_LENGTH};
+use crate::board::{board_layout_hash, board_to_vec, carried_over_to_vec, get_board_overlap, get_removable_indices, get_words_on_board, is_board_valid_full, is_board_valid_horizontal, is_board_valid_vertical, is_connected, is_word_on_board, play_formed_valid_words, score_board, score_solution, stranded_letters, Board, Direction, LetterUsage, PlayedWord, SolutionScore};
+#[cfg(feature = "diagonal")]
+use crate::board::{is_board_valid_diagonal, MIN_DIAGONAL_WORD_LENGTH};
+use crate::trie::{indexed_words, DictionaryIndex, IndexedWord, Trie};
+
+/// Settings for a solve
+#[derive(Clone)]
+pub struct SolveConfig {
+    /// Maximum number of letters currently on the board that can be used in a newly played word
+    pub filter_letters_on_board: usize,
+    /// Maximum number of words to check before stopping
+    pub max_words_to_check: usize,
+    /// Maximum number of threads to solve with (if `None`, then the available parallelism is used; see `solver_threads`)
+    pub threads: Option<usize>,
+    /// Maximum number of milliseconds to spend on the solve before stopping (if 0, then there is no limit)
+    pub max_solve_time_ms: u64,
+    /// Whether to try words containing the most common remaining letters first (including the first word; see `order_by_priority` and `best_first_word`)
+    pub use_frequency_ordering: bool,
+    /// If given, solve using a single thread with words ordered by this seed (see `order_for_seed`), so that the result is repeatable
+    pub seed: Option<u64>,
+    /// If given, build from scratch by racing this many randomly seeded solves (see `solve_hand_multiseed`), ignoring `seed`, `num_solutions` and `required_word`
+    pub seed_count: Option<usize>,
+    /// How many distinct boards to look for when building from scratch; with 1, every thread stops as soon as any finds a board (the others are returned in `SolvedHand::alternatives`)
+    pub num_solutions: usize,
+    /// Which solutions to prefer when building from scratch; unless `Fastest`, the search continues after the first solution (see `EXTRA_SEARCH_DIVISOR`) and the best solutions found are returned
+    pub solution_preference: SolutionPreference,
+    /// Whether to keep the closest attempt from scratch (the board that left the fewest letters unplayed, with those letters in `SolvedHand::unplaced`) rather than `SolverError::LettersLeftOver` or `SolverError::StrandedLetters` if no board uses the whole hand; not used when racing seeds
+    pub allow_partial: bool,
+    /// Number of rows/columns in the board
+    pub board_size: usize,
+    /// Maximum number of columns the solved board can span (if `None`, then the board size); the first word played from scratch must fit across it
+    pub max_width: Option<usize>,
+    /// Maximum number of rows the solved board can span (if `None`, then the board size)
+    pub max_height: Option<usize>,
+    /// Minimum length of the words formed on the board
+    pub min_word_length: usize,
+    /// If given, a word the board must contain (see `check_required_word`): it is played first, either off of the previous board (see `play_existing`) or as the first word from scratch
+    pub required_word: Option<Word>,
+    /// Whether the tiles can be re-centered once they spread across most of the board (see `play_further`); never done when playing off of a previous board, whose tiles must stay where they are
+    pub recenter: bool
+}
+impl Default for SolveConfig {
+    /// Gets the same settings that the app starts with
+    fn default() -> Self {
+        SolveConfig { filter_letters_on_board: 2, max_words_to_check: 50_000, threads: None, max_solve_time_ms: 0, use_frequency_ordering: false, seed: None, seed_count: None, num_solutions: 1, solution_preference: SolutionPreference::Fastest, allow_partial: false, board_size: BOARD_SIZE, max_width: None, max_height: None, min_word_length: DEFAULT_MIN_WORD_LENGTH, required_word: None, recenter: true }
+    }
+}
+
+/// A solved hand, which the next hand can be played off of (see `Solver::solve_incremental`)
+pub struct SolvedBoard {
+    /// The solved board and the hand it uses (without any unplaced letters)
+    pub game: GameState,
+    /// How the hand was solved
+    pub method: SolveMethod,
+    /// Indices of the tiles that are in the same place as on the previous board
+    pub(crate) previous_idxs: HashSet<(usize, usize)>,
+    /// Other distinct solutions found when more than one was asked for, each with its indices in the same place as on the previous board
+    alternatives: Vec<(BoardAndIdxs, HashSet<(usize, usize)>)>,
+    /// The letters of the hand that aren't on the board (only when a partial board is allowed)
+    pub unplaced: Letters,
+    /// How long it took to solve the hand in milliseconds
+    pub elapsed: u128,
+    /// Whether the hand was solved deterministically
+    pub deterministic: bool
+}
+impl SolvedBoard {
+    /// Converts the board, followed by any alternatives, into `Solution`s to send to the frontend
+    /// # Returns
+    /// `Vec<Solution>` - At least one `Solution`, best first
+    pub fn solutions(&self) -> Vec<Solution> {
+        let game = &self.game;
+        let mut solutions = vec![solution(&game.board, game.min_col, game.max_col, game.min_row, game.max_row, &self.previous_idxs, letters_to_chars(&self.unplaced), self.elapsed, self.deterministic)];
+        for ((board, min_col, max_col, min_row, max_row), previous_idxs) in self.alternatives.iter() {
+            solutions.push(solution(board, *min_col, *max_col, *min_row, *max_row, previous_idxs, Vec::new(), self.elapsed, self.deterministic));
+        }
+        solutions
+    }
+}
+
+/// Creates the `Solution` of a board
+/// # Arguments
+/// * `board` - The solved board
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `previous_idxs` - Indices of the tiles that are in the same place as on the previous board
+/// * `unplaced` - The letters of the hand that aren't on the board
+/// * `elapsed` - How long it took to solve the board in milliseconds
+/// * `deterministic` - Whether the board was solved deterministically
+/// # Returns
+/// `Solution` - The board, its words and its scores
+fn solution(board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, previous_idxs: &HashSet<(usize, usize)>, unplaced: Vec<char>, elapsed: u128, deterministic: bool) -> Solution {
+    Solution { board: board_to_vec(board, min_col, max_col, min_row, max_row), carried_over: carried_over_to_vec(min_col, max_col, min_row, max_row, previous_idxs), min_row, min_col, max_row, max_col, words: get_words_on_board(board, min_col, max_col, min_row, max_row), score: score_board(board, min_col, max_col, min_row, max_row), solution_score: score_solution(board, min_col, max_col, min_row, max_row), unplaced, elapsed, deterministic, used_long_dictionary: false, long_only_words: Vec::new() }
+}
+
+/// Solves a hand, playing off of the previous board if there is one
+/// # Arguments
+/// * `dict_to_use` - Dictionary of words to play
+/// * `dictionary_trie` - `Trie` of `dict_to_use` without `excluded_words`, so that they can't be formed incidentally either
+/// * `dictionary_index` - `DictionaryIndex` of `dict_to_use`
+/// * `previous` - The previous game state (if any)
+/// * `letters` - The number of each letter (and blanks) in the hand
+/// * `config` - Settings for the solve
+/// * `excluded_words` - Words that must not be played
+/// * `stop` - `AtomicBool` that, when set, indicates that solving should stop (it is not reset before solving)
+/// * `on_partial` - Callback that is sent the board partway through solving (see `report_partial`); not used when racing seeds
+/// # Returns
+/// `Result` with the `SolvedBoard`
+///
+/// *or `SolverError` upon failure or not finding a board (with the reason indicated by the variant)*
+pub fn solve_with(dict_to_use: &Vec<Word>, dictionary_trie: &Trie, dictionary_index: &DictionaryIndex, previous: &Option<GameState>, letters: Letters, config: &SolveConfig, excluded_words: &HashSet<Word>, stop: &Arc<AtomicBool>, on_partial: Option<&PartialCallback>) -> Result<SolvedBoard, SolverError> {
+    let now = Instant::now();
+    let deadline = get_deadline(now, config.max_solve_time_ms);
+    let (solved, deterministic) = match config.seed_count {
+        Some(seed_count) => {
+            // Racing seeds isn't deterministic, since the result depends on which finishes first
+            let first_seed: u64 = thread_rng().gen();
+            let seeds: Vec<u64> = (0..seed_count as u64).map(|i| first_seed.wrapping_add(i)).collect();
+            (solve_hand_multiseed(letters, dict_to_use, dictionary_trie, dictionary_index, excluded_words, config, &seeds, deadline, stop)?, false)
+        },
+        None => (solve_hand(letters, previous, dict_to_use, dictionary_trie, dictionary_index, excluded_words, config, deadline, stop, on_partial)?, config.seed.is_some())
+    };
+    let (board, min_col, max_col, min_row, max_row) = solved.board;
+    debug_assert!(is_connected(&board, min_col, max_col, min_row, max_row, &Vec::new()), "Solved board is split into more than one group of tiles");
+    debug_assert!(solved.alternatives.iter().all(|((board, min_col, max_col, min_row, max_row), _)| is_connected(board, *min_col, *max_col, *min_row, *max_row, &Vec::new())), "Alternative board is split into more than one group of tiles");
+    // The unplaced letters are still in the hand rather than on the board, so the next hand is played off of just the tiles on the board
+    let placed = letters_sub(&letters, &solved.unplaced).expect("The unplaced letters are part of the hand");
+    Ok(SolvedBoard { game: GameState { board, min_col, max_col, min_row, max_row, letters: placed }, method: solved.method, previous_idxs: solved.previous_idxs, alternatives: solved.alternatives, unplaced: solved.unplaced, elapsed: now.elapsed().as_millis(), deterministic })
+}
+
+/// A dictionary that hands can be solved with
+pub struct Solver {
+    /// The words of the dictionary, sorted by descending length
+    pub words: Vec<Word>,
+    /// `Trie` of `words`
+    pub trie: Trie,
+    /// `DictionaryIndex` of `words`
+    index: DictionaryIndex
+}
+impl Solver {
+    /// Creates a `Solver` that uses the given words
+    /// # Arguments
+    /// * `dictionary` - The words that can be played (case-insensitive; accented letters are played as their base letter)
+    /// # Returns
+    /// `Solver` - The solver
+    pub fn new(dictionary: Vec<String>) -> Solver {
+        let mut words: Vec<Word> = dictionary.iter().map(|word| convert_word_to_array(&word.to_uppercase())).filter(|word| !word.is_empty()).collect();
+        words.sort_by_key(|word| Reverse(word.len()));
+        let trie = Trie::from_words(&words);
+        let index = DictionaryIndex::from_words(&words);
+        Solver { words, trie, index }
+    }
+
+    /// Solves a hand from scratch
+    /// # Arguments
+    /// * `letters` - The number of each letter (and blanks) in the hand
+    /// * `config` - Settings for the solve
+    /// # Returns
+    /// `Result` with the `SolvedBoard`
+    ///
+    /// *or `SolverError` upon failure or not finding a board (with the reason indicated by the variant)*
+    pub fn solve(&self, letters: Letters, config: SolveConfig) -> Result<SolvedBoard, SolverError> {
+        solve_with(&self.words, &self.trie, &self.index, &None, letters, &config, &HashSet::new(), &Arc::new(AtomicBool::new(false)), None)
+    }
+
+    /// Solves a hand by playing off of a previous board where possible (e.g. after a peel), otherwise from scratch
+    /// # Arguments
+    /// * `prev` - The previously solved board
+    /// * `letters` - The number of each letter (and blanks) in the new hand
+    /// * `config` - Settings for the solve
+    /// # Returns
+    /// `Result` with the `SolvedBoard`
+    ///
+    /// *or `SolverError` upon failure or not finding a board (with the reason indicated by the variant)*
+    pub fn solve_incremental(&self, prev: &SolvedBoard, letters: Letters, config: SolveConfig) -> Result<SolvedBoard, SolverError> {
+        solve_with(&self.words, &self.trie, &self.index, &Some(prev.game.clone()), letters, &config, &HashSet::new(), &Arc::new(AtomicBool::new(false)), None)
+    }
+}
+
+/// Callback that receives the board partway through solving (see `play_bananagrams_stream`)
+pub type PartialCallback = Arc<dyn Fn(PartialSolution) + Send + Sync>;
+/// Spare lists of words that each level of the recursive search filters into, so that a new list doesn't have to be allocated for every word played (see `play_further`)
+pub(crate) type WordBuffers<'w> = Vec<Vec<IndexedWord<'w>>>;
+/// Whether a word could be validly played, plus the board's new minimum and maximum played columns and rows (see `try_play_word_horizontal`)
+type PlayOutcome = (bool, usize, usize, usize, usize);
+/// A board found by a thread of `solve_hand`: a hash of its layout (see `board_layout_hash`), the indices in the same place as on the previous board, and the board with its minimum and maximum played columns and rows
+type FoundBoard = (u64, HashSet<(usize, usize)>, Board, usize, usize, usize, usize);
+
+/// When preferring a kind of board (see `SolutionPreference`), the search continues after the first solution for 1/N of the words checked to find it
+const EXTRA_SEARCH_DIVISOR: usize = 5;
+/// Number of recursion depths that `SolverStats` counts words for (deeper words are counted with the deepest)
+const STATS_DEPTHS: usize = 20;
+/// Number of words checked between each check of the solve's deadline (see `should_stop`)
+const DEADLINE_CHECK_INTERVAL: usize = 1024;
+
+thread_local! {
+    /// Statistics of the searches run on the current thread (see `take_solver_stats`)
+    static SOLVER_STATS: RefCell<SolverStats> = RefCell::new(SolverStats::default());
+}
+
+/// Removes words that can't be played with `current_letters` plus a set number of `board_letters`
+/// # Arguments
+/// * `current_letters` - Letters currently available in the hand
+/// * `board_letters` - Letters played on the board
+/// * `word_being_checked` - Word to check if it contains the appropriate number of letters
+/// * `filter_letters_on_board` - Maximum number of letters from `board_letters` that can be used when checking if the word can be played
+/// * `trie` - If given, a `Trie` to walk alongside the word, rejecting it as soon as its prefix can't be continued with the remaining letters
+/// # Returns
+/// * `bool` - Whether `word_being_checked` should pass the filter
+pub fn check_filter_after_play_later(mut current_letters: Letters, mut board_letters: Letters, word_being_checked: &Word, filter_letters_on_board: usize, trie: Option<&Trie>) -> bool {
+    // Every letter comes from the hand or (up to the filter) the board, so longer words can't be played
+    if word_being_checked.len() > letters_count(&current_letters) + filter_letters_on_board {
+        return false;
+    }
+    // If no board letters can be used, then the word just has to be makeable from the hand (and a word of the trie, if given)
+    if filter_letters_on_board == 0 {
+        return is_makeable(word_being_checked, &current_letters) && trie.is_none_or(|t| t.contains(word_being_checked));
+    }
+    let mut num_from_board = 0usize;
+    let mut position = (0usize, 0usize);
+    for (i, letter) in word_being_checked.iter().enumerate() {
+        if let Some(t) = trie {
+            // Check that some word continues the prefix with a letter we could still play
+            let can_use_board = num_from_board < filter_letters_on_board;
+            if !t.can_continue(position.0, position.1, |l| current_letters[l] > 0 || current_letters[BLANK] > 0 || (can_use_board && board_letters[l] > 0)) {
+                return false;
+            }
+            match t.step(position.0, position.1, *letter) {
+                Some(p) => position = p,
+                None => return false
+            }
+            if i == word_being_checked.len()-1 && (position.1 < t.nodes[position.0].label.len() || !t.nodes[position.0].is_word) {
+                return false;
+            }
+        }
+        // Use a blank from the hand before using a letter from the board
+        if current_letters[*letter] == 0 && current_letters[BLANK] > 0 {
+            current_letters[BLANK] -= 1;
+            continue;
+        }
+        let num_in_hand = current_letters.get_mut(*letter).unwrap();
+        if *num_in_hand == 0 {
+            if num_from_board == filter_letters_on_board {
+                return false;
+            }
+            let num_on_board = board_letters.get_mut(*letter).unwrap();
+            if *num_on_board == 0 {
+                return false;
+            }
+            *num_on_board -= 1;
+            num_from_board += 1;
+        }
+        else {
+            *num_in_hand -= 1;
+        }
+    }
+    true
+}
+
+/// Records a word being checked at the given depth in the current thread's `SolverStats`
+/// # Arguments
+/// * `depth` - Depth of the recursive call checking the word
+fn record_word_checked(depth: usize) {
+    SOLVER_STATS.with(|stats| {
+        let mut stats = stats.borrow_mut();
+        stats.max_depth_reached = stats.max_depth_reached.max(depth);
+        stats.words_checked_at_each_depth[depth.min(STATS_DEPTHS-1)] += 1;
+    });
+}
+
+/// Records a backtrack (i.e. undoing a word after everything played after it failed) in the current thread's `SolverStats`
+fn record_backtrack() {
+    SOLVER_STATS.with(|stats| stats.borrow_mut().backtracks += 1);
+}
+
+/// Records the letters left in the hand after validly playing a word in the current thread's `SolverStats`, along with a copy of the board, if fewer are left than after any previous play
+/// # Arguments
+/// * `letters` - Letters remaining in the hand
+/// * `board` - The board after playing the word
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+fn record_letters_remaining(letters: &Letters, board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize) {
+    let remaining: usize = letters.iter().sum();
+    SOLVER_STATS.with(|stats| {
+        let mut stats = stats.borrow_mut();
+        if stats.fewest_letters_remaining.as_ref().is_none_or(|(fewest, _, _)| remaining < *fewest) {
+            // The board is only copied when a new closest attempt is reached, which happens at most once per tile in the hand
+            stats.fewest_letters_remaining = Some((remaining, *letters, (board.clone(), min_col, max_col, min_row, max_row)));
+        }
+    });
+}
+
+/// Adds statistics (such as those of a finished solver thread) to the current thread's `SolverStats`
+/// # Arguments
+/// * `other` - Statistics to add
+fn add_solver_stats(other: &SolverStats) {
+    SOLVER_STATS.with(|stats| stats.borrow_mut().merge(other));
+}
+
+/// Takes the statistics recorded on the current thread, resetting them
+/// 
+/// Solver threads pass their statistics back to the thread that spawned them when they finish, so this includes every search run on this thread's behalf
+/// # Returns
+/// `SolverStats` - Statistics recorded since they were last taken
+pub fn take_solver_stats() -> SolverStats {
+    SOLVER_STATS.with(|stats| std::mem::take(&mut *stats.borrow_mut()))
+}
+
+/// Sends the board to `on_partial` after a word has been played at the top level of the search
+/// # Arguments
+/// * `on_partial` - Callback to send the board to (if `None`, nothing is sent)
+/// * `depth` - Depth of the recursive call that played the word (nothing is sent unless this is 0)
+/// * `board` - The board
+/// * `min_col` - Minimum occupied column index
+/// * `max_col` - Maximum occupied column index
+/// * `min_row` - Minimum occupied row index
+/// * `max_row` - Maximum occupied row index
+/// * `letters` - Letters remaining in the hand
+/// * `words_checked` - The number of words checked so far
+fn report_partial(on_partial: Option<&PartialCallback>, depth: usize, board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, letters: &Letters, words_checked: usize) {
+    if let Some(callback) = on_partial {
+        if depth == 0 {
+            callback(PartialSolution { board: board_to_vec(board, min_col, max_col, min_row, max_row), letters_remaining: letters.iter().sum(), words_checked });
+        }
+    }
+}
+
+/// Checks which words can be played after the first
+/// # Arguments
+/// * `letters` - Length-26 array of originally available letters
+/// * `word_being_checked` - Word that is being checked if playable
+/// * `played_on_board` - Set of the letters played on the board
+/// # Returns
+/// * `bool` - Whether the `word_being_checked` is playable
+fn check_filter_after_play(mut letters: Letters, word_being_checked: &Word, played_on_board: &HashSet<usize>) -> bool {
+    let mut already_seen_negative = false;
+    for letter in word_being_checked.iter() {
+        if letters[*letter] == 0 && letters[BLANK] > 0 {
+            letters[BLANK] -= 1;
+            continue;
+        }
+        let elem = letters.get_mut(*letter).unwrap();
+        if *elem == 0 && (!played_on_board.contains(letter) || already_seen_negative) {
+            return false;
+        }
+        else if *elem == 0 {
+            already_seen_negative = true;
+        }
+        else {
+            *elem -= 1;
+        }
+    }
+    true
+}
+
+/// Gets the minimum and maximum columns where a word could be played at `row` on `board`
+/// # Arguments
+/// * `board` - Board to search
+/// * `row` - Row to check
+/// * `min_col` - Minimum occupied column on `board`
+/// * `max_col` - Maximum occupied column on `board`
+/// # Returns
+/// * `(usize, usize)` - Length-2 tuple of the (minimum column, maximum column) where a word could be played, i.e. the first and last columns in which `row` or a row next to it holds a tile
+pub fn get_col_limits(board: &Board, row: usize, min_col: usize, max_col: usize) -> (usize, usize) {
+    let mut leftmost = max_col;
+    let mut rightmost = min_col;
+    if row == 0 {
+        for col in min_col..max_col {
+            if !board.get_val(row, col).is_empty() || !board.get_val(row+1, col).is_empty() {
+                leftmost = col;
+                break;
+            }
+        }
+        for col in (min_col..=max_col).rev() {
+            if !board.get_val(row, col).is_empty() || !board.get_val(row+1, col).is_empty() {
+                rightmost = col;
+                break;
+            }
+        }
+    }
+    else if row == board.size()-1 {
+        for col in min_col..max_col {
+            if !board.get_val(row, col).is_empty() || !board.get_val(row-1, col).is_empty() {
+                leftmost = col;
+                break;
+            }
+        }
+        for col in (min_col..=max_col).rev() {
+            if !board.get_val(row, col).is_empty() || !board.get_val(row-1, col).is_empty() {
+                rightmost = col;
+                break;
+            }
+        }
+    }
+    else {
+        for col in min_col..max_col {
+            if !board.get_val(row-1, col).is_empty() || !board.get_val(row, col).is_empty() || !board.get_val(row+1, col).is_empty() {
+                leftmost = col;
+                break;
+            }
+        }
+        for col in (min_col..=max_col).rev() {
+            if !board.get_val(row-1, col).is_empty() || !board.get_val(row, col).is_empty() || !board.get_val(row+1, col).is_empty() {
+                rightmost = col;
+                break;
+            }
+        }
+    }
+    (leftmost, rightmost)
+}
+
+/// Gets the minimum and maximum rows where a word could be played at `col` on `board`
+/// # Arguments
+/// * `board` - Board to search
+/// * `col` - Column to check
+/// * `min_row` - Minimum occupied row on `board`
+/// * `max_row` - Maximum occupied row on `board`
+/// # Returns
+/// * `(usize, usize)` - Length-2 tuple of the (minimum row, maximum row) where a word could be played
+pub fn get_row_limits(board: &Board, col: usize, min_row: usize, max_row: usize) -> (usize, usize) {
+    let mut uppermost = min_row;
+    let mut lowermost = max_row;
+    if col == 0 {
+        for row in min_row..max_row {
+            if !board.get_val(row, col).is_empty() || !board.get_val(row, col+1).is_empty() {
+                uppermost = row;
+                break;
+            }
+        }
+        for row in (min_row..=max_row).rev() {
+            if !board.get_val(row, col).is_empty() || !board.get_val(row, col+1).is_empty() {
+                lowermost = row;
+                break;
+            }
+        }
+    }
+    else if col == board.size()-1 {
+        for row in min_row..max_row {
+            if !board.get_val(row, col).is_empty() || !board.get_val(row, col-1).is_empty() {
+                uppermost = row;
+                break;
+            }
+        }
+        for row in (min_row..=max_row).rev() {
+            if !board.get_val(row, col).is_empty() || !board.get_val(row, col-1).is_empty() {
+                lowermost = row;
+                break;
+            }
+        }
+    }
+    else {
+        for row in min_row..max_row {
+            if !board.get_val(row, col-1).is_empty() || !board.get_val(row, col).is_empty() || !board.get_val(row, col+1).is_empty() {
+                uppermost = row;
+                break;
+            }
+        }
+        for row in (min_row..=max_row).rev() {
+            if !board.get_val(row, col-1).is_empty() || !board.get_val(row, col).is_empty() || !board.get_val(row, col+1).is_empty() {
+                lowermost = row;
+                break;
+            }
+        }
+    }
+    (uppermost, lowermost)
+}
+
+/// Tries to play a word horizontally anywhere on the `board`
+/// # Arguments
+/// * `board` - The `Board` to modify in-place
+/// * `word` - Word to try to play
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `valid_words_vec` - Vector of vectors, each representing a word (see `convert_word_to_array`)
+/// * `word_buffers` - Spare word lists to filter into when playing further (see `WordBuffers`)
+/// * `valid_words_trie` - `Trie` of all valid words (for faster membership checking)
+/// * `dictionary_index` - `DictionaryIndex` of the dictionary the words come from
+/// * `letters` - Length-26 array of the number of each letter in the hand
+/// * `depth` - Depth of the current recursive call
+/// * `words_checked` - The number of words checked in total
+/// * `letters_on_board` - Length-26 array of the number of each letter currently present on the `board`
+/// * `overused_letters` - Bitmask to which each letter that `word` needed more of than the hand has is added (see `letter_mask`)
+/// * `config` - Settings for the search (see `SolveConfig`)
+/// * `deadline` - Time after which to stop checking words (if `None`, then there is no time limit)
+/// * `stop_t` - `AtomicBool` that, when set, indicates that processing should stop
+/// * `on_partial` - Callback that is sent the board whenever a word is played at the top level of the search (see `report_partial`)
+/// # Returns
+/// *`Result` with `Option` upon success with:*
+/// * `bool` - Whether the word could be validly played
+/// * `usize` - Minimum occupied column index in `board`
+/// * `usize` - Maximum occupied column index in `board`
+/// * `usize` - Minimum occupied row index in `board`
+/// * `usize` - Maximum occupied row index in `board`
+/// 
+/// *or `None` if no valid playing location was found, or empty `Err` another thread signalled to stop*
+fn try_play_word_horizontal<'w>(board: &mut Board, word: &IndexedWord, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words_vec: &[IndexedWord<'w>], word_buffers: &mut WordBuffers<'w>, valid_words_trie: &Trie, dictionary_index: &DictionaryIndex, letters: Letters, depth: usize, words_checked: &mut usize, letters_on_board: &mut Letters, overused_letters: &mut usize, config: &SolveConfig, deadline: Option<Instant>, stop_t: &Arc<AtomicBool>, on_partial: Option<&PartialCallback>) -> Result<Option<PlayOutcome>, ()> {
+    // Try across all rows (starting from one before to one after)
+    for row_idx in min_row.saturating_sub(1)..=(board.size()-1).min(max_row+1) {
+        let (leftmost_col, rightmmost_col) = get_col_limits(board, row_idx, min_col, max_col);
+        // For each row, try across all columns (starting from the farthest out the word could be played)
+        for col_idx in leftmost_col.saturating_sub(word.len())..=(board.size()-1).min(rightmmost_col+1) {
+            let res = board.play_word(word, row_idx, col_idx, Direction::Horizontal, &letters, letters_on_board);
+            if res.0 {
+                // If the word was played successfully (i.e. it's not a complete overlap and it borders at least one existing tile), then check the validity of the new words it forms
+                let new_min_col = min_col.min(col_idx);
+                let new_max_col = max_col.max(col_idx+word.len()-1);
+                let new_min_row = min_row.min(row_idx);
+                let new_max_row = max_row.max(row_idx);
+                if board.fits_span(new_min_col, new_max_col, new_min_row, new_max_row) && is_board_valid_horizontal(board, new_min_col, new_max_col, new_min_row, new_max_row, row_idx, col_idx, col_idx+word.len()-1, valid_words_trie, config.min_word_length) {
+                    debug_assert!(play_formed_valid_words(board, new_min_col, new_max_col, new_min_row, new_max_row, &res.1, valid_words_trie, config.min_word_length), "Horizontal play of {} at ({}, {}) formed an invalid word", convert_array_to_word(word), row_idx, col_idx);
+                    // If it's valid, go to the next recursive level (unless we've all the letters, at which point we're done)
+                    match res.3 {
+                        LetterUsage::Finished => {
+                            return Ok(Some((true, new_min_col, new_max_col, new_min_row, new_max_row)));
+                        },
+                        LetterUsage::Remaining => {
+                            record_letters_remaining(&res.2, board, new_min_col, new_max_col, new_min_row, new_max_row);
+                            report_partial(on_partial, depth, board, new_min_col, new_max_col, new_min_row, new_max_row, &res.2, *words_checked);
+                            let mut new_valid_words_vec = word_buffers.pop().unwrap_or_default();
+                            dictionary_index.filter_words_into(valid_words_vec, &letters, letters_on_board, config.filter_letters_on_board, &mut new_valid_words_vec);
+                            let res2 = play_further(board, new_min_col, new_max_col, new_min_row, new_max_row, &new_valid_words_vec, word_buffers, valid_words_trie, dictionary_index, res.2, depth+1, Some(word.position), words_checked, letters_on_board, config, deadline, stop_t, on_partial);
+                            word_buffers.push(new_valid_words_vec);
+                            let res2 = res2?;
+                            if res2.0 {
+                                // If that recursive stack finishes successfully, we're done! (could have used another Result or Option rather than a bool in the returned tuple, but oh well)
+                                return Ok(Some(res2));
+                            }
+                            else {
+                                // Otherwise, undo the previous play (cloning the board before each play so we don't have to undo is *way* slower)
+                                board.undo_play(&res.1, letters_on_board);
+                                record_backtrack();
+                            }
+                        },
+                        LetterUsage::Overused(_) => unreachable!()
+                    }
+                }
+                else {
+                    // If the play formed some invalid words (or left the board disconnected or too large), undo the previous play
+                    board.undo_play(&res.1, letters_on_board);
+                }
+            }
+            else {
+                // If trying to play the board was invalid, undo the play (noting if it was because the hand ran out of a letter)
+                if let LetterUsage::Overused(letter) = res.3 {
+                    *overused_letters |= 1 << letter;
+                }
+                board.undo_play(&res.1, letters_on_board);
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Tries to play a word vertically anywhere on the `board`
+/// # Arguments
+/// * `board` - The `Board` to modify in-place
+/// * `word` - Word to try to play
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `valid_words_vec` - Vector of vectors, each representing a word (see `convert_word_to_array`)
+/// * `word_buffers` - Spare word lists to filter into when playing further (see `WordBuffers`)
+/// * `valid_words_trie` - `Trie` of all valid words (for faster membership checking)
+/// * `dictionary_index` - `DictionaryIndex` of the dictionary the words come from
+/// * `letters` - Length-26 array of the number of each letter in the hand
+/// * `depth` - Depth of the current recursive call
+/// * `words_checked` - The number of words checked in total
+/// * `letters_on_board` - Length-26 array of the number of each letter currently present on the `board`
+/// * `overused_letters` - Bitmask to which each letter that `word` needed more of than the hand has is added (see `letter_mask`)
+/// * `config` - Settings for the search (see `SolveConfig`)
+/// * `deadline` - Time after which to stop checking words (if `None`, then there is no time limit)
+/// * `stop_t` - `AtomicBool` that, when set, indicates that processing should stop
+/// * `on_partial` - Callback that is sent the board whenever a word is played at the top level of the search (see `report_partial`)
+/// # Returns
+/// *`Result` with `Option` upon success with:*
+/// * `bool` - Whether the word could be validly played
+/// * `usize` - Minimum occupied column index in `board`
+/// * `usize` - Maximum occupied column index in `board`
+/// * `usize` - Minimum occupied row index in `board`
+/// * `usize` - Maximum occupied row index in `board`
+/// 
+/// *or `None` if no valid playing location was found, or empty `Err` if another thread signalled to stop*
+fn try_play_word_vertically<'w>(board: &mut Board, word: &IndexedWord, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words_vec: &[IndexedWord<'w>], word_buffers: &mut WordBuffers<'w>, valid_words_trie: &Trie, dictionary_index: &DictionaryIndex, letters: Letters, depth: usize, words_checked: &mut usize, letters_on_board: &mut Letters, overused_letters: &mut usize, config: &SolveConfig, deadline: Option<Instant>, stop_t: &Arc<AtomicBool>, on_partial: Option<&PartialCallback>) -> Result<Option<PlayOutcome>, ()> {
+    // Try down all columns
+    for col_idx in min_col.saturating_sub(1)..=(board.size()-1).min(max_col+1) {
+        let (uppermost_row, lowermost_row) = get_row_limits(board, col_idx, min_row, max_row);
+        // This is analagous to the above
+        for row_idx in uppermost_row.saturating_sub(word.len())..=(board.size()-1).min(lowermost_row+1) {
+            let res = board.play_word(word, row_idx, col_idx, Direction::Vertical, &letters, letters_on_board);
+            if res.0 {
+                let new_min_col = min_col.min(col_idx);
+                let new_max_col = max_col.max(col_idx);
+                let new_min_row = min_row.min(row_idx);
+                let new_max_row = max_row.max(row_idx+word.len()-1);
+                if board.fits_span(new_min_col, new_max_col, new_min_row, new_max_row) && is_board_valid_vertical(board, new_min_col, new_max_col, new_min_row, new_max_row, row_idx, row_idx+word.len()-1, col_idx, valid_words_trie, config.min_word_length) {
+                    debug_assert!(play_formed_valid_words(board, new_min_col, new_max_col, new_min_row, new_max_row, &res.1, valid_words_trie, config.min_word_length), "Vertical play of {} at ({}, {}) formed an invalid word", convert_array_to_word(word), row_idx, col_idx);
+                    match res.3 {
+                        LetterUsage::Finished => {
+                            return Ok(Some((true, new_min_col, new_max_col, new_min_row, new_max_row)));
+                        },
+                        LetterUsage::Remaining => {
+                            record_letters_remaining(&res.2, board, new_min_col, new_max_col, new_min_row, new_max_row);
+                            report_partial(on_partial, depth, board, new_min_col, new_max_col, new_min_row, new_max_row, &res.2, *words_checked);
+                            let mut new_valid_words_vec = word_buffers.pop().unwrap_or_default();
+                            dictionary_index.filter_words_into(valid_words_vec, &letters, letters_on_board, config.filter_letters_on_board, &mut new_valid_words_vec);
+                            let res2 = play_further(board, new_min_col, new_max_col, new_min_row, new_max_row, &new_valid_words_vec, word_buffers, valid_words_trie, dictionary_index, res.2, depth+1, Some(word.position), words_checked, letters_on_board, config, deadline, stop_t, on_partial);
+                            word_buffers.push(new_valid_words_vec);
+                            let res2 = res2?;
+                            if res2.0 {
+                                return Ok(Some(res2));
+                            }
+                            else {
+                                board.undo_play(&res.1, letters_on_board);
+                                record_backtrack();
+                            }
+                        },
+                        LetterUsage::Overused(_) => unreachable!()
+                    }
+                }
+                else {
+                    board.undo_play(&res.1, letters_on_board);
+                }
+            }
+            else {
+                if let LetterUsage::Overused(letter) = res.3 {
+                    *overused_letters |= 1 << letter;
+                }
+                board.undo_play(&res.1, letters_on_board);
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Tries to play a word diagonally (going down or up to the right) anywhere on the `board`
+/// # Arguments
+/// * `board` - The `Board` to modify in-place
+/// * `word` - Word to try to play
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `valid_words_vec` - Vector of vectors, each representing a word (see `convert_word_to_array`)
+/// * `word_buffers` - Spare word lists to filter into when playing further (see `WordBuffers`)
+/// * `valid_words_trie` - `Trie` of all valid words (for faster membership checking)
+/// * `dictionary_index` - `DictionaryIndex` of the dictionary the words come from
+/// * `letters` - Length-26 array of the number of each letter in the hand
+/// * `depth` - Depth of the current recursive call
+/// * `words_checked` - The number of words checked in total
+/// * `letters_on_board` - Length-26 array of the number of each letter currently present on the `board`
+/// * `overused_letters` - Bitmask to which each letter that `word` needed more of than the hand has is added (see `letter_mask`)
+/// * `config` - Settings for the search (see `SolveConfig`)
+/// * `deadline` - Time after which to stop checking words (if `None`, then there is no time limit)
+/// * `stop_t` - `AtomicBool` that, when set, indicates that processing should stop
+/// * `on_partial` - Callback that is sent the board whenever a word is played at the top level of the search (see `report_partial`)
+/// # Returns
+/// *`Result` with `Option` upon success with:*
+/// * `bool` - Whether the word could be validly played
+/// * `usize` - Minimum occupied column index in `board`
+/// * `usize` - Maximum occupied column index in `board`
+/// * `usize` - Minimum occupied row index in `board`
+/// * `usize` - Maximum occupied row index in `board`
+/// 
+/// *or `None` if no valid playing location was found, or empty `Err` if another thread signalled to stop*
+#[cfg(feature = "diagonal")]
+fn try_play_word_diagonal<'w>(board: &mut Board, word: &IndexedWord, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words_vec: &[IndexedWord<'w>], word_buffers: &mut WordBuffers<'w>, valid_words_trie: &Trie, dictionary_index: &DictionaryIndex, letters: Letters, depth: usize, words_checked: &mut usize, letters_on_board: &mut Letters, overused_letters: &mut usize, config: &SolveConfig, deadline: Option<Instant>, stop_t: &Arc<AtomicBool>, on_partial: Option<&PartialCallback>) -> Result<Option<PlayOutcome>, ()> {
+    let span = word.len()-1;
+    for dy in [1, -1] {
+        // Try starting from every cell the word could reach the board from (`play_word` rejects those that don't reach it)
+        for row_idx in min_row.saturating_sub(span+1)..=(board.size()-1).min(max_row+span+1) {
+            for col_idx in min_col.saturating_sub(span+1)..=(board.size()-1).min(max_col+1) {
+                let res = board.play_word(word, row_idx, col_idx, Direction::Diagonal(1, dy), &letters, letters_on_board);
+                if res.0 {
+                    let end_row = if dy == 1 {row_idx+span} else {row_idx-span};
+                    let new_min_col = min_col.min(col_idx);
+                    let new_max_col = max_col.max(col_idx+span);
+                    let new_min_row = min_row.min(row_idx.min(end_row));
+                    let new_max_row = max_row.max(row_idx.max(end_row));
+                    if board.fits_span(new_min_col, new_max_col, new_min_row, new_max_row) && is_board_valid_diagonal(board, &res.1, valid_words_trie, config.min_word_length) && is_connected(board, new_min_col, new_max_col, new_min_row, new_max_row, &Vec::new()) {
+                        debug_assert!(play_formed_valid_words(board, new_min_col, new_max_col, new_min_row, new_max_row, &res.1, valid_words_trie, config.min_word_length), "Diagonal play of {} at ({}, {}) formed an invalid word", convert_array_to_word(word), row_idx, col_idx);
+                        match res.3 {
+                            LetterUsage::Finished => {
+                                return Ok(Some((true, new_min_col, new_max_col, new_min_row, new_max_row)));
+                            },
+                            LetterUsage::Remaining => {
+                                record_letters_remaining(&res.2, board, new_min_col, new_max_col, new_min_row, new_max_row);
+                                report_partial(on_partial, depth, board, new_min_col, new_max_col, new_min_row, new_max_row, &res.2, *words_checked);
+                                let mut new_valid_words_vec = word_buffers.pop().unwrap_or_default();
+                                dictionary_index.filter_words_into(valid_words_vec, &letters, letters_on_board, config.filter_letters_on_board, &mut new_valid_words_vec);
+                                let res2 = play_further(board, new_min_col, new_max_col, new_min_row, new_max_row, &new_valid_words_vec, word_buffers, valid_words_trie, dictionary_index, res.2, depth+1, Some(word.position), words_checked, letters_on_board, config, deadline, stop_t, on_partial);
+                                word_buffers.push(new_valid_words_vec);
+                                let res2 = res2?;
+                                if res2.0 {
+                                    return Ok(Some(res2));
+                                }
+                                else {
+                                    board.undo_play(&res.1, letters_on_board);
+                                    record_backtrack();
+                                }
+                            },
+                            LetterUsage::Overused(_) => unreachable!()
+                        }
+                    }
+                    else {
+                        board.undo_play(&res.1, letters_on_board);
+                    }
+                }
+                else {
+                    if let LetterUsage::Overused(letter) = res.3 {
+                        *overused_letters |= 1 << letter;
+                    }
+                    board.undo_play(&res.1, letters_on_board);
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Tries to play each of the words diagonally on the `board`, once they've failed horizontally and vertically
+/// # Arguments
+/// * `board` - The `Board` to modify in-place
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `valid_words_vec` - Words to try
+/// * `word_buffers` - Spare word lists to filter into when playing further (see `WordBuffers`)
+/// * `skipped_letters` - Bitmask of letters that can't be played, so that words containing them are skipped (see `play_further`)
+/// * `valid_words_trie` - `Trie` of all valid words (for faster membership checking)
+/// * `dictionary_index` - `DictionaryIndex` of the dictionary the words come from
+/// * `letters` - Length-26 array of the number of each letter in the hand
+/// * `depth` - Depth of the current recursive call
+/// * `words_checked` - The number of words checked in total
+/// * `letters_on_board` - Length-26 array of the number of each letter currently present on the `board`
+/// * `config` - Settings for the search (see `SolveConfig`)
+/// * `deadline` - Time after which to stop checking words (if `None`, then there is no time limit)
+/// * `stop_t` - `AtomicBool` that, when set, indicates that processing should stop
+/// * `on_partial` - Callback that is sent the board whenever a word is played at the top level of the search (see `report_partial`)
+/// # Returns
+/// *`Result` with `Option` upon success (see `try_play_word_diagonal`)*
+/// 
+/// *or `None` if no word could be played, or empty `Err` another thread signalled to stop*
+#[cfg(feature = "diagonal")]
+fn try_play_words_diagonally<'w>(board: &mut Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words_vec: &[IndexedWord<'w>], word_buffers: &mut WordBuffers<'w>, skipped_letters: usize, valid_words_trie: &Trie, dictionary_index: &DictionaryIndex, letters: Letters, depth: usize, words_checked: &mut usize, letters_on_board: &mut Letters, config: &SolveConfig, deadline: Option<Instant>, stop_t: &Arc<AtomicBool>, on_partial: Option<&PartialCallback>) -> Result<Option<PlayOutcome>, ()> {
+    for word in valid_words_vec.iter() {
+        // Shorter words would only touch at their corners, rather than being read as a word
+        if word.len() < MIN_DIAGONAL_WORD_LENGTH || dictionary_index.mask_of(word) & skipped_letters != 0 {
+            continue;
+        }
+        *words_checked += 1;
+        record_word_checked(depth);
+        if should_stop(*words_checked, deadline, stop_t) {
+            return Err(());
+        }
+        if let Some(r) = try_play_word_diagonal(board, word, min_col, max_col, min_row, max_row, valid_words_vec, word_buffers, valid_words_trie, dictionary_index, letters, depth, words_checked, letters_on_board, &mut 0, config, deadline, stop_t, on_partial)? {
+            return Ok(Some(r));
+        }
+    }
+    Ok(None)
+}
+
+/// Gets the letters most plentiful in the hand
+/// # Arguments
+/// * `letters` - Length-27 array of the number of each letter in the hand
+/// # Returns
+/// `u32` - Bitmask (bit `i` for letter `i`) of the three most frequent letters remaining in `letters`, ties going to the earlier letter
+fn top_letters_mask(letters: &Letters) -> u32 {
+    // Blanks are ignored, since they can stand in for anything
+    let mut mask = 0u32;
+    for _ in 0..3 {
+        if let Some(letter) = (0..26).filter(|i| letters[*i] > 0 && mask & 1 << i == 0).max_by_key(|i| (letters[*i], Reverse(*i))) {
+            mask |= 1 << letter;
+        }
+    }
+    mask
+}
+
+/// Scores how strongly a word draws on the letters most plentiful in the hand
+/// # Arguments
+/// * `word` - Word to score
+/// * `top_letters` - Bitmask of the most plentiful letters in the hand (see `top_letters_mask`)
+/// # Returns
+/// `usize` - Number of letters in `word` that are in `top_letters`
+fn score_word_priority(word: &Word, top_letters: u32) -> usize {
+    word.iter().filter(|letter| top_letters & 1 << **letter != 0).count()
+}
+
+/// Orders words so that those drawing most on the letters most plentiful in the hand are tried first
+/// # Arguments
+/// * `valid_words` - Words to order
+/// * `letters` - Length-27 array of the number of each letter in the hand
+/// * `ordered` - List to fill with `valid_words` in order of descending `score_word_priority` (otherwise keeping their order)
+fn order_by_priority<'w>(valid_words: &[IndexedWord<'w>], letters: &Letters, ordered: &mut Vec<IndexedWord<'w>>) {
+    let top_letters = top_letters_mask(letters);
+    ordered.clear();
+    ordered.extend_from_slice(valid_words);
+    ordered.sort_by_key(|word| Reverse(score_word_priority(word, top_letters)));
+}
+
+/// Orders candidate first words so that those drawing on the most common letters in the hand are tried first
+/// # Arguments
+/// * `valid_words` - Words that can be made from `letters`
+/// * `letters` - Length-27 array of the number of each letter in the hand
+/// # Returns
+/// `Vec<IndexedWord>` - `valid_words` sorted by descending score, which is how much more evenly the rest of the hand is left (so a short word of common letters can come before a long word of rare ones), then by descending length, then by descending average frequency rank of their letters
+fn best_first_word<'a>(valid_words: &[IndexedWord<'a>], letters: &Letters) -> Vec<IndexedWord<'a>> {
+    // A letter's rank is the number of distinct letters in the hand that are less common than it (letters only reachable using a blank rank lowest)
+    let mut frequency_rank = [0usize; 26];
+    for i in 0..26 {
+        if letters[i] > 0 {
+            frequency_rank[i] = 1 + (0..26).filter(|j| letters[*j] > 0 && letters[*j] < letters[i]).count();
+        }
+    }
+    let mut ordered: Vec<IndexedWord> = valid_words.to_vec();
+    // How unevenly the hand is left after playing a word, as the sum of squared remaining counts (lower is more even); each letter played makes it more even, and more so the more common the letter
+    let unevenness = |word: &Word| {
+        let mut remaining = *letters;
+        for letter in word.iter() {
+            if remaining[*letter] > 0 {
+                remaining[*letter] -= 1;
+            }
+            else {
+                remaining[BLANK] = remaining[BLANK].saturating_sub(1);
+            }
+        }
+        remaining.iter().map(|count| count * count).sum::<usize>()
+    };
+    let unevenness_before: usize = letters.iter().map(|count| count * count).sum();
+    ordered.sort_by_cached_key(|word| {
+        let rank_sum: usize = word.iter().map(|l| frequency_rank[*l]).sum();
+        (Reverse(unevenness_before - unevenness(word)), Reverse(word.len()), Reverse(rank_sum * 1000 / word.len()))
+    });
+    ordered
+}
+
+/// Recursively solves Bananagrams
+/// # Arguments
+/// * `board` - The `Board` to modify in-place
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `valid_words_vec` - Vector of vectors, each representing a word (see `convert_word_to_array`), sorted by descending length
+/// * `word_buffers` - Spare word lists to filter into when playing further (see `WordBuffers`)
+/// * `valid_words_trie` - `Trie` of all valid words (for faster membership checking)
+/// * `dictionary_index` - `DictionaryIndex` of the dictionary the words come from
+/// * `letters` - Length-26 array of the number of each letter in the hand
+/// * `depth` - Depth of the current recursive call
+/// * `last_played` - Position in the dictionary of the word played just before this call, whose words sharing a letter with it are tried first (see `CandidateOrder`), or `None` if it isn't known
+/// * `words_checked` - The number of words checked in total
+/// * `letters_on_board` - Length-26 array of the number of each letter currently present on the `board`
+/// * `config` - Settings for the search (see `SolveConfig`)
+/// * `deadline` - Time after which to stop checking words (if `None`, then there is no time limit)
+/// * `stop_t` - `AtomicBool` that, when set, indicates that processing should stop
+/// * `on_partial` - Callback that is sent the board whenever a word is played at the top level of the search (see `report_partial`)
+/// # Returns
+/// *`Result` with:*
+/// * `bool` - Whether the word could be validly played
+/// * `usize` - Minimum occupied column index in `board`
+/// * `usize` - Maximum occupied column index in `board`
+/// * `usize` - Minimum occupied row index in `board`
+/// * `usize` - Maximum occupied row index in `board`
+/// 
+/// *or empty `Err` if out-of-bounds, past the maximum number of words to check or the deadline, or another thread signalled to stop*
+fn play_further<'w>(board: &mut Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, valid_words_vec: &[IndexedWord<'w>], word_buffers: &mut WordBuffers<'w>, valid_words_trie: &Trie, dictionary_index: &DictionaryIndex, letters: Letters, depth: usize, last_played: Option<usize>, words_checked: &mut usize, letters_on_board: &mut Letters, config: &SolveConfig, deadline: Option<Instant>, stop_t: &Arc<AtomicBool>, on_partial: Option<&PartialCallback>) -> Result<PlayOutcome, ()> {
+    if *words_checked > config.max_words_to_check || stop_t.load(Ordering::Relaxed) {
+        return Err(());
+    }
+    // Once the tiles spread across most of the board, re-center them so that there's room to play on every side (and continue on the re-centered board)
+    if config.recenter && (max_col - min_col > board.size()*3/4 || max_row - min_row > board.size()*3/4) {
+        let (mut compacted, new_min_col, new_max_col, new_min_row, new_max_row) = board.compact(min_col, max_col, min_row, max_row);
+        if (new_min_col, new_min_row) != (min_col, min_row) {
+            let res = play_further(&mut compacted, new_min_col, new_max_col, new_min_row, new_max_row, valid_words_vec, word_buffers, valid_words_trie, dictionary_index, letters, depth, last_played, words_checked, letters_on_board, config, deadline, stop_t, on_partial)?;
+            if res.0 {
+                *board = compacted;
+            }
+            return Ok(res);
+        }
+    }
+    // A word can't use more than the letters in the hand plus the few allowed from the board, and the words are sorted by descending length, so skip straight past those that are too long
+    let max_word_length = letters_count(&letters) + config.filter_letters_on_board;
+    let valid_words_vec = &valid_words_vec[valid_words_vec.partition_point(|word| word.len() > max_word_length)..];
+    // Optionally try the words that use up the most plentiful letters first (in a spare list, so that the words played further are still filtered from the ones sorted by length)
+    let mut ordered_words_vec: Option<Vec<IndexedWord>> = None;
+    if config.use_frequency_ordering {
+        let mut ordered = word_buffers.pop().unwrap_or_default();
+        order_by_priority(valid_words_vec, &letters, &mut ordered);
+        ordered_words_vec = Some(ordered);
+    }
+    // Try the words that share a letter with the last word played first, since they can cross it (the others can still cross earlier words or be played alongside the tiles, so are tried after)
+    if let Some(last) = last_played {
+        let candidates = ordered_words_vec.as_deref().unwrap_or(valid_words_vec);
+        let mut followers = word_buffers.pop().unwrap_or_default();
+        followers.clear();
+        followers.extend(candidates.iter().filter(|word| dictionary_index.follows(last, word)));
+        followers.extend(candidates.iter().filter(|word| !dictionary_index.follows(last, word)));
+        word_buffers.extend(ordered_words_vec.replace(followers));
+    }
+    let words_to_try = ordered_words_vec.as_deref().unwrap_or(valid_words_vec);
+    // Letters that aren't in the hand or on the board can't be played without a blank, so once a word fails because it needs one of them, every later word needing it will fail too
+    let unplayable_letters = if letters[BLANK] > 0 {0} else {(0..26).filter(|i| letters[*i] == 0 && letters_on_board[*i] == 0).fold(0, |mask, i| mask | 1 << i)};
+    let mut skipped_letters = 0;
+    // If we're at an odd depth, play horizontally first (trying to alternate horizontal-vertical-horizontal as a heuristic to solve faster)
+    if depth % 2 == 1 {
+        for word in words_to_try.iter() {
+            if dictionary_index.mask_of(word) & skipped_letters != 0 {
+                continue;
+            }
+            *words_checked += 1;
+            record_word_checked(depth);
+            if should_stop(*words_checked, deadline, stop_t) {
+                return Err(());
+            }
+            let mut overused_letters = 0;
+            if let Some(r) = try_play_word_horizontal(board, word, min_col, max_col, min_row, max_row, valid_words_vec, word_buffers, valid_words_trie, dictionary_index, letters, depth, words_checked, letters_on_board, &mut overused_letters, config, deadline, stop_t, on_partial)? {
+                return Ok(r);
+            }
+            skipped_letters |= overused_letters & unplayable_letters;
+        }
+        // If trying every word horizontally didn't work, try vertically instead
+        for word in words_to_try.iter() {
+            if dictionary_index.mask_of(word) & skipped_letters != 0 {
+                continue;
+            }
+            *words_checked += 1;
+            record_word_checked(depth);
+            if should_stop(*words_checked, deadline, stop_t) {
+                return Err(());
+            }
+            let mut overused_letters = 0;
+            if let Some(r) = try_play_word_vertically(board, word, min_col, max_col, min_row, max_row, valid_words_vec, word_buffers, valid_words_trie, dictionary_index, letters, depth, words_checked, letters_on_board, &mut overused_letters, config, deadline, stop_t, on_partial)? {
+                return Ok(r);
+            }
+            skipped_letters |= overused_letters & unplayable_letters;
+        }
+        #[cfg(feature = "diagonal")]
+        if let Some(r) = try_play_words_diagonally(board, min_col, max_col, min_row, max_row, valid_words_vec, word_buffers, skipped_letters, valid_words_trie, dictionary_index, letters, depth, words_checked, letters_on_board, config, deadline, stop_t, on_partial)? {
+            return Ok(r);
+        }
+        word_buffers.extend(ordered_words_vec);
+        Ok((false, min_col, max_col, min_row, max_row))
+    }
+    // If we're at an even depth, play vertically first. Otherwise this is analgous to the above.
+    else {
+        for word in words_to_try.iter() {
+            if dictionary_index.mask_of(word) & skipped_letters != 0 {
+                continue;
+            }
+            *words_checked += 1;
+            record_word_checked(depth);
+            if should_stop(*words_checked, deadline, stop_t) {
+                return Err(());
+            }
+            let mut overused_letters = 0;
+            if let Some(r) = try_play_word_vertically(board, word, min_col, max_col, min_row, max_row, valid_words_vec, word_buffers, valid_words_trie, dictionary_index, letters, depth, words_checked, letters_on_board, &mut overused_letters, config, deadline, stop_t, on_partial)? {
+                return Ok(r);
+            }
+            skipped_letters |= overused_letters & unplayable_letters;
+        }
+        // No point in checking horizontally for the first depth, since it would have to form a vertical word that was already checked and failed
+        if depth == 0 {
+            #[cfg(feature = "diagonal")]
+            if let Some(r) = try_play_words_diagonally(board, min_col, max_col, min_row, max_row, valid_words_vec, word_buffers, skipped_letters, valid_words_trie, dictionary_index, letters, depth, words_checked, letters_on_board, config, deadline, stop_t, on_partial)? {
+                return Ok(r);
+            }
+            word_buffers.extend(ordered_words_vec);
+            return Ok((false, min_col, max_col, min_row, max_row));
+        }
+        for word in words_to_try.iter() {
+            if dictionary_index.mask_of(word) & skipped_letters != 0 {
+                continue;
+            }
+            *words_checked += 1;
+            record_word_checked(depth);
+            if should_stop(*words_checked, deadline, stop_t) {
+                return Err(());
+            }
+            let mut overused_letters = 0;
+            if let Some(r) = try_play_word_horizontal(board, word, min_col, max_col, min_row, max_row, valid_words_vec, word_buffers, valid_words_trie, dictionary_index, letters, depth, words_checked, letters_on_board, &mut overused_letters, config, deadline, stop_t, on_partial)? {
+                return Ok(r);
+            }
+            skipped_letters |= overused_letters & unplayable_letters;
+        }
+        #[cfg(feature = "diagonal")]
+        if let Some(r) = try_play_words_diagonally(board, min_col, max_col, min_row, max_row, valid_words_vec, word_buffers, skipped_letters, valid_words_trie, dictionary_index, letters, depth, words_checked, letters_on_board, config, deadline, stop_t, on_partial)? {
+            return Ok(r);
+        }
+        word_buffers.extend(ordered_words_vec);
+        Ok((false, min_col, max_col, min_row, max_row))
+    }
+}
+
+/// Plays the first word of a solve horizontally across the middle of an empty `board`, using blanks for any letters that aren't in the hand
+/// # Arguments
+/// * `board` - The empty `Board` to play on
+/// * `word` - The word to play (which must be makeable from `letters`)
+/// * `letters` - Length-27 array of the number of each letter (and blanks) in the hand
+/// # Returns
+/// *Tuple with:*
+/// * `Letters` - The letters left in the hand
+/// * `Letters` - The letters now on the board
+/// * `usize` - Row of the word
+/// * `usize` - Column of the word's first letter
+fn play_first_word(board: &mut Board, word: &Word, letters: &Letters) -> (Letters, Letters, usize, usize) {
+    let col_start = board.size()/2 - word.len()/2;
+    let row = board.size()/2;
+    let mut use_letters: Letters = *letters;
+    let mut letters_on_board = [0usize; 27];
+    for i in 0..word.len() {
+        if use_letters[word[i]] > 0 {
+            board.set_val(row, col_start+i, word[i]);
+            use_letters[word[i]] -= 1;
+        }
+        else {
+            board.set_blank(row, col_start+i, word[i]);
+            use_letters[BLANK] -= 1;  // Should never underflow because we've verified that every word is playable with these letters
+        }
+        letters_on_board[word[i]] += 1;
+    }
+    (use_letters, letters_on_board, row, col_start)
+}
+
+/// Quickly checks whether a hand can be solved from scratch, using a single thread and giving up after `config.max_words_to_check` words
+/// # Arguments
+/// * `letters` - Length-26 array of the number of each letter in the hand
+/// * `valid_words_vec` - Words makeable from `letters`
+/// * `valid_words_trie` - `Trie` of all valid words
+/// * `dictionary_index` - `DictionaryIndex` of the dictionary the words come from
+/// * `config` - Settings for the search (see `SolveConfig`)
+/// * `stop_t` - `AtomicBool` that, when set, indicates that processing should stop
+/// # Returns
+/// `bool` - Whether a solution was found
+pub fn solves_quickly(letters: &Letters, valid_words_vec: &[IndexedWord], valid_words_trie: &Trie, dictionary_index: &DictionaryIndex, config: &SolveConfig, stop_t: &Arc<AtomicBool>) -> bool {
+    let mut words_checked = 0;
+    let mut board = Board::new(config.board_size);
+    let mut word_buffers = Vec::new();
+    // This mirrors a single thread of `play_bananagrams`
+    for word in valid_words_vec.iter() {
+        let (use_letters, mut letters_on_board, row, col_start) = play_first_word(&mut board, word, letters);
+        if use_letters.iter().all(|count| *count == 0) {
+            return true;
+        }
+        let word_letters: HashSet<usize> = HashSet::from_iter(word.iter().copied());
+        let new_valid_words_vec: Vec<IndexedWord> = valid_words_vec.iter().filter(|w| check_filter_after_play(use_letters, w, &word_letters)).copied().collect();
+        match play_further(&mut board, col_start, col_start + (word.len()-1), row, row, &new_valid_words_vec, &mut word_buffers, valid_words_trie, dictionary_index, use_letters, 0, Some(word.position), &mut words_checked, &mut letters_on_board, config, None, stop_t, None) {
+            Ok(res) if res.0 => {
+                return true;
+            },
+            Ok(_) => {},
+            Err(()) => {
+                return false;
+            }
+        }
+        for col in col_start..col_start+word.len() {
+            board.clear(row, col);
+        }
+    }
+    false
+}
+
+/// Tries to play a single letter on the board
+/// # Arguments
+/// * `board` - The `Board` on which to try to play the `letter`
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `letter` - The numeric representation of the letter to play
+/// * `valid_words_trie` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// # Returns
+/// `Option` - either `None` if no solution was found, or a `Some` tuple of `(row, col, new_min_col, new_max_col, new_min_row, new_max_row)` on success
+fn play_one_letter(board: &mut Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, letter: usize, valid_words_trie: &Trie, min_word_length: usize) -> Option<(usize, usize, usize, usize, usize, usize)> {
+    // Loop through the empty cells bordering the tiles and check if the letter works there
+    for (row, col) in board.iter_empty_adjacent(min_col, max_col, min_row, max_row) {
+        board.set_val(row, col, letter);
+        let new_min_col = min_col.min(col);
+        let new_max_col = max_col.max(col);
+        let new_min_row = min_row.min(row);
+        let new_max_row = max_row.max(row);
+        // Could also use `is_board_valid_vertical`
+        if is_board_valid_horizontal(board, new_min_col, new_max_col, new_min_row, new_max_row, row, col, col, valid_words_trie, min_word_length) {
+            // If it's valid, return the (potentially) new bounds, along with the location the letter was played
+            return Some((row, col, new_min_col, new_max_col, new_min_row, new_max_row));
+        }
+        else {
+            // If the board wasn't ok, reset this spot
+            board.clear(row, col);
+        }
+    }
+    // Return `None` if we don't find a solution
+    None
+}
+
+/// Tries to replace a letter on the board with a different one
+/// # Arguments
+/// * `board` - The `Board` on which to swap the letter (modified in-place if a swap is found)
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `old_letter` - The numeric representation of the letter to replace (tiles played as blanks are never replaced)
+/// * `new_letter` - The numeric representation of the letter to replace it with
+/// * `valid_words_trie` - `Trie` of all valid words
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// # Returns
+/// `Option` - either `None` if no valid swap was found, or a `Some` tuple of `(row, col, min_col, max_col, min_row, max_row)` on success
+fn try_swap_letter(board: &mut Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, old_letter: usize, new_letter: usize, valid_words_trie: &Trie, min_word_length: usize) -> Option<(usize, usize, usize, usize, usize, usize)> {
+    for row in min_row..=max_row {
+        for col in min_col..=max_col {
+            if board.get_val(row, col).letter() == Some(old_letter) && !board.is_blank(row, col) {
+                board.set_val(row, col, new_letter);
+                // Checks both the row and the column through the swapped cell
+                if is_board_valid_horizontal(board, min_col, max_col, min_row, max_row, row, col, col, valid_words_trie, min_word_length) {
+                    return Some((row, col, min_col, max_col, min_row, max_row));
+                }
+                else {
+                    board.set_val(row, col, old_letter);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Recursively plays on an existing board by removing letters
+/// # Arguments
+/// * `board` - Existing board (will be modified in-place)
+/// * `letters_on_board` - Current letters on the `board`
+/// * `min_col` - Minimum occupied column index in `board`
+/// * `max_col` - Maximum occupied column index in `board`
+/// * `min_row` - Minimum occupied row index in `board`
+/// * `max_row` - Maximum occupied row index in `board`
+/// * `hand_letters` - Letters currently in the hand
+/// * `valid_words_vec` - Valid words that can be played on `board`
+/// * `valid_words_trie` - `Trie` of all valid words
+/// * `dictionary_index` - `DictionaryIndex` of the dictionary the words come from
+/// * `config` - Settings for the search (see `SolveConfig`)
+/// * `deadline` - Time after which to stop checking words (if `None`, then there is no time limit)
+/// * `stop_t` - AtomicBool for early stopping
+/// * `on_partial` - Callback that is sent the board whenever a word is played at the top level of the search (see `report_partial`)
+/// # Returns
+/// `Option` - either `None` if no solution was found, or a `Some` tuple of `(new_min_col, new_max_col, new_min_row, new_max_row)` on success
+fn play_removing(board: &mut Board, letters_on_board: &mut Letters, min_col: usize, max_col: usize, min_row: usize, max_row: usize, hand_letters: Letters, valid_words_vec: &[IndexedWord], valid_words_trie: &Trie, dictionary_index: &DictionaryIndex, config: &SolveConfig, deadline: Option<Instant>, stop_t: &Arc<AtomicBool>, on_partial: Option<&PartialCallback>) -> Option<(usize, usize, usize, usize)> {
+    let mut words_checked = 0usize;
+    let mut word_buffers = Vec::new();
+    // First try to play the words on the board, first horizontally and then vertically
+    for word in valid_words_vec {
+        match try_play_word_horizontal(board, word, min_col, max_col, min_row, max_row, valid_words_vec, &mut word_buffers, valid_words_trie, dictionary_index, hand_letters, 0, &mut words_checked, &mut letters_on_board.clone(), &mut 0, config, deadline, stop_t, on_partial) {
+            Ok(r) => {
+                // Ok + Some indicates that a solution was found
+                if let Some(rr) = r {
+                    // If another thread said we're done then just return None (although I *think* this isn't necessary since the return should then be an `Err`)
+                    if stop_t.load(Ordering::Relaxed) {
+                        return None;
+                    }
+                    // If we found a solution, set `stop_t` to true and return it
+                    else if rr.0 {
+                        stop_t.store(true, Ordering::Relaxed);
+                        return Some((rr.1, rr.2, rr.3, rr.4));
+                    }
+                    // Otherwise, try to play the word vertically
+                    else {
+                        match try_play_word_vertically(board, word, min_col, max_col, min_row, max_row, valid_words_vec, &mut word_buffers, valid_words_trie, dictionary_index, hand_letters, 0, &mut words_checked, &mut letters_on_board.clone(), &mut 0, config, deadline, stop_t, on_partial) {
+                            Ok(rrr) => {
+                                if let Some(rrrr) = rrr {
+                                    if stop_t.load(Ordering::Relaxed) {
+                                        return None;
+                                    }
+                                    else if rrrr.0 {
+                                        stop_t.store(true, Ordering::Relaxed);
+                                        return Some((rrrr.1, rrrr.2, rrrr.3, rrrr.4));
+                                    }
+                                }
+                            },
+                            Err(_) => {
+                                return None;
+                            }
+                        }
+                    }
+                }
+                else {
+                    match try_play_word_vertically(board, word, min_col, max_col, min_row, max_row, valid_words_vec, &mut word_buffers, valid_words_trie, dictionary_index, hand_letters, 0, &mut words_checked, &mut letters_on_board.clone(), &mut 0, config, deadline, stop_t, on_partial) {
+                        Ok(rrr) => {
+                            if let Some(rrrr) = rrr {
+                                if stop_t.load(Ordering::Relaxed) {
+                                    return None;
+                                }
+                                else if rrrr.0 {
+                                    stop_t.store(true, Ordering::Relaxed);
+                                    return Some((rrrr.1, rrrr.2, rrrr.3, rrrr.4));
+                                }
+                            }
+                        },
+                        Err(_) => {
+                            return None;
+                        }
+                    }
+                }
+            },
+            // An `Err` indicates that a thread signalled to stop
+            Err(_) => {
+                return None;
+            }
+        }
+    }
+    // If playing the word failed, find the new removable_indices and continue recursively
+    let mut removable_indices = get_removable_indices(board, min_col, max_col, min_row, max_row, valid_words_trie);
+    removable_indices.sort_unstable_by_key(|a| a.0.len());
+    for rmv in removable_indices {
+        let mut new_letters_on_board = *letters_on_board;
+        let prev_vals = board.undo_play(&rmv.0, &mut new_letters_on_board);
+        let mut new_hand_letters = hand_letters;
+        prev_vals.iter().for_each(|(p, blank)| {
+            new_hand_letters[if *blank {BLANK} else {*p}] += 1;
+        });
+        // If we found a solution, return it
+        if let Some(res) = play_removing(board, &mut new_letters_on_board, rmv.1, rmv.2, rmv.3, rmv.4, new_hand_letters, valid_words_vec, valid_words_trie, dictionary_index, config, deadline, stop_t, on_partial) {
+            return Some(res);
+        }
+        // If we didn't find a solution because another thread said to stop, then return None
+        else if stop_t.load(Ordering::Relaxed) {
+            return None;
+        }
+        // Otherwise undo the undo and continue to the next set of removable indices
+        else {
+            for (idx, (val, blank)) in rmv.0.iter().zip(prev_vals) {
+                if blank {
+                    board.set_blank(idx.0, idx.1, val);
+                }
+                else {
+                    board.set_val(idx.0, idx.1, val);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Gets the number of threads to solve with
+/// # Arguments
+/// * `seed` - Seed for a deterministic solve (if `None`, then solving is not deterministic)
+/// * `threads` - Maximum number of threads to use (if `None`, then there is no limit)
+/// # Returns
+/// `usize` - One thread if solving deterministically (so that the result doesn't depend on which thread finishes first), otherwise the available parallelism (at most `threads`, and at least one)
+fn solver_threads(seed: Option<u64>, threads: Option<usize>) -> usize {
+    match seed {
+        Some(_) => 1,
+        None => thread::available_parallelism().unwrap_or(NonZeroUsize::new(1).unwrap()).get().min(threads.unwrap_or(usize::MAX)).max(1)
+    }
+}
+
+/// Shuffles words of the same length among themselves using the `seed`, so that different seeds give different (but repeatable) boards
+/// # Arguments
+/// * `words` - Words to reorder, which must be sorted by descending length (and remain so)
+/// * `seed` - Seed for a deterministic solve (if `None`, then `words` is left untouched)
+fn order_for_seed(words: &mut Vec<IndexedWord>, seed: Option<u64>) {
+    if let Some(seed) = seed {
+        let mut rng = StdRng::seed_from_u64(seed);
+        words.shuffle(&mut rng);
+        // This sort is stable, so words of the same length stay shuffled
+        words.sort_by_key(|word| Reverse(word.len()));
+    }
+}
+
+/// Plays a new hand of `letters` on an existing `board`
+/// 
+/// Words using only the new letters (plus letters already on the board) are tried first; if none of those work, then each set of tiles that can be
+/// removed while leaving the rest of the board valid (see `get_removable_indices`) is taken off in turn, smallest first, and the freed letters are
+/// played along with the new ones (see `play_removing`), putting the tiles back before trying the next set
+/// 
+/// If a word is required, then only boards that still contain it are accepted; if it isn't already on the board, then it is the only word tried first, and no tiles are removed
+/// # Arguments
+/// * `old_board` - Previous board solution
+/// * `min_col` - Minimum occupied column index
+/// * `max_col` - Maximum occupied column index
+/// * `min_row` - Minimum occupied row index
+/// * `max_row` - Maximum occupied row index
+/// * `letters` - Letters in the new hand
+/// * `valid_words_trie` - `Trie` of all valid words
+/// * `dictionary_index` - `DictionaryIndex` of `dict_to_use`
+/// * `dict_to_use` - The dictionary to play words from
+/// * `config` - Settings for the search (see `SolveConfig`)
+/// * `deadline` - Time after which to stop checking words (if `None`, then there is no time limit)
+/// * `excluded_words` - Words that must not be played
+/// * `stop` - `AtomicBool` that, when set, indicates that processing should stop (also set by this function when a solution is found)
+/// * `on_partial` - Callback that is sent the board whenever a word is played at the top level of the search (see `report_partial`)
+/// # Returns
+/// `Option` - either `None` if no way of playing the new letters was found, or `Some` new board along with its minimum and maximum played columns and rows
+pub fn play_existing(old_board: &Board, min_col: usize, max_col: usize, min_row: usize, max_row: usize, letters: &Letters, valid_words_trie: &Trie, dictionary_index: &DictionaryIndex, dict_to_use: &Vec<Word>, excluded_words: &HashSet<Word>, config: &SolveConfig, deadline: Option<Instant>, stop: &Arc<AtomicBool>, on_partial: Option<&PartialCallback>) -> Option<BoardAndIdxs> {
+    // The new board is compared against the old one tile by tile, so the tiles mustn't move
+    let config = &SolveConfig { recenter: false, ..config.clone() };
+    let required_word = config.required_word.as_ref();
+    // First, try to play words that use only the new letters, plus one already present on the board
+    let mut old_tiles_on_board = [0usize; 27];
+    let mut old_letters_on_board = [0usize; 27];
+    let mut played_on_board: HashSet<usize> = HashSet::new();
+    for row in min_row..=max_row {
+        for col in min_col..=max_col {
+            if let Some(letter) = old_board.get_val(row, col).letter() {
+                old_tiles_on_board[if old_board.is_blank(row, col) {BLANK} else {letter}] += 1;
+                old_letters_on_board[letter] += 1;
+                played_on_board.insert(letter);
+            }
+        }
+    }
+    // The board can only be played off of if the hand still contains all of its tiles
+    let hand_letters = letters_sub(letters, &old_tiles_on_board)?;
+    let mut valid_words_vec: Vec<IndexedWord> = indexed_words(dict_to_use).filter(|w| w.len() >= config.min_word_length && !excluded_words.contains(w.word) && check_filter_after_play_later(hand_letters, old_letters_on_board, w, config.filter_letters_on_board, Some(valid_words_trie))).collect();
+    order_for_seed(&mut valid_words_vec, config.seed);
+    let must_play_required = required_word.is_some_and(|word| !is_word_on_board(old_board, min_col, max_col, min_row, max_row, word));
+    let first_words: Vec<IndexedWord> = match required_word {
+        Some(required_word) if must_play_required => valid_words_vec.iter().copied().filter(|word| word.word == required_word).take(1).collect(),
+        _ => valid_words_vec.clone()
+    };
+    // Later plays can extend the required word into a longer one, so check that it's still there before accepting a board
+    let keeps_required = |board: &Board, bounds: &PlayOutcome| required_word.is_none_or(|word| is_word_on_board(board, bounds.1, bounds.2, bounds.3, bounds.4, word));
+    // Prepare for threading
+    let arc_valid_words_trie = Arc::new(valid_words_trie);
+    // The first thread to find a board sends it and signals the others to stop, so the channel only needs room for one board
+    let (sender, receiver) = crossbeam_channel::bounded::<BoardAndIdxs>(1);
+    if !first_words.is_empty() {
+        let default_parallelism_approx = solver_threads(config.seed, config.threads);
+        let arc_valid_words_vec = Arc::new(valid_words_vec);
+        let arc_first_words = Arc::new(first_words);
+        // Each thread takes the next untried word whenever it finishes one, so that no thread sits idle while others still have words to try
+        let next_word = Arc::new(AtomicUsize::new(0));
+        // For each thread (i.e. piece of available parallelism), spawn a new thread to check those words
+        // These threads check different initial words in the board, and whichever finishes first signals the others to stop
+        thread::scope(|s| {
+            let mut handles: Vec<thread::ScopedJoinHandle<SolverStats>> = Vec::with_capacity(default_parallelism_approx);
+            for _ in 0..default_parallelism_approx {
+                let stop_t = stop.clone();
+                let next = Arc::clone(&next_word);
+                let new_letters = hand_letters;
+                let copied_new_valid_words_vec = Arc::clone(&arc_valid_words_vec);
+                let copied_first_words = Arc::clone(&arc_first_words);
+                let copied_valid_words_trie = Arc::clone(&arc_valid_words_trie);
+                let sender = sender.clone();
+                let board_cloned = old_board.clone();
+                let letters_on_board = old_letters_on_board;
+                let handle = s.spawn(move || {
+                    // Loop through each word and play it on a new board
+                    let mut words_checked = 0;
+                    let mut board = board_cloned.clone();
+                    let mut word_buffers = Vec::new();
+                    while let Some(word) = copied_first_words.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        // Each word is tried both horizontally and vertically, so stop once the limit is reached rather than only when a search below it does
+                        if words_checked > config.max_words_to_check || stop_t.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        match try_play_word_horizontal(&mut board, word, min_col, max_col, min_row, max_row, &copied_new_valid_words_vec, &mut word_buffers, &copied_valid_words_trie, dictionary_index, new_letters, 0, &mut words_checked, &mut letters_on_board.clone(), &mut 0, config, deadline, &stop_t, on_partial) {
+                            Ok(r) => {
+                                if let Some(rr) = r {
+                                    if stop_t.load(Ordering::Relaxed) {
+                                        break;
+                                    }
+                                    else if rr.0 && keeps_required(&board, &rr) {
+                                        stop_t.store(true, Ordering::Relaxed);
+                                        let _ = sender.try_send((board, rr.1, rr.2, rr.3, rr.4));
+                                        break;
+                                    }
+                                    else {
+                                        match try_play_word_vertically(&mut board, word, min_col, max_col, min_row, max_row, &copied_new_valid_words_vec, &mut word_buffers, &copied_valid_words_trie, dictionary_index, new_letters, 0, &mut words_checked, &mut letters_on_board.clone(), &mut 0, config, deadline, &stop_t, on_partial) {
+                                            Ok(rrr) => {
+                                                if let Some(rrrr) = rrr {
+                                                    if rrrr.0 && keeps_required(&board, &rrrr) && !stop_t.load(Ordering::Relaxed) {
+                                                        stop_t.store(true, Ordering::Relaxed);
+                                                        let _ = sender.try_send((board, rrrr.1, rrrr.2, rrrr.3, rrrr.4));
+                                                        break;
+                                                    }
+                                                }
+                                            },
+                                            Err(_) => {
+                                                break;
+                                            }
+                                        }
+                                    }
+                                }
+                                else {
+                                    match try_play_word_vertically(&mut board, word, min_col, max_col, min_row, max_row, &copied_new_valid_words_vec, &mut word_buffers, &copied_valid_words_trie, dictionary_index, new_letters, 0, &mut words_checked, &mut letters_on_board.clone(), &mut 0, config, deadline, &stop_t, on_partial) {
+                                        Ok(rrr) => {
+                                            if let Some(rrrr) = rrr {
+                                                if rrrr.0 && keeps_required(&board, &rrrr) && !stop_t.load(Ordering::Relaxed) {
+                                                    stop_t.store(true, Ordering::Relaxed);
+                                                    let _ = sender.try_send((board, rrrr.1, rrrr.2, rrrr.3, rrrr.4));
+                                                    break;
+                                                }
+                                            }
+                                        },
+                                        Err(_) => {
+                                            break;
+                                        }
+                                    }
+                                }
+                            },
+                            Err(_) => {
+                                break;
+                            }
+                        }
+                    }
+                    take_solver_stats()
+                });
+                handles.push(handle);
+            }
+            // Wait for all the threads
+            for handle in handles {
+                if let Ok(stats) = handle.join() {
+                    add_solver_stats(&stats);
+                }
+            }
+        });    
+        // If we're done, return the result
+        if let Ok(result) = receiver.try_recv() {
+            return Some(result);
+        }
+    }
+
+    // Removing tiles never plays the required word first, so it can only help if the word is already on the board
+    if must_play_required {
+        return None;
+    }
+    // If that didn't work (or there were no valid words), try recursively looping through indices that can be removed
+    let arc_dict_to_use = Arc::new(dict_to_use);
+    let mut removable_indices = get_removable_indices(old_board, min_col, max_col, min_row, max_row, valid_words_trie);
+    removable_indices.sort_unstable_by_key(|a| a.0.len());  // Try smaller sets of removable indices first
+    let default_parallelism_approx = solver_threads(config.seed, config.threads);
+    // As above, each thread takes the next untried set of indices whenever it finishes one
+    let next_removable = Arc::new(AtomicUsize::new(0));
+    let removable_indices = &removable_indices;
+    thread::scope(|s| {
+        let mut handles: Vec<thread::ScopedJoinHandle<SolverStats>> = Vec::with_capacity(default_parallelism_approx);
+        for _ in 0..default_parallelism_approx {
+            let stop_t = stop.clone();
+            let next = Arc::clone(&next_removable);
+            let mut cloned_board = old_board.clone();
+            let letters_on_board = old_letters_on_board;
+            let new_letters = hand_letters;
+            let copied_valid_words_vec = Arc::clone(&arc_dict_to_use);
+            let copied_valid_words_trie = Arc::clone(&arc_valid_words_trie);
+            let sender = sender.clone();
+            let handle = s.spawn(move || {
+                while let Some(r) = removable_indices.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    // "Undo" the letters that we want to remove
+                    let mut new_letters_on_board = letters_on_board;
+                    let prev_letters = cloned_board.undo_play(&r.0, &mut new_letters_on_board);
+                    let mut new_hand_letters = new_letters;
+                    prev_letters.iter().for_each(|(p, blank)| {
+                        new_hand_letters[if *blank {BLANK} else {*p}] += 1;
+                    });
+                    let valid_words_vec: Vec<IndexedWord> = indexed_words(&copied_valid_words_vec).filter(|w| w.len() >= config.min_word_length && !excluded_words.contains(w.word) && check_filter_after_play_later(new_hand_letters, new_letters_on_board, w, config.filter_letters_on_board, Some(valid_words_trie))).collect();
+                    // If we found a solution, set it as a solution and break (setting stop_t is performed in `play_removing`)
+                    // Removing tiles can split or shorten words that the incremental checks never revisit, so check the whole board before accepting it
+                    if let Some(res) = play_removing(&mut cloned_board, &mut new_letters_on_board, r.1, r.2, r.3, r.4, new_hand_letters, &valid_words_vec, &copied_valid_words_trie, dictionary_index, config, deadline, &stop_t, on_partial).filter(|res| is_board_valid_full(&cloned_board, res.0, res.1, res.2, res.3, valid_words_trie, config.min_word_length) && required_word.is_none_or(|word| is_word_on_board(&cloned_board, res.0, res.1, res.2, res.3, word))) {
+                        let _ = sender.try_send((cloned_board, res.0, res.1, res.2, res.3));
+                        break;
+                    }
+                    else {
+                        // Otherwise if stop_t was set, break (another thread has solved it)
+                        if stop_t.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        // Othwerwise undo the undo and try with the next set of letters to remove
+                        else {
+                            for (idx, (val, blank)) in r.0.iter().zip(prev_letters) {
+                                if blank {
+                                    cloned_board.set_blank(idx.0, idx.1, val);
+                                }
+                                else {
+                                    cloned_board.set_val(idx.0, idx.1, val);
+                                }
+                            }
+                        }
+                    }
+                }
+                take_solver_stats()
+            });
+            handles.push(handle);
+        }
+        // Wait for all the threads
+        for handle in handles {
+            if let Ok(stats) = handle.join() {
+                add_solver_stats(&stats);
+            }
+        }
+    });
+    // If we're done, return the result (otherwise, return None)
+    receiver.try_recv().ok()
+}
+
+/// For comparing a current hand of letters to a previous hand
+enum LetterComparison {
+    /// At least one letter has fewer than the previous letter (and none have more)
+    SomeLess,
+    /// At least one letter has fewer than the previous letter and at least one has more
+    GreaterAndLess,
+    /// All letters are the same except exactly one is greater by exactly one
+    GreaterByOne,
+    /// One or more letters are greater by one or more
+    GreaterByMoreThanOne,
+    /// The hand is the same as before
+    Same
+}
+
+impl fmt::Debug for LetterComparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LetterComparison::SomeLess => write!(f, "Some less"),
+            LetterComparison::GreaterAndLess => write!(f, "Greater and less"),
+            LetterComparison::GreaterByOne => write!(f, "Greater by one"),
+            LetterComparison::GreaterByMoreThanOne => write!(f, "Greater by more than one"),
+            LetterComparison::Same => write!(f, "Same")
+        }
+     }
+}
+
+/// How a hand was solved
+#[derive(Copy, Clone, PartialEq)]
+pub enum SolveMethod {
+    /// The hand was the same as before, so the previous board was kept
+    Unchanged,
+    /// A single new letter was added to the previous board
+    OneLetter,
+    /// New letters were swapped into the previous board (with any letter replaced that is still in the hand added elsewhere)
+    Swap,
+    /// New words were played on (or after removing some tiles from) the previous board
+    Existing,
+    /// The board was rebuilt from scratch
+    Scratch,
+    /// No board used the whole hand, so the closest attempt from scratch was kept (see `solve_hand`)
+    Partial
+}
+
+/// The result of solving a hand
+pub struct SolvedHand {
+    /// The solved board and its minimum and maximum played columns and rows
+    pub board: BoardAndIdxs,
+    /// Indices of the tiles that are in the same place as on the previous board
+    pub(crate) previous_idxs: HashSet<(usize, usize)>,
+    /// How the hand was solved
+    pub method: SolveMethod,
+    /// Other distinct solutions found when more than one was asked for (see `solve_hand`), each with its indices in the same place as on the previous board
+    alternatives: Vec<(BoardAndIdxs, HashSet<(usize, usize)>)>,
+    /// The letters of the hand that aren't on the board (only when a partial board is allowed)
+    pub unplaced: Letters
+}
+
+/// Struct returned when a board is solved
+#[derive(Serialize)]
+pub struct Solution {
+    /// The solved board
+    pub board: Vec<Vec<String>>,
+    /// Whether each cell of `board` holds a tile that is in the same place as on the previous board
+    pub carried_over: Vec<Vec<bool>>,
+    /// The row of the full board that the first row of `board` is in (boards played off of the previous board keep its rows and columns)
+    pub min_row: usize,
+    /// The column of the full board that the first column of `board` is in
+    pub min_col: usize,
+    /// The row of the full board that the last row of `board` is in
+    pub max_row: usize,
+    /// The column of the full board that the last column of `board` is in
+    pub max_col: usize,
+    /// The words in the solved board
+    pub words: Vec<PlayedWord>,
+    /// The Scrabble-style score of the solved board
+    pub score: u32,
+    /// How compact the solved board is (see `SolutionPreference`)
+    pub solution_score: SolutionScore,
+    /// The letters of the hand that couldn't be placed on the board (empty unless a partial board was allowed)
+    pub unplaced: Vec<char>,
+    /// How long it took to solve the board in milliseconds
+    pub elapsed: u128,
+    /// Whether the board was solved deterministically (using a single thread, which is usually slower)
+    pub deterministic: bool,
+    /// Whether the short dictionary couldn't solve the hand, so the long dictionary was used instead (see `CurrentSettings::fallback_to_long_dictionary`)
+    pub used_long_dictionary: bool,
+    /// The words on the board that aren't in the short dictionary (empty unless `used_long_dictionary`)
+    pub long_only_words: Vec<String>
+}
+
+impl Solution {
+    /// Marks each word on the board that isn't in the short dictionary as obscure
+    /// # Arguments
+    /// * `short_trie` - `Trie` of the short dictionary of the current language
+    pub fn mark_obscure_words(&mut self, short_trie: &Trie) {
+        for played in self.words.iter_mut() {
+            played.obscure = !short_trie.contains(&convert_word_to_array(&played.word));
+        }
+    }
+
+    /// Marks each occurrence of the word the solution was required to contain
+    /// # Arguments
+    /// * `required_word` - The required word
+    pub fn mark_required_word(&mut self, required_word: &Word) {
+        let required_word = convert_array_to_word(required_word);
+        for played in self.words.iter_mut() {
+            played.required = played.word == required_word;
+        }
+    }
+}
+
+/// Statistics of the recursive search, as returned by `play_bananagrams_with_stats` (to help choose a maximum number of words to check)
+#[derive(Clone, Default, Serialize)]
+pub struct SolverStats {
+    /// The deepest level of recursion reached
+    max_depth_reached: usize,
+    /// The number of words checked at each depth of recursion
+    words_checked_at_each_depth: [usize; STATS_DEPTHS],
+    /// The number of times a played word was undone after everything played after it failed
+    backtracks: usize,
+    /// The fewest letters left in the hand after validly playing a word, along with those letters and the board they were left by (if any word was played)
+    #[serde(skip)]
+    fewest_letters_remaining: Option<(usize, Letters, BoardAndIdxs)>
+}
+
+impl SolverStats {
+    /// Adds the statistics of another search to these
+    /// # Arguments
+    /// * `other` - Statistics to add
+    fn merge(&mut self, other: &SolverStats) {
+        self.max_depth_reached = self.max_depth_reached.max(other.max_depth_reached);
+        for (count, other_count) in self.words_checked_at_each_depth.iter_mut().zip(other.words_checked_at_each_depth) {
+            *count += other_count;
+        }
+        self.backtracks += other.backtracks;
+        if other.fewest_letters_remaining.as_ref().is_some_and(|(other_fewest, _, _)| self.fewest_letters_remaining.as_ref().is_none_or(|(fewest, _, _)| other_fewest < fewest)) {
+            self.fewest_letters_remaining = other.fewest_letters_remaining.clone();
+        }
+    }
+}
+
+/// A board partway through being solved, sent to the frontend by `play_bananagrams_stream`
+#[derive(Clone, Serialize)]
+pub struct PartialSolution {
+    /// The board so far
+    pub board: Vec<Vec<String>>,
+    /// The number of letters in the hand that have yet to be played
+    letters_remaining: usize,
+    /// The number of words checked so far by the thread that played this board
+    words_checked: usize
+}
+
+/// Errors returned when solving a hand, so that the frontend can tell them apart without matching on their messages
+#[derive(Clone, Debug, PartialEq)]
+pub enum SolverError {
+    /// The search finished without finding a board
+    NoSolution,
+    /// The search finished without finding a board, and the closest it came left these letters unplayed (in alphabetical order, with blanks as `BLANK_CHAR`)
+    LettersLeftOver(Vec<char>),
+    /// No words can be made from the hand at all
+    NoPlayableWords,
+    /// No word that can be made from the hand uses these letters, so they can never all be played (dumping one of them may help)
+    StrandedLetters(Vec<char>),
+    /// A letter (or `BLANK_CHAR`) was given a negative count
+    InvalidLetterCount { letter: char, count: i64 },
+    /// A letter was missing from the hand sent by the frontend
+    MissingLetter(char),
+    /// The solve was cancelled by the user
+    Cancelled,
+    /// The maximum solve time passed before a board was found
+    Timeout,
+    /// The search finished without finding a board that contains this required word
+    RequiredWordUnplayable(String),
+    /// The search finished without finding a board that fits within this many columns and rows
+    ExceedsMaxSpan { max_width: usize, max_height: usize },
+    /// Anything else (e.g. failing to get a lock or an invalid argument), with its reason
+    Internal(String)
+}
+
+impl SolverError {
+    /// Gets the name of the variant, which is sent to the frontend as the error's `kind`
+    /// # Returns
+    /// `&'static str` - The name of the variant
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SolverError::NoSolution => "NoSolution",
+            SolverError::LettersLeftOver(_) => "LettersLeftOver",
+            SolverError::NoPlayableWords => "NoPlayableWords",
+            SolverError::StrandedLetters(_) => "StrandedLetters",
+            SolverError::InvalidLetterCount { .. } => "InvalidLetterCount",
+            SolverError::MissingLetter(_) => "MissingLetter",
+            SolverError::Cancelled => "Cancelled",
+            SolverError::Timeout => "Timeout",
+            SolverError::RequiredWordUnplayable(_) => "RequiredWordUnplayable",
+            SolverError::ExceedsMaxSpan { .. } => "ExceedsMaxSpan",
+            SolverError::Internal(_) => "Internal"
+        }
+    }
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolverError::NoSolution => write!(f, "No solution found - dump and try again!"),
+            SolverError::LettersLeftOver(letters) => {
+                let letter_list = letters.iter().map(char::to_string).collect::<Vec<String>>().join(", ");
+                let tiles = if letters.len() == 1 {"tile"} else {"tiles"};
+                // Suggest dumping the letter that is hardest to play, going by its value (blanks are worth keeping)
+                match letters.iter().filter(|letter| **letter != BLANK_CHAR).min_by_key(|letter| Reverse(LETTER_VALUES[**letter as usize - 65])) {
+                    Some(letter) => write!(f, "No solution found - the best attempt left {} {}: {} (consider dumping the {})", letters.len(), tiles, letter_list, letter),
+                    None => write!(f, "No solution found - the best attempt left {} {}: {}", letters.len(), tiles, letter_list)
+                }
+            },
+            SolverError::NoPlayableWords => write!(f, "No valid words can be formed from the current letters - dump and try again!"),
+            SolverError::StrandedLetters(letters) if letters.len() == 1 => write!(f, "No valid word uses the {} - dump it and try again!", letters[0]),
+            SolverError::StrandedLetters(letters) => write!(f, "No valid words use the letters {} - dump one of them and try again!", letters.iter().map(char::to_string).collect::<Vec<String>>().join(", ")),
+            SolverError::InvalidLetterCount { letter: BLANK_CHAR, count } => write!(f, "Number of blanks is {}, but must be greater than or equal to 0!", count),
+            SolverError::InvalidLetterCount { letter, count } => write!(f, "Number of letter {} is {}, but must be greater than or equal to 0!", letter, count),
+            SolverError::MissingLetter(letter) => write!(f, "Missing letter: {}", letter),
+            SolverError::Cancelled => write!(f, "Solve cancelled by user"),
+            SolverError::Timeout => write!(f, "Timed out - dump and try again!"),
+            SolverError::RequiredWordUnplayable(word) => write!(f, "No solution found that includes {} - try another word!", word),
+            SolverError::ExceedsMaxSpan { max_width, max_height } => write!(f, "No solution found that fits within {} columns and {} rows - dump or allow a larger board!", max_width, max_height),
+            SolverError::Internal(reason) => write!(f, "{}", reason)
+        }
+    }
+}
+
+impl From<&str> for SolverError {
+    fn from(reason: &str) -> Self {
+        SolverError::Internal(reason.to_owned())
+    }
+}
+
+impl From<String> for SolverError {
+    fn from(reason: String) -> Self {
+        SolverError::Internal(reason)
+    }
+}
+
+impl From<SolverError> for String {
+    fn from(error: SolverError) -> Self {
+        error.to_string()
+    }
+}
+
+/// How a `SolverError` is sent to the frontend
+#[derive(Serialize)]
+struct SerializedSolverError {
+    /// Name of the `SolverError` variant
+    pub kind: &'static str,
+    /// Human-readable description of the error
+    pub message: String,
+    /// The letter the error is about (for `InvalidLetterCount` and `MissingLetter`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub letter: Option<char>,
+    /// The invalid count (for `InvalidLetterCount`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<i64>,
+    /// The letters the error is about (for `StrandedLetters` and `LettersLeftOver`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub letters: Option<Vec<char>>
+}
+
+impl Serialize for SolverError {
+    /// Serializes the error as an object tagged with its `kind`, along with its `message` and any letters and count it is about
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (letter, count) = match self {
+            SolverError::InvalidLetterCount { letter, count } => (Some(*letter), Some(*count)),
+            SolverError::MissingLetter(letter) => (Some(*letter), None),
+            _ => (None, None)
+        };
+        let letters = match self {
+            SolverError::StrandedLetters(letters) | SolverError::LettersLeftOver(letters) => Some(letters.clone()),
+            _ => None
+        };
+        SerializedSolverError { kind: self.kind(), message: self.to_string(), letter, count, letters }.serialize(serializer)
+    }
+}
+
+/// The previous game state
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(into = "SavedGame", try_from = "SavedGame")]
+pub struct GameState {
+    /// The previous board
+    pub board: Board,
+    /// The minimum played column in `board`
+    pub min_col: usize,
+    /// The maximum played column in `board`
+    pub max_col: usize,
+    /// The minimum played row in `board`
+    pub min_row: usize,
+    /// The maximum played row in `board`
+    pub max_row: usize,
+    /// The hand used to make `board`
+    pub letters: Letters
+}
+
+/// A `GameState` as saved to disk (the bounds of the board are recomputed when loading)
+#[derive(Serialize, Deserialize)]
+struct SavedGame {
+    /// The board
+    pub board: Board,
+    /// The hand used to make `board`
+    pub letters: Letters
+}
+
+impl From<GameState> for SavedGame {
+    fn from(game: GameState) -> SavedGame {
+        SavedGame { board: game.board, letters: game.letters }
+    }
+}
+
+impl TryFrom<SavedGame> for GameState {
+    type Error = String;
+
+    /// Converts a `SavedGame` into a `GameState`, recomputing the minimum and maximum played rows and columns
+    fn try_from(saved: SavedGame) -> Result<GameState, String> {
+        let board_size = saved.board.size();
+        let mut min_col = board_size;
+        let mut max_col = 0;
+        let mut min_row = board_size;
+        let mut max_row = 0;
+        for row in 0..board_size {
+            for col in 0..board_size {
+                if !saved.board.get_val(row, col).is_empty() {
+                    min_col = min_col.min(col);
+                    max_col = max_col.max(col);
+                    min_row = min_row.min(row);
+                    max_row = max_row.max(row);
+                }
+            }
+        }
+        if min_col == board_size {
+            return Err("The saved board has no tiles".to_owned());
+        }
+        // Every tile in the hand must have been played on the board
+        let mut letters_on_board = [0usize; 27];
+        for row in min_row..=max_row {
+            for col in min_col..=max_col {
+                if let Some(letter) = saved.board.get_val(row, col).letter() {
+                    letters_on_board[if saved.board.is_blank(row, col) {BLANK} else {letter}] += 1;
+                }
+            }
+        }
+        if letters_on_board != saved.letters {
+            return Err("The saved board does not match the saved hand".to_owned());
+        }
+        Ok(GameState { board: saved.board, min_col, max_col, min_row, max_row, letters: saved.letters })
+    }
+}
+
+/// Which solutions the solver prefers
+#[derive(Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum SolutionPreference {
+    /// Return the first solution found
+    #[default]
+    Fastest,
+    /// Keep searching for a while and return the board with the smallest bounding box
+    Compact,
+    /// Keep searching for a while and return the board with the fewest words
+    FewestWords
+}
+
+impl SolutionPreference {
+    /// Gets the key that solutions are sorted by, with the preferred solutions first
+    /// # Arguments
+    /// * `score` - Score of the solution (see `score_solution`)
+    /// # Returns
+    /// `(usize, usize)` - The number of words then the area when preferring the fewest words, otherwise the area then the number of words
+    pub(crate) fn sort_key(&self, score: &SolutionScore) -> (usize, usize) {
+        match self {
+            SolutionPreference::FewestWords => (score.word_count, score.area),
+            _ => (score.area, score.word_count)
+        }
+    }
+}
+
+/// Gets the time by which a solve must finish
+/// # Arguments
+/// * `start` - When the solve started
+/// * `max_solve_time_ms` - Maximum number of milliseconds to spend on the solve (if 0, then there is no limit)
+/// # Returns
+/// `Option` with the deadline, or `None` if there is no limit
+pub fn get_deadline(start: Instant, max_solve_time_ms: u64) -> Option<Instant> {
+    if max_solve_time_ms == 0 {
+        None
+    }
+    else {
+        Some(start + Duration::from_millis(max_solve_time_ms))
+    }
+}
+
+/// Checks whether a deadline has passed
+/// # Arguments
+/// * `deadline` - The deadline (if `None`, then it can never pass)
+/// # Returns
+/// `bool` - Whether the current time is after `deadline`
+pub fn past_deadline(deadline: Option<Instant>) -> bool {
+    deadline.is_some_and(|d| Instant::now() > d)
+}
+
+/// Checks whether a search should stop after checking another word
+/// # Arguments
+/// * `words_checked` - The number of words checked in total, including the one just checked
+/// * `deadline` - Time after which to stop checking words (if `None`, then there is no time limit); getting the time is comparatively slow, so it's only checked every `DEADLINE_CHECK_INTERVAL` words
+/// * `stop_t` - `AtomicBool` that, when set, indicates that processing should stop (which is set here when the deadline has passed, so that every other thread stops too)
+/// # Returns
+/// `bool` - Whether another thread signalled to stop or the deadline has passed
+fn should_stop(words_checked: usize, deadline: Option<Instant>, stop_t: &Arc<AtomicBool>) -> bool {
+    if stop_t.load(Ordering::Relaxed) {
+        return true;
+    }
+    if words_checked.is_multiple_of(DEADLINE_CHECK_INTERVAL) && past_deadline(deadline) {
+        stop_t.store(true, Ordering::Relaxed);
+        return true;
+    }
+    false
+}
+
+/// Checks that a word the solution is required to contain could be played at all
+/// # Arguments
+/// * `word` - The required word
+/// * `letters` - Length-27 array of the number of each letter (and blanks) in the hand
+/// * `dictionary_trie` - `Trie` of the dictionary being played from
+/// * `excluded_words` - Words that must not be played
+/// * `min_word_length` - Minimum length of a word that can be formed on the board
+/// * `board_size` - Number of rows/columns in the board
+/// # Returns
+/// `Result` with nothing if `word` can be played
+/// 
+/// *or `SolverError::Internal` explaining why it can't*
+fn check_required_word(word: &Word, letters: &Letters, dictionary_trie: &Trie, excluded_words: &HashSet<Word>, min_word_length: usize, board_size: usize) -> Result<(), SolverError> {
+    let as_string = convert_array_to_word(word);
+    let max_length = MAX_WORD_LENGTH.min(board_size);
+    if word.len() > max_length {
+        return Err(format!("{} is too long - words can be at most {} letters!", as_string, max_length).into());
+    }
+    if word.len() < min_word_length {
+        return Err(format!("{} is too short - words must be at least {} letters!", as_string, min_word_length).into());
+    }
+    if excluded_words.contains(word) {
+        return Err(format!("{} is an excluded word!", as_string).into());
+    }
+    if !dictionary_trie.contains(word) {
+        return Err(format!("{} isn't in the dictionary!", as_string).into());
+    }
+    if !is_makeable(word, letters) {
+        return Err(format!("There aren't enough letters in the hand to make {}!", as_string).into());
+    }
+    Ok(())
+}
+
+/// Solves a hand, building off of the previous board where possible
+/// # Arguments
+/// * `letters` - Length-26 array of the number of each letter in the hand
+/// * `previous` - The previous game state (if any)
+/// * `dict_to_use` - Dictionary of words to play
+/// * `dictionary_trie` - `Trie` of `dict_to_use` without `excluded_words`, so that they can't be formed incidentally either
+/// * `dictionary_index` - `DictionaryIndex` of the dictionary the words come from
+/// * `excluded_words` - Words that must not be played
+/// * `config` - Settings for the solve (see `SolveConfig`); its time limit is given by `deadline` instead
+/// * `deadline` - Time after which to stop checking words (if `None`, then there is no time limit)
+/// * `stop` - `AtomicBool` that, when set, indicates that processing should stop
+/// * `on_partial` - Callback that is sent the board whenever a word is played at the top level of the search (see `report_partial`)
+/// # Returns
+/// `Result` with the `SolvedHand`
+/// 
+/// *or `SolverError` upon failure or not finding a board (with the reason indicated by the variant)*
+pub fn solve_hand(letters: Letters, previous: &Option<GameState>, dict_to_use: &Vec<Word>, dictionary_trie: &Trie, dictionary_index: &DictionaryIndex, excluded_words: &HashSet<Word>, config: &SolveConfig, deadline: Option<Instant>, stop: &Arc<AtomicBool>, on_partial: Option<&PartialCallback>) -> Result<SolvedHand, SolverError> {
+    let required_word = config.required_word.as_ref();
+    if let Some(word) = required_word {
+        check_required_word(word, &letters, dictionary_trie, excluded_words, config.min_word_length, config.board_size)?;
+    }
+    let mut previous_board: Option<BoardAndIdxs> = None;
+    match previous {
+        Some(prev_state) => {
+            previous_board = Some((prev_state.board.clone(), prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row));
+            // The maximum span may have changed since the previous board was played, in which case it must be rebuilt from scratch
+            let mut span_board = prev_state.board.clone();
+            span_board.set_max_span(config.max_width, config.max_height);
+            let fits_span = span_board.fits_span(prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row);
+            let mut seen_greater: Option<usize> = None;
+            let mut seen_less = false;
+            let mut comparison = LetterComparison::Same;
+            for (i, (count, previous_count)) in letters.iter().zip(prev_state.letters.iter()).enumerate() {
+                if count < previous_count {
+                    seen_less = true;
+                }
+                else if count > previous_count && (seen_greater.is_some() || count - previous_count != 1) {
+                    comparison = LetterComparison::GreaterByMoreThanOne;
+                    seen_greater = Some(i);
+                }
+                else if count > previous_count {
+                    comparison = LetterComparison::GreaterByOne;
+                    seen_greater = Some(i);
+                }
+            }
+            if seen_less {
+                comparison = if seen_greater.is_some() {LetterComparison::GreaterAndLess} else {LetterComparison::SomeLess};
+            }
+            // If the previous board contains a word that has since been excluded, then it must be rebuilt from scratch
+            let contains_excluded = !excluded_words.is_empty() && get_words_on_board(&prev_state.board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row).iter().any(|w| excluded_words.contains(&convert_word_to_array(&w.word)));
+            match comparison {
+                _ if contains_excluded || !fits_span => {/* We just want to continue to the code that starts from scratch */},
+                _ if required_word.is_some() => {
+                    // Playing one letter or swapping could break up the required word, so instead it is played first off of the previous board (unless it's already there)
+                    let required_word = required_word.expect("A word is required");
+                    match comparison {
+                        LetterComparison::Same if is_word_on_board(&prev_state.board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, required_word) => {
+                            return Ok(SolvedHand { board: (prev_state.board.clone(), prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row), previous_idxs: HashSet::new(), method: SolveMethod::Unchanged, alternatives: Vec::new() , unplaced: [0usize; 27] });
+                        },
+                        LetterComparison::GreaterByOne | LetterComparison::GreaterByMoreThanOne => {
+                            let attempt = play_existing(&span_board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, &letters, dictionary_trie, dictionary_index, dict_to_use, excluded_words, config, deadline, stop, on_partial);
+                            match attempt {
+                                Some(result) => {
+                                    let previous_idxs = get_board_overlap(&prev_state.board, &result.0, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, result.1, result.2, result.3, result.4);
+                                    return Ok(SolvedHand { board: (result.0, result.1, result.2, result.3, result.4), previous_idxs, method: SolveMethod::Existing, alternatives: Vec::new() , unplaced: [0usize; 27] });
+                                },
+                                None => {
+                                    if past_deadline(deadline) {
+                                        return Err(SolverError::Timeout);
+                                    }
+                                    else if stop.load(Ordering::Relaxed) {
+                                        return Err(SolverError::Cancelled);
+                                    }
+                                    /* Otherwise we want to continue with the code that builds from scratch */
+                                }
+                            }
+                        },
+                        _ => {/* The previous board uses letters no longer in the hand, so build from scratch */}
+                    }
+                },
+                LetterComparison::Same => {
+                    // If the hand is the same then no need to do anything
+                    return Ok(SolvedHand { board: (prev_state.board.clone(), prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row), previous_idxs: HashSet::new(), method: SolveMethod::Unchanged, alternatives: Vec::new() , unplaced: [0usize; 27] });
+                },
+                LetterComparison::GreaterByOne if seen_greater.is_some_and(|letter| letter != BLANK) => {
+                    // If only a single letter has increased by one, then first check just that letter
+                    let seen_greater = seen_greater.expect("Only one letter increased");
+                    let mut board = span_board.clone();
+                    let mut res = play_one_letter(&mut board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, seen_greater, dictionary_trie, config.min_word_length);
+                    // A letter played beyond the maximum span is taken back off
+                    if let Some(result) = res.filter(|result| !board.fits_span(result.2, result.3, result.4, result.5)) {
+                        board.clear(result.0, result.1);
+                        res = None;
+                    }
+                    match res {
+                        Some(result) => {
+                            let previous_idxs = get_board_overlap(&prev_state.board, &board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, result.2, result.3, result.4, result.5);
+                            return Ok(SolvedHand { board: (board, result.2, result.3, result.4, result.5), previous_idxs, method: SolveMethod::OneLetter, alternatives: Vec::new() , unplaced: [0usize; 27] });
+                        },
+                        None => {
+                            // If we failed when playing one letter, try swapping it for a letter on the board and then playing the letter it replaced
+                            let mut swapped: Option<(usize, usize, usize, usize, usize, usize)> = None;
+                            for old_letter in (0..26).filter(|l| *l != seen_greater) {
+                                if let Some(swap) = try_swap_letter(&mut board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, old_letter, seen_greater, dictionary_trie, config.min_word_length) {
+                                    match play_one_letter(&mut board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, old_letter, dictionary_trie, config.min_word_length) {
+                                        Some(result) if board.fits_span(result.2, result.3, result.4, result.5) => {
+                                            swapped = Some(result);
+                                            break;
+                                        },
+                                        played => {
+                                            if let Some(result) = played {
+                                                board.clear(result.0, result.1);
+                                            }
+                                            board.set_val(swap.0, swap.1, old_letter);
+                                        }
+                                    }
+                                }
+                            }
+                            if let Some(result) = swapped {
+                                let previous_idxs = get_board_overlap(&prev_state.board, &board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, result.2, result.3, result.4, result.5);
+                                return Ok(SolvedHand { board: (board, result.2, result.3, result.4, result.5), previous_idxs, method: SolveMethod::Swap, alternatives: Vec::new() , unplaced: [0usize; 27] });
+                            }
+                            // Otherwise, try playing off the existing board
+                            let attempt = play_existing(&span_board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, &letters, dictionary_trie, dictionary_index, dict_to_use, excluded_words, config, deadline, stop, on_partial);
+                            match attempt {
+                                Some(result) => {
+                                    let previous_idxs = get_board_overlap(&prev_state.board, &result.0, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, result.1, result.2, result.3, result.4);
+                                    return Ok(SolvedHand { board: (result.0, result.1, result.2, result.3, result.4), previous_idxs, method: SolveMethod::Existing, alternatives: Vec::new() , unplaced: [0usize; 27] });
+                                },
+                                None => {
+                                    if past_deadline(deadline) {
+                                        return Err(SolverError::Timeout);
+                                    }
+                                    else if stop.load(Ordering::Relaxed) {
+                                        return Err(SolverError::Cancelled);
+                                    }
+                                    /* Otherwise we want to continue with the code that builds from scratch */
+                                }
+                            }
+                        }
+                    }
+                },
+                LetterComparison::GreaterByOne | LetterComparison::GreaterByMoreThanOne => {
+                    // If a letter has increased by more than one, multiple have increased by one or more, or a blank was added, then try playing off the existing board
+                    let attempt = play_existing(&span_board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, &letters, dictionary_trie, dictionary_index, dict_to_use, excluded_words, config, deadline, stop, on_partial);
+                    match attempt {
+                        Some(result) => {
+                            let previous_idxs = get_board_overlap(&prev_state.board, &result.0, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, result.1, result.2, result.3, result.4);
+                            return Ok(SolvedHand { board: (result.0, result.1, result.2, result.3, result.4), previous_idxs, method: SolveMethod::Existing, alternatives: Vec::new() , unplaced: [0usize; 27] });
+                        },
+                        None => {
+                            if past_deadline(deadline) {
+                                return Err(SolverError::Timeout);
+                            }
+                            else if stop.load(Ordering::Relaxed) {
+                                return Err(SolverError::Cancelled);
+                            }
+                            /* Otherwise we want to continue with the code that builds from scratch */
+                        }
+                    }
+                },
+                LetterComparison::GreaterAndLess => {
+                    // The previous board uses letters that are no longer in the hand, so first swap each of them for a letter that increased...
+                    let mut board = span_board.clone();
+                    let mut increased = [0usize; 27];
+                    let mut decreased = [0usize; 27];
+                    for i in 0..27 {
+                        increased[i] = letters[i].saturating_sub(prev_state.letters[i]);
+                        decreased[i] = prev_state.letters[i].saturating_sub(letters[i]);
+                    }
+                    // Tiles played as blanks are never swapped (see `try_swap_letter`)
+                    let mut swapped_all = decreased[BLANK] == 0;
+                    for (old_letter, decrease) in decreased.iter_mut().enumerate().take(26) {
+                        while swapped_all && *decrease > 0 {
+                            match (0..26).find(|new_letter| increased[*new_letter] > 0 && try_swap_letter(&mut board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, old_letter, *new_letter, dictionary_trie, config.min_word_length).is_some()) {
+                                Some(new_letter) => {
+                                    increased[new_letter] -= 1;
+                                    *decrease -= 1;
+                                },
+                                None => swapped_all = false
+                            }
+                        }
+                    }
+                    if swapped_all {
+                        if increased.iter().all(|count| *count == 0) {
+                            let previous_idxs = get_board_overlap(&prev_state.board, &board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row);
+                            return Ok(SolvedHand { board: (board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row), previous_idxs, method: SolveMethod::Swap, alternatives: Vec::new() , unplaced: [0usize; 27] });
+                        }
+                        // ...and then play the rest of the letters that increased off of the swapped board
+                        let attempt = play_existing(&board, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, &letters, dictionary_trie, dictionary_index, dict_to_use, excluded_words, config, deadline, stop, on_partial);
+                        match attempt {
+                            Some(result) => {
+                                let previous_idxs = get_board_overlap(&prev_state.board, &result.0, prev_state.min_col, prev_state.max_col, prev_state.min_row, prev_state.max_row, result.1, result.2, result.3, result.4);
+                                return Ok(SolvedHand { board: (result.0, result.1, result.2, result.3, result.4), previous_idxs, method: SolveMethod::Existing, alternatives: Vec::new() , unplaced: [0usize; 27] });
+                            },
+                            None => {
+                                if past_deadline(deadline) {
+                                    return Err(SolverError::Timeout);
+                                }
+                                else if stop.load(Ordering::Relaxed) {
+                                    return Err(SolverError::Cancelled);
+                                }
+                                /* Otherwise we want to continue with the code that builds from scratch */
+                            }
+                        }
+                    }
+                },
+                LetterComparison::SomeLess => {/* We just want to continue to the code that starts from scratch */}
+            }
+        },
+        None => {/* We just want to continue to the code that starts from scratch */}
+    }
+    // Play from scratch
+    // Get a vector of all valid words (a custom dictionary may have words too long to fit on the board)
+    let mut valid_words_vec: Vec<IndexedWord> = indexed_words(dict_to_use).filter(|word| (config.min_word_length..=config.board_size).contains(&word.len()) && !excluded_words.contains(word.word) && is_makeable(word, &letters)).collect();
+    if valid_words_vec.is_empty() {
+        return Err(SolverError::NoPlayableWords);
+    }
+    let stranded = stranded_letters(&letters, &valid_words_vec);
+    let mut letters = letters;
+    let mut set_aside = [0usize; 27];
+    if !stranded.is_empty() {
+        if !config.allow_partial {
+            return Err(SolverError::StrandedLetters(stranded));
+        }
+        // No board can use these letters, so look for one that uses the rest of the hand
+        for c in stranded {
+            let letter = letter_index(c).expect("Stranded letters are uppercase");
+            set_aside[letter] = letters[letter];
+            letters[letter] = 0;
+        }
+    }
+    // Words longer than the maximum span can't be played at all, and the first word (which is played across) can't be wider than the maximum width
+    let (span_width, span_height) = (config.max_width.unwrap_or(config.board_size).min(config.board_size), config.max_height.unwrap_or(config.board_size).min(config.board_size));
+    valid_words_vec.retain(|word| word.len() <= span_width.max(span_height));
+    order_for_seed(&mut valid_words_vec, config.seed);
+    let default_parallelism_approx = solver_threads(config.seed, config.threads);
+    // A required word is the only first word; otherwise optionally have every thread start from the most promising first words (later plays are ordered as usual)
+    let mut first_words: Vec<IndexedWord> = match required_word {
+        Some(required_word) => valid_words_vec.iter().copied().filter(|word| word.word == required_word).take(1).collect(),
+        None if config.use_frequency_ordering => best_first_word(&valid_words_vec, &letters),
+        None => valid_words_vec.clone()
+    };
+    first_words.retain(|word| word.len() <= span_width);
+    let first_words = Arc::new(first_words);
+    let new_board = || {
+        let mut board = Board::new(config.board_size);
+        board.set_max_span(config.max_width, config.max_height);
+        board
+    };
+    // Each thread takes the next untried first word whenever it finishes one, so that no thread sits idle while others still have words to try
+    let next_word = Arc::new(AtomicUsize::new(0));
+    let char_vec: Vec<FoundBoard> = Vec::new();
+    let ret_val = Arc::new(Mutex::new(char_vec));
+    let tried: Arc<Mutex<HashSet<&Word>>> = Arc::new(Mutex::new(HashSet::new()));
+    let valid_words_vec_len = valid_words_vec.len();
+    let arc_valid_words_vec = Arc::new(valid_words_vec);
+    let arc_dictionary_trie = Arc::new(dictionary_trie);
+    // Set aside the statistics of earlier searches, so that the closest this search comes to a solution can be told apart
+    let earlier_stats = take_solver_stats();
+    // For each thread (i.e. piece of available parallelism), spawn a new thread to check those words
+    // These threads check different initial words in the board, and whichever finishes first signals the others to stop
+    thread::scope(|s| {
+        let mut handles: Vec<thread::ScopedJoinHandle<SolverStats>> = Vec::with_capacity(default_parallelism_approx);
+        for _ in 0..default_parallelism_approx {
+            let stop_t = stop.clone();
+            let copied_first_words = Arc::clone(&first_words);
+            let next = Arc::clone(&next_word);
+            let new_letters = letters;
+            let copied_new_valid_words_vec = Arc::clone(&arc_valid_words_vec);
+            let copied_dictionary_trie = Arc::clone(&arc_dictionary_trie);
+            let conn = Arc::clone(&ret_val);
+            let cloned_previous_board = previous_board.clone();
+            let tried_words = Arc::clone(&tried);
+            let handle = s.spawn(move || {
+                // Loop through each word and play it on a new board
+                let mut words_checked = 0;
+                // Once this thread finds the first solution, its word limit is lowered so that it keeps searching for better ones for a while and then stops the others
+                let mut thread_config = config.clone();
+                let mut extending_search = false;
+                let mut board = new_board();
+                let mut word_buffers = Vec::new();
+                while let Some(word) = copied_first_words.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let (use_letters, mut letters_on_board, row, col_start) = play_first_word(&mut board, word, &new_letters);
+                    let min_col = col_start;
+                    let min_row = row;
+                    let max_col = col_start + (word.len()-1);
+                    let max_row = row;
+                    if use_letters.iter().all(|count| *count == 0) {
+                        if !stop_t.load(Ordering::Relaxed) {
+                            if config.num_solutions == 1 && config.solution_preference == SolutionPreference::Fastest {
+                                stop_t.store(true, Ordering::Relaxed);
+                            }
+                            let mut ret = conn.lock().expect("Failed to get lock on shared ret_val");
+                            if ret.is_empty() && config.solution_preference != SolutionPreference::Fastest {
+                                thread_config.max_words_to_check = config.max_words_to_check.min(words_checked + words_checked/EXTRA_SEARCH_DIVISOR);
+                                extending_search = true;
+                            }
+                            let previous_idxs = match cloned_previous_board {
+                                Some(ref prev) => get_board_overlap(&prev.0, &board, prev.1, prev.2, prev.3, prev.4, min_col, max_col, min_row, max_row),
+                                None => HashSet::new()
+                            };
+                            let layout = board_layout_hash(&board, min_col, max_col, min_row, max_row);
+                            if !ret.iter().any(|r| r.0 == layout) {
+                                ret.push((layout, previous_idxs, board.clone(), min_col, max_col, min_row, max_row));
+                            }
+                            if ret.len() >= config.num_solutions && config.solution_preference == SolutionPreference::Fastest {
+                                stop_t.store(true, Ordering::Relaxed);
+                                break;
+                            }
+                        }
+                    }
+                    else {
+                        record_letters_remaining(&use_letters, &board, min_col, max_col, min_row, max_row);
+                        // Reduce the set of remaining words to check to those that can be played with the letters not in the first word (plus only one of the tiles played in the first word)
+                        let word_letters: HashSet<usize> = HashSet::from_iter(word.iter().copied());
+                        let mut new_valid_words_vec: Vec<IndexedWord> = Vec::with_capacity(valid_words_vec_len);
+                        for w in copied_new_valid_words_vec.iter() {
+                            if check_filter_after_play(use_letters, w, &word_letters) && !tried_words.lock().expect("Failed to get lock on tried_words").contains(w.word) {
+                                new_valid_words_vec.push(*w);
+                            }
+                        }
+                        // Begin the recursive processing
+                        let result = play_further(&mut board, min_col, max_col, min_row, max_row, &new_valid_words_vec, &mut word_buffers, &copied_dictionary_trie, dictionary_index, use_letters, 0, Some(word.position), &mut words_checked, &mut letters_on_board, &thread_config, deadline, &stop_t, on_partial);
+                        match result {
+                            // If the result was good, then store it and signal other threads to finish (so long as another thread isn't doing so)
+                            Ok(res) => {
+                                // Later plays can extend the required word into a longer one, in which case the board can't be used
+                                if res.0 && required_word.is_none_or(|required_word| is_word_on_board(&board, res.1, res.2, res.3, res.4, required_word)) && !stop_t.load(Ordering::Relaxed) {
+                                    if config.num_solutions == 1 && config.solution_preference == SolutionPreference::Fastest {
+                                        stop_t.store(true, Ordering::Relaxed);
+                                    }
+                                    // The expect will panic the thread but I think that's ok
+                                    let mut ret = conn.lock().expect("Failed to get lock on shared ret_val");
+                                    if ret.is_empty() && config.solution_preference != SolutionPreference::Fastest {
+                                        thread_config.max_words_to_check = config.max_words_to_check.min(words_checked + words_checked/EXTRA_SEARCH_DIVISOR);
+                                        extending_search = true;
+                                    }
+                                    let previous_idxs = match cloned_previous_board {
+                                        Some(ref prev) => get_board_overlap(&prev.0, &board, prev.1, prev.2, prev.3, prev.4, res.1, res.2, res.3, res.4),
+                                        None => HashSet::new()
+                                    };
+                                    // The same board can be reached from different starting words, so only keep distinct layouts
+                                    let layout = board_layout_hash(&board, res.1, res.2, res.3, res.4);
+                                    if !ret.iter().any(|r| r.0 == layout) {
+                                        ret.push((layout, previous_idxs, board.clone(), res.1, res.2, res.3, res.4));
+                                    }
+                                    if ret.len() >= config.num_solutions && config.solution_preference == SolutionPreference::Fastest {
+                                        stop_t.store(true, Ordering::Relaxed);
+                                        break;
+                                    }
+                                    // Otherwise keep looking for more, starting again from an empty board
+                                    board = new_board();
+                                    continue;
+                                }
+                                else {
+                                    tried_words.lock().expect("Failed to get lock on tried words").insert(word.word);
+                                }
+                            },
+                            // If an error (we're out of bounds or another thread signalled to stop) then we're done
+                            Err(()) => {
+                                break;
+                            }
+                        }
+                    }
+                    for col in min_col..=max_col {
+                        board.clear(row, col);
+                    }
+                }
+                if extending_search {
+                    stop_t.store(true, Ordering::Relaxed);
+                }
+                take_solver_stats()
+            });
+            handles.push(handle);
+        }
+        // Wait for all the threads
+        for handle in handles {
+            if let Ok(stats) = handle.join() {
+                add_solver_stats(&stats);
+            }
+        }
+    });
+    let search_stats = take_solver_stats();
+    add_solver_stats(&earlier_stats);
+    add_solver_stats(&search_stats);
+    // If we're done, return the result
+    let mut ret: std::sync::MutexGuard<'_, Vec<FoundBoard>>;
+    match ret_val.lock() {
+        Ok(locked) => {
+            ret = locked;
+        },
+        _ => {
+            return Err("Failed to get lock on shared ret_val when checking return".into());
+        }
+    }
+    if !ret.is_empty() {
+        // Put the preferred boards first (the most compact, and then those with the fewest words, unless preferring the fewest words)
+        ret.sort_by_cached_key(|r| config.solution_preference.sort_key(&score_solution(&r.2, r.3, r.4, r.5, r.6)));
+        ret.truncate(config.num_solutions);
+        let mut solutions = ret.drain(..).map(|(_, previous_idxs, board, min_col, max_col, min_row, max_row)| ((board, min_col, max_col, min_row, max_row), previous_idxs));
+        let (board, previous_idxs) = solutions.next().expect("ret is not empty");
+        let method = if set_aside.iter().all(|count| *count == 0) {SolveMethod::Scratch} else {SolveMethod::Partial};
+        return Ok(SolvedHand { board, previous_idxs, method, alternatives: solutions.collect(), unplaced: set_aside });
+    }
+    // If no solution was found but the deadline has passed, then we ran out of time rather than exhausting the search
+    if past_deadline(deadline) {
+        return Err(SolverError::Timeout);
+    }
+    // Otherwise if the stop flag is set, then the user cancelled the solve
+    if stop.load(Ordering::Relaxed) {
+        return Err(SolverError::Cancelled);
+    }
+    // Otherwise, if a word was required (and a partial board isn't allowed), it's the reason no board was found
+    if let Some(required_word) = required_word.filter(|_| !config.allow_partial) {
+        return Err(SolverError::RequiredWordUnplayable(convert_array_to_word(required_word)));
+    }
+    // Likewise if the board's span was limited
+    if (config.max_width.is_some() || config.max_height.is_some()) && !config.allow_partial {
+        return Err(SolverError::ExceedsMaxSpan { max_width: span_width, max_height: span_height });
+    }
+    // Otherwise report the letters left over by the closest attempt (if any word could be played at all), or keep that attempt if allowed
+    if let Some((_, mut letters_remaining, closest)) = search_stats.fewest_letters_remaining {
+        if config.allow_partial {
+            letters_remaining = letters_add(&letters_remaining, &set_aside);
+            let previous_idxs = match previous_board {
+                Some(ref prev) => get_board_overlap(&prev.0, &closest.0, prev.1, prev.2, prev.3, prev.4, closest.1, closest.2, closest.3, closest.4),
+                None => HashSet::new()
+            };
+            return Ok(SolvedHand { board: closest, previous_idxs, method: SolveMethod::Partial, alternatives: Vec::new(), unplaced: letters_remaining });
+        }
+        return Err(SolverError::LettersLeftOver(letters_to_chars(&letters_remaining)));
+    }
+    Err(SolverError::NoSolution)
+}
+
+/// Solves a hand from scratch by racing several seeded solves against each other, so that each explores its own ordering of the words
+/// # Arguments
+/// * `letters` - Length-26 array of the number of each letter in the hand
+/// * `dict_to_use` - Dictionary of words to play
+/// * `dictionary_trie` - `Trie` of the same dictionary
+/// * `dictionary_index` - `DictionaryIndex` of the same dictionary
+/// * `excluded_words` - Words that must not be played
+/// * `config` - Settings for each seed's solve (see `SolveConfig`); each looks for a single board on one thread without a required word, so the seed, thread, solution and required word settings are ignored
+/// * `seeds` - Seed of each solve (see `order_for_seed`); each runs on its own thread
+/// * `deadline` - Time after which to stop checking words (if `None`, then there is no time limit)
+/// * `stop` - `AtomicBool` shared by every solve, which is set once any of them finds a board (and can be set to cancel them all)
+/// # Returns
+/// `Result` with the first `SolvedHand` found
+/// 
+/// *or `SolverError` (from the first seed) if no seed found a solution*
+pub fn solve_hand_multiseed(letters: Letters, dict_to_use: &Vec<Word>, dictionary_trie: &Trie, dictionary_index: &DictionaryIndex, excluded_words: &HashSet<Word>, config: &SolveConfig, seeds: &[u64], deadline: Option<Instant>, stop: &Arc<AtomicBool>) -> Result<SolvedHand, SolverError> {
+    if seeds.is_empty() {
+        return Err("At least one seed must be given".into());
+    }
+    let mut results: Vec<Result<SolvedHand, SolverError>> = thread::scope(|s| {
+        let handles: Vec<thread::ScopedJoinHandle<(Result<SolvedHand, SolverError>, SolverStats)>> = seeds.iter().map(|seed| {
+            let seed_config = SolveConfig { seed: Some(*seed), threads: None, num_solutions: 1, solution_preference: SolutionPreference::Fastest, allow_partial: false, required_word: None, ..config.clone() };
+            s.spawn(move || (solve_hand(letters, &None, dict_to_use, dictionary_trie, dictionary_index, excluded_words, &seed_config, deadline, stop, None), take_solver_stats()))
+        }).collect();
+        handles.into_iter().map(|handle| match handle.join() {
+            Ok((result, stats)) => {
+                add_solver_stats(&stats);
+                result
+            },
+            Err(_) => Err("A solver thread panicked".into())
+        }).collect()
+    });
+    // More than one seed can finish at nearly the same time, in which case any of their boards will do
+    match results.iter().position(|result| result.is_ok()) {
+        Some(idx) => results.swap_remove(idx),
+        None => results.swap_remove(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use crate::{letter_mask, string_to_letters};
+    use crate::board::{are_runs_valid, Cell};
+    use crate::test_helpers::{place, test_dictionary, test_solver, test_words};
+
+    #[test]
+    fn overused_letter_reported() {
+        let words: Vec<Word> = ["CAT", "TAX"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let index = DictionaryIndex::from_words(&words);
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        let mut letters_on_board = [0usize; 27];
+        for letter in convert_word_to_array("CAT") {
+            letters_on_board[letter] += 1;
+        }
+        let mut hand = [0usize; 27];
+        hand[0] = 1;
+        let res = board.play_word(&words[1], 70, 72, Direction::Horizontal, &hand, &mut letters_on_board);
+        assert!(matches!(res.3, LetterUsage::Overused(23)));
+        board.undo_play(&res.1, &mut letters_on_board);
+        let valid_words: Vec<IndexedWord> = indexed_words(&words).collect();
+        let mut overused_letters = 0;
+        let played = try_play_word_vertically(&mut board, &valid_words[1], 70, 72, 70, 70, &valid_words, &mut Vec::new(), &trie, &index, hand, 0, &mut 0, &mut letters_on_board, &mut overused_letters, &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), None);
+        assert!(matches!(played, Ok(None)));
+        assert_ne!(overused_letters & 1 << 23, 0);
+        assert_eq!(index.mask_of(&valid_words[1]), letter_mask(&words[1]));
+    }
+
+    #[test]
+    fn letter_swapped_only_into_valid_words() {
+        let words: Vec<Word> = ["CAT", "BAT", "HAT"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        assert_eq!(try_swap_letter(&mut board, 70, 72, 70, 70, 2, 25, &trie, 2), None);
+        assert_eq!(board.get_val(70, 70).letter(), Some(2));
+        assert_eq!(try_swap_letter(&mut board, 70, 72, 70, 70, 2, 1, &trie, 2), Some((70, 70, 70, 72, 70, 70)));
+        assert_eq!(board.get_val(70, 70).letter(), Some(1));
+    }
+
+    #[test]
+    fn hand_solved_on_smaller_boards() {
+        let (words, trie, index) = test_dictionary();
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("HELLOWORLDQUEEN") {
+            letters[letter] += 1;
+        }
+        for board_size in [20, 72] {
+            let solved = solve_hand(letters, &None, &words, &trie, &index, &HashSet::new(), &SolveConfig { board_size, ..SolveConfig::default() }, None, &Arc::new(AtomicBool::new(false)), None).unwrap();
+            let (board, min_col, max_col, min_row, max_row) = solved.board;
+            assert_eq!(board.size(), board_size);
+            assert!(max_col < board_size && max_row < board_size);
+            let mut letters_on_board = [0usize; 27];
+            for row in min_row..=max_row {
+                for col in min_col..=max_col {
+                    if let Some(letter) = board.get_val(row, col).letter() {
+                        letters_on_board[letter] += 1;
+                    }
+                }
+            }
+            assert_eq!(letters_on_board, letters);
+            assert!(is_connected(&board, min_col, max_col, min_row, max_row, &Vec::new()));
+            assert!(get_words_on_board(&board, min_col, max_col, min_row, max_row).iter().all(|w| trie.contains(&convert_word_to_array(&w.word))));
+        }
+    }
+
+    #[test]
+    fn filter_prunes_prefixes_with_trie() {
+        let words: Vec<Word> = ["CAT", "CART", "DOG"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let mut hand = [0usize; 27];
+        for letter in convert_word_to_array("CATD") {
+            hand[letter] += 1;
+        }
+        let board = [0usize; 27];
+        for word in words.iter() {
+            assert_eq!(check_filter_after_play_later(hand, board, word, 0, Some(&trie)), check_filter_after_play_later(hand, board, word, 0, None));
+        }
+        // Words that aren't in the trie are rejected at the first letter that leaves it
+        assert!(check_filter_after_play_later(hand, board, &convert_word_to_array("ACT"), 0, None));
+        assert!(!check_filter_after_play_later(hand, board, &convert_word_to_array("ACT"), 0, Some(&trie)));
+        assert!(!check_filter_after_play_later(hand, board, &convert_word_to_array("CA"), 0, Some(&trie)));
+        // Words longer than the hand plus the usable board letters are rejected up front
+        let mut board = [0usize; 27];
+        board[convert_word_to_array("R")[0]] = 1;
+        assert!(check_filter_after_play_later(hand, board, &words[1], 1, Some(&trie)));
+        assert!(!check_filter_after_play_later([0usize; 27], board, &words[1], 1, None));
+    }
+
+    #[test]
+    fn indexed_filter_matches_letter_by_letter_filter() {
+        let words: Vec<Word> = include_str!("dictionary.txt").lines().map(convert_word_to_array).collect();
+        let index = DictionaryIndex::from_words(&words);
+        let mut rng = StdRng::seed_from_u64(1517);
+        for _ in 0..5000 {
+            let position = rng.gen_range(0..words.len());
+            let word = IndexedWord { position, word: &words[position] };
+            let mut hand = [0usize; 27];
+            let mut board = [0usize; 27];
+            for _ in 0..rng.gen_range(0..25) {
+                hand[rng.gen_range(0..27)] += 1;
+            }
+            for _ in 0..rng.gen_range(0..40) {
+                board[rng.gen_range(0..26)] += 1;
+            }
+            let filter_letters_on_board = rng.gen_range(0..4);
+            assert_eq!(index.filter_words(&[word], &hand, &board, filter_letters_on_board).len() == 1, check_filter_after_play_later(hand, board, &word, filter_letters_on_board, None), "{}", convert_array_to_word(&word));
+        }
+        // Words from outside the indexed dictionary are still filtered correctly
+        let other = convert_word_to_array("CAT");
+        let other = IndexedWord { position: words.len(), word: &other };
+        let mut hand = [0usize; 27];
+        hand[2] = 1;
+        hand[0] = 1;
+        assert!(index.filter_words(&[other], &hand, &[0; 27], 2).is_empty());
+        let mut board = [0usize; 27];
+        board[19] = 1;
+        assert_eq!(index.filter_words(&[other], &hand, &board, 1).len(), 1);
+    }
+
+    #[test]
+    fn partial_solutions_reported_while_solving() {
+        let (words, trie, index) = test_dictionary();
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("HELLOWORLDQUEEN") {
+            letters[letter] += 1;
+        }
+        let partials: Arc<Mutex<Vec<PartialSolution>>> = Arc::new(Mutex::new(Vec::new()));
+        let collected = Arc::clone(&partials);
+        let on_partial: PartialCallback = Arc::new(move |partial| collected.lock().unwrap().push(partial));
+        solve_hand(letters, &None, &words, &trie, &index, &HashSet::new(), &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), Some(&on_partial)).unwrap();
+        let partials = partials.lock().unwrap();
+        assert!(!partials.is_empty());
+        // Boards are only reported while letters remain to be played
+        assert!(partials.iter().all(|p| p.letters_remaining > 0 && p.letters_remaining < 15 && !p.board.is_empty()));
+    }
+
+    #[test]
+    fn seeded_solves_are_repeatable() {
+        let (words, trie, index) = test_dictionary();
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("HELLOWORLDQUEENSTAB") {
+            letters[letter] += 1;
+        }
+        let solve = |seed: Option<u64>| {
+            let solved = solve_hand(letters, &None, &words, &trie, &index, &HashSet::new(), &SolveConfig { seed, ..SolveConfig::default() }, None, &Arc::new(AtomicBool::new(false)), None).unwrap();
+            let (board, min_col, max_col, min_row, max_row) = solved.board;
+            board_to_vec(&board, min_col, max_col, min_row, max_row)
+        };
+        for seed in [0, 1518] {
+            assert_eq!(solve(Some(seed)), solve(Some(seed)));
+        }
+    }
+
+    #[test]
+    fn distinct_alternatives_sorted_by_area() {
+        let (words, trie, index) = test_dictionary();
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("HELLOWORLDQUEEN") {
+            letters[letter] += 1;
+        }
+        let solved = solve_hand(letters, &None, &words, &trie, &index, &HashSet::new(), &SolveConfig { num_solutions: 3, ..SolveConfig::default() }, None, &Arc::new(AtomicBool::new(false)), None).unwrap();
+        assert!(solved.alternatives.len() <= 2);
+        let boards: Vec<&BoardAndIdxs> = std::iter::once(&solved.board).chain(solved.alternatives.iter().map(|(b, _)| b)).collect();
+        let hashes: HashSet<u64> = boards.iter().map(|(board, min_col, max_col, min_row, max_row)| board_layout_hash(board, *min_col, *max_col, *min_row, *max_row)).collect();
+        assert_eq!(hashes.len(), boards.len());
+        let areas: Vec<usize> = boards.iter().map(|(_, min_col, max_col, min_row, max_row)| (max_col - min_col + 1) * (max_row - min_row + 1)).collect();
+        assert!(areas.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn first_words_ordered_by_how_evenly_hand_left() {
+        let words: Vec<Word> = ["TEE", "TEA", "ZEE", "EATEN", "AT"].iter().map(|w| convert_word_to_array(w)).collect();
+        let refs: Vec<IndexedWord> = indexed_words(&words).collect();
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("EEEETTANZ") {
+            letters[letter] += 1;
+        }
+        let ordered: Vec<String> = best_first_word(&refs, &letters).iter().map(|w| convert_array_to_word(w)).collect();
+        assert_eq!(ordered, vec!["EATEN", "TEE", "ZEE", "TEA", "AT"]);
+    }
+
+    #[test]
+    fn compact_board_preferred() {
+        // Both boards use the letters ACDENOT
+        let mut snake = Board::new(BOARD_SIZE);
+        place(&mut snake, "CAT", 70, 70, Direction::Horizontal);
+        place(&mut snake, "TEN", 70, 72, Direction::Vertical);
+        place(&mut snake, "NOD", 72, 72, Direction::Horizontal);
+        let mut compact = Board::new(BOARD_SIZE);
+        place(&mut compact, "DOCENT", 70, 70, Direction::Horizontal);
+        place(&mut compact, "AT", 69, 75, Direction::Vertical);
+        let snake_score = score_solution(&snake, 70, 74, 70, 72);
+        let compact_score = score_solution(&compact, 70, 75, 69, 70);
+        #[cfg(not(feature = "diagonal"))]
+        assert_eq!((snake_score.area, snake_score.word_count), (15, 3));
+        // The A, E and O of the snake also make a diagonal word
+        #[cfg(feature = "diagonal")]
+        assert_eq!((snake_score.area, snake_score.word_count), (15, 4));
+        assert_eq!((compact_score.area, compact_score.word_count), (12, 2));
+        assert_eq!(compact_score.average_word_length, 4.0);
+        assert!(SolutionPreference::Compact.sort_key(&compact_score) < SolutionPreference::Compact.sort_key(&snake_score));
+        assert!(SolutionPreference::FewestWords.sort_key(&compact_score) < SolutionPreference::FewestWords.sort_key(&snake_score));
+    }
+
+    #[test]
+    fn compact_solve_no_larger_than_fastest() {
+        let (words, trie, index) = test_dictionary();
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("HELLOWORLDQUEENSTAB") {
+            letters[letter] += 1;
+        }
+        // A seeded solve uses one thread, so the compact search sees the fastest solution before looking further
+        let area = |solution_preference: SolutionPreference| {
+            let solved = solve_hand(letters, &None, &words, &trie, &index, &HashSet::new(), &SolveConfig { seed: Some(0), solution_preference, ..SolveConfig::default() }, None, &Arc::new(AtomicBool::new(false)), None).unwrap();
+            let (board, min_col, max_col, min_row, max_row) = solved.board;
+            score_solution(&board, min_col, max_col, min_row, max_row).area
+        };
+        assert!(area(SolutionPreference::Compact) <= area(SolutionPreference::Fastest));
+    }
+
+    #[test]
+    fn single_word_board_can_be_built_off() {
+        let (words, trie, index) = test_dictionary();
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CATFISH", 70, 70, Direction::Horizontal);
+        let removable = get_removable_indices(&board, 70, 76, 70, 70, &trie);
+        assert!(!removable.is_empty());
+        // Whatever is left after removing the end of the word must still be a word (or a single tile)
+        for (indices, new_min_col, new_max_col, _, _) in removable.iter() {
+            assert_eq!(indices.last(), Some(&(70, 76)));
+            assert!(*new_max_col == 70 || trie.contains(&(*new_min_col..=*new_max_col).filter_map(|col| board.get_val(70, col).letter()).collect::<Word>()));
+        }
+        // An X can't be played using just one letter of CATFISH, so the board must be partly taken apart
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("CATFISHX") {
+            letters[letter] += 1;
+        }
+        let (new_board, min_col, max_col, min_row, max_row) = play_existing(&board, 70, 76, 70, 70, &letters, &trie, &index, &words, &HashSet::new(), &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), None).unwrap();
+        assert!(is_connected(&new_board, min_col, max_col, min_row, max_row, &Vec::new()));
+        assert!(get_words_on_board(&new_board, min_col, max_col, min_row, max_row).iter().all(|w| trie.contains(&convert_word_to_array(&w.word))));
+    }
+
+    #[test]
+    fn racing_seeds_solve_hand() {
+        let (words, trie, index) = test_dictionary();
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("HELLOWORLDQUEENSTAB") {
+            letters[letter] += 1;
+        }
+        let stop = Arc::new(AtomicBool::new(false));
+        let solved = solve_hand_multiseed(letters, &words, &trie, &index, &HashSet::new(), &SolveConfig::default(), &[1, 2, 3, 4], None, &stop).unwrap();
+        let (board, min_col, max_col, min_row, max_row) = solved.board;
+        assert!(solved.method == SolveMethod::Scratch);
+        assert!(is_connected(&board, min_col, max_col, min_row, max_row, &Vec::new()));
+        assert!(get_words_on_board(&board, min_col, max_col, min_row, max_row).iter().all(|w| trie.contains(&convert_word_to_array(&w.word))));
+        // The winning seed stops the others
+        assert!(stop.load(Ordering::Relaxed));
+        assert!(solve_hand_multiseed(letters, &words, &trie, &index, &HashSet::new(), &SolveConfig::default(), &[], None, &stop).is_err());
+    }
+
+    #[test]
+    fn spread_out_board_compacted_while_solving() {
+        let words = vec![convert_word_to_array("ABCDEFGHIJKLMNOPQ"), convert_word_to_array("QA")];
+        let trie = Trie::from_words(&words);
+        let index = DictionaryIndex::from_words(&words);
+        let valid_words: Vec<IndexedWord> = indexed_words(&words).skip(1).collect();
+        let mut board = Board::new(20);
+        place(&mut board, "ABCDEFGHIJKLMNOPQ", 0, 0, Direction::Horizontal);
+        let mut letters_on_board = [0usize; 27];
+        for letter in words[0].iter() {
+            letters_on_board[*letter] += 1;
+        }
+        let mut hand = [0usize; 27];
+        hand[0] = 1;
+        // The word spans 17 of the 20 columns, so the board is re-centered before QA is played down from the Q
+        let res = play_further(&mut board, 0, 16, 0, 0, &valid_words, &mut Vec::new(), &trie, &index, hand, 0, None, &mut 0, &mut letters_on_board, &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), None).unwrap();
+        assert_eq!(res, (true, 2, 18, 10, 11));
+        assert_eq!(board.get_val(10, 2).letter(), Some(0));
+        assert_eq!(board.get_val(11, 18).letter(), Some(0));
+        assert!(board.get_val(0, 0).is_empty());
+    }
+
+    #[test]
+    fn peel_on_spread_out_board_keeps_previous_tiles_in_place() {
+        let words = vec![convert_word_to_array("ABCDEFGHIJKLMNOPQ"), convert_word_to_array("QAT"), convert_word_to_array("TA")];
+        let trie = Trie::from_words(&words);
+        let index = DictionaryIndex::from_words(&words);
+        let mut board = Board::new(20);
+        place(&mut board, "ABCDEFGHIJKLMNOPQ", 0, 0, Direction::Horizontal);
+        let letters = string_to_letters("ABCDEFGHIJKLMNOPQ").unwrap();
+        let previous = Some(GameState { board, min_col: 0, max_col: 16, min_row: 0, max_row: 0, letters });
+        // The previous word spans 17 of the 20 columns, but the board isn't re-centered, so every tile of it is still where it was once QAT and TA are played
+        let solved = solve_hand(letters_add(&letters, &string_to_letters("AAT").unwrap()), &previous, &words, &trie, &index, &HashSet::new(), &SolveConfig { threads: Some(1), board_size: 20, ..SolveConfig::default() }, None, &Arc::new(AtomicBool::new(false)), None).unwrap();
+        assert!(solved.method == SolveMethod::Existing);
+        let (board, min_col, max_col, min_row, max_row) = solved.board;
+        assert_eq!((min_col, min_row), (0, 0));
+        assert_eq!(board_to_vec(&board, 0, 16, 0, 0), vec!["ABCDEFGHIJKLMNOPQ".chars().map(String::from).collect::<Vec<String>>()]);
+        assert!(is_board_valid_full(&board, min_col, max_col, min_row, max_row, &trie, 2));
+        assert_eq!(solved.previous_idxs, (0..17).map(|col| (0, col)).collect());
+    }
+
+    #[test]
+    fn one_new_letter_played_with_long_dictionary() {
+        let short_words = test_words();
+        let mut long_words: Vec<Word> = include_str!("dictionary.txt").lines().map(convert_word_to_array).collect();
+        long_words.sort_by_key(|word| Reverse(word.len()));
+        let long_trie = Trie::from_words(&long_words);
+        let long_index = DictionaryIndex::from_words(&long_words);
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "JAM", 70, 70, Direction::Horizontal);
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("JAM") {
+            letters[letter] += 1;
+        }
+        // JAMB is only in the long dictionary, and the B can't be added to JAM using the short one
+        assert!(play_one_letter(&mut board.clone(), 70, 72, 70, 70, 1, &Trie::from_words(&short_words), 2).is_none());
+        let previous = Some(GameState { board, min_col: 70, max_col: 72, min_row: 70, max_row: 70, letters });
+        letters[1] += 1;
+        let solved = solve_hand(letters, &previous, &long_words, &long_trie, &long_index, &HashSet::new(), &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), None).unwrap();
+        assert!(solved.method == SolveMethod::OneLetter);
+        let (board, min_col, max_col, min_row, max_row) = solved.board;
+        assert!(get_words_on_board(&board, min_col, max_col, min_row, max_row).iter().all(|w| long_trie.contains(&convert_word_to_array(&w.word))));
+    }
+
+    #[test]
+    fn search_statistics_gathered_from_solver_threads() {
+        let (words, trie, index) = test_dictionary();
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("HELLOWORLDQUEENSTAB") {
+            letters[letter] += 1;
+        }
+        take_solver_stats();
+        solve_hand(letters, &None, &words, &trie, &index, &HashSet::new(), &SolveConfig { max_words_to_check: 1_000_000, ..SolveConfig::default() }, None, &Arc::new(AtomicBool::new(false)), None).unwrap();
+        // The words are checked on other threads, but their statistics are passed back to this one
+        let stats = take_solver_stats();
+        assert!(stats.max_depth_reached > 0);
+        assert!(stats.words_checked_at_each_depth[0] > 0);
+        assert!(stats.words_checked_at_each_depth[stats.max_depth_reached.min(STATS_DEPTHS-1)] > 0);
+        assert!(stats.words_checked_at_each_depth[stats.max_depth_reached+1..].iter().all(|count| *count == 0));
+        assert_eq!(take_solver_stats().words_checked_at_each_depth.iter().sum::<usize>(), 0);
+    }
+
+    #[test]
+    fn stranded_letters_fail_before_searching() {
+        let words: Vec<Word> = ["QUIT", "SAT", "TIE", "EAT", "MY", "BY"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let index = DictionaryIndex::from_words(&words);
+        let solve = |hand: &str| {
+            let letters = string_to_letters(hand).unwrap();
+            // With no limit on the words checked, only the pre-check keeps these from searching every combination
+            solve_hand(letters, &None, &words, &trie, &index, &HashSet::new(), &SolveConfig { max_words_to_check: usize::MAX, threads: Some(1), ..SolveConfig::default() }, None, &Arc::new(AtomicBool::new(false)), None).err()
+        };
+        // Some words can be made from this all-consonant hand, but none use the K or Z
+        let error = solve("BMYKZ").unwrap();
+        assert_eq!(error, SolverError::StrandedLetters(vec!['K', 'Z']));
+        assert_eq!(serde_json::to_value(&error).unwrap(), serde_json::json!({"kind": "StrandedLetters", "message": "No valid words use the letters K, Z - dump one of them and try again!", "letters": ["K", "Z"]}));
+        // Without a U, the Q can't be played
+        let error = solve("QTIEAS").unwrap();
+        assert_eq!(error, SolverError::StrandedLetters(vec!['Q']));
+        assert_eq!(error.to_string(), "No valid word uses the Q - dump it and try again!");
+        // But a blank can stand in for the U
+        assert_ne!(solve("QTIEAS?"), Some(SolverError::StrandedLetters(vec!['Q'])));
+    }
+
+    #[test]
+    fn word_sharing_no_letters_played_alongside_board() {
+        let words: Vec<Word> = ["HI", "ON", "HO", "IN"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let index = DictionaryIndex::from_words(&words);
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "HI", 70, 70, Direction::Horizontal);
+        let mut letters_on_board = [0usize; 27];
+        for letter in convert_word_to_array("HI") {
+            letters_on_board[letter] += 1;
+        }
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("ON") {
+            letters[letter] += 1;
+        }
+        // ON has no letters in common with HI, but fits beneath it by forming HO and IN, so words that share no letters with the last word played are still tried
+        let valid_words: Vec<IndexedWord> = indexed_words(&words).skip(1).take(1).collect();
+        let res = play_further(&mut board, 70, 71, 70, 70, &valid_words, &mut Vec::new(), &trie, &index, letters, 1, Some(0), &mut 0, &mut letters_on_board, &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), None);
+        assert_eq!(res, Ok((true, 70, 71, 70, 71)));
+        assert_eq!(board_to_vec(&board, 70, 71, 70, 71), vec![vec!["H", "I"], vec!["O", "N"]]);
+    }
+
+    #[test]
+    fn words_too_long_for_the_hand_are_skipped() {
+        let words: Vec<Word> = ["HOSTING", "HI", "ON", "HO", "IN"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let index = DictionaryIndex::from_words(&words);
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "HI", 70, 70, Direction::Horizontal);
+        let mut letters_on_board = [0usize; 27];
+        for letter in convert_word_to_array("HI") {
+            letters_on_board[letter] += 1;
+        }
+        let letters = string_to_letters("ON").unwrap();
+        // Two letters in the hand plus one from the board can't make a seven-letter word, so only ON is checked
+        let valid_words: Vec<IndexedWord> = indexed_words(&words).step_by(2).take(2).collect();
+        let mut words_checked = 0;
+        let res = play_further(&mut board, 70, 71, 70, 70, &valid_words, &mut Vec::new(), &trie, &index, letters, 1, None, &mut words_checked, &mut letters_on_board, &SolveConfig { filter_letters_on_board: 1, ..SolveConfig::default() }, None, &Arc::new(AtomicBool::new(false)), None);
+        assert_eq!(res, Ok((true, 70, 71, 70, 71)));
+        assert_eq!(words_checked, 1);
+    }
+
+    #[test]
+    fn full_solve_without_app_state() {
+        let solver = test_solver();
+        let valid_words: HashSet<String> = include_str!("updated_short_dictionary.txt").lines().map(|word| word.to_uppercase()).collect();
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("HELLOWORLD") {
+            letters[letter] += 1;
+        }
+        let config = SolveConfig { threads: Some(1), ..SolveConfig::default() };
+        let solved = solver.solve(letters, config.clone()).unwrap();
+        assert_eq!(solved.game.letters, letters);
+        let solution = &solved.solutions()[0];
+        assert!(solution.words.iter().all(|word| valid_words.contains(&word.word)));
+        let mut tiles: Vec<char> = solution.board.iter().flatten().flat_map(|tile| tile.chars()).filter(|c| c.is_ascii_alphabetic()).collect();
+        tiles.sort();
+        let mut expected: Vec<char> = "HELLOWORLD".chars().collect();
+        expected.sort();
+        assert_eq!(tiles, expected);
+        // A peel is played off of the previous board
+        letters[convert_word_to_array("S")[0]] += 1;
+        let peeled = solver.solve_incremental(&solved, letters, config).unwrap();
+        assert_eq!(peeled.game.letters, letters);
+        assert!(peeled.solutions()[0].words.iter().all(|word| valid_words.contains(&word.word)));
+    }
+
+    #[test]
+    fn hand_with_letters_exchanged_keeps_previous_board() {
+        let words: Vec<Word> = ["CAT", "COT", "DOG"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let index = DictionaryIndex::from_words(&words);
+        let hand = |word: &str| {
+            let mut letters = [0usize; 27];
+            for letter in convert_word_to_array(word) {
+                letters[letter] += 1;
+            }
+            letters
+        };
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        let previous = Some(GameState { board, min_col: 70, max_col: 72, min_row: 70, max_row: 70, letters: hand("CAT") });
+        let stop = Arc::new(AtomicBool::new(false));
+        // The A was exchanged for an O, which can take its place
+        let solved = solve_hand(hand("COT"), &previous, &words, &trie, &index, &HashSet::new(), &SolveConfig { threads: Some(1), ..SolveConfig::default() }, None, &stop, None).unwrap();
+        assert!(solved.method == SolveMethod::Swap);
+        let (board, min_col, max_col, min_row, max_row) = solved.board;
+        assert_eq!(board_to_vec(&board, min_col, max_col, min_row, max_row), vec![vec!["C", "O", "T"]]);
+        assert_eq!(solved.previous_idxs, HashSet::from([(70, 70), (70, 72)]));
+        // The A was exchanged for DOG, so the O takes its place and DOG is played through it
+        let solved = solve_hand(hand("CTDOG"), &previous, &words, &trie, &index, &HashSet::new(), &SolveConfig { threads: Some(1), ..SolveConfig::default() }, None, &stop, None).unwrap();
+        assert!(solved.method == SolveMethod::Existing);
+        let (board, min_col, max_col, min_row, max_row) = solved.board;
+        assert_eq!(board_to_vec(&board, min_col, max_col, min_row, max_row), vec![vec![" ", "D", " "], vec!["C", "O", "T"], vec![" ", "G", " "]]);
+    }
+
+    #[test]
+    fn peel_played_vertically_off_previous_board() {
+        let words: Vec<Word> = ["TODAY", "TO"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let index = DictionaryIndex::from_words(&words);
+        let hand = |word: &str| {
+            let mut letters = [0usize; 27];
+            for letter in convert_word_to_array(word) {
+                letters[letter] += 1;
+            }
+            letters
+        };
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "TO", 70, 70, Direction::Vertical);
+        let previous = Some(GameState { board, min_col: 70, max_col: 70, min_row: 70, max_row: 71, letters: hand("TO") });
+        // The new letters can only be played by extending the column downwards
+        let solved = solve_hand(hand("TODAY"), &previous, &words, &trie, &index, &HashSet::new(), &SolveConfig { threads: Some(1), ..SolveConfig::default() }, None, &Arc::new(AtomicBool::new(false)), None).ok().unwrap();
+        assert!(solved.method == SolveMethod::Existing);
+        let (board, min_col, max_col, min_row, max_row) = solved.board;
+        assert_eq!((min_col, min_row), (70, 70));
+        assert_eq!(board_to_vec(&board, min_col, max_col, min_row, max_row), vec![vec!["T"], vec!["O"], vec!["D"], vec!["A"], vec!["Y"]]);
+        assert_eq!(solved.previous_idxs, HashSet::from([(70, 70), (71, 70)]));
+    }
+
+    #[test]
+    fn peel_marks_carried_over_tiles() {
+        let solver = test_solver();
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("HELLOWORLD") {
+            letters[letter] += 1;
+        }
+        let config = SolveConfig { threads: Some(1), ..SolveConfig::default() };
+        let solved = solver.solve(letters, config.clone()).unwrap();
+        let first = serde_json::to_value(&solved.solutions()[0]).unwrap();
+        assert!(first["carried_over"].as_array().unwrap().iter().flat_map(|row| row.as_array().unwrap()).all(|cell| cell == false));
+        letters[convert_word_to_array("S")[0]] += 1;
+        let peeled = solver.solve_incremental(&solved, letters, config).unwrap();
+        let json = serde_json::to_value(&peeled.solutions()[0]).unwrap();
+        let (previous, game) = (&solved.game, &peeled.game);
+        // Exactly the tiles in the same place as on the previous board are carried over, and the letters themselves are unmarked
+        let mut num_carried_over = 0;
+        for (i, (row, carried_over_row)) in json["board"].as_array().unwrap().iter().zip(json["carried_over"].as_array().unwrap()).enumerate() {
+            for (j, (cell, carried_over)) in row.as_array().unwrap().iter().zip(carried_over_row.as_array().unwrap()).enumerate() {
+                let (row, col) = (game.min_row+i, game.min_col+j);
+                let in_previous = (previous.min_row..=previous.max_row).contains(&row) && (previous.min_col..=previous.max_col).contains(&col);
+                let expected = in_previous && !previous.board.get_val(row, col).is_empty() && previous.board.get_val(row, col) == game.board.get_val(row, col);
+                assert_eq!(carried_over.as_bool(), Some(expected));
+                assert_eq!(cell.as_str().unwrap().len(), 1);
+                num_carried_over += expected as usize;
+            }
+        }
+        assert!(num_carried_over > 0);
+    }
+
+    #[test]
+    fn col_limits_span_tiles_in_adjacent_rows() {
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "DOG", 70, 50, Direction::Vertical);
+        place(&mut board, "CAT", 70, 70, Direction::Vertical);
+        // Rows holding tiles in both columns, and the rows just above and below them (which hold no tiles themselves)
+        for row in 69..=73 {
+            assert_eq!(get_col_limits(&board, row, 50, 70), (50, 70));
+        }
+        // Two rows below the word at column 50, only the columns next to newer tiles count
+        place(&mut board, "AT", 73, 68, Direction::Horizontal);
+        assert_eq!(get_col_limits(&board, 74, 50, 70), (68, 69));
+        assert_eq!(get_col_limits(&board, 73, 50, 70), (50, 70));
+    }
+
+    #[test]
+    fn peel_keeps_absolute_coordinates() {
+        let solver = test_solver();
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("HELLOWORLD") {
+            letters[letter] += 1;
+        }
+        let config = SolveConfig { threads: Some(1), ..SolveConfig::default() };
+        let solved = solver.solve(letters, config.clone()).unwrap();
+        letters[convert_word_to_array("S")[0]] += 1;
+        let peeled = solver.solve_incremental(&solved, letters, config).unwrap();
+        assert!(peeled.method != SolveMethod::Scratch);
+        let (before, after) = (serde_json::to_value(&solved.solutions()[0]).unwrap(), serde_json::to_value(&peeled.solutions()[0]).unwrap());
+        let bound = |json: &serde_json::Value, name: &str| json[name].as_u64().unwrap() as usize;
+        // Gets the tile at an absolute row and column from a response (or a space if it is outside of the returned grid)
+        let tile_at = |json: &serde_json::Value, row: usize, col: usize| {
+            if (bound(json, "min_row")..=bound(json, "max_row")).contains(&row) && (bound(json, "min_col")..=bound(json, "max_col")).contains(&col) {
+                json["board"][row-bound(json, "min_row")][col-bound(json, "min_col")].as_str().unwrap().to_owned()
+            }
+            else {
+                " ".to_owned()
+            }
+        };
+        assert_eq!(after["board"].as_array().unwrap().len(), bound(&after, "max_row")-bound(&after, "min_row")+1);
+        let mut num_carried_over = 0;
+        for row in bound(&after, "min_row")..=bound(&after, "max_row") {
+            for col in bound(&after, "min_col")..=bound(&after, "max_col") {
+                if after["carried_over"][row-bound(&after, "min_row")][col-bound(&after, "min_col")] == true {
+                    assert_eq!(tile_at(&after, row, col), tile_at(&before, row, col));
+                    num_carried_over += 1;
+                }
+            }
+        }
+        assert!(num_carried_over > 0);
+    }
+
+    #[test]
+    fn failed_solve_reports_letters_left_over() {
+        let words: Vec<Word> = ["CAT", "QI"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let index = DictionaryIndex::from_words(&words);
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("CATQI") {
+            letters[letter] += 1;
+        }
+        // CAT and QI share no letters, can't be played beside each other, and (with the diagonal rule) the only word long enough to be played diagonally is CAT itself, so the best attempt plays CAT and leaves QI
+        take_solver_stats();
+        let error = solve_hand(letters, &None, &words, &trie, &index, &HashSet::new(), &SolveConfig { threads: Some(1), ..SolveConfig::default() }, None, &Arc::new(AtomicBool::new(false)), None).err().unwrap();
+        assert_eq!(error, SolverError::LettersLeftOver(vec!['I', 'Q']));
+        assert_eq!(error.to_string(), "No solution found - the best attempt left 2 tiles: I, Q (consider dumping the Q)");
+        assert_eq!(serde_json::to_value(&error).unwrap()["letters"], serde_json::json!(["I", "Q"]));
+        assert_eq!(take_solver_stats().fewest_letters_remaining.map(|(fewest, _, _)| fewest), Some(2));
+    }
+
+    #[test]
+    fn partial_board_leaves_stranded_letter_unplaced() {
+        let solver = test_solver();
+        let mut letters = [0usize; 27];
+        for letter in convert_word_to_array("HELLOWORLDQ") {
+            letters[letter] += 1;
+        }
+        // Without a U, I or A the Q can't be played, so the whole hand fails unless a partial board is allowed
+        assert_eq!(solver.solve(letters, SolveConfig { threads: Some(1), ..SolveConfig::default() }).err(), Some(SolverError::StrandedLetters(vec!['Q'])));
+        let solved = solver.solve(letters, SolveConfig { threads: Some(1), allow_partial: true, ..SolveConfig::default() }).ok().unwrap();
+        assert!(solved.method == SolveMethod::Partial);
+        let solution = solved.solutions().swap_remove(0);
+        assert_eq!(solution.unplaced, vec!['Q']);
+        assert_eq!(solution.board.iter().flatten().filter(|cell| !cell.trim().is_empty()).count(), 10);
+        // The stored game only holds the tiles on the board, so a later peel is played off of them
+        let mut placed = letters;
+        placed[convert_word_to_array("Q")[0]] = 0;
+        assert_eq!(solved.game.letters, placed);
+    }
+
+    #[test]
+    fn words_shorter_than_minimum_rejected() {
+        let words: Vec<Word> = ["CAT", "AX"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let cells = |s: &str| s.chars().map(|c| if c == ' ' {Cell::EMPTY} else {Cell::new(convert_word_to_array(&c.to_string())[0]).unwrap()}).collect::<Vec<Cell>>();
+        // Single letters are just tiles crossing the line, so they're allowed whatever the minimum
+        assert!(are_runs_valid(&cells("CAT A"), &trie, 3, &mut Vec::new()));
+        assert!(!are_runs_valid(&cells("CAT AX"), &trie, 3, &mut Vec::new()));
+        let solver = test_solver();
+        let letters = string_to_letters("HELLOWORLDTESTING").unwrap();
+        let solved = solver.solve(letters, SolveConfig { threads: Some(1), seed: Some(0), min_word_length: 3, ..SolveConfig::default() }).ok().unwrap();
+        let solution = solved.solutions().swap_remove(0);
+        assert!(!solution.words.is_empty());
+        assert!(solution.words.iter().all(|played| played.word.len() >= 3));
+    }
+
+    #[test]
+    fn word_played_against_last_row_and_column() {
+        let words: Vec<Word> = ["CAT", "AT"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let index = DictionaryIndex::from_words(&words);
+        let valid_words: Vec<IndexedWord> = indexed_words(&words).collect();
+        let last = BOARD_SIZE-1;
+        let mut hand = [0usize; 27];
+        hand[0] = 1;
+        // "CAT" along the last row, with "AT" played down onto its T in the last column
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", last, last-2, Direction::Horizontal);
+        let mut letters_on_board = string_to_letters("CAT").unwrap();
+        let played = try_play_word_vertically(&mut board, &valid_words[1], last-2, last, last, last, &valid_words, &mut Vec::new(), &trie, &index, hand, 0, &mut 0, &mut letters_on_board, &mut 0, &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), None);
+        assert_eq!(played, Ok(Some((true, last-2, last, last-1, last))));
+        // "CAT" down the last column, with "AT" played across onto its T in the last row
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", last-2, last, Direction::Vertical);
+        let mut letters_on_board = string_to_letters("CAT").unwrap();
+        let played = try_play_word_horizontal(&mut board, &valid_words[1], last, last, last-2, last, &valid_words, &mut Vec::new(), &trie, &index, hand, 0, &mut 0, &mut letters_on_board, &mut 0, &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), None);
+        assert_eq!(played, Ok(Some((true, last-1, last, last-2, last))));
+        // Without the letters for "AT", every row and column up to the edge is tried without playing it
+        let mut letters_on_board = string_to_letters("CAT").unwrap();
+        let played = try_play_word_horizontal(&mut board, &valid_words[1], last, last, last-2, last, &valid_words, &mut Vec::new(), &trie, &index, [0usize; 27], 0, &mut 0, &mut letters_on_board, &mut 0, &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), None);
+        assert_eq!(played, Ok(None));
+        let played = try_play_word_vertically(&mut board, &valid_words[1], last, last, last-2, last, &valid_words, &mut Vec::new(), &trie, &index, [0usize; 27], 0, &mut 0, &mut letters_on_board, &mut 0, &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), None);
+        assert_eq!(played, Ok(None));
+    }
+
+    #[test]
+    fn required_word_on_solved_board() {
+        let solver = test_solver();
+        let hand = |s: &str| string_to_letters(s).unwrap();
+        let config = SolveConfig { threads: Some(1), required_word: Some(convert_word_to_array("BANANA")), ..SolveConfig::default() };
+        let solved = solver.solve(hand("BANANACAT"), config.clone()).unwrap();
+        let mut solution = solved.solutions().remove(0);
+        solution.mark_required_word(&convert_word_to_array("BANANA"));
+        assert!(solution.words.iter().any(|played| played.word == "BANANA" && played.required));
+        assert!(solution.words.iter().all(|played| played.required == (played.word == "BANANA")));
+        // Played first off of the previous board when it isn't already there
+        let previous = solver.solve(hand("CAT"), SolveConfig { threads: Some(1), ..SolveConfig::default() }).unwrap();
+        let peeled = solver.solve_incremental(&previous, hand("CATBANANA"), config.clone()).unwrap();
+        assert!(peeled.method == SolveMethod::Existing);
+        let game = &peeled.game;
+        assert!(is_word_on_board(&game.board, game.min_col, game.max_col, game.min_row, game.max_row, &convert_word_to_array("BANANA")));
+        // Words that can't be played at all are rejected before solving
+        let error = |word: &str, letters: &str| match solver.solve(hand(letters), SolveConfig { required_word: Some(convert_word_to_array(word)), ..config.clone() }).err() {
+            Some(SolverError::Internal(reason)) => reason,
+            other => panic!("Unexpected result: {:?}", other)
+        };
+        assert!(error("ABNANA", "BANANACAT").contains("isn't in the dictionary"));
+        assert!(error("BANANAS", "BANANACAT").contains("aren't enough letters"));
+        assert!(error("ABCDEFGHIJKLMNOPQR", "ABCDEFGHIJKLMNOPQR").contains("too long"));
+        // If the rest of the hand can only be played by extending the required word, then there's no board
+        let solver = Solver::new(vec!["AB".to_owned(), "ABC".to_owned()]);
+        assert!(solver.solve(hand("ABC"), SolveConfig { threads: Some(1), ..SolveConfig::default() }).is_ok());
+        assert_eq!(solver.solve(hand("ABC"), SolveConfig { threads: Some(1), required_word: Some(convert_word_to_array("AB")), ..SolveConfig::default() }).err(), Some(SolverError::RequiredWordUnplayable("AB".to_owned())));
+    }
+
+    #[test]
+    #[cfg(feature = "diagonal")]
+    fn diagonal_word_placed_and_validated() {
+        let words: Vec<Word> = ["CAT", "TOE", "DOG"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        let index = DictionaryIndex::from_words(&words);
+        let mut board = Board::new(BOARD_SIZE);
+        place(&mut board, "CAT", 70, 70, Direction::Horizontal);
+        let mut letters_on_board = [0usize; 27];
+        for letter in convert_word_to_array("CAT") {
+            letters_on_board[letter] += 1;
+        }
+        let mut hand = [0usize; 27];
+        for letter in convert_word_to_array("OE") {
+            hand[letter] += 1;
+        }
+        // TOE goes down and to the right from the T of CAT
+        let mut played_board = board.clone();
+        let (valid, played, remaining, _) = played_board.play_word(&words[1], 70, 72, Direction::Diagonal(1, 1), &hand, &mut letters_on_board.clone());
+        assert!(valid);
+        assert_eq!(played, vec![(71, 73), (72, 74)]);
+        assert!(remaining.iter().all(|count| *count == 0));
+        assert!(is_board_valid_diagonal(&played_board, &played, &trie, 2));
+        assert!(is_board_valid_full(&played_board, 70, 74, 70, 72, &trie, 2));
+        assert!(is_connected(&played_board, 70, 74, 70, 72, &Vec::new()));
+        // The same placement is found when searching
+        let valid_words: Vec<IndexedWord> = indexed_words(&words).collect();
+        let found = try_play_word_diagonal(&mut board.clone(), &valid_words[1], 70, 72, 70, 70, &valid_words, &mut Vec::new(), &trie, &index, hand, 0, &mut 0, &mut letters_on_board, &mut 0, &SolveConfig::default(), None, &Arc::new(AtomicBool::new(false)), None);
+        assert_eq!(found, Ok(Some((true, 70, 74, 70, 72))));
+        // A letter continuing the diagonal makes TOES, which isn't a word, whichever way the letter is played
+        played_board.set_val(73, 75, convert_word_to_array("S")[0]);
+        assert!(!is_board_valid_diagonal(&played_board, &played, &trie, 2));
+        assert!(!is_board_valid_diagonal(&played_board, &[(73, 75)], &trie, 2));
+        assert!(!is_board_valid_horizontal(&played_board, 70, 75, 70, 73, 73, 75, 75, &trie, 2));
+        assert!(!is_board_valid_full(&played_board, 70, 75, 70, 73, &trie, 2));
+        // Letters only touching at their corners neither make a diagonal word nor join the words together
+        place(&mut board, "DOG", 71, 73, Direction::Horizontal);
+        assert!(is_board_valid_full(&board, 70, 75, 70, 71, &trie, 2));
+        assert!(!is_board_valid_horizontal(&board, 70, 75, 70, 71, 71, 73, 75, &trie, 2));
+        assert!(!is_connected(&board, 70, 75, 70, 71, &Vec::new()));
+    }
+
+    #[test]
+    fn blanks_stand_in_for_missing_letters() {
+        let hand = |s: &str| string_to_letters(s).unwrap();
+        let word = |s: &str| convert_word_to_array(s);
+        assert!(is_makeable(&word("CAT"), &hand("CA?")));
+        assert!(is_makeable(&word("CAT"), &hand("C??")));
+        assert!(is_makeable(&word("CATS"), &hand("CAT?")));
+        assert!(!is_makeable(&word("CAT"), &hand("C?")));
+        assert!(!is_makeable(&word("CATS"), &hand("CA?")));
+        // The first word uses a blank only for the letter missing from the hand
+        let mut board = Board::new(BOARD_SIZE);
+        let (remaining, on_board, row, col) = play_first_word(&mut board, &word("CAT"), &hand("CA?"));
+        assert!(remaining.iter().all(|count| *count == 0));
+        assert_eq!(on_board, hand("CAT"));
+        assert!(!board.is_blank(row, col) && !board.is_blank(row, col+1) && board.is_blank(row, col+2));
+        // A later word can also be played with a blank
+        let (valid, played, remaining, _) = board.play_word(&word("AT"), row, col+1, Direction::Vertical, &hand("?"), &mut on_board.clone());
+        assert!(valid);
+        assert_eq!(played, vec![(row+1, col+1)]);
+        assert!(board.is_blank(row+1, col+1) && board.get_val(row+1, col+1).letter() == Some(word("T")[0]));
+        assert!(remaining.iter().all(|count| *count == 0));
+        // Blanks are shown as lowercase letters
+        assert_eq!(board_to_vec(&board, col, col+2, row, row+1), vec![vec!["C", "A", "t"], vec![" ", "t", " "]]);
+    }
+
+    #[test]
+    fn words_with_most_common_letters_ordered_first() {
+        let letters = string_to_letters("EEEETTTAASBUY?").unwrap();
+        let top_letters = top_letters_mask(&letters);
+        assert_eq!(top_letters, 1 << 4 | 1 << 19 | 1 << 0);
+        // Ties go to the earlier letter, and blanks are never counted
+        assert_eq!(top_letters_mask(&string_to_letters("DDCCBBAA??").unwrap()), 0b1111 & !(1 << 3));
+        let words: Vec<Word> = ["BUSY", "TEAT", "SEAT", "BEE", "EAT"].iter().map(|word| convert_word_to_array(word)).collect();
+        let valid_words: Vec<IndexedWord> = indexed_words(&words).collect();
+        let mut ordered = vec![valid_words[0]];
+        order_by_priority(&valid_words, &letters, &mut ordered);
+        let ordered: Vec<String> = ordered.iter().map(|word| convert_array_to_word(word)).collect();
+        assert_eq!(ordered, vec!["TEAT", "SEAT", "EAT", "BEE", "BUSY"]);
+    }
+
+    #[test]
+    fn shorter_first_word_of_common_letters_preferred() {
+        let words: Vec<Word> = ["QUIZ", "TEE", "ZEE"].iter().map(|w| convert_word_to_array(w)).collect();
+        let refs: Vec<IndexedWord> = indexed_words(&words).collect();
+        let letters = string_to_letters("EEEEEETTQUIZ").unwrap();
+        let ordered: Vec<String> = best_first_word(&refs, &letters).iter().map(|w| convert_array_to_word(w)).collect();
+        assert_eq!(ordered, vec!["TEE", "ZEE", "QUIZ"]);
+    }
+
+    #[test]
+    fn short_deadline_stops_hard_solve_early() {
+        let (words, trie, index) = test_dictionary();
+        // With no limit on the words checked, this hand keeps searching for well over a second
+        let letters = string_to_letters("AEEIIOOUUQQXXZZJJKKVV").unwrap();
+        let stop = Arc::new(AtomicBool::new(false));
+        let start = Instant::now();
+        let result = solve_hand(letters, &None, &words, &trie, &index, &HashSet::new(), &SolveConfig { max_words_to_check: usize::MAX, ..SolveConfig::default() }, get_deadline(start, 1), &stop, None);
+        assert_eq!(result.err(), Some(SolverError::Timeout));
+        assert!(start.elapsed() < Duration::from_secs(1));
+        // Running out of time signals every thread to stop
+        assert!(stop.load(Ordering::Relaxed));
+    }
+}
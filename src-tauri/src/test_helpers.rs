@@ -0,0 +1,39 @@
+//! Fixtures shared by the tests of the library and of the app, which each declare this module
+
+use std::cmp::Reverse;
+use bananagrams_solver::{convert_word_to_array, Word};
+use bananagrams_solver::board::{Board, Direction};
+use bananagrams_solver::solver::Solver;
+use bananagrams_solver::trie::{DictionaryIndex, Trie};
+
+/// The words of the short dictionary, longest first
+pub(crate) fn test_words() -> Vec<Word> {
+    let mut words: Vec<Word> = include_str!("updated_short_dictionary.txt").lines().map(convert_word_to_array).collect();
+    words.sort_by_key(|word| Reverse(word.len()));
+    words
+}
+
+/// The short dictionary's words (longest first), with their `Trie` and `DictionaryIndex`
+pub(crate) fn test_dictionary() -> (Vec<Word>, Trie, DictionaryIndex) {
+    let words = test_words();
+    let trie = Trie::from_words(&words);
+    let index = DictionaryIndex::from_words(&words);
+    (words, trie, index)
+}
+
+/// A `Solver` using the short dictionary
+pub(crate) fn test_solver() -> Solver {
+    Solver::new(include_str!("updated_short_dictionary.txt").lines().map(String::from).collect())
+}
+
+/// Plays `word` onto `board` without any validity checks
+pub(crate) fn place(board: &mut Board, word: &str, row: usize, col: usize, direction: Direction) {
+    for (i, letter) in convert_word_to_array(word).into_iter().enumerate() {
+        match direction {
+            Direction::Horizontal => board.set_val(row, col+i, letter),
+            Direction::Vertical => board.set_val(row+i, col, letter),
+            #[cfg(feature = "diagonal")]
+            Direction::Diagonal(dx, dy) => board.set_val(row.wrapping_add_signed(dy as isize * i as isize), col.wrapping_add_signed(dx as isize * i as isize), letter)
+        }
+    }
+}
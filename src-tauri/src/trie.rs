@@ -0,0 +1,364 @@
+//! The dictionary, as a `Trie` for checking whether words are valid and a `DictionaryIndex` for finding the words that a hand can make
+
+use std::ops::Deref;
+use crate::{letter_mask, Letters, Word, BLANK};
+use crate::solver::check_filter_after_play_later;
+
+/// A node in a `Trie`
+pub(crate) struct TrieNode {
+    /// The letters along the edge leading into this node (more than one if the path was compressed)
+    pub(crate) label: Word,
+    /// Indices into `Trie.nodes` of this node's children
+    children: Vec<usize>,
+    /// Whether the path ending at this node is a complete word
+    pub(crate) is_word: bool
+}
+
+/// A compressed trie (radix tree) of words, used for checking whether a word is valid letter-by-letter
+pub struct Trie {
+    /// The nodes of the trie, with the root at index 0
+    pub(crate) nodes: Vec<TrieNode>
+}
+
+impl Trie {
+    /// Creates a new, empty `Trie`
+    fn new() -> Trie {
+        Trie { nodes: vec![TrieNode { label: Vec::new(), children: Vec::new(), is_word: false }] }
+    }
+
+    /// Creates a `Trie` containing all of the given `words`
+    /// # Arguments
+    /// * `words` - Words to insert into the trie
+    /// # Returns
+    /// `Trie` - The trie containing all `words`
+    pub fn from_words<'a>(words: impl IntoIterator<Item = &'a Word>) -> Trie {
+        let mut trie = Trie::new();
+        for word in words {
+            trie.insert(word);
+        }
+        trie
+    }
+
+    /// Finds the child of `node` whose label begins with `letter`
+    /// # Arguments
+    /// * `node` - Index of the node whose children to search
+    /// * `letter` - The first letter of the child's label
+    /// # Returns
+    /// `Option` - The index of the child, or `None` if there isn't one
+    fn find_child(&self, node: usize, letter: usize) -> Option<usize> {
+        self.nodes[node].children.iter().find(|c| self.nodes[**c].label[0] == letter).copied()
+    }
+
+    /// Inserts a `word` into the trie, splitting compressed edges as needed
+    /// # Arguments
+    /// * `word` - The word to insert
+    pub fn insert(&mut self, word: &[usize]) {
+        let mut node = 0;
+        let mut rest = word;
+        while !rest.is_empty() {
+            match self.find_child(node, rest[0]) {
+                Some(child) => {
+                    let common = self.nodes[child].label.iter().zip(rest.iter()).take_while(|(a, b)| a == b).count();
+                    if common < self.nodes[child].label.len() {
+                        // Split the child's edge so that it ends where `rest` diverges
+                        let split_label = self.nodes[child].label.split_off(common);
+                        let split_children = std::mem::take(&mut self.nodes[child].children);
+                        let split_is_word = self.nodes[child].is_word;
+                        self.nodes.push(TrieNode { label: split_label, children: split_children, is_word: split_is_word });
+                        let split_idx = self.nodes.len() - 1;
+                        self.nodes[child].children.push(split_idx);
+                        self.nodes[child].is_word = false;
+                    }
+                    node = child;
+                    rest = &rest[common..];
+                },
+                None => {
+                    self.nodes.push(TrieNode { label: rest.to_vec(), children: Vec::new(), is_word: true });
+                    let new_idx = self.nodes.len() - 1;
+                    self.nodes[node].children.push(new_idx);
+                    return;
+                }
+            }
+        }
+        self.nodes[node].is_word = true;
+    }
+
+    /// Follows the edge for `letter` from a position in the trie
+    /// # Arguments
+    /// * `node` - Index of the current node
+    /// * `offset` - How many letters of the current node's label have been matched so far
+    /// * `letter` - The next letter
+    /// # Returns
+    /// `Option` - The `(node, offset)` position after `letter`, or `None` if no word continues with `letter`
+    pub(crate) fn step(&self, node: usize, offset: usize, letter: usize) -> Option<(usize, usize)> {
+        if offset < self.nodes[node].label.len() {
+            if self.nodes[node].label[offset] == letter {
+                Some((node, offset+1))
+            }
+            else {
+                None
+            }
+        }
+        else {
+            self.find_child(node, letter).map(|child| (child, 1))
+        }
+    }
+
+    /// Checks whether any word continues from a position in the trie with a letter satisfying `available`
+    /// # Arguments
+    /// * `node` - Index of the current node
+    /// * `offset` - How many letters of the current node's label have been matched so far
+    /// * `available` - Whether a given letter can be played next
+    /// # Returns
+    /// `bool` - Whether any next letter is available
+    pub(crate) fn can_continue(&self, node: usize, offset: usize, available: impl Fn(usize) -> bool) -> bool {
+        if offset < self.nodes[node].label.len() {
+            available(self.nodes[node].label[offset])
+        }
+        else {
+            self.nodes[node].children.iter().any(|c| available(self.nodes[*c].label[0]))
+        }
+    }
+
+    /// Checks whether a `word` is in the trie, stopping as soon as no word can begin with the letters seen so far
+    /// # Arguments
+    /// * `word` - The word to check
+    /// # Returns
+    /// `bool` - Whether `word` is in the trie
+    pub fn contains(&self, word: &[usize]) -> bool {
+        let mut node = 0;
+        let mut rest = word;
+        while !rest.is_empty() {
+            match self.find_child(node, rest[0]) {
+                Some(child) => {
+                    if !rest.starts_with(&self.nodes[child].label) {
+                        return false;
+                    }
+                    rest = &rest[self.nodes[child].label.len()..];
+                    node = child;
+                },
+                None => {
+                    return false;
+                }
+            }
+        }
+        self.nodes[node].is_word
+    }
+}
+
+/// A word of a dictionary, along with its position in it so that the word's entry in a `DictionaryIndex` of the dictionary can be found directly
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct IndexedWord<'w> {
+    /// Position of the word in the dictionary
+    pub position: usize,
+    /// The word itself
+    pub word: &'w Word
+}
+
+impl Deref for IndexedWord<'_> {
+    type Target = Word;
+
+    fn deref(&self) -> &Word {
+        self.word
+    }
+}
+
+/// Pairs each word of a dictionary with its position in it
+/// # Arguments
+/// * `words` - The dictionary
+/// # Returns
+/// `impl Iterator<Item = IndexedWord>` - Each word of `words`, in order
+pub fn indexed_words(words: &[Word]) -> impl Iterator<Item = IndexedWord<'_>> {
+    words.iter().enumerate().map(|(position, word)| IndexedWord { position, word })
+}
+
+/// The number of each letter in every word of a dictionary, used for quickly checking whether words can still be played
+/// 
+/// Words are looked up by their position in the dictionary (see `IndexedWord`), so the index must only be used with words of the dictionary it was built from
+pub struct DictionaryIndex {
+    /// The number of each letter (`A` to `Z`) in each word of the dictionary
+    letter_counts: Vec<[u8; 26]>,
+    /// The letters of each word of the dictionary, for ordering the words to try after each play
+    candidate_order: CandidateOrder
+}
+
+impl DictionaryIndex {
+    /// Creates a `DictionaryIndex` of the given `words`
+    /// # Arguments
+    /// * `words` - The dictionary to index
+    /// # Returns
+    /// `DictionaryIndex` - The index of `words`
+    pub fn from_words(words: &[Word]) -> DictionaryIndex {
+        let letter_counts = words.iter().map(|word| {
+            let mut counts = [0u8; 26];
+            for letter in word.iter() {
+                counts[*letter] = counts[*letter].saturating_add(1);
+            }
+            counts
+        }).collect();
+        DictionaryIndex { letter_counts, candidate_order: CandidateOrder::new(words) }
+    }
+
+    /// Gets the bitmask of the letters in a `word` (see `letter_mask`)
+    /// # Arguments
+    /// * `word` - Word of the indexed dictionary
+    /// # Returns
+    /// `usize` - Bitmask with bit `i` set if letter `i` is in `word`
+    pub(crate) fn mask_of(&self, word: &IndexedWord) -> usize {
+        self.candidate_order.mask(word.position).unwrap_or_else(|| letter_mask(word))
+    }
+
+    /// Gets whether a `word` could cross the word played before it, and so should be tried before the words that can't (see `CandidateOrder`)
+    /// # Arguments
+    /// * `last_played` - Position of the last word played within the indexed dictionary
+    /// * `word` - Word of the indexed dictionary that could be played next
+    /// # Returns
+    /// `bool` - Whether `word` shares at least one letter with the last word played
+    pub(crate) fn follows(&self, last_played: usize, word: &IndexedWord) -> bool {
+        self.candidate_order.share_letters(last_played, word.position)
+    }
+
+    /// Removes words that can't be played with `current_letters` plus a set number of `board_letters`, giving the same result as `check_filter_after_play_later`
+    /// # Arguments
+    /// * `words` - Words to filter
+    /// * `current_letters` - Letters currently available in the hand
+    /// * `board_letters` - Letters played on the board
+    /// * `filter_letters_on_board` - Maximum number of letters from `board_letters` that can be used when checking if a word can be played
+    /// # Returns
+    /// * `Vec<IndexedWord>` - The words from `words` that pass the filter
+    #[cfg(test)]
+    pub(crate) fn filter_words<'a>(&self, words: &[IndexedWord<'a>], current_letters: &Letters, board_letters: &Letters, filter_letters_on_board: usize) -> Vec<IndexedWord<'a>> {
+        let mut filtered = Vec::new();
+        self.filter_words_into(words, current_letters, board_letters, filter_letters_on_board, &mut filtered);
+        filtered
+    }
+
+    /// Removes words that can't be played with `current_letters` plus a set number of `board_letters` (see `filter_words`), writing those that pass into an existing list so that its allocation can be reused
+    /// # Arguments
+    /// * `words` - Words of the indexed dictionary to filter
+    /// * `current_letters` - Letters currently available in the hand
+    /// * `board_letters` - Letters played on the board
+    /// * `filter_letters_on_board` - Maximum number of letters from `board_letters` that can be used when checking if a word can be played
+    /// * `filtered` - List that is cleared and then given the words from `words` that pass the filter
+    pub(crate) fn filter_words_into<'a>(&self, words: &[IndexedWord<'a>], current_letters: &Letters, board_letters: &Letters, filter_letters_on_board: usize, filtered: &mut Vec<IndexedWord<'a>>) {
+        filtered.clear();
+        // Which letters blanks stand in for depends on the order of the letters in the word, so those hands are checked letter-by-letter
+        if current_letters[BLANK] > 0 {
+            filtered.extend(words.iter().filter(|w| check_filter_after_play_later(*current_letters, *board_letters, w, filter_letters_on_board, None)).copied());
+            return;
+        }
+        let mut hand = [0u8; 26];
+        let mut board = [0u8; 26];
+        for i in 0..26 {
+            hand[i] = current_letters[i].min(u8::MAX as usize) as u8;
+            board[i] = board_letters[i].min(u8::MAX as usize) as u8;
+        }
+        for word in words.iter() {
+            let passes = match self.letter_counts.get(word.position) {
+                // The byte arithmetic below can't overflow for words of up to 255 letters
+                Some(counts) if word.len() <= u8::MAX as usize => {
+                    // Kept to byte arithmetic without branches so that the loop can be vectorized
+                    let mut too_few_on_board = false;
+                    let mut num_from_board = 0u8;
+                    for i in 0..26 {
+                        let from_board = counts[i].saturating_sub(hand[i]);
+                        too_few_on_board |= from_board > board[i];
+                        num_from_board = num_from_board.wrapping_add(from_board);
+                    }
+                    !too_few_on_board && (num_from_board as usize) <= filter_letters_on_board
+                },
+                _ => check_filter_after_play_later(*current_letters, *board_letters, word, filter_letters_on_board, None)
+            };
+            if passes {
+                filtered.push(*word);
+            }
+        }
+    }
+}
+
+/// Orders the words to try after each play, putting those that share a letter with the word just played (so could be played across it) first
+/// 
+/// The other words are still tried afterwards rather than left out, since they can cross earlier words or be played alongside the tiles. Nearly every pair of words shares a letter anyway, so listing
+/// the words each word shares letters with would take most of the dictionary per word; instead each word's letters are kept as a bitmask and compared when needed
+pub(crate) struct CandidateOrder {
+    /// Bitmask of the letters in each word of the dictionary (see `letter_mask`)
+    letter_masks: Vec<usize>
+}
+
+impl CandidateOrder {
+    /// Creates a `CandidateOrder` of the given `words`
+    /// # Arguments
+    /// * `words` - The dictionary to order
+    /// # Returns
+    /// `CandidateOrder` - The order of `words`, referring to each word by its position in `words`
+    pub(crate) fn new(words: &[Word]) -> CandidateOrder {
+        CandidateOrder { letter_masks: words.iter().map(letter_mask).collect() }
+    }
+
+    /// Gets the bitmask of the letters in a word (see `letter_mask`)
+    /// # Arguments
+    /// * `word` - Position of the word in the dictionary
+    /// # Returns
+    /// `Option` - Bitmask with bit `i` set if letter `i` is in the word, or `None` if `word` is past the end of the dictionary
+    fn mask(&self, word: usize) -> Option<usize> {
+        self.letter_masks.get(word).copied()
+    }
+
+    /// Gets whether two words share at least one letter
+    /// # Arguments
+    /// * `a` - Position of one word in the dictionary
+    /// * `b` - Position of the other word in the dictionary
+    /// # Returns
+    /// `bool` - Whether the words have a letter in common (`false` if either is past the end of the dictionary)
+    fn share_letters(&self, a: usize, b: usize) -> bool {
+        match (self.mask(a), self.mask(b)) {
+            (Some(mask_a), Some(mask_b)) => mask_a & mask_b != 0,
+            _ => false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert_word_to_array;
+
+    #[test]
+    fn trie_finds_words_and_not_prefixes() {
+        // "CAT" is a prefix of "CATS" and "CATTLE", and "CA" is a prefix of every word without being one itself
+        let words: Vec<Word> = ["CATS", "CAT", "CATTLE", "CAR", "DOG"].iter().map(|w| convert_word_to_array(w)).collect();
+        let trie = Trie::from_words(&words);
+        for word in words.iter() {
+            assert!(trie.contains(word));
+        }
+        for not_word in ["", "C", "CA", "CATT", "CATTL", "CATSS", "CARS", "DO", "DOGS", "ACT", "X"] {
+            assert!(!trie.contains(&convert_word_to_array(not_word)), "{not_word} shouldn't be in the trie");
+        }
+        assert!(!Trie::from_words(&Vec::new()).contains(&convert_word_to_array("CAT")));
+        // Stepping letter-by-letter follows prefixes through split edges, and stops where no word continues
+        let [c, a, t, s, l, o] = ["C", "A", "T", "S", "L", "O"].map(|letter| convert_word_to_array(letter)[0]);
+        let (node, offset) = [c, a, t].into_iter().try_fold((0, 0), |(node, offset), letter| trie.step(node, offset, letter)).unwrap();
+        assert!(trie.can_continue(node, offset, |letter| letter == s));
+        assert!(trie.can_continue(node, offset, |letter| letter == t));
+        assert!(!trie.can_continue(node, offset, |letter| letter == l));
+        assert!(trie.step(node, offset, o).is_none());
+        assert!(trie.step(0, 0, a).is_none());
+    }
+
+    #[test]
+    fn words_sharing_letters_tried_first() {
+        let words: Vec<Word> = ["CAT", "DOG", "TOE"].iter().map(|w| convert_word_to_array(w)).collect();
+        let order = CandidateOrder::new(&words);
+        assert!(order.share_letters(0, 2));
+        assert!(order.share_letters(1, 2));
+        assert!(!order.share_letters(0, 1));
+        assert!(!order.share_letters(0, 3));
+        // The index looks words up by their position in the dictionary
+        let index = DictionaryIndex::from_words(&words);
+        let indexed: Vec<IndexedWord> = indexed_words(&words).collect();
+        assert_eq!(indexed[2], IndexedWord { position: 2, word: &words[2] });
+        assert!(index.follows(1, &indexed[2]));
+        assert!(!index.follows(0, &indexed[1]));
+        assert_eq!(index.mask_of(&indexed[1]), letter_mask(&words[1]));
+    }
+}
@@ -5,12 +5,13 @@ import 'primeicons/primeicons.css';
 import { Splitter, SplitterPanel } from "primereact/splitter";
 import { Toast } from "primereact/toast";
 import { invoke } from "@tauri-apps/api/tauri";
+import { listen } from "@tauri-apps/api/event";
 import { sendNotification } from "@tauri-apps/api/notification";
 import "./App.css";
 import LetterInput from "./letter_input";
 import ResultsDisplay from "./results_display";
 import PlayableWords from "./playable_words";
-import { result_t } from "./types";
+import { result_t, solver_error_t } from "./types";
 import Settings from "./settings";
 
 export default function App() {
@@ -30,26 +31,66 @@ export default function App() {
         document.addEventListener("contextmenu", e => e.preventDefault());
     }, []);
 
+    // Say if the game from when the app was last open couldn't be restored
+    useEffect(() => {
+        invoke<string|null>("take_autosave_error").then(error => {
+            if (error) {
+                toast.current?.show({severity: "warn", summary: "Couldn't restore the last game", detail: error});
+            }
+        });
+    }, []);
+
     /**
      * Runs the solver
      * @param letters Mapping of length-one letter strings to the number of that letter present in the hand
+     * @param fresh Whether to build a new board rather than playing off of the current one
      */
-    const startRunning = (letters: Map<string, number>) => {
+    const startRunning = (letters: Map<string, number>, fresh: boolean) => {
         setRunning(true);
-        invoke("play_bananagrams", { availableLetters: letters })
+        // Show the board as it's being built until the solve finishes (events can still arrive just after it does)
+        const previousResults = results;
+        let finished = false;
+        const unlisten = listen<{ board: string[][], letters_remaining: number, words_checked: number }>("partial-solution", event => {
+            if (!finished) {
+                setResults({ elapsed: 0, board: event.payload.board });
+            }
+        });
+        invoke(fresh ? "play_bananagrams_fresh" : "play_bananagrams_stream", { availableLetters: letters })
             .then(res => {
+                finished = true;
                 const results = res as result_t;
                 setResults(results);
                 setUndoPossible(true);
                 setRedoPossible(false);
+                if (results.used_long_dictionary) {
+                    toast.current?.show({severity: "info", summary: "Used the full dictionary", detail: "The short dictionary couldn't solve this hand" + (results.long_only_words?.length ? `; these words are only in the full dictionary: ${results.long_only_words.join(", ")}` : "")});
+                }
                 if (results.elapsed > 5000) {
                     sendNotification({ title: "Completed", body: "The board has been solved!" });
                 }
             })
-            .catch(error => {
-                toast.current?.show({severity: "error", summary: "Uh oh!", detail: "" + error});
+            .catch((error: solver_error_t) => {
+                finished = true;
+                setResults(previousResults);
+                switch (error.kind) {
+                    case "Cancelled":
+                        toast.current?.show({severity: "info", summary: "Cancelled", detail: error.message});
+                        break;
+                    case "NoSolution":
+                    case "LettersLeftOver":
+                    case "NoPlayableWords":
+                    case "StrandedLetters":
+                    case "Timeout":
+                        toast.current?.show({severity: "warn", summary: "No board found", detail: error.message});
+                        break;
+                    default:
+                        toast.current?.show({severity: "error", summary: "Uh oh!", detail: error.message});
+                }
             })
-            .finally(() => setRunning(false));
+            .finally(() => {
+                unlisten.then(f => f());
+                setRunning(false);
+            });
     }
 
     /**
@@ -11,7 +11,7 @@ import { readText, writeText } from "@tauri-apps/api/clipboard";
 import { invoke } from "@tauri-apps/api/tauri";
 import { Dropdown } from "primereact/dropdown";
 import { TabPanel, TabView } from "primereact/tabview";
-import { result_t } from "./types";
+import { result_t, solver_error_t } from "./types";
 
 interface LetterInputProps {
     /**
@@ -21,8 +21,9 @@ interface LetterInputProps {
     /**
      * Function to start solving the game
      * @param letters Map of every letter to the number present in the hand
+     * @param fresh Whether to build a new board rather than playing off of the current one
      */
-    startRunning: (letters: Map<string, number>) => void,
+    startRunning: (letters: Map<string, number>, fresh: boolean) => void,
     /**
      * Whether the game is being solved or not
      */
@@ -72,6 +73,14 @@ interface LetterInputProps {
  * Array of all uppercase Latin letters in alphabetical order
  */
 const UPPERCASE = [..."ABCDEFGHIJKLMNOPQRSTUVWXYZ"];
+/**
+ * The character used for a blank tile, which can stand in for any letter
+ */
+const BLANK = "?";
+/**
+ * Array of all uppercase Latin letters in alphabetical order, followed by the blank
+ */
+const TILES = [...UPPERCASE, BLANK];
 /**
  * Array of all digits
  */
@@ -89,10 +98,10 @@ export default function LetterInput(props: LetterInputProps){
     const m = new Map();
     const num_letters = new Map<string, number>();
     const invalid = new Map<string, boolean>();
-    const how_many = [13, 3, 3, 6, 18, 3, 4, 3, 12, 2, 2, 5, 3, 8, 11, 3, 2, 9, 6, 9, 6, 3, 3, 2, 3, 2];
+    const how_many = [13, 3, 3, 6, 18, 3, 4, 3, 12, 2, 2, 5, 3, 8, 11, 3, 2, 9, 6, 9, 6, 3, 3, 2, 3, 2, 2];
     const individual_cm_refs: RefObject<ContextMenu>[] = [];
     const individual_cms: ReactElement<ContextMenu>[] = [];
-    UPPERCASE.forEach((c, i) => {
+    TILES.forEach((c, i) => {
         m.set(c, 0);
         num_letters.set(c, how_many[i]);
         invalid.set(c, false);
@@ -117,8 +126,9 @@ export default function LetterInput(props: LetterInputProps){
     const [typeInVisible, setTypeInVisible] = useState(false);
     const [typedIn, setTypedIn] = useState("");
     const [randomNum, setRandomNum] = useState<number|null>(21);
-    const [randomFrom, setRandomFrom] = useState<"standard Bananagrams"|"double Bananagrams"|"infinite set">("standard Bananagrams");
+    const [randomFrom, setRandomFrom] = useState<"standard Bananagrams"|"double Bananagrams"|"triple Bananagrams"|"infinite set">("standard Bananagrams");
     const [playableWordsLoading, setPlayableWordsLoading] = useState(false);
+    const [bunchStarted, setBunchStarted] = useState(false);
 
     // Show the custom context menu on right click
     useEffect(() => {
@@ -127,6 +137,23 @@ export default function LetterInput(props: LetterInputProps){
         }
     }, [props.contextMenu]);
 
+    // Restore the game from when the app was last open (if any)
+    useEffect(() => {
+        invoke("get_last_game").then((res: any) => {
+            if (res.solution.length > 0) {
+                const new_map = new Map<string, number>();
+                TILES.forEach(c => {
+                    new_map.set(c, res.letters[c] ?? 0);
+                });
+                setLetterNums(new_map);
+                props.setUndoPossible(res.undo_possible);
+                props.setRedoPossible(res.redo_possible);
+                props.setResults({ elapsed: 0, board: res.solution });
+            }
+        })
+        .catch(err => props.toast.current?.show({severity: "error", summary: "Could not restore the last game", detail: err.toString()}));
+    }, []);
+
     // Give focus to the text input (since there's an animation delay on it appearing, so not using a timeout doesn't always work)
     useEffect(() => {
         if (typeInVisible) {
@@ -141,7 +168,7 @@ export default function LetterInput(props: LetterInputProps){
     const copyLetters = (what: "text"|"table"|"json") => {
         if (what === "text") {
             let s = "";
-            UPPERCASE.forEach(letter => {
+            TILES.forEach(letter => {
                 const num = letterNums.get(letter);
                 if (num != null) {
                     for (let i=0; i<num; i++) {
@@ -184,9 +211,9 @@ export default function LetterInput(props: LetterInputProps){
             if (val != null) {
                 // First check if it's in the "Copy as text" format
                 const as_array = [...val].map(c => c.toUpperCase());
-                if (as_array.every(c => UPPERCASE.includes(c))) {
+                if (as_array.every(c => TILES.includes(c))) {
                     const new_map = new Map<string, number>();
-                    UPPERCASE.forEach(c => {
+                    TILES.forEach(c => {
                         new_map.set(c, 0);
                     });
                     as_array.forEach(c => new_map.set(c, new_map.get(c)!+1));
@@ -264,7 +291,7 @@ export default function LetterInput(props: LetterInputProps){
     const resetLetters = () => {
         if (Array.from(letterNums.values()).some(val => val! > 0)) {
             const empty_letters = new Map();
-            UPPERCASE.forEach(letter => {
+            TILES.forEach(letter => {
                 empty_letters.set(letter, 0);
             });
             confirmDialog({
@@ -298,7 +325,7 @@ export default function LetterInput(props: LetterInputProps){
                 let s = "";
                 let all_valid = true;
                 for (const char of val) {
-                    if (!UPPERCASE.includes(char.toUpperCase())) {
+                    if (!TILES.includes(char.toUpperCase())) {
                         all_valid = false;
                         break;
                     }
@@ -394,7 +421,7 @@ export default function LetterInput(props: LetterInputProps){
      */
     const useLetters = () => {
         const new_map = new Map<string, number>();
-        UPPERCASE.forEach(c => {
+        TILES.forEach(c => {
             new_map.set(c, count_letter_in_string(c, typedIn));
         });
         setLetterNums(new_map);
@@ -406,7 +433,7 @@ export default function LetterInput(props: LetterInputProps){
      */
     const addLetters = () => {
         const new_letters = new Map(letterNums);
-        UPPERCASE.forEach(c => {
+        TILES.forEach(c => {
             new_letters.set(c, (new_letters.get(c) ?? 0) + count_letter_in_string(c, typedIn));
         });
         setLetterNums(new_letters);
@@ -420,26 +447,51 @@ export default function LetterInput(props: LetterInputProps){
         if (randomNum == null || isNaN(randomNum) || randomNum <= 0) {
             props.toast.current?.show({severity: "warn", summary: "Invalid number", detail: "The number of letters to choose must be greater than 0"});
         }
-        else if (randomNum > 144 && randomFrom === "standard Bananagrams") {
-            props.toast.current?.show({severity: "warn", summary: "Too many letters", detail: "No more than 144 tiles can be chosen from standard Bananagrams"});
+        else if (randomNum > 146 && randomFrom === "standard Bananagrams") {
+            props.toast.current?.show({severity: "warn", summary: "Too many letters", detail: "No more than 146 tiles (144 letters and 2 blanks) can be chosen from standard Bananagrams"});
         }
-        else if (randomNum > 288 && randomFrom === "double Bananagrams") {
-            props.toast.current?.show({severity: "warn", summary: "Too many letters", detail: "No more than 288 tiles can be chosen from double Bananagrams"});
+        else if (randomNum > 292 && randomFrom === "double Bananagrams") {
+            props.toast.current?.show({severity: "warn", summary: "Too many letters", detail: "No more than 292 tiles (288 letters and 4 blanks) can be chosen from double Bananagrams"});
+        }
+        else if (randomNum > 438 && randomFrom === "triple Bananagrams") {
+            props.toast.current?.show({severity: "warn", summary: "Too many letters", detail: "No more than 438 tiles (432 letters and 6 blanks) can be chosen from triple Bananagrams"});
         }
         else {
-            invoke("get_random_letters", {what: randomFrom, howMany: randomNum}).then(res => {
+            // Bananagrams hands are drawn from a fresh bunch, which is then kept for peeling
+            const from_bunch = randomFrom !== "infinite set";
+            const started = from_bunch ? invoke("start_bunch", {what: randomFrom}) : Promise.resolve();
+            started.then(() => invoke("get_random_letters", {what: randomFrom, howMany: randomNum, fromBunch: from_bunch})).then(res => {
                 const result = res as Record<string, number>;
                 const new_map = new Map<string, number>();
-                UPPERCASE.forEach(c => {
+                TILES.forEach(c => {
                     new_map.set(c, result[c] ?? 0);
                 });
                 setLetterNums(new_map);
+                setBunchStarted(from_bunch);
                 cancelInput();
             })
             .catch(error => props.toast.current?.show({severity: "error", summary: "Error generating letters", detail: "An error occurred generating random letters: " + error}));
         }
     }
 
+    /**
+     * Draws one more tile from the bunch and adds it to the hand
+     */
+    const peel = () => {
+        invoke("peel_from_bunch", {count: 1}).then(res => {
+            const result = res as Record<string, number>;
+            const new_letters = new Map(letterNums);
+            TILES.forEach(c => {
+                new_letters.set(c, (new_letters.get(c) ?? 0) + (result[c] ?? 0));
+            });
+            setLetterNums(new_letters);
+        })
+        .catch(error => {
+            setBunchStarted(false);
+            props.toast.current?.show({severity: "warn", summary: "Could not peel", detail: "" + error});
+        });
+    }
+
     /**
      * Attempts to display words makeable with the hand of letters
      */
@@ -454,7 +506,7 @@ export default function LetterInput(props: LetterInputProps){
         else {
             setPlayableWordsLoading(true);
             const letters = new Map<string, number>();
-            UPPERCASE.forEach(c => {
+            TILES.forEach(c => {
                 letters.set(c, letterNums.get(c) ?? 0);
             });
             invoke("get_playable_words", { availableLetters: letters }).then(res => {
@@ -462,7 +514,7 @@ export default function LetterInput(props: LetterInputProps){
                 props.setPlayableWords(result);
                 props.setPlayableWordsVisible(true);
             })
-            .catch(error => props.toast.current?.show({"severity": "warn", "summary": "An error occurred", "detail": "An error occurred getting the playable words: " + error}))
+            .catch((error: solver_error_t) => props.toast.current?.show({"severity": "warn", "summary": "An error occurred", "detail": "An error occurred getting the playable words: " + error.message}))
             .finally(() => setPlayableWordsLoading(false));
         }
     }
@@ -487,8 +539,9 @@ export default function LetterInput(props: LetterInputProps){
 
     /**
      * Callback to start solving the puzzle
+     * @param fresh Whether to build a new board rather than playing off of the current one
      */
-    const solve = () => {
+    const solve = (fresh: boolean) => {
         let s = 0;
         for (const value of letterNums.values()) {
             s += value ?? 0;
@@ -498,20 +551,28 @@ export default function LetterInput(props: LetterInputProps){
         }
         else {
             const letters = new Map<string, number>();
-            UPPERCASE.forEach(c => {
+            TILES.forEach(c => {
                 letters.set(c, letterNums.get(c) ?? 0);
             });
-            props.startRunning(letters);
+            props.startRunning(letters, fresh);
         }
     }
 
+    /**
+     * Cancels the solve currently in progress
+     */
+    const cancelSolve = () => {
+        invoke("cancel_solve")
+        .catch(err => props.toast.current?.show({severity: "error", summary: "Could not cancel", detail: err.toString()}));
+    }
+
     /**
      * Performs an undo
      */
     const undo = () => {
         invoke("undo").then((res: any) => {
             const new_map = new Map<string, number>();
-            UPPERCASE.forEach(c => {
+            TILES.forEach(c => {
                 new_map.set(c, res.letters[c] ?? 0);
             });
             setLetterNums(new_map);
@@ -528,7 +589,7 @@ export default function LetterInput(props: LetterInputProps){
     const redo = () => {
         invoke("redo").then((res: any) => {
             const new_map = new Map<string, number>();
-            UPPERCASE.forEach(c => {
+            TILES.forEach(c => {
                 new_map.set(c, res.letters[c] ?? 0);
             });
             setLetterNums(new_map);
@@ -552,7 +613,7 @@ export default function LetterInput(props: LetterInputProps){
                 <TabPanel header="Type in letters">
                 {no_letters ?
                     <form onSubmit={e => {e.preventDefault(); useLetters()}} autoComplete="off">
-                        <InputText value={typedIn} onChange={e => setTypedIn(e.target.value.toUpperCase())} keyfilter="alpha" id="typeIn" onContextMenu={e => type_in_cm.current?.show(e)} style={{width: "100%"}}/>
+                        <InputText value={typedIn} onChange={e => setTypedIn(e.target.value.toUpperCase())} keyfilter={/[a-z?]/i} id="typeIn" onContextMenu={e => type_in_cm.current?.show(e)} style={{width: "100%"}}/>
                         <div style={{marginTop: "5px", textAlign: "center"}}>
                             <Button type="submit" label="Use letters" icon="pi pi-arrow-right" iconPos="right" style={{marginTop: "5px", marginRight: "5px"}}/>
                             <Button type="reset" label="Cancel" icon="pi pi-times" iconPos="right" severity="secondary" onClick={cancelInput}/>
@@ -560,7 +621,7 @@ export default function LetterInput(props: LetterInputProps){
                     </form>
                     :
                     <div>
-                        <InputText value={typedIn} onChange={e => setTypedIn(e.target.value.toUpperCase())} keyfilter="alpha" id="typeIn" onContextMenu={e => type_in_cm.current?.show(e)} style={{width: "100%"}}/>
+                        <InputText value={typedIn} onChange={e => setTypedIn(e.target.value.toUpperCase())} keyfilter={/[a-z?]/i} id="typeIn" onContextMenu={e => type_in_cm.current?.show(e)} style={{width: "100%"}}/>
                         <div style={{marginTop: "5px", textAlign: "center"}}>
                             <Button label="Replace letters" icon="pi pi-arrow-right" iconPos="right" onClick={useLetters}/>
                             <Button label="Add letters" icon="pi pi-plus" iconPos="right" style={{marginLeft: "5px", marginRight: "5px"}} onClick={addLetters}/>
@@ -573,10 +634,11 @@ export default function LetterInput(props: LetterInputProps){
                         <span>Choose </span>
                         <InputNumber value={randomNum} onChange={e => setRandomNum(e.value)} min={0} size={3} onContextMenu={e => random_num_cm.current?.show(e)}/>
                         <span> random letters from </span>
-                        <Dropdown value={randomFrom} onChange={e => setRandomFrom(e.value)} options={["standard Bananagrams", "double Bananagrams", "infinite set"]}/>
+                        <Dropdown value={randomFrom} onChange={e => setRandomFrom(e.value)} options={["standard Bananagrams", "double Bananagrams", "triple Bananagrams", "infinite set"]}/>
                         <br/>
-                        {randomFrom === "standard Bananagrams" ? <div style={{display: "flex", justifyContent: "center", marginTop: "5px", marginBottom: "5px"}}><small>144 max</small><br/></div>
-                        : randomFrom === "double Bananagrams" ? <div style={{display: "flex", justifyContent: "center", marginTop: "5px", marginBottom: "5px"}}><small>288 max</small><br/></div>
+                        {randomFrom === "standard Bananagrams" ? <div style={{display: "flex", justifyContent: "center", marginTop: "5px", marginBottom: "5px"}}><small>146 max</small><br/></div>
+                        : randomFrom === "double Bananagrams" ? <div style={{display: "flex", justifyContent: "center", marginTop: "5px", marginBottom: "5px"}}><small>292 max</small><br/></div>
+                        : randomFrom === "triple Bananagrams" ? <div style={{display: "flex", justifyContent: "center", marginTop: "5px", marginBottom: "5px"}}><small>438 max</small><br/></div>
                         : null}
                         <div style={{textAlign: "center"}}>
                             <Button type="submit" label="Choose letters" icon="pi pi-arrow-right" iconPos="right" style={{marginTop: "5px", marginRight: "5px"}}/>
@@ -587,7 +649,7 @@ export default function LetterInput(props: LetterInputProps){
             </TabView>            
         </Dialog>
         <div>
-            {UPPERCASE.map((c, i) => {
+            {TILES.map((c, i) => {
                 return (
                     <span className="letter-input-span" key={"span-"+c}><label htmlFor={"char-"+c} className="letter-input-label">{c}:</label>
                         <InputNumber inputId={"char-"+c} value={letterNums.get(c)} onValueChange={e => changeLetterNum(c, e)} min={0} size={1} showButtons inputStyle={{padding: "5px", width: "3rem"}} incrementButtonClassName="input-button-type" decrementButtonClassName="input-button-type" className={lettersInvalid.get(c) ? "p-invalid" : undefined} style={{marginTop: "5px", paddingLeft: "5px"}} onContextMenu={e => individual_cm_refs[i].current?.show(e)}/>
@@ -600,10 +662,13 @@ export default function LetterInput(props: LetterInputProps){
         <div className="button-div">
             <Button type="button" label="Input letters" icon="pi pi-book" iconPos="right" style={{padding: "8px", marginRight: "2%"}} onClick={() => setTypeInVisible(true)}/>
             <Button type="button" label="View playable words" icon="pi pi-eye" iconPos="right" style={{padding: "8px",}} onClick={viewPlayableWords} loading={playableWordsLoading}/>
+            {bunchStarted ? <Button type="button" label="Peel" icon="pi pi-plus" iconPos="right" style={{padding: "8px", marginLeft: "2%"}} onClick={peel} disabled={props.running}/> : null}
         </div>
         <div className="button-div">
             <Dropdown placeholder="Reset" options={["Reset hand", "Reset board"]} style={{marginRight: "2%"}} onChange={e => doReset(e.value)} disabled={props.running} className="reset-dropdown" panelClassName="reset-dropdown" pt={{input: {style: {color: "white"}}, item: {className: "reset-dropdown-item"}, trigger: {style: {color: "white"}}}}/>
-            <Button type="button" label="Solve" icon="pi pi-arrow-right" iconPos="right" severity="success" onClick={solve} loading={props.running}/>
+            <Button type="button" label="Solve" icon="pi pi-arrow-right" iconPos="right" severity="success" onClick={() => solve(false)} loading={props.running}/>
+            {props.running ? null : <Button type="button" label="Fresh solve" icon="pi pi-replay" iconPos="right" severity="success" outlined onClick={() => solve(true)} style={{marginLeft: "2%"}}/>}
+            {props.running ? <Button type="button" label="Cancel" icon="pi pi-times" iconPos="right" severity="danger" onClick={cancelSolve} style={{marginLeft: "2%"}}/> : null}
         </div>
         <div className="button-div" style={{marginTop: "15px"}}>
             <Button label="Undo" icon="pi pi-undo" iconPos="right" onClick={undo} style={{marginRight: "2%"}} disabled={props.running || !props.undoPossible}/>
@@ -203,8 +203,8 @@ export default function ResultsDisplay(props: ResultsDisplayProps) {
                                         if (val.trim() === "") {
                                             return <td key={"row-"+i+"-cell-"+j} className="emptyCell"></td>
                                         }
-                                        else if (val.length === 2) {
-                                            return <td key={"row-"+i+"-cell-"+j} className="previouslyPlayedCell" aria-label={`Row ${i}, column ${j}: ${val.charAt(0)} (previously played)`}>{val.charAt(0)}</td>
+                                        else if (props.results!.carried_over?.[i][j]) {
+                                            return <td key={"row-"+i+"-cell-"+j} className="previouslyPlayedCell" aria-label={`Row ${i}, column ${j}: ${val} (previously played)`}>{val}</td>
                                         }
                                         else {
                                             return <td key={"row-"+i+"-cell-"+j} className="occupiedCell" aria-label={`Row ${i}, column ${j}: ${val}`}>{val}</td>
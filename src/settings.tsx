@@ -1,8 +1,10 @@
 import { invoke } from "@tauri-apps/api";
 import { Button } from "primereact/button"
+import { Checkbox } from "primereact/checkbox";
 import { Dialog } from "primereact/dialog";
 import { Dropdown } from "primereact/dropdown";
 import { InputNumber } from "primereact/inputnumber";
+import { InputText } from "primereact/inputtext";
 import { OverlayPanel } from "primereact/overlaypanel";
 import { Toast } from "primereact/toast"
 import { RefObject, useEffect, useRef, useState } from "react"
@@ -23,12 +25,35 @@ interface SettingsProps {
 export default function Settings(props: SettingsProps) {
     const [showSettings, setShowSettings] = useState(false);
     const [filterLettersOnBoard, setFilterLettersOnBoard] = useState<number|null>(2);
-    const [whichDictionary, setWhichDictionary] = useState<"Short"|"Full">("Short");
+    const [whichDictionary, setWhichDictionary] = useState<"Short"|"Full"|"Custom">("Short");
+    const [customDictionaryPath, setCustomDictionaryPath] = useState("");
+    const [customDictionaryLoading, setCustomDictionaryLoading] = useState(false);
     const [maximumWordsToCheck, setMaximumWordsToCheck] = useState<number|null>(50_000);
+    const [maxSolveTimeMs, setMaxSolveTimeMs] = useState<number|null>(0);
+    const [boardSize, setBoardSize] = useState<number|null>(144);
+    const [useFrequencyOrdering, setUseFrequencyOrdering] = useState(false);
+    const [deterministic, setDeterministic] = useState(false);
+    const [language, setLanguage] = useState<"English"|"Spanish"|"French">("English");
+    const [solutionPreference, setSolutionPreference] = useState<"Fastest"|"Compact"|"FewestWords">("Fastest");
+    const [historyDepth, setHistoryDepth] = useState<number|null>(10);
+    const [minWordLength, setMinWordLength] = useState<number|null>(2);
+    const [fallbackToLongDictionary, setFallbackToLongDictionary] = useState(false);
+    const [maxWidth, setMaxWidth] = useState<number|null>(null);
+    const [maxHeight, setMaxHeight] = useState<number|null>(null);
     const [aboutVisible, setAboutVisible] = useState(false);
     const filterLettersInfo = useRef<OverlayPanel>(null);
     const maxWordsInfo = useRef<OverlayPanel>(null);
+    const maxSolveTimeInfo = useRef<OverlayPanel>(null);
+    const boardSizeInfo = useRef<OverlayPanel>(null);
     const whichDictionaryInfo = useRef<OverlayPanel>(null);
+    const frequencyOrderingInfo = useRef<OverlayPanel>(null);
+    const deterministicInfo = useRef<OverlayPanel>(null);
+    const languageInfo = useRef<OverlayPanel>(null);
+    const solutionPreferenceInfo = useRef<OverlayPanel>(null);
+    const historyDepthInfo = useRef<OverlayPanel>(null);
+    const minWordLengthInfo = useRef<OverlayPanel>(null);
+    const fallbackInfo = useRef<OverlayPanel>(null);
+    const maxSpanInfo = useRef<OverlayPanel>(null);
 
     // Get the available settings whenever the popup is shown
     useEffect(() => {
@@ -36,7 +61,18 @@ export default function Settings(props: SettingsProps) {
             invoke("get_settings").then((res: any) => {
                 setFilterLettersOnBoard(res.filter_letters_on_board);
                 setMaximumWordsToCheck(res.maximum_words_to_check);
-                setWhichDictionary(res.use_long_dictionary ? "Full" : "Short")
+                setMaxSolveTimeMs(res.max_solve_time_ms);
+                setBoardSize(res.board_size);
+                setWhichDictionary(res.dictionary === "Long" ? "Full" : res.dictionary);
+                setUseFrequencyOrdering(res.use_frequency_ordering);
+                setDeterministic(res.deterministic);
+                setLanguage(res.language);
+                setSolutionPreference(res.solution_preference);
+                setHistoryDepth(res.history_depth);
+                setMinWordLength(res.min_word_length);
+                setFallbackToLongDictionary(res.fallback_to_long_dictionary);
+                setMaxWidth(res.max_width ?? null);
+                setMaxHeight(res.max_height ?? null);
             });
         }
     }, [showSettings]);
@@ -51,39 +87,80 @@ export default function Settings(props: SettingsProps) {
         else if (filterLettersOnBoard < 0) {
             props.toast.current?.show({severity: "warn", summary: "Invalid usable letters on board", detail: "The number of usable letters on the board must be a non-negative integer."})
         }
-        else if (filterLettersOnBoard >= 2**32) {
-            props.toast.current?.show({severity: "warn", summary: "Invalid usable letters on board", detail: "The number of usable letters on the board must be less than 2³²"});
+        else if (filterLettersOnBoard > 17) {
+            props.toast.current?.show({severity: "warn", summary: "Invalid usable letters on board", detail: "The number of usable letters on the board can be at most 17 (the length of the longest word)"});
         }
         else if (maximumWordsToCheck == null) {
             props.toast.current?.show({severity: "warn", summary: "Missing maximum iterations", detail: "The maximum iterations must be provided"});
         }
-        else if (maximumWordsToCheck < 0) {
-            props.toast.current?.show({severity: "warn", summary: "Invalid maximum iterations", detail: "The maximum iterations must be a non-negative integer"});
+        else if (maximumWordsToCheck < 1000) {
+            props.toast.current?.show({severity: "warn", summary: "Invalid maximum iterations", detail: "The maximum iterations must be at least 1,000"});
         }
         else if (maximumWordsToCheck >= 2**32) {
             props.toast.current?.show({severity: "warn", summary: "Invalid maximum iterations", detail: "The maximum iterations must be less than 2³²"});
         }
+        else if (maxSolveTimeMs == null) {
+            props.toast.current?.show({severity: "warn", summary: "Missing time limit", detail: "The time limit must be provided"});
+        }
+        else if (maxSolveTimeMs < 0) {
+            props.toast.current?.show({severity: "warn", summary: "Invalid time limit", detail: "The time limit must be a non-negative integer"});
+        }
+        else if (boardSize == null) {
+            props.toast.current?.show({severity: "warn", summary: "Missing board size", detail: "The board size must be provided"});
+        }
+        else if (boardSize < 17 || boardSize > 1000) {
+            props.toast.current?.show({severity: "warn", summary: "Invalid board size", detail: "The board size must be between 17 and 1000"});
+        }
+        else if (historyDepth == null) {
+            props.toast.current?.show({severity: "warn", summary: "Missing undo history", detail: "The number of games to keep for undoing must be provided"});
+        }
+        else if (historyDepth < 1) {
+            props.toast.current?.show({severity: "warn", summary: "Invalid undo history", detail: "At least one game must be kept for undoing"});
+        }
+        else if (minWordLength == null) {
+            props.toast.current?.show({severity: "warn", summary: "Missing minimum word length", detail: "The minimum word length must be provided"});
+        }
+        else if (minWordLength < 2 || minWordLength > 17) {
+            props.toast.current?.show({severity: "warn", summary: "Invalid minimum word length", detail: "The minimum word length must be between 2 and 17 (the length of the longest word)"});
+        }
+        else if (maxWidth != null && (maxWidth < minWordLength || maxWidth > boardSize)) {
+            props.toast.current?.show({severity: "warn", summary: "Invalid maximum width", detail: `The maximum width must be between ${minWordLength} (the minimum word length) and ${boardSize} (the board size)`});
+        }
+        else if (maxHeight != null && (maxHeight < 1 || maxHeight > boardSize)) {
+            props.toast.current?.show({severity: "warn", summary: "Invalid maximum height", detail: `The maximum height must be between 1 and ${boardSize} (the board size)`});
+        }
         else {
-            invoke("set_settings", { filterLettersOnBoard, maximumWordsToCheck, useLongDictionary: whichDictionary === "Full"})
+            invoke("set_settings", { filterLettersOnBoard, maximumWordsToCheck, maxSolveTimeMs, boardSize, dictionary: whichDictionary === "Full" ? "Long" : whichDictionary, useFrequencyOrdering, deterministic, language, solutionPreference, historyDepth, minWordLength, fallbackToLongDictionary, maxWidth, maxHeight})
             .then(() => setShowSettings(false))
             .catch(err => props.toast.current?.show({severity: "error", summary: "Error updating settings", detail: `An error occurred: ${err}`}));
         }
     }
 
+    /**
+     * Loads the custom dictionary at the entered path
+     */
+    const loadCustomDictionary = () => {
+        setCustomDictionaryLoading(true);
+        invoke("load_custom_dictionary", { path: customDictionaryPath })
+        .then(res => props.toast.current?.show({severity: "success", summary: "Dictionary loaded", detail: `Loaded ${res} words`}))
+        .catch(err => props.toast.current?.show({severity: "error", summary: "Error loading dictionary", detail: `An error occurred: ${err}`}))
+        .finally(() => setCustomDictionaryLoading(false));
+    }
+
     return (
         <>
         <Dialog header="Settings" visible={showSettings} onHide={() => setShowSettings(false)}>
             <div className="settings-div">
-                <label htmlFor="filter_letters_on_board">Usable letters on board:</label> <InputNumber value={filterLettersOnBoard} onChange={e => setFilterLettersOnBoard(e.value)} min={0} max={2**32-1} inputId="filter_letters_on_board"/>
+                <label htmlFor="filter_letters_on_board">Usable letters on board:</label> <InputNumber value={filterLettersOnBoard} onChange={e => setFilterLettersOnBoard(e.value)} min={0} max={17} inputId="filter_letters_on_board"/>
                 <OverlayPanel ref={filterLettersInfo} style={{maxWidth: "33vw"}}>
                     <p>The maximum number of letters on the board that can be used in conjuction with letters in the hand when filtering playable words</p>
                     <p><strong>Lower values:</strong> <em>Usually</em> faster solutions</p>
-                    <p><strong>Higher values:</strong> <em>Usually</em> slower solutions, but more likely to find a solution if one exists. For an exhaustive search, use a value greater than the total number of letters.</p>
+                    <p><strong>Higher values:</strong> <em>Usually</em> slower solutions, but more likely to find a solution if one exists. For an exhaustive search, use 17 (the length of the longest word).</p>
                 </OverlayPanel>
                 <i className="pi pi-info-circle info-overlay" onClick={e => filterLettersInfo.current?.toggle(e)} aria-haspopup></i>
             </div>
             <div className="settings-div">
-                <label htmlFor="max_words_to_check">Maximum iterations:</label> <InputNumber value={maximumWordsToCheck} onChange={e => setMaximumWordsToCheck(e.value)} min={0} max={2**32-1} inputId="max_words_to_check"/>
+                <label htmlFor="max_words_to_check">Maximum iterations:</label> <InputNumber value={maximumWordsToCheck} onChange={e => setMaximumWordsToCheck(e.value)} min={1000} max={2**32-1} inputId="max_words_to_check"/>
                 <OverlayPanel ref={maxWordsInfo} style={{maxWidth: "33vw"}}>
                     <p>The maximum number of iterations before the solver stops and returns no solution (i.e. a "dump") - this applies separately to each of the first six words checked</p>
                     <p><strong>Lower values:</strong> Faster "dump" solutions</p>
@@ -92,14 +169,105 @@ export default function Settings(props: SettingsProps) {
                 <i className="pi pi-info-circle info-overlay" onClick={e => maxWordsInfo.current?.toggle(e)} aria-haspopup></i>
             </div>
             <div className="settings-div">
-                <label htmlFor="use_dictionary">Dictionary:</label> <Dropdown value={whichDictionary} onChange={e => setWhichDictionary(e.value)} options={["Short", "Full"]} inputId="use_dictionary"/>
+                <label htmlFor="max_solve_time_ms">Time limit (ms):</label> <InputNumber value={maxSolveTimeMs} onChange={e => setMaxSolveTimeMs(e.value)} min={0} inputId="max_solve_time_ms"/>
+                <OverlayPanel ref={maxSolveTimeInfo} style={{maxWidth: "33vw"}}>
+                    <p>The maximum time in milliseconds to spend solving before the solver stops and returns no solution (i.e. a "dump") - use 0 for no limit</p>
+                    <p>This applies in addition to the maximum iterations, and is useful for large hands or slower computers</p>
+                </OverlayPanel>
+                <i className="pi pi-info-circle info-overlay" onClick={e => maxSolveTimeInfo.current?.toggle(e)} aria-haspopup></i>
+            </div>
+            <div className="settings-div">
+                <label htmlFor="board_size">Board size:</label> <InputNumber value={boardSize} onChange={e => setBoardSize(e.value)} min={17} max={1000} inputId="board_size"/>
+                <OverlayPanel ref={boardSizeInfo} style={{maxWidth: "33vw"}}>
+                    <p>The number of rows and columns in the board that the solver can play on</p>
+                    <p><strong>Lower values:</strong> Faster solutions for small hands (such as with a reduced set of tiles), but large hands may not fit</p>
+                    <p><strong>Higher values:</strong> Room for larger hands. The default of 144 fits every standard Bananagrams tile in a line.</p>
+                </OverlayPanel>
+                <i className="pi pi-info-circle info-overlay" onClick={e => boardSizeInfo.current?.toggle(e)} aria-haspopup></i>
+            </div>
+            <div className="settings-div">
+                <label htmlFor="history_depth">Undo history:</label> <InputNumber value={historyDepth} onChange={e => setHistoryDepth(e.value)} min={1} inputId="history_depth"/>
+                <OverlayPanel ref={historyDepthInfo} style={{maxWidth: "33vw"}}>
+                    <p>The number of previous games that can be undone, and of solved boards kept in the board history</p>
+                    <p>When more games are played, the oldest ones are forgotten</p>
+                </OverlayPanel>
+                <i className="pi pi-info-circle info-overlay" onClick={e => historyDepthInfo.current?.toggle(e)} aria-haspopup></i>
+            </div>
+            <div className="settings-div">
+                <label htmlFor="min_word_length">Minimum word length:</label> <InputNumber value={minWordLength} onChange={e => setMinWordLength(e.value)} min={2} max={17} inputId="min_word_length"/>
+                <OverlayPanel ref={minWordLengthInfo} style={{maxWidth: "33vw"}}>
+                    <p>The shortest word that can be formed anywhere on the board, including where words cross</p>
+                    <p><strong>Higher values:</strong> Avoids obscure short words like "AA" or "ZO", but fewer hands can be solved</p>
+                </OverlayPanel>
+                <i className="pi pi-info-circle info-overlay" onClick={e => minWordLengthInfo.current?.toggle(e)} aria-haspopup></i>
+            </div>
+            <div className="settings-div">
+                <label htmlFor="max_width">Maximum width:</label> <InputNumber value={maxWidth} onChange={e => setMaxWidth(e.value)} min={2} placeholder="Board size" inputId="max_width"/>
+                <label htmlFor="max_height" style={{marginLeft: "5px"}}>height:</label> <InputNumber value={maxHeight} onChange={e => setMaxHeight(e.value)} min={1} placeholder="Board size" inputId="max_height"/>
+                <OverlayPanel ref={maxSpanInfo} style={{maxWidth: "33vw"}}>
+                    <p>The most columns and rows the solved board can take up, such as to fit the table being played on - leave empty for no limit beyond the board size</p>
+                    <p><strong>Lower values:</strong> More compact boards, but fewer hands can be solved and long words may not fit</p>
+                </OverlayPanel>
+                <i className="pi pi-info-circle info-overlay" onClick={e => maxSpanInfo.current?.toggle(e)} aria-haspopup></i>
+            </div>
+            <div className="settings-div">
+                <label htmlFor="use_dictionary">Dictionary:</label> <Dropdown value={whichDictionary} onChange={e => setWhichDictionary(e.value)} options={["Short", "Full", "Custom"]} inputId="use_dictionary"/>
                 <OverlayPanel ref={whichDictionaryInfo} style={{maxWidth: "33vw"}}>
                     <p>Which dictionary to use</p>
                     <p><strong>Short:</strong> Contains 30,515 words</p>
                     <p><strong>Full:</strong> Contains 178,663 words, including some that some players might consider questionable</p>
+                    <p><strong>Custom:</strong> A newline-delimited word list loaded from a file</p>
                 </OverlayPanel>
                 <i className="pi pi-info-circle info-overlay" onClick={e => whichDictionaryInfo.current?.toggle(e)} aria-haspopup></i>
             </div>
+            {whichDictionary === "Short" ?
+            <div className="settings-div">
+                <label htmlFor="fallback_to_long_dictionary">Fall back to full dictionary:</label> <Checkbox checked={fallbackToLongDictionary} onChange={e => setFallbackToLongDictionary(e.checked ?? false)} inputId="fallback_to_long_dictionary"/>
+                <OverlayPanel ref={fallbackInfo} style={{maxWidth: "33vw"}}>
+                    <p>Whether to try again with the full dictionary when the short dictionary can't solve a hand</p>
+                    <p>The retry gets its own maximum number of words to check and solve time, so solving can take up to twice as long. Words that are only in the full dictionary are listed after solving.</p>
+                </OverlayPanel>
+                <i className="pi pi-info-circle info-overlay" onClick={e => fallbackInfo.current?.toggle(e)} aria-haspopup></i>
+            </div> : null}
+            <div className="settings-div">
+                <label htmlFor="language">Language:</label> <Dropdown value={language} onChange={e => setLanguage(e.value)} options={["English", "Spanish", "French"]} inputId="language"/>
+                <OverlayPanel ref={languageInfo} style={{maxWidth: "33vw"}}>
+                    <p>Which language's short and full dictionaries to use</p>
+                    <p>Spanish and French word lists are read from the <code>dictionaries</code> folder in the app data folder, as <code>spanish_long.txt</code> (and optionally <code>spanish_short.txt</code>) or the same for <code>french</code>. Accented letters are treated as their base letter.</p>
+                </OverlayPanel>
+                <i className="pi pi-info-circle info-overlay" onClick={e => languageInfo.current?.toggle(e)} aria-haspopup></i>
+            </div>
+            {whichDictionary === "Custom" ?
+            <div className="settings-div">
+                <label htmlFor="custom_dictionary_path">Word list:</label> <InputText value={customDictionaryPath} onChange={e => setCustomDictionaryPath(e.target.value)} id="custom_dictionary_path"/>
+                <Button label="Load" icon="pi pi-upload" iconPos="right" onClick={loadCustomDictionary} loading={customDictionaryLoading} disabled={customDictionaryPath.length === 0} style={{marginLeft: "5px"}}/>
+            </div> : null}
+            <div className="settings-div">
+                <label htmlFor="use_frequency_ordering">Prioritize common letters:</label> <Checkbox checked={useFrequencyOrdering} onChange={e => setUseFrequencyOrdering(e.checked ?? false)} inputId="use_frequency_ordering"/>
+                <OverlayPanel ref={frequencyOrderingInfo} style={{maxWidth: "33vw"}}>
+                    <p>Whether to first try words that use the letters most plentiful in the hand</p>
+                    <p>This can find solutions for hands heavy in a few letters sooner, but may be slower on more balanced hands</p>
+                </OverlayPanel>
+                <i className="pi pi-info-circle info-overlay" onClick={e => frequencyOrderingInfo.current?.toggle(e)} aria-haspopup></i>
+            </div>
+            <div className="settings-div">
+                <label htmlFor="deterministic">Deterministic solving:</label> <Checkbox checked={deterministic} onChange={e => setDeterministic(e.checked ?? false)} inputId="deterministic"/>
+                <OverlayPanel ref={deterministicInfo} style={{maxWidth: "33vw"}}>
+                    <p>Whether the same hand should always produce the same board</p>
+                    <p>This solves using a single thread, so it is usually slower</p>
+                </OverlayPanel>
+                <i className="pi pi-info-circle info-overlay" onClick={e => deterministicInfo.current?.toggle(e)} aria-haspopup></i>
+            </div>
+            <div className="settings-div">
+                <label htmlFor="solution_preference">Preferred solution:</label> <Dropdown value={solutionPreference} onChange={e => setSolutionPreference(e.value)} options={[{label: "Fastest", value: "Fastest"}, {label: "Most compact", value: "Compact"}, {label: "Fewest words", value: "FewestWords"}]} inputId="solution_preference"/>
+                <OverlayPanel ref={solutionPreferenceInfo} style={{maxWidth: "33vw"}}>
+                    <p>Which solution to return when building a board from scratch</p>
+                    <p><strong>Fastest:</strong> The first solution found</p>
+                    <p><strong>Most compact:</strong> Keeps searching for a little longer and returns the board with the smallest area</p>
+                    <p><strong>Fewest words:</strong> Keeps searching for a little longer and returns the board with the fewest words</p>
+                </OverlayPanel>
+                <i className="pi pi-info-circle info-overlay" onClick={e => solutionPreferenceInfo.current?.toggle(e)} aria-haspopup></i>
+            </div>
             <div style={{display: "flex", justifyContent: "center", marginTop: "15px"}}>
                 <Button label="Use settings" icon="pi pi-arrow-right" iconPos="right" onClick={setSettings}/>
                 <Button label="Cancel" icon="pi pi-times" iconPos="right" severity="secondary" onClick={() => setShowSettings(false)} style={{marginLeft: "5px"}}/>
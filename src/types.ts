@@ -6,8 +6,66 @@ export type result_t = {
      * 2D array of characters of the solution
      */
     board: string[][],
+    /**
+     * Whether each cell of `board` holds a tile in the same place as on the previous board (absent while the board is still being solved)
+     */
+    carried_over?: boolean[][],
+    /**
+     * The row of the full board that the first row of `board` is in, so that tiles kept between solves keep their position
+     */
+    min_row?: number,
+    /**
+     * The column of the full board that the first column of `board` is in
+     */
+    min_col?: number,
+    /**
+     * The row of the full board that the last row of `board` is in
+     */
+    max_row?: number,
+    /**
+     * The column of the full board that the last column of `board` is in
+     */
+    max_col?: number,
+    /**
+     * The letters of the hand that couldn't be placed, when a partial board was allowed
+     */
+    unplaced?: string[],
+    /**
+     * Whether the short dictionary couldn't solve the hand, so the long dictionary was used instead
+     */
+    used_long_dictionary?: boolean,
+    /**
+     * The words on the board that aren't in the short dictionary (empty unless `used_long_dictionary`)
+     */
+    long_only_words?: string[],
     /**
      * The time the function took to run
      */
     elapsed: number
-};
\ No newline at end of file
+};
+
+/**
+ * Type of the error returned when solving fails
+ */
+export type solver_error_t = {
+    /**
+     * Which error occurred, so that it can be handled without matching on the message
+     */
+    kind: "NoSolution"|"LettersLeftOver"|"NoPlayableWords"|"StrandedLetters"|"InvalidLetterCount"|"MissingLetter"|"Cancelled"|"Timeout"|"RequiredWordUnplayable"|"ExceedsMaxSpan"|"Internal",
+    /**
+     * Human-readable description of the error
+     */
+    message: string,
+    /**
+     * The letter the error is about (for `InvalidLetterCount` and `MissingLetter`)
+     */
+    letter?: string,
+    /**
+     * The invalid count (for `InvalidLetterCount`)
+     */
+    count?: number,
+    /**
+     * The letters that no playable word uses (for `StrandedLetters`), or that the closest attempt left unplayed (for `LettersLeftOver`), one of which could be dumped
+     */
+    letters?: string[]
+};